@@ -37,7 +37,7 @@ type EthereumCacheDbWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
 pub fn run() {
     let (seed, iterations) = try_init_env_vars().expect("Failed to parse env vars");
 
-    let run_call_data = IBURNTPIX::runCall { seed, iterations }.abi_encode();
+    let run_call_data = run_call_data(seed, iterations);
 
     let db = init_db();
 
@@ -45,7 +45,7 @@ pub fn run() {
         .modify_tx_env(|tx| {
             tx.caller = address!("1000000000000000000000000000000000000000");
             tx.transact_to = TxKind::Call(BURNTPIX_MAIN_ADDRESS);
-            tx.data = run_call_data.clone().into();
+            tx.data = run_call_data.clone();
         })
         .with_db(db)
         .with_default_ext_ctx()
@@ -107,6 +107,11 @@ fn try_from_hex_to_u32(hex: &str) -> Result<u32, Box<dyn Error>> {
     u32::from_str_radix(trimmed, 16).map_err(|e| format!("Failed to parse hex: {}", e).into())
 }
 
+/// Returns the call data of `run(seed, iterations)` of the main contract.
+pub fn run_call_data(seed: u32, iterations: U256) -> Bytes {
+    IBURNTPIX::runCall { seed, iterations }.abi_encode().into()
+}
+
 fn insert_account_info(cache_db: &mut CacheDB<EmptyDB>, addr: Address, code: Bytes) {
     let code_hash = hex::encode(keccak256(&code));
     let account_info = AccountInfo::new(
@@ -118,7 +123,8 @@ fn insert_account_info(cache_db: &mut CacheDB<EmptyDB>, addr: Address, code: Byt
     cache_db.insert_account_info(addr, account_info);
 }
 
-fn init_db() -> CacheDB<EmptyDB> {
+/// Returns the database with the contracts and storage of Burntpix on mainnet.
+pub fn init_db() -> CacheDB<EmptyDB> {
     let mut cache_db = CacheDB::new(EmptyDB::default());
 
     insert_account_info(
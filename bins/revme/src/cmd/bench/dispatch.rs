@@ -0,0 +1,112 @@
+//! Compares the dispatch of the plain instruction table with the specialized dispatch of
+//! `hot_contracts_register` on contracts deployed on mainnet.
+//!
+//! Build with the `superinstructions` feature to fuse common instruction sequences in the
+//! specialized dispatch.
+use super::{burntpix, snailtracer};
+use database::CacheDB;
+use revm::{
+    bytecode::Bytecode,
+    database_interface::EmptyDB,
+    handler::{register::EvmHandler, specialize::hot_contracts_register},
+    primitives::{address, bytes, Address, HashSet, TxKind, U256},
+    state::AccountInfo,
+    wiring::EthereumWiring,
+    Evm,
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+type EthereumCacheDbWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+
+/// Number of executed instructions, counted by [`count_instructions`].
+static INSTRUCTIONS: AtomicU64 = AtomicU64::new(0);
+
+pub fn run() {
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        Address::ZERO,
+        AccountInfo::from_bytecode(Bytecode::new_raw(snailtracer::CONTRACT_DATA.clone())),
+    );
+    bench("snailtracer", db, Address::ZERO, bytes!("30627b7c"), 50);
+
+    bench(
+        "burntpix",
+        burntpix::init_db(),
+        burntpix::static_data::BURNTPIX_MAIN_ADDRESS,
+        burntpix::run_call_data(0, U256::from(0x2710)),
+        50,
+    );
+}
+
+/// Executes the call `runs` times with both dispatches and prints the instructions per second
+/// of the fastest execution.
+fn bench(name: &str, db: CacheDB<EmptyDB>, to: Address, data: revm::primitives::Bytes, runs: u32) {
+    let hot_contracts = db
+        .accounts
+        .values()
+        .filter_map(|account| account.info())
+        .filter(|info| !info.is_empty_code_hash())
+        .map(|info| info.code_hash)
+        .collect::<HashSet<_>>();
+    let evm = Evm::<EthereumCacheDbWiring>::builder()
+        .with_db(db)
+        .with_default_ext_ctx()
+        .modify_tx_env(|tx| {
+            tx.caller = address!("1000000000000000000000000000000000000000");
+            tx.transact_to = TxKind::Call(to);
+            tx.data = data;
+        })
+        .build();
+
+    INSTRUCTIONS.store(0, Ordering::Relaxed);
+    let mut evm = evm
+        .modify()
+        .append_handler_register(count_instructions)
+        .build();
+    assert!(evm.transact().unwrap().result.is_success());
+    let instructions = INSTRUCTIONS.load(Ordering::Relaxed);
+
+    // Rebuilding the handler drops the counting register.
+    let mut evm = evm.modify().reset_handler().build();
+    let plain = time(&mut evm, runs);
+
+    let mut evm = evm
+        .modify()
+        .append_handler_register_box(hot_contracts_register(hot_contracts))
+        .build();
+    let specialized = time(&mut evm, runs);
+
+    let per_second = |elapsed: f64| instructions as f64 / elapsed / 1e6;
+    println!("{name}: {instructions} instructions");
+    println!("  plain:       {:.1} M instructions/s", per_second(plain));
+    println!(
+        "  specialized: {:.1} M instructions/s",
+        per_second(specialized)
+    );
+}
+
+/// Returns the seconds of the fastest of `runs` executions, after one execution to warm up.
+///
+/// The fastest execution is the least disturbed by the rest of the system.
+fn time(evm: &mut Evm<'_, EthereumCacheDbWiring>, runs: u32) -> f64 {
+    assert!(evm.transact().unwrap().result.is_success());
+    (0..runs)
+        .map(|_| {
+            let timer = Instant::now();
+            let _ = evm.transact().unwrap();
+            timer.elapsed().as_secs_f64()
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn count_instructions(handler: &mut EvmHandler<'_, EthereumCacheDbWiring>) {
+    handler
+        .instruction_table
+        .update_all(|instruction, interpreter, host| {
+            INSTRUCTIONS.fetch_add(1, Ordering::Relaxed);
+            instruction(interpreter, host)
+        });
+}
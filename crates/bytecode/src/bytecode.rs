@@ -1,11 +1,12 @@
 use crate::{
     eip7702::{Eip7702Bytecode, EIP7702_MAGIC_BYTES},
+    legacy::static_storage_slots,
     BytecodeDecodeError, Eof, JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode,
     EOF_MAGIC_BYTES,
 };
 use core::fmt::Debug;
-use primitives::{keccak256, Address, Bytes, B256, KECCAK_EMPTY};
-use std::sync::Arc;
+use primitives::{keccak256, Address, Bytes, B256, KECCAK_EMPTY, U256};
+use std::{sync::Arc, vec::Vec};
 
 /// State of the [`Bytecode`] analysis.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -219,6 +220,19 @@ impl Bytecode {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns storage slots that are statically known to be accessed by legacy bytecode.
+    ///
+    /// See [`crate::legacy::static_storage_slots`]. Returns empty list for EOF and EIP-7702
+    /// bytecode.
+    pub fn static_storage_slots(&self) -> Vec<U256> {
+        match self {
+            Self::LegacyRaw(_) | Self::LegacyAnalyzed(_) => {
+                static_storage_slots(self.original_byte_slice())
+            }
+            Self::Eof(_) | Self::Eip7702(_) => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4,4 +4,4 @@ mod raw;
 
 pub use analyzed::LegacyAnalyzedBytecode;
 pub use jump_map::JumpTable;
-pub use raw::{analyze_legacy, LegacyRawBytecode};
+pub use raw::{analyze_legacy, static_storage_slots, LegacyRawBytecode};
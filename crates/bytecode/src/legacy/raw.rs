@@ -2,7 +2,7 @@ use super::{JumpTable, LegacyAnalyzedBytecode};
 use crate::opcode;
 use bitvec::{bitvec, order::Lsb0, vec::BitVec};
 use core::ops::Deref;
-use primitives::Bytes;
+use primitives::{Bytes, U256};
 use std::{sync::Arc, vec::Vec};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -72,3 +72,62 @@ pub fn analyze_legacy(bytetecode: &[u8]) -> JumpTable {
 
     JumpTable(Arc::new(jumps))
 }
+
+/// Returns storage slots that are statically known to be accessed by the bytecode.
+///
+/// A slot is detected when `SLOAD` or `SSTORE` directly follows a `PUSH` instruction, as
+/// compilers emit it for state variables at fixed slots. Slots computed at runtime, like
+/// mapping or array entries, are not detected.
+pub fn static_storage_slots(bytecode: &[u8]) -> Vec<U256> {
+    let mut slots = Vec::new();
+    let mut last_push = None;
+    let mut i = 0;
+    while i < bytecode.len() {
+        let op = bytecode[i];
+        let push_offset = op.wrapping_sub(opcode::PUSH1);
+        if op == opcode::PUSH0 {
+            last_push = Some(U256::ZERO);
+            i += 1;
+        } else if push_offset < 32 {
+            let size = push_offset as usize + 1;
+            // Truncated push is padded with zeros as in execution.
+            let data = &bytecode[i + 1..(i + 1 + size).min(bytecode.len())];
+            let mut word = [0u8; 32];
+            word[32 - size..32 - size + data.len()].copy_from_slice(data);
+            last_push = Some(U256::from_be_bytes(word));
+            i += 1 + size;
+        } else {
+            if matches!(op, opcode::SLOAD | opcode::SSTORE) {
+                if let Some(slot) = last_push {
+                    if !slots.contains(&slot) {
+                        slots.push(slot);
+                    }
+                }
+            }
+            last_push = None;
+            i += 1;
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode::{ADD, PUSH0, PUSH1, PUSH2, SLOAD, SSTORE};
+
+    #[test]
+    fn detects_static_slots() {
+        let code = [
+            PUSH0, SLOAD, // slot 0
+            PUSH1, 0x01, PUSH2, 0x01, 0x02, SSTORE, // slot 0x0102
+            PUSH1, 0x01, PUSH1, 0x02, ADD, SLOAD, // computed slot
+            PUSH1, 0x00, SLOAD, // slot 0 again
+            PUSH2, 0x01, // truncated
+        ];
+        assert_eq!(
+            static_storage_slots(&code),
+            vec![U256::ZERO, U256::from(0x0102)]
+        );
+    }
+}
@@ -1,9 +1,13 @@
 //! EVM opcode definitions and utilities.
 
+pub mod disassemble;
 #[cfg(feature = "parse")]
 pub mod parse;
 
+pub use disassemble::{disassemble, disassemble_eof, Instruction};
+
 use core::{fmt, ptr::NonNull};
+use specification::hardfork::SpecId;
 
 /// An EVM opcode.
 ///
@@ -211,6 +215,8 @@ pub struct OpCodeInfo {
     not_eof: bool,
     /// If the opcode stops execution. aka STOP, RETURN, ..
     terminating: bool,
+    /// Specification in which the opcode was introduced.
+    introduced: SpecId,
 }
 
 impl fmt::Debug for OpCodeInfo {
@@ -222,6 +228,7 @@ impl fmt::Debug for OpCodeInfo {
             .field("not_eof", &self.is_disabled_in_eof())
             .field("terminating", &self.is_terminating())
             .field("immediate_size", &self.immediate_size())
+            .field("introduced", &self.introduced())
             .finish()
     }
 }
@@ -238,6 +245,7 @@ impl OpCodeInfo {
             not_eof: false,
             terminating: false,
             immediate_size: 0,
+            introduced: SpecId::FRONTIER,
         }
     }
 
@@ -287,6 +295,20 @@ impl OpCodeInfo {
     pub const fn immediate_size(&self) -> u8 {
         self.immediate_size
     }
+
+    /// Returns the specification in which the opcode was introduced.
+    ///
+    /// EOF opcodes are introduced in [`SpecId::PRAGUE_EOF`].
+    #[inline]
+    pub const fn introduced(&self) -> SpecId {
+        self.introduced
+    }
+
+    /// Returns whether the opcode is available in the given specification.
+    #[inline]
+    pub const fn is_enabled_in(&self, spec_id: SpecId) -> bool {
+        spec_id.is_enabled_in(self.introduced)
+    }
 }
 
 /// Sets the EOF flag to false.
@@ -313,6 +335,13 @@ pub const fn terminating(mut op: OpCodeInfo) -> OpCodeInfo {
     op
 }
 
+/// Sets the specification in which the opcode was introduced.
+#[inline]
+pub const fn introduced(mut op: OpCodeInfo, spec_id: SpecId) -> OpCodeInfo {
+    op.introduced = spec_id;
+    op
+}
+
 /// Sets the number of stack inputs and outputs.
 #[inline]
 pub const fn stack_io(mut op: OpCodeInfo, inputs: u8, outputs: u8) -> OpCodeInfo {
@@ -412,9 +441,9 @@ opcodes! {
     0x18 => XOR  => stack_io(2, 1);
     0x19 => NOT  => stack_io(1, 1);
     0x1A => BYTE => stack_io(2, 1);
-    0x1B => SHL  => stack_io(2, 1);
-    0x1C => SHR  => stack_io(2, 1);
-    0x1D => SAR  => stack_io(2, 1);
+    0x1B => SHL  => stack_io(2, 1), introduced(SpecId::CONSTANTINOPLE);
+    0x1C => SHR  => stack_io(2, 1), introduced(SpecId::CONSTANTINOPLE);
+    0x1D => SAR  => stack_io(2, 1), introduced(SpecId::CONSTANTINOPLE);
     // 0x1E
     // 0x1F
     0x20 => KECCAK256 => stack_io(2, 1);
@@ -447,20 +476,20 @@ opcodes! {
     0x3A => GASPRICE     => stack_io(0, 1);
     0x3B => EXTCODESIZE  => stack_io(1, 1), not_eof;
     0x3C => EXTCODECOPY  => stack_io(4, 0), not_eof;
-    0x3D => RETURNDATASIZE => stack_io(0, 1);
-    0x3E => RETURNDATACOPY => stack_io(3, 0);
-    0x3F => EXTCODEHASH  => stack_io(1, 1), not_eof;
+    0x3D => RETURNDATASIZE => stack_io(0, 1), introduced(SpecId::BYZANTIUM);
+    0x3E => RETURNDATACOPY => stack_io(3, 0), introduced(SpecId::BYZANTIUM);
+    0x3F => EXTCODEHASH  => stack_io(1, 1), not_eof, introduced(SpecId::CONSTANTINOPLE);
     0x40 => BLOCKHASH    => stack_io(1, 1);
     0x41 => COINBASE     => stack_io(0, 1);
     0x42 => TIMESTAMP    => stack_io(0, 1);
     0x43 => NUMBER       => stack_io(0, 1);
     0x44 => DIFFICULTY   => stack_io(0, 1);
     0x45 => GASLIMIT     => stack_io(0, 1);
-    0x46 => CHAINID      => stack_io(0, 1);
-    0x47 => SELFBALANCE  => stack_io(0, 1);
-    0x48 => BASEFEE      => stack_io(0, 1);
-    0x49 => BLOBHASH     => stack_io(1, 1);
-    0x4A => BLOBBASEFEE  => stack_io(0, 1);
+    0x46 => CHAINID      => stack_io(0, 1), introduced(SpecId::ISTANBUL);
+    0x47 => SELFBALANCE  => stack_io(0, 1), introduced(SpecId::ISTANBUL);
+    0x48 => BASEFEE      => stack_io(0, 1), introduced(SpecId::LONDON);
+    0x49 => BLOBHASH     => stack_io(1, 1), introduced(SpecId::CANCUN);
+    0x4A => BLOBBASEFEE  => stack_io(0, 1), introduced(SpecId::CANCUN);
     // 0x4B
     // 0x4C
     // 0x4D
@@ -478,11 +507,11 @@ opcodes! {
     0x59 => MSIZE    => stack_io(0, 1);
     0x5A => GAS      => stack_io(0, 1), not_eof;
     0x5B => JUMPDEST => stack_io(0, 0);
-    0x5C => TLOAD    => stack_io(1, 1);
-    0x5D => TSTORE   => stack_io(2, 0);
-    0x5E => MCOPY    => stack_io(3, 0);
+    0x5C => TLOAD    => stack_io(1, 1), introduced(SpecId::CANCUN);
+    0x5D => TSTORE   => stack_io(2, 0), introduced(SpecId::CANCUN);
+    0x5E => MCOPY    => stack_io(3, 0), introduced(SpecId::CANCUN);
 
-    0x5F => PUSH0  => stack_io(0, 1);
+    0x5F => PUSH0  => stack_io(0, 1), introduced(SpecId::SHANGHAI);
     0x60 => PUSH1  => stack_io(0, 1), immediate_size(1);
     0x61 => PUSH2  => stack_io(0, 1), immediate_size(2);
     0x62 => PUSH3  => stack_io(0, 1), immediate_size(3);
@@ -598,10 +627,10 @@ opcodes! {
     // 0xCD
     // 0xCE
     // 0xCF
-    0xD0 => DATALOAD=> stack_io(1, 1);
-    0xD1 => DATALOADN => stack_io(0, 1), immediate_size(2);
-    0xD2 => DATASIZE=> stack_io(0, 1);
-    0xD3 => DATACOPY=> stack_io(3, 0);
+    0xD0 => DATALOAD=> stack_io(1, 1), introduced(SpecId::PRAGUE_EOF);
+    0xD1 => DATALOADN => stack_io(0, 1), immediate_size(2), introduced(SpecId::PRAGUE_EOF);
+    0xD2 => DATASIZE=> stack_io(0, 1), introduced(SpecId::PRAGUE_EOF);
+    0xD3 => DATACOPY=> stack_io(3, 0), introduced(SpecId::PRAGUE_EOF);
     // 0xD4
     // 0xD5
     // 0xD6
@@ -614,36 +643,36 @@ opcodes! {
     // 0xDD
     // 0xDE
     // 0xDF
-    0xE0 => RJUMP    => stack_io(0, 0), immediate_size(2), terminating;
-    0xE1 => RJUMPI   => stack_io(1, 0), immediate_size(2);
-    0xE2 => RJUMPV   => stack_io(1, 0), immediate_size(1);
-    0xE3 => CALLF    => stack_io(0, 0), immediate_size(2);
-    0xE4 => RETF     => stack_io(0, 0), terminating;
-    0xE5 => JUMPF    => stack_io(0, 0), immediate_size(2), terminating;
-    0xE6 => DUPN     => stack_io(0, 1), immediate_size(1);
-    0xE7 => SWAPN    => stack_io(0, 0), immediate_size(1);
-    0xE8 => EXCHANGE => stack_io(0, 0), immediate_size(1);
+    0xE0 => RJUMP    => stack_io(0, 0), immediate_size(2), terminating, introduced(SpecId::PRAGUE_EOF);
+    0xE1 => RJUMPI   => stack_io(1, 0), immediate_size(2), introduced(SpecId::PRAGUE_EOF);
+    0xE2 => RJUMPV   => stack_io(1, 0), immediate_size(1), introduced(SpecId::PRAGUE_EOF);
+    0xE3 => CALLF    => stack_io(0, 0), immediate_size(2), introduced(SpecId::PRAGUE_EOF);
+    0xE4 => RETF     => stack_io(0, 0), terminating, introduced(SpecId::PRAGUE_EOF);
+    0xE5 => JUMPF    => stack_io(0, 0), immediate_size(2), terminating, introduced(SpecId::PRAGUE_EOF);
+    0xE6 => DUPN     => stack_io(0, 1), immediate_size(1), introduced(SpecId::PRAGUE_EOF);
+    0xE7 => SWAPN    => stack_io(0, 0), immediate_size(1), introduced(SpecId::PRAGUE_EOF);
+    0xE8 => EXCHANGE => stack_io(0, 0), immediate_size(1), introduced(SpecId::PRAGUE_EOF);
     // 0xE9
     // 0xEA
     // 0xEB
-    0xEC => EOFCREATE      => stack_io(4, 1), immediate_size(1);
+    0xEC => EOFCREATE      => stack_io(4, 1), immediate_size(1), introduced(SpecId::PRAGUE_EOF);
     // 0xED
-    0xEE => RETURNCONTRACT => stack_io(2, 0), immediate_size(1), terminating;
+    0xEE => RETURNCONTRACT => stack_io(2, 0), immediate_size(1), terminating, introduced(SpecId::PRAGUE_EOF);
     // 0xEF
     0xF0 => CREATE       => stack_io(3, 1), not_eof;
     0xF1 => CALL         => stack_io(7, 1), not_eof;
     0xF2 => CALLCODE     => stack_io(7, 1), not_eof;
     0xF3 => RETURN       => stack_io(2, 0), terminating;
-    0xF4 => DELEGATECALL => stack_io(6, 1), not_eof;
-    0xF5 => CREATE2      => stack_io(4, 1), not_eof;
+    0xF4 => DELEGATECALL => stack_io(6, 1), not_eof, introduced(SpecId::HOMESTEAD);
+    0xF5 => CREATE2      => stack_io(4, 1), not_eof, introduced(SpecId::PETERSBURG);
     // 0xF6
-    0xF7 => RETURNDATALOAD  => stack_io(1, 1);
-    0xF8 => EXTCALL         => stack_io(4, 1);
-    0xF9 => EXTDELEGATECALL => stack_io(3, 1);
-    0xFA => STATICCALL      => stack_io(6, 1), not_eof;
-    0xFB => EXTSTATICCALL   => stack_io(3, 1);
+    0xF7 => RETURNDATALOAD  => stack_io(1, 1), introduced(SpecId::PRAGUE_EOF);
+    0xF8 => EXTCALL         => stack_io(4, 1), introduced(SpecId::PRAGUE_EOF);
+    0xF9 => EXTDELEGATECALL => stack_io(3, 1), introduced(SpecId::PRAGUE_EOF);
+    0xFA => STATICCALL      => stack_io(6, 1), not_eof, introduced(SpecId::BYZANTIUM);
+    0xFB => EXTSTATICCALL   => stack_io(3, 1), introduced(SpecId::PRAGUE_EOF);
     // 0xFC
-    0xFD => REVERT       => stack_io(2, 0), terminating;
+    0xFD => REVERT       => stack_io(2, 0), terminating, introduced(SpecId::BYZANTIUM);
     0xFE => INVALID      => stack_io(0, 0), terminating;
     0xFF => SELFDESTRUCT => stack_io(1, 0), not_eof, terminating;
 }
@@ -677,6 +706,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_introduced() {
+        assert_eq!(OpCode::ADD.info().introduced(), SpecId::FRONTIER);
+        assert_eq!(OpCode::PUSH0.info().introduced(), SpecId::SHANGHAI);
+        assert!(!OpCode::TLOAD.info().is_enabled_in(SpecId::SHANGHAI));
+        assert!(OpCode::TLOAD.info().is_enabled_in(SpecId::CANCUN));
+        assert!(!OpCode::RJUMP.info().is_enabled_in(SpecId::PRAGUE));
+    }
+
     #[test]
     fn test_immediate_size() {
         let mut expected = [0u8; 256];
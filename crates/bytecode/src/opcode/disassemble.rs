@@ -0,0 +1,111 @@
+use super::{OpCode, OpCodeInfo, OPCODE_INFO, RJUMPV};
+use core::fmt;
+use primitives::hex;
+use std::vec::Vec;
+
+/// Instruction decoded by [`disassemble`] or [`disassemble_eof`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction<'a> {
+    /// Position of the opcode in the bytecode.
+    pub pc: usize,
+    /// Opcode byte.
+    pub opcode: u8,
+    /// Immediate bytes of the instruction.
+    ///
+    /// Shorter than the immediate size of the opcode if the bytecode is truncated.
+    pub immediate: &'a [u8],
+}
+
+impl Instruction<'_> {
+    /// Returns the opcode info, `None` if the opcode is unknown.
+    #[inline]
+    pub const fn info(&self) -> Option<OpCodeInfo> {
+        OPCODE_INFO[self.opcode as usize]
+    }
+}
+
+impl fmt::Display for Instruction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.info() {
+            Some(info) => f.write_str(info.name())?,
+            None => write!(f, "UNKNOWN(0x{:02X})", self.opcode)?,
+        }
+        if !self.immediate.is_empty() {
+            write!(f, " 0x{}", hex::encode(self.immediate))?;
+        }
+        Ok(())
+    }
+}
+
+/// Disassembles legacy bytecode.
+///
+/// Only `PUSH` opcodes have immediate bytes, unknown opcodes are returned as they are.
+pub fn disassemble(bytecode: &[u8]) -> Vec<Instruction<'_>> {
+    disassemble_inner(bytecode, false)
+}
+
+/// Disassembles a code section of EOF bytecode.
+///
+/// Immediate bytes of EOF opcodes are decoded, including the jump table of `RJUMPV`.
+pub fn disassemble_eof(code: &[u8]) -> Vec<Instruction<'_>> {
+    disassemble_inner(code, true)
+}
+
+fn disassemble_inner(bytecode: &[u8], is_eof: bool) -> Vec<Instruction<'_>> {
+    let mut instructions = Vec::new();
+    let mut pc = 0;
+    while let Some(&opcode) = bytecode.get(pc) {
+        let immediate_size = match OPCODE_INFO[opcode as usize] {
+            // RJUMPV immediate is the max index followed by a two byte offset per index.
+            Some(_) if is_eof && opcode == RJUMPV => bytecode
+                .get(pc + 1)
+                .map_or(1, |max_index| 1 + (*max_index as usize + 1) * 2),
+            Some(info) if is_eof || OpCode::is_push_by_op(opcode) => info.immediate_size() as usize,
+            _ => 0,
+        };
+        let start = pc + 1;
+        let end = (start + immediate_size).min(bytecode.len());
+        instructions.push(Instruction {
+            pc,
+            opcode,
+            immediate: &bytecode[start..end],
+        });
+        pc = start + immediate_size;
+    }
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcode::{ADD, PUSH1, PUSH2, RJUMPV, STOP};
+    use std::{string::ToString, vec};
+
+    #[test]
+    fn disassemble_legacy() {
+        let instructions = disassemble(&[PUSH1, 0x01, PUSH1, 0x02, ADD, 0x0C, RJUMPV, PUSH2, 0xff]);
+        let text: Vec<_> = instructions.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            text,
+            vec![
+                "PUSH1 0x01",
+                "PUSH1 0x02",
+                "ADD",
+                "UNKNOWN(0x0C)",
+                "RJUMPV",
+                "PUSH2 0xff"
+            ]
+        );
+        assert_eq!(instructions[4].pc, 6);
+        assert_eq!(instructions[5].info().unwrap().immediate_size(), 2);
+    }
+
+    #[test]
+    fn disassemble_eof_code() {
+        let instructions = disassemble_eof(&[RJUMPV, 0x01, 0x00, 0x01, 0x00, 0x02, STOP]);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].immediate, &[0x01, 0x00, 0x01, 0x00, 0x02]);
+        assert_eq!(instructions[1].pc, 6);
+        assert_eq!(instructions[1].opcode, STOP);
+    }
+}
@@ -0,0 +1,220 @@
+//! Loader and writer of the `alloc` section of Geth genesis files.
+use crate::{AccountState, CacheDB, DbAccount, InMemoryDB};
+use primitives::{alloy_primitives::U64, Address, Bytes, HashMap, U256};
+use serde::{Deserialize, Serialize};
+use state::{AccountInfo, Bytecode};
+use std::{collections::BTreeMap, path::Path, vec::Vec};
+
+/// Genesis file in the Geth format.
+///
+/// Only the `alloc` section is read and written, the chain config and the genesis block header
+/// fields are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Genesis {
+    /// Accounts that exist at genesis.
+    #[serde(default)]
+    pub alloc: BTreeMap<Address, GenesisAccount>,
+}
+
+/// Account of the genesis `alloc` section.
+///
+/// Numbers can be either hex with a `0x` prefix or decimal strings, as accepted by Geth. They are
+/// written as hex, empty fields other than the balance are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisAccount {
+    /// Account balance.
+    #[serde(default)]
+    pub balance: U256,
+    /// Account nonce.
+    #[serde(default, skip_serializing_if = "U64::is_zero")]
+    pub nonce: U64,
+    /// Account code.
+    #[serde(default, skip_serializing_if = "<[u8]>::is_empty")]
+    pub code: Bytes,
+    /// Account storage.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<U256, U256>,
+}
+
+impl Genesis {
+    /// Parses the genesis from JSON.
+    pub fn from_json(json: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(json)
+    }
+
+    /// Serializes the genesis to JSON.
+    pub fn to_json(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec_pretty(self)
+    }
+}
+
+impl<ExtDB> CacheDB<ExtDB> {
+    /// Inserts the genesis accounts.
+    ///
+    /// Existing accounts are replaced together with their storage.
+    pub fn insert_genesis(&mut self, genesis: &Genesis) {
+        for (address, account) in &genesis.alloc {
+            let mut info = AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce.to(),
+                ..Default::default()
+            };
+            if !account.code.is_empty() {
+                info.code = Some(Bytecode::new_raw(account.code.clone()));
+            }
+            self.insert_contract(&mut info);
+            self.mark_dirty(address);
+            self.accounts.insert(
+                *address,
+                DbAccount {
+                    info,
+                    account_state: AccountState::StorageCleared,
+                    storage: account
+                        .storage
+                        .iter()
+                        .filter(|(_, value)| !value.is_zero())
+                        .map(|(slot, value)| (*slot, *value))
+                        .collect::<HashMap<_, _>>(),
+                },
+            );
+        }
+    }
+
+    /// Returns the cached accounts as a genesis.
+    ///
+    /// Accounts of the underlying database that were never loaded are missing and accounts that
+    /// do not exist are skipped. Storage slots with a zero value are omitted.
+    pub fn to_genesis(&self) -> Genesis {
+        let alloc = self
+            .accounts
+            .iter()
+            .filter_map(|(address, account)| {
+                let info = account.info()?;
+                let code = info
+                    .code
+                    .or_else(|| self.contracts.get(&info.code_hash).cloned())
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default();
+                let account = GenesisAccount {
+                    balance: info.balance,
+                    nonce: U64::from(info.nonce),
+                    code,
+                    storage: account
+                        .storage
+                        .iter()
+                        .filter(|(_, value)| !value.is_zero())
+                        .map(|(slot, value)| (*slot, *value))
+                        .collect(),
+                };
+                Some((*address, account))
+            })
+            .collect();
+        Genesis { alloc }
+    }
+}
+
+impl InMemoryDB {
+    /// Creates a database with the accounts of a genesis file in the Geth format.
+    pub fn from_genesis_json(json: &[u8]) -> Result<Self, serde_json::Error> {
+        let mut db = Self::default();
+        db.insert_genesis(&Genesis::from_json(json)?);
+        Ok(db)
+    }
+
+    /// Reads a genesis file in the Geth format and creates a database with its accounts.
+    pub fn from_genesis_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::from_genesis_json(&std::fs::read(path)?)?)
+    }
+
+    /// Writes the accounts to a genesis file in the Geth format.
+    ///
+    /// The file can be loaded back with [`InMemoryDB::from_genesis_file`].
+    pub fn write_genesis_file(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_genesis().to_json()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database_interface::DatabaseRef;
+    use primitives::{address, bytes, KECCAK_EMPTY};
+
+    const GENESIS: &str = r#"{
+        "config": { "chainId": 1337 },
+        "gasLimit": "0x1c9c380",
+        "alloc": {
+            "0x00000000000000000000000000000000000000aa": {
+                "balance": "1000000000000000000"
+            },
+            "00000000000000000000000000000000000000bb": {
+                "balance": "0x10",
+                "nonce": "0x2",
+                "code": "0x6001600055",
+                "storage": {
+                    "0x01": "0x2a",
+                    "0x0000000000000000000000000000000000000000000000000000000000000002": "0x00"
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn from_genesis_json() {
+        let db = InMemoryDB::from_genesis_json(GENESIS.as_bytes()).unwrap();
+
+        let eoa = db
+            .basic_ref(address!("00000000000000000000000000000000000000aa"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(eoa.balance, U256::from(10).pow(U256::from(18)));
+        assert_eq!(eoa.nonce, 0);
+        assert_eq!(eoa.code_hash, KECCAK_EMPTY);
+
+        let contract_address = address!("00000000000000000000000000000000000000bb");
+        let contract = db.basic_ref(contract_address).unwrap().unwrap();
+        assert_eq!(contract.balance, U256::from(0x10));
+        assert_eq!(contract.nonce, 2);
+        assert_eq!(
+            db.code_by_hash_ref(contract.code_hash)
+                .unwrap()
+                .original_bytes(),
+            bytes!("6001600055")
+        );
+        assert_eq!(
+            db.storage_ref(contract_address, U256::from(1)).unwrap(),
+            U256::from(42)
+        );
+        assert_eq!(
+            db.storage_ref(contract_address, U256::from(2)).unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn invalid_genesis() {
+        assert!(InMemoryDB::from_genesis_json(b"{\"alloc\": {\"0x01\": {}}}").is_err());
+        assert!(InMemoryDB::from_genesis_json(b"not json").is_err());
+        assert!(InMemoryDB::from_genesis_file("/non/existent/genesis.json").is_err());
+    }
+
+    #[test]
+    fn genesis_roundtrip() {
+        let db = InMemoryDB::from_genesis_json(GENESIS.as_bytes()).unwrap();
+        let genesis = db.to_genesis();
+        assert_eq!(genesis.alloc.len(), 2);
+        // The zero storage slot is dropped.
+        assert_eq!(
+            genesis.alloc[&address!("00000000000000000000000000000000000000bb")]
+                .storage
+                .len(),
+            1
+        );
+
+        let path = std::env::temp_dir().join("revm_genesis_roundtrip.json");
+        db.write_genesis_file(&path).unwrap();
+        let loaded = InMemoryDB::from_genesis_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.to_genesis(), genesis);
+    }
+}
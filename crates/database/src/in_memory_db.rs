@@ -31,6 +31,34 @@ pub struct CacheDB<ExtDB> {
     ///
     /// Note: this is read-only, data is never written to this database.
     pub db: ExtDB,
+    /// Accounts that were loaded from `db` and not modified since, see [CacheDB::prune].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    clean: CleanAccounts,
+}
+
+/// Recency of the cached accounts that can be evicted.
+#[derive(Debug, Clone, Default)]
+struct CleanAccounts {
+    /// Incremented on every access.
+    tick: u64,
+    /// Tick of the last access of every unmodified account.
+    last_access: HashMap<Address, u64>,
+}
+
+impl CleanAccounts {
+    /// Records an access of an account that was just loaded from the underlying database.
+    fn loaded(&mut self, address: Address) {
+        self.tick += 1;
+        self.last_access.insert(address, self.tick);
+    }
+
+    /// Records an access of a cached account.
+    fn accessed(&mut self, address: &Address) {
+        if let Some(last_access) = self.last_access.get_mut(address) {
+            self.tick += 1;
+            *last_access = self.tick;
+        }
+    }
 }
 
 impl<ExtDB: Default> Default for CacheDB<ExtDB> {
@@ -50,6 +78,7 @@ impl<ExtDB> CacheDB<ExtDB> {
             logs: Vec::default(),
             block_hashes: HashMap::default(),
             db,
+            clean: CleanAccounts::default(),
         }
     }
 
@@ -77,15 +106,134 @@ impl<ExtDB> CacheDB<ExtDB> {
     /// Insert account info but not override storage
     pub fn insert_account_info(&mut self, address: Address, mut info: AccountInfo) {
         self.insert_contract(&mut info);
+        self.mark_dirty(&address);
         self.accounts.entry(address).or_default().info = info;
     }
+
+    /// Marks the account as modified, so it is never evicted by [CacheDB::prune].
+    ///
+    /// All methods of the cache do this on their own. Call it after modifying an account
+    /// through the `accounts` map directly.
+    pub fn mark_dirty(&mut self, address: &Address) {
+        self.clean.last_access.remove(address);
+    }
+
+    /// Evicts the least recently used accounts until at most `capacity` accounts and storage
+    /// slots are cached, and returns the number of evicted accounts.
+    ///
+    /// Only accounts that were loaded from the underlying database and not modified since are
+    /// evicted, together with their storage, so they are loaded again on the next access.
+    /// Modified accounts are never evicted, so the cache can stay above `capacity`. Reads
+    /// through [DatabaseRef] do not count as accesses.
+    pub fn prune(&mut self, capacity: usize) -> usize {
+        let mut len: usize = self
+            .accounts
+            .values()
+            .map(|account| 1 + account.storage.len())
+            .sum();
+        if len <= capacity {
+            return 0;
+        }
+
+        let mut candidates: Vec<_> = self
+            .clean
+            .last_access
+            .iter()
+            .map(|(address, last_access)| (*last_access, *address))
+            .collect();
+        candidates.sort_unstable();
+
+        let mut evicted = 0;
+        for (_, address) in candidates {
+            if len <= capacity {
+                break;
+            }
+            self.clean.last_access.remove(&address);
+            if let Some(account) = self.accounts.remove(&address) {
+                len -= 1 + account.storage.len();
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+}
+
+impl<ExtDB> CacheDB<ExtDB> {
+    /// Wraps this database into a new empty overlay.
+    ///
+    /// Reads fall through to `self` while all writes land in the returned top layer.
+    pub fn nest(self) -> CacheDB<Self> {
+        CacheDB::new(self)
+    }
+}
+
+impl<ExtDB> CacheDB<CacheDB<ExtDB>> {
+    /// Discards all changes of this overlay and returns the underlying layer.
+    pub fn discard_outer(self) -> CacheDB<ExtDB> {
+        self.db
+    }
+
+    /// Merges this overlay into the underlying layer and returns it.
+    ///
+    /// Accounts, contracts, logs and block hashes of the top layer take precedence.
+    pub fn flatten(self) -> CacheDB<ExtDB> {
+        let Self {
+            accounts,
+            contracts,
+            logs,
+            block_hashes,
+            db: mut inner,
+            clean,
+        } = self;
+
+        for (address, account) in accounts {
+            // Unmodified accounts of the top layer hold the values of the inner layer.
+            if !clean.last_access.contains_key(&address) {
+                inner.mark_dirty(&address);
+            }
+            match inner.accounts.entry(address) {
+                Entry::Vacant(entry) => {
+                    if clean.last_access.contains_key(&address) {
+                        inner.clean.loaded(address);
+                    }
+                    entry.insert(account);
+                }
+                Entry::Occupied(entry) => {
+                    let db_account = entry.into_mut();
+                    if matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    ) {
+                        *db_account = account;
+                        continue;
+                    }
+                    db_account.account_state = match db_account.account_state {
+                        // Storage of the inner layer is already authoritative.
+                        AccountState::StorageCleared | AccountState::NotExisting => {
+                            AccountState::StorageCleared
+                        }
+                        AccountState::Touched => AccountState::Touched,
+                        AccountState::None => account.account_state,
+                    };
+                    db_account.info = account.info;
+                    db_account.storage.extend(account.storage);
+                }
+            }
+        }
+        inner.contracts.extend(contracts);
+        inner.logs.extend(logs);
+        inner.block_hashes.extend(block_hashes);
+        inner
+    }
 }
 
 impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
     /// Returns the account for the given address.
     ///
     /// If the account was not found in the cache, it will be loaded from the underlying database.
+    /// The account is marked as modified, see [CacheDB::mark_dirty].
     pub fn load_account(&mut self, address: Address) -> Result<&mut DbAccount, ExtDB::Error> {
+        self.mark_dirty(&address);
         let db = &self.db;
         match self.accounts.entry(address) {
             Entry::Occupied(entry) => Ok(entry.into_mut()),
@@ -131,7 +279,8 @@ impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
             if !account.is_touched() {
                 continue;
             }
-            if account.is_selfdestructed() {
+            self.mark_dirty(&address);
+            if account.is_selfdestructed() || account.is_state_cleared() {
                 let db_account = self.accounts.entry(address).or_default();
                 db_account.storage.clear();
                 db_account.account_state = AccountState::NotExisting;
@@ -168,16 +317,22 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
 
     fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
         let basic = match self.accounts.entry(address) {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => entry.insert(
-                self.db
+            Entry::Occupied(entry) => {
+                self.clean.accessed(&address);
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => {
+                let account = self
+                    .db
                     .basic_ref(address)?
                     .map(|info| DbAccount {
                         info,
                         ..Default::default()
                     })
-                    .unwrap_or_else(DbAccount::new_not_existing),
-            ),
+                    .unwrap_or_else(DbAccount::new_not_existing);
+                self.clean.loaded(address);
+                entry.insert(account)
+            }
         };
         Ok(basic.info())
     }
@@ -198,6 +353,7 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
     fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
         match self.accounts.entry(address) {
             Entry::Occupied(mut acc_entry) => {
+                self.clean.accessed(&address);
                 let acc_entry = acc_entry.get_mut();
                 match acc_entry.storage.entry(index) {
                     Entry::Occupied(entry) => Ok(*entry.get()),
@@ -227,6 +383,7 @@ impl<ExtDB: DatabaseRef> Database for CacheDB<ExtDB> {
                     (info.into(), U256::ZERO)
                 };
                 acc_entry.insert(account);
+                self.clean.loaded(address);
                 Ok(value)
             }
         }
@@ -412,9 +569,9 @@ impl Database for BenchmarkDB {
 #[cfg(test)]
 mod tests {
     use super::{CacheDB, EmptyDB};
-    use database_interface::Database;
+    use database_interface::{Database, DatabaseCommit, DatabaseRef};
     use primitives::{Address, HashMap, U256};
-    use state::AccountInfo;
+    use state::{Account, AccountInfo};
 
     #[test]
     fn test_insert_account_storage() {
@@ -468,6 +625,102 @@ mod tests {
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
 
+    #[test]
+    fn test_nested_overlays_flatten() {
+        let account = Address::with_last_byte(42);
+        let (key0, key1) = (U256::from(1), U256::from(2));
+        let mut base = CacheDB::new(EmptyDB::default());
+        base.insert_account_info(
+            account,
+            AccountInfo {
+                nonce: 1,
+                ..Default::default()
+            },
+        );
+        base.insert_account_storage(account, key0, U256::from(10))
+            .unwrap();
+
+        let mut session = base.nest();
+        session
+            .insert_account_storage(account, key1, U256::from(20))
+            .unwrap();
+
+        let mut request = session.nest();
+        request.insert_account_info(
+            account,
+            AccountInfo {
+                nonce: 3,
+                ..Default::default()
+            },
+        );
+        assert_eq!(request.storage(account, key0), Ok(U256::from(10)));
+        assert_eq!(request.storage(account, key1), Ok(U256::from(20)));
+
+        // Dropping the request layer leaves the session untouched.
+        let session = request.clone().discard_outer();
+        assert_eq!(session.basic_ref(account).unwrap().unwrap().nonce, 1);
+
+        let mut base = request.flatten().flatten();
+        assert_eq!(base.basic(account).unwrap().unwrap().nonce, 3);
+        assert_eq!(base.storage(account, key0), Ok(U256::from(10)));
+        assert_eq!(base.storage(account, key1), Ok(U256::from(20)));
+    }
+
+    #[test]
+    fn test_commit_state_cleared() {
+        let account = Address::with_last_byte(42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_storage(account, U256::ZERO, U256::from(1))
+            .unwrap();
+
+        let mut changed = Account::default();
+        changed.mark_touch();
+        changed.mark_state_cleared();
+        db.commit(HashMap::from_iter([(account, changed)]));
+        assert_eq!(db.basic(account), Ok(None));
+        assert_eq!(db.storage(account, U256::ZERO), Ok(U256::ZERO));
+    }
+
+    #[test]
+    fn test_prune_least_recently_used() {
+        let [a, b, c] = [1, 2, 3].map(Address::with_last_byte);
+        let mut base = CacheDB::new(EmptyDB::default());
+        for (nonce, address) in [a, b, c].into_iter().enumerate() {
+            base.insert_account_info(
+                address,
+                AccountInfo {
+                    nonce: nonce as u64,
+                    ..Default::default()
+                },
+            );
+        }
+        base.insert_account_storage(b, U256::from(1), U256::from(10))
+            .unwrap();
+
+        let mut db = base.nest();
+        db.basic(a).unwrap();
+        assert_eq!(db.storage(b, U256::from(1)), Ok(U256::from(10)));
+        let mut changed = Account::from(db.basic(c).unwrap().unwrap());
+        changed.info.nonce = 5;
+        changed.mark_touch();
+        db.commit(HashMap::from_iter([(c, changed)]));
+        db.basic(a).unwrap();
+
+        // `b` with its slot is the least recently used.
+        assert_eq!(db.prune(2), 1);
+        assert!(db.accounts.contains_key(&a) && !db.accounts.contains_key(&b));
+        assert_eq!(db.prune(2), 0);
+
+        // The modified `c` is never evicted.
+        assert_eq!(db.prune(0), 1);
+        assert_eq!(db.accounts.len(), 1);
+        assert_eq!(db.basic(c).unwrap().unwrap().nonce, 5);
+
+        // Evicted accounts are loaded again.
+        assert_eq!(db.basic(a).unwrap().unwrap().nonce, 0);
+        assert_eq!(db.storage(b, U256::from(1)), Ok(U256::from(10)));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize_deserialize_cachedb() {
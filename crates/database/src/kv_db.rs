@@ -0,0 +1,363 @@
+use alloy_rlp::{Decodable, Header};
+use bytecode::BytecodeDecodeError;
+use core::fmt;
+use database_interface::{Database, DatabaseRef};
+use primitives::{Address, Bytes, B256, KECCAK_EMPTY, U256};
+use state::{AccountInfo, Bytecode};
+
+/// Key-value store that holds the state, e.g. the tables of an MDBX database of a node.
+///
+/// Values are returned encoded, they are decoded by the [`KvCodec`] of the [`KvDatabase`].
+pub trait KvStore {
+    /// The store error type.
+    type Error;
+    /// Encoded value, e.g. a `Vec<u8>` or a slice borrowed from a read transaction.
+    type Value: AsRef<[u8]>;
+
+    /// Returns the encoded account, `None` if it does not exist.
+    fn account(&self, address: Address) -> Result<Option<Self::Value>, Self::Error>;
+
+    /// Returns the encoded storage entry, `None` if the slot is empty.
+    fn storage(&self, address: Address, index: U256) -> Result<Option<Self::Value>, Self::Error>;
+
+    /// Returns the encoded bytecode by its hash.
+    fn code(&self, code_hash: B256) -> Result<Option<Self::Value>, Self::Error>;
+
+    /// Returns the hash of the block.
+    fn block_hash(&self, number: u64) -> Result<Option<B256>, Self::Error>;
+}
+
+/// Decodes the values of a [`KvStore`].
+pub trait KvCodec {
+    /// The codec error type.
+    type Error;
+
+    /// Decodes an account. The code of the account is loaded by its hash.
+    fn decode_account(&self, value: &[u8]) -> Result<AccountInfo, Self::Error>;
+
+    /// Decodes a storage entry.
+    fn decode_storage(&self, value: &[u8]) -> Result<U256, Self::Error>;
+
+    /// Decodes a bytecode.
+    fn decode_code(&self, value: &[u8]) -> Result<Bytecode, Self::Error>;
+}
+
+/// [`KvCodec`] of the encoding of the state trie.
+///
+/// Accounts are `rlp([nonce, balance, storage_root, code_hash])`, storage entries are the RLP
+/// of the value and codes are the raw bytecode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RlpCodec;
+
+/// Error of the [`RlpCodec`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RlpCodecError {
+    /// Value is not valid RLP.
+    Rlp(alloy_rlp::Error),
+    /// Bytecode is not valid.
+    Bytecode(BytecodeDecodeError),
+}
+
+impl core::error::Error for RlpCodecError {}
+
+impl fmt::Display for RlpCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rlp(e) => write!(f, "invalid RLP: {e}"),
+            Self::Bytecode(e) => write!(f, "invalid bytecode: {e}"),
+        }
+    }
+}
+
+impl From<alloy_rlp::Error> for RlpCodecError {
+    fn from(value: alloy_rlp::Error) -> Self {
+        Self::Rlp(value)
+    }
+}
+
+impl KvCodec for RlpCodec {
+    type Error = RlpCodecError;
+
+    fn decode_account(&self, mut value: &[u8]) -> Result<AccountInfo, Self::Error> {
+        let header = Header::decode(&mut value)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString.into());
+        }
+        if header.payload_length != value.len() {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: value.len(),
+            }
+            .into());
+        }
+        let nonce = u64::decode(&mut value)?;
+        let balance = U256::decode(&mut value)?;
+        let _storage_root = B256::decode(&mut value)?;
+        let code_hash = B256::decode(&mut value)?;
+        if !value.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength.into());
+        }
+        Ok(AccountInfo {
+            balance,
+            nonce,
+            code_hash,
+            code: None,
+        })
+    }
+
+    fn decode_storage(&self, mut value: &[u8]) -> Result<U256, Self::Error> {
+        Ok(U256::decode(&mut value)?)
+    }
+
+    fn decode_code(&self, value: &[u8]) -> Result<Bytecode, Self::Error> {
+        Bytecode::new_raw_checked(Bytes::copy_from_slice(value)).map_err(RlpCodecError::Bytecode)
+    }
+}
+
+/// Error returned by [`KvDatabase`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KvDatabaseError<S, C> {
+    /// Error of the store.
+    Store(S),
+    /// Error of the codec.
+    Codec(C),
+    /// Bytecode of a code hash is not in the store.
+    MissingCode(B256),
+    /// Block hash is not in the store.
+    MissingBlockHash(u64),
+}
+
+impl<S: fmt::Debug + fmt::Display, C: fmt::Debug + fmt::Display> core::error::Error
+    for KvDatabaseError<S, C>
+{
+}
+
+impl<S: fmt::Display, C: fmt::Display> fmt::Display for KvDatabaseError<S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "store error: {e}"),
+            Self::Codec(e) => write!(f, "codec error: {e}"),
+            Self::MissingCode(code_hash) => write!(f, "code {code_hash} is missing in store"),
+            Self::MissingBlockHash(number) => {
+                write!(f, "block hash {number} is missing in store")
+            }
+        }
+    }
+}
+
+/// A [Database] implementation over a [`KvStore`].
+///
+/// Node integrators implement the few lookups of [`KvStore`] on top of their storage engine
+/// and pick a [`KvCodec`] that matches the encoding of their tables, instead of implementing
+/// [Database] themselves. Defaults to the [`RlpCodec`].
+#[derive(Clone, Debug, Default)]
+pub struct KvDatabase<S, C = RlpCodec> {
+    /// The store all reads are served from.
+    pub store: S,
+    /// The codec of the values of the store.
+    pub codec: C,
+}
+
+impl<S> KvDatabase<S> {
+    /// Creates new database from the store, with the [`RlpCodec`].
+    pub fn new(store: S) -> Self {
+        Self::with_codec(store, RlpCodec)
+    }
+}
+
+impl<S, C> KvDatabase<S, C> {
+    /// Creates new database from the store and the codec of its values.
+    pub fn with_codec(store: S, codec: C) -> Self {
+        Self { store, codec }
+    }
+
+    /// Returns the store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+impl<S: KvStore, C: KvCodec> DatabaseRef for KvDatabase<S, C> {
+    type Error = KvDatabaseError<S::Error, C::Error>;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(value) = self
+            .store
+            .account(address)
+            .map_err(KvDatabaseError::Store)?
+        else {
+            return Ok(None);
+        };
+        self.codec
+            .decode_account(value.as_ref())
+            .map(Some)
+            .map_err(KvDatabaseError::Codec)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default());
+        }
+        let value = self
+            .store
+            .code(code_hash)
+            .map_err(KvDatabaseError::Store)?
+            .ok_or(KvDatabaseError::MissingCode(code_hash))?;
+        self.codec
+            .decode_code(value.as_ref())
+            .map_err(KvDatabaseError::Codec)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        match self
+            .store
+            .storage(address, index)
+            .map_err(KvDatabaseError::Store)?
+        {
+            Some(value) => self
+                .codec
+                .decode_storage(value.as_ref())
+                .map_err(KvDatabaseError::Codec),
+            None => Ok(U256::ZERO),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.store
+            .block_hash(number)
+            .map_err(KvDatabaseError::Store)?
+            .ok_or(KvDatabaseError::MissingBlockHash(number))
+    }
+}
+
+impl<S: KvStore, C: KvCodec> Database for KvDatabase<S, C> {
+    type Error = KvDatabaseError<S::Error, C::Error>;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use core::convert::Infallible;
+    use primitives::{address, keccak256, HashMap};
+
+    /// Store of a single table keyed by the concatenated key.
+    #[derive(Default)]
+    struct MapStore(HashMap<Vec<u8>, Vec<u8>>);
+
+    impl KvStore for MapStore {
+        type Error = Infallible;
+        type Value = Vec<u8>;
+
+        fn account(&self, address: Address) -> Result<Option<Vec<u8>>, Self::Error> {
+            Ok(self.0.get(address.as_slice()).cloned())
+        }
+
+        fn storage(&self, address: Address, index: U256) -> Result<Option<Vec<u8>>, Self::Error> {
+            let key = [address.as_slice(), &index.to_be_bytes::<32>()].concat();
+            Ok(self.0.get(&key).cloned())
+        }
+
+        fn code(&self, code_hash: B256) -> Result<Option<Vec<u8>>, Self::Error> {
+            Ok(self.0.get(code_hash.as_slice()).cloned())
+        }
+
+        fn block_hash(&self, _number: u64) -> Result<Option<B256>, Self::Error> {
+            Ok(None)
+        }
+    }
+
+    fn encode_account(nonce: u64, balance: U256, code_hash: B256) -> Vec<u8> {
+        let storage_root = B256::ZERO;
+        let mut out = Vec::new();
+        Header {
+            list: true,
+            payload_length: nonce.length()
+                + balance.length()
+                + storage_root.length()
+                + code_hash.length(),
+        }
+        .encode(&mut out);
+        nonce.encode(&mut out);
+        balance.encode(&mut out);
+        storage_root.encode(&mut out);
+        code_hash.encode(&mut out);
+        out
+    }
+
+    #[test]
+    fn reads_through_codec() {
+        let address = address!("0000000000000000000000000000000000001000");
+        let code = [0x60, 0x00, 0x00];
+        let code_hash = keccak256(code);
+        let index = U256::from(3);
+
+        let mut store = MapStore::default();
+        store.0.insert(
+            address.to_vec(),
+            encode_account(7, U256::from(1_000), code_hash),
+        );
+        let mut value = Vec::new();
+        U256::from(42).encode(&mut value);
+        store.0.insert(
+            [address.as_slice(), &index.to_be_bytes::<32>()].concat(),
+            value,
+        );
+        store.0.insert(code_hash.to_vec(), code.to_vec());
+        let db = KvDatabase::new(store);
+
+        let info = db.basic_ref(address).unwrap().unwrap();
+        assert_eq!(info.nonce, 7);
+        assert_eq!(info.balance, U256::from(1_000));
+        assert_eq!(info.code_hash, code_hash);
+        assert_eq!(
+            db.code_by_hash_ref(code_hash)
+                .unwrap()
+                .original_byte_slice(),
+            code
+        );
+        assert_eq!(db.storage_ref(address, index).unwrap(), U256::from(42));
+        assert_eq!(db.storage_ref(address, U256::ZERO).unwrap(), U256::ZERO);
+        assert_eq!(db.basic_ref(Address::ZERO).unwrap(), None);
+        assert_eq!(
+            db.code_by_hash_ref(KECCAK_EMPTY).unwrap(),
+            Bytecode::default()
+        );
+        assert_eq!(
+            db.code_by_hash_ref(B256::ZERO),
+            Err(KvDatabaseError::MissingCode(B256::ZERO))
+        );
+        assert_eq!(
+            db.block_hash_ref(1),
+            Err(KvDatabaseError::MissingBlockHash(1))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_account() {
+        let mut account = encode_account(1, U256::ZERO, KECCAK_EMPTY);
+        account.push(0x80);
+        assert!(RlpCodec.decode_account(&account).is_err());
+        assert!(RlpCodec.decode_account(&[0x80]).is_err());
+    }
+}
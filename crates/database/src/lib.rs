@@ -2,16 +2,24 @@
 
 #[cfg(feature = "alloydb")]
 mod alloydb;
+#[cfg(feature = "serde-json")]
+mod genesis;
 
 pub mod in_memory_db;
+pub mod kv_db;
 pub mod states;
+pub mod witness_db;
 
 #[cfg(feature = "alloydb")]
 pub use alloydb::{AlloyDB, BlockId};
+#[cfg(feature = "serde-json")]
+pub use genesis::{Genesis, GenesisAccount};
 
 pub use in_memory_db::*;
+pub use kv_db::{KvCodec, KvDatabase, KvDatabaseError, KvStore, RlpCodec, RlpCodecError};
 pub use states::{
     AccountRevert, AccountStatus, BundleAccount, BundleState, CacheState, DBBox,
     OriginalValuesKnown, PlainAccount, RevertToSlot, State, StateBuilder, StateDBBox,
     StorageWithOriginalValues, TransitionAccount, TransitionState,
 };
+pub use witness_db::{ExecutionWitness, MissingWitnessData, WitnessDB, WitnessRecorder};
@@ -0,0 +1,329 @@
+use core::fmt;
+use database_interface::{Database, DatabaseCommit, DatabaseRef};
+use primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
+use state::{Account, AccountInfo, Bytecode};
+
+/// Data needed to execute a block without access to the full state.
+///
+/// Every account, storage slot, code and block hash that is read during the execution must
+/// be present. Proofs of the data are expected to be verified before it is used.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionWitness {
+    /// Accounts where `None` means that the account is proven to not exist.
+    pub accounts: HashMap<Address, Option<AccountInfo>>,
+    /// Storage slots of the accounts.
+    ///
+    /// Slots of accounts that are proven to not exist are always zero and can be omitted.
+    pub storage: HashMap<Address, HashMap<U256, U256>>,
+    /// Contract bytecodes by their code hash.
+    pub codes: HashMap<B256, Bytecode>,
+    /// Block hashes by block number.
+    pub block_hashes: HashMap<u64, B256>,
+}
+
+/// Error returned by [`WitnessDB`] when the requested data is not in the witness.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingWitnessData {
+    /// Account is missing.
+    Account(Address),
+    /// Storage slot is missing.
+    Storage {
+        /// Address of the account.
+        address: Address,
+        /// Storage index.
+        index: U256,
+    },
+    /// Bytecode is missing.
+    Code(B256),
+    /// Block hash is missing.
+    BlockHash(u64),
+}
+
+impl core::error::Error for MissingWitnessData {}
+
+impl fmt::Display for MissingWitnessData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account(address) => write!(f, "account {address} is missing in witness"),
+            Self::Storage { address, index } => {
+                write!(f, "storage {index} of {address} is missing in witness")
+            }
+            Self::Code(code_hash) => write!(f, "code {code_hash} is missing in witness"),
+            Self::BlockHash(number) => write!(f, "block hash {number} is missing in witness"),
+        }
+    }
+}
+
+/// A [Database] implementation that uses an [`ExecutionWitness`] as its only data source.
+///
+/// Reading anything that is not in the witness fails with [`MissingWitnessData`], which
+/// allows stateless validation of blocks.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WitnessDB {
+    /// The witness used to serve all reads.
+    pub witness: ExecutionWitness,
+}
+
+impl WitnessDB {
+    /// Creates new database from the witness.
+    pub fn new(witness: ExecutionWitness) -> Self {
+        Self { witness }
+    }
+
+    /// Returns the witness.
+    pub fn into_witness(self) -> ExecutionWitness {
+        self.witness
+    }
+}
+
+impl DatabaseRef for WitnessDB {
+    type Error = MissingWitnessData;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.witness
+            .accounts
+            .get(&address)
+            .cloned()
+            .ok_or(MissingWitnessData::Account(address))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if code_hash == KECCAK_EMPTY {
+            return Ok(Bytecode::default());
+        }
+        self.witness
+            .codes
+            .get(&code_hash)
+            .cloned()
+            .ok_or(MissingWitnessData::Code(code_hash))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        if let Some(value) = self
+            .witness
+            .storage
+            .get(&address)
+            .and_then(|storage| storage.get(&index))
+        {
+            return Ok(*value);
+        }
+        match self.witness.accounts.get(&address) {
+            // Storage of not existing account is empty.
+            Some(None) => Ok(U256::ZERO),
+            _ => Err(MissingWitnessData::Storage { address, index }),
+        }
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.witness
+            .block_hashes
+            .get(&number)
+            .copied()
+            .ok_or(MissingWitnessData::BlockHash(number))
+    }
+}
+
+impl Database for WitnessDB {
+    type Error = MissingWitnessData;
+
+    #[inline]
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.basic_ref(address)
+    }
+
+    #[inline]
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.code_by_hash_ref(code_hash)
+    }
+
+    #[inline]
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.storage_ref(address, index)
+    }
+
+    #[inline]
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hash_ref(number)
+    }
+}
+
+/// Database wrapper that records every read into an [`ExecutionWitness`].
+///
+/// The first value read for each entry is recorded, so when the block is executed on top of
+/// a database that gets the changes committed, the witness still contains the state before
+/// the block. Codes are moved out of the recorded accounts into
+/// [`ExecutionWitness::codes`] so each of them is kept only once.
+///
+/// The recorded witness can be used with a [`WitnessDB`] to re-execute the same block.
+#[derive(Clone, Debug, Default)]
+pub struct WitnessRecorder<DB> {
+    /// The wrapped database.
+    pub db: DB,
+    /// The witness of all reads so far.
+    pub witness: ExecutionWitness,
+}
+
+impl<DB> WitnessRecorder<DB> {
+    /// Wraps the database with an empty witness.
+    pub fn new(db: DB) -> Self {
+        Self {
+            db,
+            witness: ExecutionWitness::default(),
+        }
+    }
+
+    /// Returns the recorded witness.
+    pub fn into_witness(self) -> ExecutionWitness {
+        self.witness
+    }
+
+    /// Returns the wrapped database and the recorded witness.
+    pub fn into_parts(self) -> (DB, ExecutionWitness) {
+        (self.db, self.witness)
+    }
+
+    fn record_code(&mut self, code_hash: B256, code: &Bytecode) {
+        if code_hash != KECCAK_EMPTY {
+            self.witness
+                .codes
+                .entry(code_hash)
+                .or_insert_with(|| code.clone());
+        }
+    }
+}
+
+impl<DB: Database> Database for WitnessRecorder<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        if !self.witness.accounts.contains_key(&address) {
+            let mut recorded = info.clone();
+            if let Some(info) = &mut recorded {
+                if let Some(code) = info.code.take() {
+                    self.record_code(info.code_hash, &code);
+                }
+            }
+            self.witness.accounts.insert(address, recorded);
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.record_code(code_hash, &code);
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.witness
+            .storage
+            .entry(address)
+            .or_default()
+            .entry(index)
+            .or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.witness.block_hashes.entry(number).or_insert(hash);
+        Ok(hash)
+    }
+}
+
+impl<DB: DatabaseCommit> DatabaseCommit for WitnessRecorder<DB> {
+    #[inline]
+    fn commit(&mut self, changes: HashMap<Address, Account>) {
+        self.db.commit(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryDB;
+
+    #[test]
+    fn reads_only_witness() {
+        let existing = Address::with_last_byte(1);
+        let missing = Address::with_last_byte(2);
+        let absent = Address::with_last_byte(3);
+        let code = Bytecode::new_raw([0x00].into());
+
+        let mut witness = ExecutionWitness::default();
+        witness
+            .accounts
+            .insert(existing, Some(AccountInfo::from_bytecode(code.clone())));
+        witness.accounts.insert(absent, None);
+        witness
+            .storage
+            .insert(existing, HashMap::from_iter([(U256::ZERO, U256::from(7))]));
+        witness.codes.insert(code.hash_slow(), code.clone());
+        witness.block_hashes.insert(1, B256::with_last_byte(1));
+        let mut db = WitnessDB::new(witness);
+
+        let info = db.basic(existing).unwrap().unwrap();
+        assert_eq!(db.code_by_hash(info.code_hash), Ok(code));
+        assert_eq!(db.storage(existing, U256::ZERO), Ok(U256::from(7)));
+        assert_eq!(
+            db.storage(existing, U256::from(1)),
+            Err(MissingWitnessData::Storage {
+                address: existing,
+                index: U256::from(1)
+            })
+        );
+        assert_eq!(db.basic(absent), Ok(None));
+        assert_eq!(db.storage(absent, U256::from(1)), Ok(U256::ZERO));
+        assert_eq!(db.basic(missing), Err(MissingWitnessData::Account(missing)));
+        assert_eq!(db.block_hash(1), Ok(B256::with_last_byte(1)));
+        assert_eq!(db.block_hash(2), Err(MissingWitnessData::BlockHash(2)));
+        assert_eq!(
+            db.code_by_hash(B256::ZERO),
+            Err(MissingWitnessData::Code(B256::ZERO))
+        );
+    }
+
+    #[test]
+    fn records_first_reads() {
+        let contract = Address::with_last_byte(1);
+        let absent = Address::with_last_byte(2);
+        let code = Bytecode::new_raw([0x00].into());
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code.clone()));
+        db.insert_account_storage(contract, U256::ZERO, U256::from(7))
+            .unwrap();
+        let mut recorder = WitnessRecorder::new(db);
+
+        let info = recorder.basic(contract).unwrap().unwrap();
+        assert_eq!(info.code, Some(code.clone()));
+        assert_eq!(recorder.basic(absent), Ok(None));
+        assert_eq!(recorder.storage(contract, U256::ZERO), Ok(U256::from(7)));
+        assert_eq!(recorder.storage(absent, U256::ZERO), Ok(U256::ZERO));
+        let hash = recorder.block_hash(1).unwrap();
+
+        // Later reads do not overwrite the recorded values.
+        recorder
+            .db
+            .insert_account_storage(contract, U256::ZERO, U256::from(8))
+            .unwrap();
+        assert_eq!(recorder.storage(contract, U256::ZERO), Ok(U256::from(8)));
+
+        let witness = recorder.into_witness();
+        // Code is only kept by its hash.
+        assert_eq!(witness.accounts[&contract].as_ref().unwrap().code, None);
+        assert_eq!(witness.codes.len(), 1);
+
+        let mut db = WitnessDB::new(witness);
+        let info = db.basic(contract).unwrap().unwrap();
+        assert_eq!(db.code_by_hash(info.code_hash), Ok(code));
+        assert_eq!(db.basic(absent), Ok(None));
+        assert_eq!(db.storage(contract, U256::ZERO), Ok(U256::from(7)));
+        assert_eq!(db.block_hash(1), Ok(hash));
+    }
+}
@@ -0,0 +1,183 @@
+//! Debugger. Inspector that pauses execution at breakpoints and hands control to a callback.
+
+use crate::Inspector;
+use core::fmt;
+use revm::{
+    bytecode::opcode,
+    interpreter::Interpreter,
+    primitives::{Address, B256, U256},
+    EvmContext, EvmWiring, JournaledState,
+};
+use std::vec::Vec;
+
+/// Condition that pauses the [`Debugger`] before an instruction is executed.
+#[derive(Clone, Copy, Debug)]
+pub enum Breakpoint {
+    /// Instruction at the program counter of the code with the hash.
+    Pc {
+        /// Hash of the executed code.
+        code_hash: B256,
+        /// Program counter of the instruction.
+        pc: usize,
+    },
+    /// Every instruction with the opcode.
+    Opcode(u8),
+    /// `SLOAD` or `SSTORE` of the storage slot of the account.
+    Storage {
+        /// Address of the account.
+        address: Address,
+        /// Watched storage slot.
+        slot: U256,
+    },
+    /// Every instruction for which the condition holds.
+    Condition(fn(&Interpreter) -> bool),
+}
+
+impl Breakpoint {
+    /// Returns `true` if the next instruction of the interpreter hits the breakpoint.
+    pub fn is_hit(&self, interp: &Interpreter) -> bool {
+        match *self {
+            Self::Pc { code_hash, pc } => {
+                interp.contract.hash == Some(code_hash) && interp.program_counter() == pc
+            }
+            Self::Opcode(op) => interp.current_opcode() == op,
+            Self::Storage { address, slot } => {
+                matches!(interp.current_opcode(), opcode::SLOAD | opcode::SSTORE)
+                    && interp.contract.target_address == address
+                    && interp.stack.peek(0) == Ok(slot)
+            }
+            Self::Condition(condition) => condition(interp),
+        }
+    }
+}
+
+/// What the [`Debugger`] does after its callback returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DebugAction {
+    /// Runs until the next breakpoint is hit.
+    #[default]
+    Continue,
+    /// Pauses again before the next instruction, in any frame.
+    Step,
+}
+
+/// Inspector that pauses before instructions that hit a [`Breakpoint`] and calls the callback.
+///
+/// The callback receives the interpreter and the journaled state, which it can inspect and
+/// modify, and the index of the hit breakpoint, or `None` if the debugger is stepping. Its [`DebugAction`]
+/// decides where execution pauses next. If several breakpoints are hit, the first is reported.
+///
+/// Needs [`crate::inspector_handle_register`] to be registered.
+pub struct Debugger<F> {
+    breakpoints: Vec<Breakpoint>,
+    callback: F,
+    stepping: bool,
+}
+
+impl<F> fmt::Debug for Debugger<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .field("stepping", &self.stepping)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Debugger<F> {
+    /// Creates a debugger with the breakpoints and the callback.
+    pub fn new(breakpoints: Vec<Breakpoint>, callback: F) -> Self {
+        Self {
+            breakpoints,
+            callback,
+            stepping: false,
+        }
+    }
+
+    /// Returns the breakpoints.
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Returns the mutable breakpoints, they can be changed between instructions.
+    pub fn breakpoints_mut(&mut self) -> &mut Vec<Breakpoint> {
+        &mut self.breakpoints
+    }
+}
+
+impl<EvmWiringT, F> Inspector<EvmWiringT> for Debugger<F>
+where
+    EvmWiringT: EvmWiring,
+    F: FnMut(&mut Interpreter, &mut JournaledState, Option<usize>) -> DebugAction,
+{
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        let hit = self.breakpoints.iter().position(|b| b.is_hit(interp));
+        if hit.is_none() && !self.stepping {
+            return;
+        }
+        self.stepping =
+            (self.callback)(interp, &mut context.journaled_state, hit) == DebugAction::Step;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{
+            opcode::{PUSH1, SLOAD, SSTORE, STOP},
+            Bytecode,
+        },
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm,
+    };
+    use std::vec;
+
+    #[test]
+    fn pauses_at_breakpoints() {
+        let contract = Address::ZERO;
+        let bytecode =
+            Bytecode::new_raw([PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, SLOAD, STOP].into());
+        let code_hash = bytecode.hash_slow();
+
+        let mut paused = Vec::new();
+        let debugger = Debugger::new(
+            vec![
+                Breakpoint::Opcode(SLOAD),
+                Breakpoint::Storage {
+                    address: contract,
+                    slot: U256::ZERO,
+                },
+                Breakpoint::Pc { code_hash, pc: 0 },
+            ],
+            |interp: &mut Interpreter, _: &mut JournaledState, hit| {
+                paused.push((interp.program_counter(), hit));
+                if interp.program_counter() == 0 {
+                    DebugAction::Step
+                } else {
+                    DebugAction::Continue
+                }
+            },
+        );
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, _>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(debugger)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        drop(evm);
+
+        assert_eq!(
+            paused,
+            [(0, Some(2)), (2, None), (4, Some(1)), (7, Some(0))]
+        );
+    }
+}
@@ -3,7 +3,7 @@ use core::cell::RefCell;
 use revm::{
     bytecode::opcode,
     handler::register::EvmHandler,
-    interpreter::{table::DynInstruction, InstructionResult, Interpreter},
+    interpreter::{table::DynInstruction, CallInput, InstructionResult, Interpreter},
     wiring::result::EVMResultGeneric,
     Context, EvmWiring, FrameOrResult, FrameResult, JournalEntry,
 };
@@ -80,12 +80,14 @@ pub fn inspector_handle_register<
                     .get_inspector()
                     .selfdestruct(*address, *target, *had_balance);
             }
-            Some(JournalEntry::BalanceTransfer {
-                from, to, balance, ..
+            Some(JournalEntry::SelfdestructCredit {
+                address,
+                target,
+                balance,
             }) => {
                 host.external
                     .get_inspector()
-                    .selfdestruct(*from, *to, *balance);
+                    .selfdestruct(*address, *target, *balance);
             }
             _ => {}
         }
@@ -123,7 +125,11 @@ pub fn inspector_handle_register<
     // Call handler
     let call_input_stack_inner = call_input_stack.clone();
     let prev_handle = handler.execution.call.clone();
-    handler.execution.call = Arc::new(move |ctx, mut inputs| {
+    handler.execution.call = Arc::new(move |ctx, mut inputs, shared_memory| {
+        // Inspectors see the call data, copy it out of the memory of the caller.
+        if let CallInput::SharedBuffer(_) = inputs.input {
+            inputs.input = CallInput::Bytes(inputs.input.bytes(shared_memory));
+        }
         // Call inspector to change input or return outcome.
         let outcome = ctx.external.get_inspector().call(&mut ctx.evm, &mut inputs);
         call_input_stack_inner.borrow_mut().push(inputs.clone());
@@ -131,11 +137,31 @@ pub fn inspector_handle_register<
             return Ok(FrameOrResult::Result(FrameResult::Call(outcome)));
         }
 
-        let mut frame_or_result = prev_handle(ctx, inputs);
-        if let Ok(FrameOrResult::Frame(frame)) = &mut frame_or_result {
-            ctx.external
+        let is_precompile = ctx.evm.precompiles.contains(&inputs.bytecode_address);
+        if is_precompile {
+            ctx.external.get_inspector().precompile_call(
+                &mut ctx.evm,
+                &inputs.bytecode_address,
+                &inputs.input.bytes(shared_memory),
+                inputs.gas_limit,
+            );
+        }
+
+        let mut frame_or_result = prev_handle(ctx, inputs, shared_memory);
+        match &mut frame_or_result {
+            Ok(FrameOrResult::Frame(frame)) => ctx
+                .external
                 .get_inspector()
-                .initialize_interp(frame.interpreter_mut(), &mut ctx.evm)
+                .initialize_interp(frame.interpreter_mut(), &mut ctx.evm),
+            // Precompiles are executed in place and return a result instead of a frame.
+            Ok(FrameOrResult::Result(FrameResult::Call(outcome))) if is_precompile => {
+                ctx.external.get_inspector().precompile_end(
+                    &mut ctx.evm,
+                    &outcome.result.output,
+                    outcome.result.gas.spent(),
+                )
+            }
+            _ => {}
         }
         frame_or_result
     });
@@ -269,7 +295,7 @@ mod tests {
         bytecode::{opcode, Bytecode},
         database_interface::EmptyDB,
         interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
-        primitives::{address, Bytes, TxKind},
+        primitives::{address, Address, Bytes, TxKind, U256},
         wiring::{DefaultEthereumWiring, EthereumWiring, EvmWiring as PrimitiveEvmWiring},
         Evm, EvmContext, EvmWiring,
     };
@@ -396,6 +422,185 @@ mod tests {
         assert!(inspector.call_end);
     }
 
+    #[derive(Default, Debug)]
+    struct PrecompileInspector {
+        calls: Vec<(Address, Bytes, u64)>,
+        ends: Vec<(Bytes, u64)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for PrecompileInspector {
+        fn precompile_call(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            address: &Address,
+            input: &Bytes,
+            gas: u64,
+        ) {
+            self.calls.push((*address, input.clone(), gas));
+        }
+
+        fn precompile_end(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            output: &Bytes,
+            gas_used: u64,
+        ) {
+            self.ends.push((output.clone(), gas_used));
+        }
+    }
+
+    #[test]
+    fn test_inspector_precompile_hooks() {
+        // Calls the identity precompile with 32 zero bytes and 0x1000 gas.
+        let bytecode = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x20,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x04,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::STATICCALL,
+            opcode::STOP,
+        ]));
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, PrecompileInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = evm.into_context().external;
+        let input = Bytes::from(vec![0; 32]);
+        assert_eq!(
+            inspector.calls,
+            [(
+                address!("0000000000000000000000000000000000000004"),
+                input.clone(),
+                0x1000
+            )]
+        );
+        // 15 base gas and 3 gas per word.
+        assert_eq!(inspector.ends, [(input, 18)]);
+    }
+
+    #[derive(Default, Debug)]
+    struct FrameInspector {
+        calls: Vec<(u64, u64, bool, Option<U256>)>,
+        creates: Vec<(u64, U256)>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for FrameInspector {
+        fn call(
+            &mut self,
+            context: &mut EvmContext<EvmWiringT>,
+            inputs: &mut CallInputs,
+        ) -> Option<CallOutcome> {
+            // The frame is not created yet.
+            assert_eq!(inputs.depth, context.journaled_state.depth() + 1);
+            self.calls.push((
+                inputs.depth,
+                inputs.gas_limit,
+                inputs.is_static,
+                inputs.transfer_value(),
+            ));
+            None
+        }
+
+        fn create(
+            &mut self,
+            _context: &mut EvmContext<EvmWiringT>,
+            inputs: &mut CreateInputs,
+        ) -> Option<CreateOutcome> {
+            self.creates.push((inputs.depth, inputs.value));
+            None
+        }
+    }
+
+    #[test]
+    fn test_inspector_frame_info() {
+        // Sends 1 wei to the identity precompile with 0x1000 gas, static calls it with 0x1000
+        // gas and creates an empty contract with 2 wei.
+        let bytecode = Bytecode::new_raw(Bytes::from(vec![
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x04,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x04,
+            opcode::PUSH2,
+            0x10,
+            0x00,
+            opcode::STATICCALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x02,
+            opcode::CREATE,
+            opcode::STOP,
+        ]));
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, FrameInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let inspector = evm.into_context().external;
+        assert_eq!(
+            inspector.calls,
+            [
+                (1, 100_000 - 21_000, false, Some(U256::ZERO)),
+                // Call stipend is added to the gas limit.
+                (2, 0x1000 + 2300, false, Some(U256::from(1))),
+                (2, 0x1000, true, Some(U256::ZERO)),
+            ]
+        );
+        assert_eq!(inspector.creates, [(2, U256::from(2))]);
+    }
+
     #[test]
     fn test_inspector_reg() {
         let mut noop = NoOpInspector;
@@ -3,7 +3,7 @@ use revm::{
     interpreter::{
         CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
     },
-    primitives::{Address, Log, U256},
+    primitives::{Address, Bytes, Log, U256},
     EvmContext, EvmWiring,
 };
 
@@ -48,6 +48,22 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
         let _ = context;
     }
 
+    /// Called when a watchpoint registered with [`crate::watchpoint_handle_register`] is hit.
+    ///
+    /// `index` is the position of the watchpoint in the registered list. It is called after the
+    /// instruction that hit the watchpoint has been executed.
+    #[inline]
+    fn watchpoint(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<EvmWiringT>,
+        index: usize,
+    ) {
+        let _ = interp;
+        let _ = context;
+        let _ = index;
+    }
+
     /// Called when a log is emitted.
     #[inline]
     fn log(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>, log: &Log) {
@@ -87,6 +103,39 @@ pub trait Inspector<EvmWiringT: EvmWiring> {
         outcome
     }
 
+    /// Called when a precompile is about to be executed, after [`Inspector::call`].
+    ///
+    /// `gas` is the gas limit of the call.
+    #[inline]
+    fn precompile_call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        address: &Address,
+        input: &Bytes,
+        gas: u64,
+    ) {
+        let _ = context;
+        let _ = address;
+        let _ = input;
+        let _ = gas;
+    }
+
+    /// Called when a precompile has been executed, before [`Inspector::call_end`].
+    ///
+    /// `gas_used` is zero if the call failed before the precompile was run, e.g. because of the
+    /// call depth limit.
+    #[inline]
+    fn precompile_end(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        output: &Bytes,
+        gas_used: u64,
+    ) {
+        let _ = context;
+        let _ = output;
+        let _ = gas_used;
+    }
+
     /// Called when a contract is about to be created.
     ///
     /// If this returns `Some` then the [CreateOutcome] is used to override the result of the creation.
@@ -7,22 +7,39 @@ extern crate alloc as std;
 
 #[cfg(feature = "std")]
 mod customprinter;
+mod debugger;
 #[cfg(all(feature = "std", feature = "serde-json"))]
 mod eip3155;
 mod gas;
 mod handler_register;
 mod inspector;
 mod noop;
+mod opcode_coverage;
+mod pc_coverage;
+mod replay;
+#[cfg(feature = "tracing")]
+mod span_tracer;
+mod watchpoint;
 
+pub use debugger::{Breakpoint, DebugAction};
 pub use handler_register::{inspector_handle_register, GetInspector};
 pub use inspector::Inspector;
+pub use opcode_coverage::{OpcodeCount, OpcodeCoverageReport, SpecCoverage};
+pub use pc_coverage::CoverageBitmap;
+pub use replay::{replay_block, ReplayedTransaction};
+pub use watchpoint::{watchpoint_handle_register, StackCondition, Watchpoint};
 
 /// [Inspector] implementations.
 pub mod inspectors {
     #[cfg(feature = "std")]
     pub use super::customprinter::CustomPrintTracer;
+    pub use super::debugger::Debugger;
     #[cfg(all(feature = "std", feature = "serde-json"))]
     pub use super::eip3155::TracerEip3155;
     pub use super::gas::GasInspector;
     pub use super::noop::NoOpInspector;
+    pub use super::opcode_coverage::OpcodeCoverage;
+    pub use super::pc_coverage::PcCoverage;
+    #[cfg(feature = "tracing")]
+    pub use super::span_tracer::SpanTracer;
 }
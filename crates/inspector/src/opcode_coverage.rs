@@ -0,0 +1,184 @@
+//! OpcodeCoverage. Inspector that records executed opcodes for each specification.
+
+use crate::Inspector;
+use revm::{
+    bytecode::opcode::OPCODE_INFO, interpreter::Interpreter, specification::hardfork::SpecId,
+    EvmContext, EvmWiring,
+};
+use std::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// [Inspector] that counts executed opcodes for each specification.
+///
+/// Counts of multiple runs can be combined with [`OpcodeCoverage::merge`] and
+/// [`OpcodeCoverage::report`] lists executed and missed opcodes, so fuzzing campaigns can
+/// check which instructions were reached before trusting the results.
+#[derive(Clone, Debug, Default)]
+pub struct OpcodeCoverage {
+    counts: BTreeMap<SpecId, Box<[u64; 256]>>,
+}
+
+impl OpcodeCoverage {
+    /// Returns how many times the opcode was executed in the given specification.
+    pub fn count(&self, spec_id: SpecId, opcode: u8) -> u64 {
+        self.counts
+            .get(&spec_id)
+            .map_or(0, |counts| counts[opcode as usize])
+    }
+
+    /// Adds counts of the other coverage to this one.
+    pub fn merge(&mut self, other: &Self) {
+        for (spec_id, other_counts) in &other.counts {
+            let counts = self
+                .counts
+                .entry(*spec_id)
+                .or_insert_with(|| Box::new([0; 256]));
+            for (count, other) in counts.iter_mut().zip(other_counts.iter()) {
+                *count += other;
+            }
+        }
+    }
+
+    /// Creates the report for all specifications that were executed.
+    pub fn report(&self) -> OpcodeCoverageReport {
+        let specs = self
+            .counts
+            .iter()
+            .map(|(spec_id, counts)| {
+                let mut executed = Vec::new();
+                let mut missed = Vec::new();
+                for (opcode, count) in counts.iter().enumerate() {
+                    let info = OPCODE_INFO[opcode];
+                    if *count > 0 {
+                        executed.push(OpcodeCount {
+                            opcode: opcode as u8,
+                            name: info.map_or_else(
+                                || format!("UNKNOWN(0x{opcode:02X})"),
+                                |info| info.name().to_string(),
+                            ),
+                            count: *count,
+                        });
+                    } else if let Some(info) = info.filter(|info| info.is_enabled_in(*spec_id)) {
+                        missed.push(info.name().to_string());
+                    }
+                }
+                SpecCoverage {
+                    spec_id: *spec_id,
+                    executed,
+                    missed,
+                }
+            })
+            .collect();
+        OpcodeCoverageReport { specs }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for OpcodeCoverage {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        let counts = self
+            .counts
+            .entry(context.spec_id())
+            .or_insert_with(|| Box::new([0; 256]));
+        counts[interp.current_opcode() as usize] += 1;
+    }
+}
+
+/// Opcode coverage report created by [`OpcodeCoverage::report`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpcodeCoverageReport {
+    /// Coverage of each executed specification, in ascending order.
+    pub specs: Vec<SpecCoverage>,
+}
+
+/// Opcode coverage of a single specification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpecCoverage {
+    /// Specification the opcodes were executed in.
+    pub spec_id: SpecId,
+    /// Executed opcodes, in ascending order.
+    pub executed: Vec<OpcodeCount>,
+    /// Names of the opcodes available in the specification that were not executed.
+    pub missed: Vec<String>,
+}
+
+/// Number of executions of an opcode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpcodeCount {
+    /// Opcode byte.
+    pub opcode: u8,
+    /// Opcode name.
+    pub name: String,
+    /// Number of executions.
+    pub count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    #[test]
+    fn records_coverage() {
+        let bytecode = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x02,
+                opcode::ADD,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, OpcodeCoverage>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let mut coverage = evm.into_context().external;
+        assert_eq!(coverage.count(SpecId::CANCUN, opcode::PUSH1), 2);
+        assert_eq!(coverage.count(SpecId::CANCUN, opcode::ADD), 1);
+        assert_eq!(coverage.count(SpecId::SHANGHAI, opcode::ADD), 0);
+
+        coverage.merge(&coverage.clone());
+        let report = coverage.report();
+        assert_eq!(report.specs.len(), 1);
+        let spec = &report.specs[0];
+        assert_eq!(spec.spec_id, SpecId::CANCUN);
+        assert_eq!(
+            spec.executed[1],
+            OpcodeCount {
+                opcode: opcode::ADD,
+                name: "ADD".to_string(),
+                count: 2,
+            }
+        );
+        assert!(spec.missed.iter().any(|name| name == "CALLCODE"));
+        // Not available in Cancun.
+        assert!(!spec.missed.iter().any(|name| name == "RJUMP"));
+    }
+}
@@ -0,0 +1,221 @@
+//! PcCoverage. Inspector that records executed program counters of each contract.
+
+use crate::Inspector;
+use revm::{interpreter::Interpreter, primitives::B256, EvmContext, EvmWiring};
+use std::{collections::BTreeMap, vec, vec::Vec};
+
+/// Bitmap of the program counters of a contract that were executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl CoverageBitmap {
+    /// Creates an empty bitmap for code of the given length.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    /// Returns the length of the code.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the code is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Marks the program counter as executed.
+    ///
+    /// Returns `true` if it was not executed before. Program counters past the end of the
+    /// code are ignored.
+    pub fn insert(&mut self, pc: usize) -> bool {
+        if pc >= self.len {
+            return false;
+        }
+        let word = &mut self.words[pc / 64];
+        let bit = 1 << (pc % 64);
+        let is_new = *word & bit == 0;
+        *word |= bit;
+        is_new
+    }
+
+    /// Returns `true` if the program counter was executed.
+    pub fn contains(&self, pc: usize) -> bool {
+        self.words
+            .get(pc / 64)
+            .is_some_and(|word| word & (1 << (pc % 64)) != 0)
+    }
+
+    /// Returns the number of executed program counters.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the executed program counters in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|pc| self.contains(*pc))
+    }
+
+    /// Returns the raw bitmap, bit `pc % 64` of word `pc / 64` is set if `pc` was executed.
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Adds the executed program counters of the other bitmap to this one.
+    ///
+    /// Returns the number of program counters that were not executed before.
+    pub fn merge(&mut self, other: &Self) -> usize {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        self.len = self.len.max(other.len);
+        let mut new = 0;
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            new += (other & !*word).count_ones() as usize;
+            *word |= other;
+        }
+        new
+    }
+}
+
+/// [Inspector] that records which program counters of each contract were executed.
+///
+/// Contracts are keyed by their code hash, so the same code deployed at different addresses
+/// shares a bitmap. Coverage-guided fuzzers can use the number of new program counters
+/// returned by [`PcCoverage::merge`] to decide whether an input is interesting, without the
+/// cost of a full struct log.
+///
+/// Only legacy bytecode is recorded, EOF program counters are relative to a code section.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PcCoverage {
+    contracts: BTreeMap<B256, CoverageBitmap>,
+}
+
+impl PcCoverage {
+    /// Returns the bitmap of the contract with the given code hash.
+    pub fn get(&self, code_hash: &B256) -> Option<&CoverageBitmap> {
+        self.contracts.get(code_hash)
+    }
+
+    /// Returns the bitmaps of all executed contracts.
+    pub fn contracts(&self) -> &BTreeMap<B256, CoverageBitmap> {
+        &self.contracts
+    }
+
+    /// Adds the coverage of the other inspector to this one.
+    ///
+    /// Returns the number of program counters that were not executed before.
+    pub fn merge(&mut self, other: &Self) -> usize {
+        other
+            .contracts
+            .iter()
+            .map(|(hash, bitmap)| self.contracts.entry(*hash).or_default().merge(bitmap))
+            .sum()
+    }
+
+    /// Clears the recorded coverage.
+    pub fn clear(&mut self) {
+        self.contracts.clear();
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for PcCoverage {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        if interp.is_eof {
+            return;
+        }
+        let Some(hash) = interp.contract.hash else {
+            return;
+        };
+        self.contracts
+            .entry(hash)
+            .or_insert_with(|| CoverageBitmap::new(interp.bytecode.len()))
+            .insert(interp.program_counter());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    #[test]
+    fn bitmap() {
+        let mut bitmap = CoverageBitmap::new(70);
+        assert!(bitmap.insert(0));
+        assert!(bitmap.insert(65));
+        assert!(!bitmap.insert(65));
+        assert!(!bitmap.insert(70));
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), [0, 65]);
+        assert_eq!(bitmap.as_words(), [1, 2]);
+
+        let mut other = CoverageBitmap::new(70);
+        other.insert(0);
+        other.insert(1);
+        assert_eq!(bitmap.merge(&other), 1);
+        assert_eq!(bitmap.count(), 3);
+
+        // Longer code within the same word count extends the length.
+        let mut longer = CoverageBitmap::new(100);
+        longer.insert(99);
+        assert_eq!(bitmap.merge(&longer), 1);
+        assert_eq!(bitmap.len(), 100);
+        assert_eq!(bitmap.iter().last(), Some(99));
+    }
+
+    #[test]
+    fn records_coverage() {
+        let bytecode = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x06,
+                opcode::JUMPI,
+                opcode::INVALID,
+                opcode::JUMPDEST,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let hash = bytecode.hash_slow();
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, PcCoverage>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap();
+
+        let coverage = evm.into_context().external;
+        let bitmap = coverage.get(&hash).unwrap();
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), [0, 2, 4, 6, 7]);
+        assert!(!bitmap.contains(5));
+
+        let mut merged = PcCoverage::default();
+        assert_eq!(merged.merge(&coverage), 5);
+        assert_eq!(merged.merge(&coverage), 0);
+    }
+}
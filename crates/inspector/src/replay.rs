@@ -0,0 +1,271 @@
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
+    },
+    primitives::{Address, Bytes, Log, U256},
+    wiring::result::{EVMResultGeneric, ExecutionResult},
+    DatabaseCommit, Evm, EvmContext, EvmWiring,
+};
+use std::vec::Vec;
+
+/// Forwards the callbacks to the inner inspector if it is set.
+impl<EvmWiringT: EvmWiring, INSP: Inspector<EvmWiringT>> Inspector<EvmWiringT> for Option<INSP> {
+    #[inline]
+    fn initialize_interp(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<EvmWiringT>,
+    ) {
+        if let Some(inspector) = self {
+            inspector.initialize_interp(interp, context);
+        }
+    }
+
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        if let Some(inspector) = self {
+            inspector.step(interp, context);
+        }
+    }
+
+    #[inline]
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>) {
+        if let Some(inspector) = self {
+            inspector.step_end(interp, context);
+        }
+    }
+
+    #[inline]
+    fn watchpoint(
+        &mut self,
+        interp: &mut Interpreter,
+        context: &mut EvmContext<EvmWiringT>,
+        index: usize,
+    ) {
+        if let Some(inspector) = self {
+            inspector.watchpoint(interp, context, index);
+        }
+    }
+
+    #[inline]
+    fn log(&mut self, interp: &mut Interpreter, context: &mut EvmContext<EvmWiringT>, log: &Log) {
+        if let Some(inspector) = self {
+            inspector.log(interp, context, log);
+        }
+    }
+
+    #[inline]
+    fn call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.as_mut()
+            .and_then(|inspector| inspector.call(context, inputs))
+    }
+
+    #[inline]
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        match self {
+            Some(inspector) => inspector.call_end(context, inputs, outcome),
+            None => outcome,
+        }
+    }
+
+    #[inline]
+    fn precompile_call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        address: &Address,
+        input: &Bytes,
+        gas: u64,
+    ) {
+        if let Some(inspector) = self {
+            inspector.precompile_call(context, address, input, gas);
+        }
+    }
+
+    #[inline]
+    fn precompile_end(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        output: &Bytes,
+        gas_used: u64,
+    ) {
+        if let Some(inspector) = self {
+            inspector.precompile_end(context, output, gas_used);
+        }
+    }
+
+    #[inline]
+    fn create(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.as_mut()
+            .and_then(|inspector| inspector.create(context, inputs))
+    }
+
+    #[inline]
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        match self {
+            Some(inspector) => inspector.create_end(context, inputs, outcome),
+            None => outcome,
+        }
+    }
+
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.as_mut()
+            .and_then(|inspector| inspector.eofcreate(context, inputs))
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        match self {
+            Some(inspector) => inspector.eofcreate_end(context, inputs, outcome),
+            None => outcome,
+        }
+    }
+
+    #[inline]
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if let Some(inspector) = self {
+            inspector.selfdestruct(contract, target, value);
+        }
+    }
+}
+
+/// Result of a transaction executed by [`replay_block`] and its inspector.
+pub type ReplayedTransaction<EvmWiringT, INSP> = (
+    ExecutionResult<<EvmWiringT as revm::wiring::EvmWiring>::HaltReason>,
+    Option<INSP>,
+);
+
+/// Executes the transactions of a block in order and inspects only the selected ones.
+///
+/// `inspector` is called with the index of every transaction and returns the inspector to
+/// attach to it, or `None` to execute it without one. Every transaction is committed before
+/// the next one is executed, so an inspected transaction sees the state left by the preceding
+/// ones, as it did in the block.
+///
+/// The EVM has to be built with an `Option` of the inspector as the external context and
+/// the [`crate::inspector_handle_register`]. The block environment is not changed and the
+/// external context is `None` when this returns.
+///
+/// Returns the result of every transaction together with its inspector, or the first error.
+pub fn replay_block<EvmWiringT, INSP>(
+    evm: &mut Evm<'_, EvmWiringT>,
+    txs: impl IntoIterator<Item = EvmWiringT::Transaction>,
+    mut inspector: impl FnMut(usize) -> Option<INSP>,
+) -> EVMResultGeneric<Vec<ReplayedTransaction<EvmWiringT, INSP>>, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<ExternalContext = Option<INSP>, Database: DatabaseCommit>,
+    INSP: Inspector<EvmWiringT>,
+{
+    let mut replayed = Vec::new();
+    for (index, tx) in txs.into_iter().enumerate() {
+        *evm.tx_mut() = tx;
+        evm.context.external = inspector(index);
+        let result = evm.transact_commit();
+        let inspector = evm.context.external.take();
+        replayed.push((result?, inspector));
+    }
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::InMemoryDB;
+    use revm::{
+        bytecode::{
+            opcode::{ADD, PUSH1, SLOAD, SSTORE, STOP},
+            Bytecode,
+        },
+        primitives::{address, TxKind},
+        state::AccountInfo,
+        wiring::{default::TxEnv, EthereumWiring},
+    };
+
+    /// Records the values stored by `SSTORE`.
+    #[derive(Debug, Default)]
+    struct StoredValues(Vec<U256>);
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for StoredValues {
+        fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+            if interp.current_opcode() == SSTORE {
+                self.0.push(interp.stack.peek(1).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn inspects_selected_transactions() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("0000000000000000000000000000000000001000");
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::from(1_000_000_000),
+                ..Default::default()
+            },
+        );
+        // Increments the first storage slot.
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [
+                    PUSH1, 0x00, SLOAD, PUSH1, 0x01, ADD, PUSH1, 0x00, SSTORE, STOP,
+                ]
+                .into(),
+            )),
+        );
+
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, Option<StoredValues>>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .append_handler_register(inspector_handle_register)
+            .build();
+        let txs = (0..3).map(|nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(contract),
+            gas_limit: 100_000,
+            nonce,
+            ..Default::default()
+        });
+        let replayed = replay_block(&mut evm, txs, |index| {
+            (index == 1).then(StoredValues::default)
+        })
+        .unwrap();
+
+        assert_eq!(replayed.len(), 3);
+        assert!(replayed.iter().all(|(result, _)| result.is_success()));
+        assert!(replayed[0].1.is_none());
+        // Second transaction sees the slot written by the first one.
+        assert_eq!(replayed[1].1.as_ref().unwrap().0, [U256::from(2)]);
+        assert!(replayed[2].1.is_none());
+        assert!(evm.context.external.is_none());
+    }
+}
@@ -0,0 +1,248 @@
+//! Inspector that emits the execution as [`tracing`] spans and events.
+
+use crate::Inspector;
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, EOFCreateInputs, Interpreter,
+        InterpreterResult,
+    },
+    primitives::Address,
+    EvmContext, EvmWiring,
+};
+use tracing::{field, span::EnteredSpan};
+
+/// [Inspector] that emits [`tracing`] spans of the transaction and its call frames.
+///
+/// The outermost frame is an `INFO` span named `transaction`, nested frames are `DEBUG` spans
+/// named `frame`. Both record the gas used and the [`InstructionResult`] of the frame when it
+/// ends, so any `tracing` subscriber, e.g. an OpenTelemetry exporter, gets the execution
+/// without a custom inspector.
+///
+/// With [`Self::with_opcode_batches`] a `TRACE` event is emitted for every batch of executed
+/// opcodes as well.
+///
+/// [`InstructionResult`]: revm::interpreter::InstructionResult
+#[derive(Debug, Default)]
+pub struct SpanTracer {
+    /// Spans of the frames that are executing.
+    spans: Vec<EnteredSpan>,
+    /// Number of opcodes in a batch, `None` if no opcode events are emitted.
+    batch_size: Option<usize>,
+    /// Number of opcodes executed in the current batch.
+    batch_len: usize,
+}
+
+impl SpanTracer {
+    /// Creates a tracer that emits spans of the frames only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits a `TRACE` event after every `size` opcodes executed in a frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn with_opcode_batches(mut self, size: usize) -> Self {
+        assert!(size > 0, "opcode batch size must not be zero");
+        self.batch_size = Some(size);
+        self
+    }
+
+    /// Enters the span of a new frame.
+    fn enter_frame(
+        &mut self,
+        depth: u64,
+        kind: &'static str,
+        caller: Address,
+        target: Option<Address>,
+        gas_limit: u64,
+    ) {
+        self.flush_batch();
+        let target = target.map(field::display);
+        let span = if depth == 0 {
+            tracing::info_span!(
+                "transaction",
+                kind,
+                %caller,
+                target,
+                gas_limit,
+                gas_used = field::Empty,
+                result = field::Empty,
+            )
+        } else {
+            tracing::debug_span!(
+                "frame",
+                depth,
+                kind,
+                %caller,
+                target,
+                gas_limit,
+                gas_used = field::Empty,
+                result = field::Empty,
+            )
+        };
+        self.spans.push(span.entered());
+    }
+
+    /// Records the result of the frame and exits its span.
+    fn exit_frame(&mut self, result: &InterpreterResult) {
+        self.flush_batch();
+        if let Some(span) = self.spans.pop() {
+            span.record("gas_used", result.gas.spent());
+            span.record("result", field::debug(result.result));
+        }
+    }
+
+    /// Emits the opcodes of the unfinished batch.
+    fn flush_batch(&mut self) {
+        if self.batch_len > 0 {
+            tracing::trace!(opcodes = self.batch_len, "opcodes");
+            self.batch_len = 0;
+        }
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for SpanTracer {
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<EvmWiringT>) {
+        let Some(batch_size) = self.batch_size else {
+            return;
+        };
+        self.batch_len += 1;
+        if self.batch_len == batch_size {
+            tracing::trace!(
+                opcodes = batch_size,
+                pc = interp.program_counter(),
+                gas_remaining = interp.gas.remaining(),
+                "opcodes"
+            );
+            self.batch_len = 0;
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter_frame(
+            context.journaled_state.depth(),
+            "call",
+            inputs.caller,
+            Some(inputs.target_address),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit_frame(&outcome.result);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter_frame(
+            context.journaled_state.depth(),
+            "create",
+            inputs.caller,
+            None,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit_frame(&outcome.result);
+        outcome
+    }
+
+    fn eofcreate(
+        &mut self,
+        context: &mut EvmContext<EvmWiringT>,
+        inputs: &mut EOFCreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter_frame(
+            context.journaled_state.depth(),
+            "eofcreate",
+            inputs.caller,
+            None,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn eofcreate_end(
+        &mut self,
+        _context: &mut EvmContext<EvmWiringT>,
+        _inputs: &EOFCreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit_frame(&outcome.result);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inspector_handle_register;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::{opcode, Bytecode},
+        primitives::TxKind,
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    #[test]
+    fn closes_all_spans() {
+        // Calls the identity precompile.
+        let bytecode = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x04,
+                opcode::GAS,
+                opcode::STATICCALL,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, SpanTracer>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(SpanTracer::new().with_opcode_batches(4))
+            .modify_tx_env(|tx| {
+                tx.caller = Address::with_last_byte(1);
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        let tracer = &evm.context.external;
+        assert!(tracer.spans.is_empty());
+        assert_eq!(tracer.batch_len, 0);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{GetInspector, Inspector};
+use core::ops::Range;
+use revm::{
+    bytecode::opcode::{self, OpCode},
+    handler::register::HandleRegisterBox,
+    interpreter::{InstructionResult, Interpreter, Stack},
+    primitives::{Address, U256},
+    Context, EvmWiring,
+};
+use std::{boxed::Box, sync::Arc, vec, vec::Vec};
+
+/// Condition evaluated inside the interpreter that calls [`Inspector::watchpoint`] when hit.
+#[derive(Clone, Debug)]
+pub enum Watchpoint {
+    /// Storage slot of the account is written by `SSTORE`.
+    Storage {
+        /// Address of the account.
+        address: Address,
+        /// Watched storage slot.
+        slot: U256,
+    },
+    /// Memory range of the current call is changed by an instruction.
+    ///
+    /// Output of a sub call is copied to memory after the call instruction returns and
+    /// is not tracked.
+    Memory(Range<usize>),
+    /// Stack satisfies the condition after one of the opcodes is executed.
+    Stack {
+        /// Opcodes after which the condition is evaluated.
+        opcodes: Vec<u8>,
+        /// Condition on the stack.
+        condition: StackCondition,
+    },
+}
+
+/// Condition of a [`Watchpoint::Stack`].
+pub type StackCondition = fn(&Stack) -> bool;
+
+/// Indexes and conditions of the stack watchpoints of an opcode.
+type StackWatchpoints = Arc<[(usize, StackCondition)]>;
+
+/// Register that evaluates the given watchpoints and calls [`Inspector::watchpoint`] when
+/// one of them is hit.
+///
+/// Only instructions that can hit a watchpoint are wrapped: `SSTORE` for storage watchpoints,
+/// instructions that modify memory for memory watchpoints and the listed opcodes for stack
+/// watchpoints. It does not call `step`/`step_end` and can be used without
+/// [`crate::inspector_handle_register`].
+pub fn watchpoint_handle_register<'a, EvmWiringT>(
+    watchpoints: Vec<Watchpoint>,
+) -> HandleRegisterBox<'a, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<ExternalContext: GetInspector<EvmWiringT>>,
+{
+    let mut storage = Vec::new();
+    let mut memory = Vec::new();
+    // Stack watchpoints by opcode.
+    let mut stack: Vec<Vec<(usize, StackCondition)>> = vec![Vec::new(); 256];
+    for (index, watchpoint) in watchpoints.into_iter().enumerate() {
+        match watchpoint {
+            Watchpoint::Storage { address, slot } => storage.push((index, address, slot)),
+            Watchpoint::Memory(range) => memory.push((index, range)),
+            Watchpoint::Stack { opcodes, condition } => {
+                for op in opcodes {
+                    let watched = &mut stack[op as usize];
+                    if watched.last().map(|(last, _)| *last) != Some(index) {
+                        watched.push((index, condition));
+                    }
+                }
+            }
+        }
+    }
+    let storage: Arc<[(usize, Address, U256)]> = storage.into();
+    let memory: Arc<[(usize, Range<usize>)]> = memory.into();
+    let stack: Vec<(u8, StackWatchpoints)> = stack
+        .into_iter()
+        .enumerate()
+        .filter(|(_, watched)| !watched.is_empty())
+        .map(|(op, watched)| (op as u8, watched.into()))
+        .collect();
+
+    Box::new(move |handler| {
+        let table = &mut handler.instruction_table;
+
+        if !storage.is_empty() {
+            let storage = storage.clone();
+            table.update_boxed(opcode::SSTORE, move |prev, interpreter, host| {
+                let key = interpreter.stack.peek(0);
+                prev(interpreter, host);
+                let Ok(key) = key else { return };
+                if interpreter.instruction_result != InstructionResult::Continue {
+                    return;
+                }
+                let address = interpreter.contract.target_address;
+                for (index, _, _) in storage
+                    .iter()
+                    .filter(|(_, watched, slot)| *watched == address && *slot == key)
+                {
+                    hit(interpreter, host, *index);
+                }
+            });
+        }
+
+        if !memory.is_empty() {
+            for op in
+                (0..=u8::MAX).filter(|op| OpCode::new(*op).is_some_and(|op| op.modifies_memory()))
+            {
+                let memory = memory.clone();
+                table.update_boxed(op, move |prev, interpreter, host| {
+                    let before: Vec<_> = memory
+                        .iter()
+                        .map(|(_, range)| memory_range(interpreter, range))
+                        .collect();
+                    prev(interpreter, host);
+                    for ((index, range), before) in memory.iter().zip(before) {
+                        if memory_range(interpreter, range) != before {
+                            hit(interpreter, host, *index);
+                        }
+                    }
+                });
+            }
+        }
+
+        for (op, watched) in &stack {
+            let watched = watched.clone();
+            table.update_boxed(*op, move |prev, interpreter, host| {
+                prev(interpreter, host);
+                for (index, condition) in watched.iter() {
+                    if condition(&interpreter.stack) {
+                        hit(interpreter, host, *index);
+                    }
+                }
+            });
+        }
+    })
+}
+
+#[inline]
+fn hit<EvmWiringT>(interpreter: &mut Interpreter, host: &mut Context<EvmWiringT>, index: usize)
+where
+    EvmWiringT: EvmWiring<ExternalContext: GetInspector<EvmWiringT>>,
+{
+    host.external
+        .get_inspector()
+        .watchpoint(interpreter, &mut host.evm, index);
+}
+
+/// Returns the memory range, bytes that are not allocated are zero.
+#[inline]
+fn memory_range(interpreter: &Interpreter, range: &Range<usize>) -> Vec<u8> {
+    let memory = interpreter.shared_memory.context_memory();
+    let mut out = vec![0; range.len()];
+    if let Some(slice) = memory.get(range.start..range.end.min(memory.len())) {
+        out[..slice.len()].copy_from_slice(slice);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm, EvmContext,
+    };
+
+    #[derive(Default, Debug)]
+    struct WatchInspector {
+        hits: Vec<usize>,
+    }
+
+    impl<EvmWiringT: EvmWiring> Inspector<EvmWiringT> for WatchInspector {
+        fn watchpoint(
+            &mut self,
+            _interp: &mut Interpreter,
+            _context: &mut EvmContext<EvmWiringT>,
+            index: usize,
+        ) {
+            self.hits.push(index);
+        }
+    }
+
+    #[test]
+    fn watchpoints_are_hit() {
+        let contract = address!("0000000000000000000000000000000000000000");
+        let bytecode = Bytecode::new_raw(
+            [
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x00,
+                opcode::SSTORE,
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x40,
+                opcode::MSTORE,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::MSTORE,
+                opcode::STOP,
+            ]
+            .into(),
+        );
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, WatchInspector>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_external_context(WatchInspector::default())
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register_box(watchpoint_handle_register(vec![
+                Watchpoint::Storage {
+                    address: contract,
+                    slot: U256::ZERO,
+                },
+                Watchpoint::Storage {
+                    address: contract,
+                    slot: U256::from(1),
+                },
+                // Written with a non zero value.
+                Watchpoint::Memory(0x40..0x60),
+                // Written with zero, memory is not changed.
+                Watchpoint::Memory(0x00..0x20),
+                Watchpoint::Stack {
+                    opcodes: vec![opcode::PUSH1],
+                    condition: |stack| stack.len() == 2,
+                },
+                // Not evaluated after `PUSH1`.
+                Watchpoint::Stack {
+                    opcodes: vec![opcode::STOP],
+                    condition: |stack| stack.len() == 2,
+                },
+            ]))
+            .build();
+
+        evm.transact().unwrap();
+
+        let inspector = evm.into_context().external;
+        // Stack has two items after each pair of pushes.
+        assert_eq!(inspector.hits, vec![4, 0, 4, 2, 4]);
+    }
+}
@@ -2,9 +2,11 @@
 
 mod calc;
 mod constants;
+mod table;
 
 pub use calc::*;
 pub use constants::*;
+pub use table::*;
 
 /// Represents the state of gas during execution.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
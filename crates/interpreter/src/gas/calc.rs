@@ -1,4 +1,4 @@
-use super::constants::*;
+use super::{constants::*, GasTable};
 use crate::{num_words, AccountLoad, Eip7702CodeLoad, SStoreResult, SelfDestructResult, StateLoad};
 use primitives::U256;
 use specification::{eip7702, hardfork::SpecId};
@@ -15,55 +15,9 @@ macro_rules! tri {
 }
 
 /// `SSTORE` opcode refund calculation.
-#[allow(clippy::collapsible_else_if)]
 #[inline]
 pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
-    if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-3529: Reduction in refunds
-        let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
-            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
-        } else {
-            REFUND_SSTORE_CLEARS
-        };
-        if vals.is_new_eq_present() {
-            0
-        } else {
-            if vals.is_original_eq_present() && vals.is_new_zero() {
-                sstore_clears_schedule
-            } else {
-                let mut refund = 0;
-
-                if !vals.is_original_zero() {
-                    if vals.is_present_zero() {
-                        refund -= sstore_clears_schedule;
-                    } else if vals.is_new_zero() {
-                        refund += sstore_clears_schedule;
-                    }
-                }
-
-                if vals.is_original_eq_new() {
-                    let (gas_sstore_reset, gas_sload) = if spec_id.is_enabled_in(SpecId::BERLIN) {
-                        (SSTORE_RESET - COLD_SLOAD_COST, WARM_STORAGE_READ_COST)
-                    } else {
-                        (SSTORE_RESET, sload_cost(spec_id, false))
-                    };
-                    if vals.is_original_zero() {
-                        refund += (SSTORE_SET - gas_sload) as i64;
-                    } else {
-                        refund += (gas_sstore_reset - gas_sload) as i64;
-                    }
-                }
-
-                refund
-            }
-        }
-    } else {
-        if !vals.is_present_zero() && vals.is_new_zero() {
-            REFUND_SSTORE_CLEARS
-        } else {
-            0
-        }
-    }
+    GasTable::new(spec_id).sstore_refund(vals)
 }
 
 /// `CREATE2` opcode cost calculation.
@@ -172,67 +126,13 @@ pub const fn initcode_cost(len: u64) -> u64 {
 /// `SLOAD` opcode cost calculation.
 #[inline]
 pub const fn sload_cost(spec_id: SpecId, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        if is_cold {
-            COLD_SLOAD_COST
-        } else {
-            WARM_STORAGE_READ_COST
-        }
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // EIP-1884: Repricing for trie-size-dependent opcodes
-        INSTANBUL_SLOAD_GAS
-    } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
-        // EIP-150: Gas cost changes for IO-heavy operations
-        200
-    } else {
-        50
-    }
+    GasTable::new(spec_id).sload_cost(is_cold)
 }
 
 /// `SSTORE` opcode cost calculation.
 #[inline]
 pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
-    if spec_id.is_enabled_in(SpecId::BERLIN) {
-        // Berlin specification logic
-        let mut gas_cost = istanbul_sstore_cost::<WARM_STORAGE_READ_COST, WARM_SSTORE_RESET>(vals);
-
-        if is_cold {
-            gas_cost += COLD_SLOAD_COST;
-        }
-        gas_cost
-    } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-        // Istanbul logic
-        istanbul_sstore_cost::<INSTANBUL_SLOAD_GAS, SSTORE_RESET>(vals)
-    } else {
-        // Frontier logic
-        frontier_sstore_cost(vals)
-    }
-}
-
-/// EIP-2200: Structured Definitions for Net Gas Metering
-#[inline]
-fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
-    vals: &SStoreResult,
-) -> u64 {
-    if vals.is_new_eq_present() {
-        SLOAD_GAS
-    } else if vals.is_original_eq_present() && vals.is_original_zero() {
-        SSTORE_SET
-    } else if vals.is_original_eq_present() {
-        SSTORE_RESET_GAS
-    } else {
-        SLOAD_GAS
-    }
-}
-
-/// Frontier sstore cost just had two cases set and reset values.
-#[inline]
-fn frontier_sstore_cost(vals: &SStoreResult) -> u64 {
-    if vals.is_present_zero() && !vals.is_new_zero() {
-        SSTORE_SET
-    } else {
-        SSTORE_RESET
-    }
+    GasTable::new(spec_id).sstore_cost(vals, is_cold)
 }
 
 /// `SELFDESTRUCT` opcode cost calculation.
@@ -356,6 +256,45 @@ pub const fn memory_gas(num_words: u64) -> u64 {
         .saturating_add(num_words.saturating_mul(num_words) / 512)
 }
 
+/// Memory expansion cost of growing the memory from `current_len` to `new_len` bytes.
+///
+/// Zero if the memory does not grow.
+#[inline]
+pub const fn memory_expansion_cost(current_len: usize, new_len: usize) -> u64 {
+    memory_gas_for_len(new_len).saturating_sub(memory_gas_for_len(current_len))
+}
+
+/// Cost of the transaction input data.
+#[inline]
+pub fn calldata_cost(spec_id: SpecId, input: &[u8]) -> u64 {
+    calldata_tokens(input, spec_id.is_enabled_in(SpecId::ISTANBUL)) * STANDARD_TOKEN_COST
+}
+
+/// Number of calldata tokens of the input, as defined by EIP-7623.
+///
+/// Zero bytes count as one token, non zero bytes as four tokens since Istanbul and as
+/// seventeen tokens before. Every token costs [`STANDARD_TOKEN_COST`] gas.
+#[inline]
+pub fn calldata_tokens(input: &[u8], is_istanbul: bool) -> u64 {
+    let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
+    let non_zero_data_len = input.len() as u64 - zero_data_len;
+
+    // EIP-2028: Transaction data gas cost reduction
+    let non_zero_data_cost = if is_istanbul {
+        TRANSACTION_NON_ZERO_DATA_INIT
+    } else {
+        TRANSACTION_NON_ZERO_DATA_FRONTIER
+    };
+    zero_data_len + non_zero_data_len * (non_zero_data_cost / STANDARD_TOKEN_COST)
+}
+
+/// EIP-7623: Minimum gas a transaction with the input is charged, regardless of its execution.
+///
+/// The gas limit of the transaction has to cover it too.
+pub fn calldata_floor_gas(input: &[u8]) -> u64 {
+    TRANSACTION_BASE + calldata_tokens(input, true) * TOTAL_COST_FLOOR_PER_TOKEN
+}
+
 /// Initial gas that is deducted for transaction to be included.
 /// Initial gas contains initial stipend gas, gas for access list and input data.
 pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
@@ -365,19 +304,8 @@ pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
     access_list: Option<&AccessListT>,
     authorization_list_num: u64,
 ) -> u64 {
-    let mut initial_gas = 0;
-    let zero_data_len = input.iter().filter(|v| **v == 0).count() as u64;
-    let non_zero_data_len = input.len() as u64 - zero_data_len;
-
     // initdate stipend
-    initial_gas += zero_data_len * TRANSACTION_ZERO_DATA;
-    // EIP-2028: Transaction data gas cost reduction
-    initial_gas += non_zero_data_len
-        * if spec_id.is_enabled_in(SpecId::ISTANBUL) {
-            16
-        } else {
-            68
-        };
+    let mut initial_gas = calldata_cost(spec_id, input);
 
     // get number of access list account and storages.
     if let Some(access_list) = access_list {
@@ -392,10 +320,10 @@ pub fn validate_initial_tx_gas<AccessListT: AccessListTrait>(
             // EIP-2: Homestead Hard-fork Changes
             53000
         } else {
-            21000
+            TRANSACTION_BASE
         }
     } else {
-        21000
+        TRANSACTION_BASE
     };
 
     // EIP-3860: Limit and meter initcode
@@ -0,0 +1,264 @@
+use super::constants::*;
+use crate::SStoreResult;
+use specification::hardfork::SpecId;
+
+/// Storage access pricing of the `SLOAD` and `SSTORE` opcodes.
+///
+/// [`GasTable::new`] returns the mainnet schedule of a spec, which is what
+/// [`sload_cost`](super::sload_cost), [`sstore_cost`](super::sstore_cost) and
+/// [`sstore_refund`](super::sstore_refund) use. Chains with a modified schedule can provide
+/// their own table to the host, see `HostRead::gas_table`, instead of replacing the
+/// instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GasTable {
+    /// Cost of `SLOAD` of a warm slot. Before Berlin all slots are priced as warm.
+    ///
+    /// With net gas metering it is also the cost of a no-op `SSTORE`.
+    pub sload: u64,
+    /// Cost of `SLOAD` of a cold slot and the surcharge of `SSTORE` to a cold slot.
+    ///
+    /// `None` if there is no warm and cold access distinction, before Berlin.
+    pub cold_sload: Option<u64>,
+    /// Cost of `SSTORE` that sets a zero slot to a non-zero value.
+    pub sstore_set: u64,
+    /// Cost of `SSTORE` that changes a non-zero slot, excluding the cold surcharge.
+    pub sstore_reset: u64,
+    /// Refund of `SSTORE` that clears a slot.
+    pub sstore_clears_schedule: i64,
+    /// Whether `SSTORE` uses net gas metering, see EIP-2200.
+    pub net_metering: bool,
+}
+
+impl GasTable {
+    /// Returns the mainnet gas table of the given spec.
+    pub const fn new(spec_id: SpecId) -> Self {
+        let sload = if spec_id.is_enabled_in(SpecId::BERLIN) {
+            WARM_STORAGE_READ_COST
+        } else if spec_id.is_enabled_in(SpecId::ISTANBUL) {
+            // EIP-1884: Repricing for trie-size-dependent opcodes
+            INSTANBUL_SLOAD_GAS
+        } else if spec_id.is_enabled_in(SpecId::TANGERINE) {
+            // EIP-150: Gas cost changes for IO-heavy operations
+            200
+        } else {
+            50
+        };
+        // EIP-2929: Gas cost increases for state access opcodes
+        let (cold_sload, sstore_reset) = if spec_id.is_enabled_in(SpecId::BERLIN) {
+            (Some(COLD_SLOAD_COST), WARM_SSTORE_RESET)
+        } else {
+            (None, SSTORE_RESET)
+        };
+        // EIP-3529: Reduction in refunds
+        let sstore_clears_schedule = if spec_id.is_enabled_in(SpecId::LONDON) {
+            (SSTORE_RESET - COLD_SLOAD_COST + ACCESS_LIST_STORAGE_KEY) as i64
+        } else {
+            REFUND_SSTORE_CLEARS
+        };
+        Self {
+            sload,
+            cold_sload,
+            sstore_set: SSTORE_SET,
+            sstore_reset,
+            sstore_clears_schedule,
+            // EIP-2200: Structured Definitions for Net Gas Metering
+            net_metering: spec_id.is_enabled_in(SpecId::ISTANBUL),
+        }
+    }
+
+    /// `SLOAD` opcode cost calculation.
+    #[inline]
+    pub const fn sload_cost(&self, is_cold: bool) -> u64 {
+        match self.cold_sload {
+            Some(cold_sload) if is_cold => cold_sload,
+            _ => self.sload,
+        }
+    }
+
+    /// `SSTORE` opcode cost calculation.
+    #[inline]
+    pub fn sstore_cost(&self, vals: &SStoreResult, is_cold: bool) -> u64 {
+        let mut gas_cost = if !self.net_metering {
+            // Frontier sstore cost just had two cases set and reset values.
+            if vals.is_present_zero() && !vals.is_new_zero() {
+                self.sstore_set
+            } else {
+                self.sstore_reset
+            }
+        } else if vals.is_new_eq_present() {
+            self.sload
+        } else if vals.is_original_eq_present() && vals.is_original_zero() {
+            self.sstore_set
+        } else if vals.is_original_eq_present() {
+            self.sstore_reset
+        } else {
+            self.sload
+        };
+        if let (true, Some(cold_sload)) = (is_cold, self.cold_sload) {
+            gas_cost += cold_sload;
+        }
+        gas_cost
+    }
+
+    /// `SSTORE` opcode refund calculation.
+    #[allow(clippy::collapsible_else_if)]
+    #[inline]
+    pub fn sstore_refund(&self, vals: &SStoreResult) -> i64 {
+        if !self.net_metering {
+            return if !vals.is_present_zero() && vals.is_new_zero() {
+                self.sstore_clears_schedule
+            } else {
+                0
+            };
+        }
+
+        if vals.is_new_eq_present() {
+            0
+        } else {
+            if vals.is_original_eq_present() && vals.is_new_zero() {
+                self.sstore_clears_schedule
+            } else {
+                let mut refund = 0;
+
+                if !vals.is_original_zero() {
+                    if vals.is_present_zero() {
+                        refund -= self.sstore_clears_schedule;
+                    } else if vals.is_new_zero() {
+                        refund += self.sstore_clears_schedule;
+                    }
+                }
+
+                if vals.is_original_eq_new() {
+                    if vals.is_original_zero() {
+                        refund += (self.sstore_set - self.sload) as i64;
+                    } else {
+                        refund += (self.sstore_reset - self.sload) as i64;
+                    }
+                }
+
+                refund
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::U256;
+
+    fn vals(original: u8, present: u8, new: u8) -> SStoreResult {
+        SStoreResult {
+            original_value: U256::from(original),
+            present_value: U256::from(present),
+            new_value: U256::from(new),
+        }
+    }
+
+    #[test]
+    fn mainnet_schedule() {
+        let table = GasTable::new(SpecId::LONDON);
+        assert_eq!(table.sload_cost(true), 2100);
+        assert_eq!(table.sload_cost(false), 100);
+        assert_eq!(table.sstore_cost(&vals(0, 0, 1), true), 22_100);
+        assert_eq!(table.sstore_cost(&vals(1, 1, 2), false), 2_900);
+        assert_eq!(table.sstore_refund(&vals(1, 1, 0)), 4_800);
+
+        let table = GasTable::new(SpecId::ISTANBUL);
+        assert_eq!(table.sload_cost(true), 800);
+        assert_eq!(table.sstore_cost(&vals(0, 0, 1), true), 20_000);
+        assert_eq!(table.sstore_refund(&vals(1, 1, 0)), 15_000);
+
+        let table = GasTable::new(SpecId::FRONTIER);
+        assert_eq!(table.sload_cost(true), 50);
+        assert_eq!(table.sstore_cost(&vals(1, 1, 1), false), 5_000);
+        assert_eq!(table.sstore_refund(&vals(1, 1, 0)), 15_000);
+    }
+
+    /// Executes `PUSH1 value PUSH1 0 SSTORE` for every value on a warm slot.
+    ///
+    /// Returns the used gas and the refund.
+    fn run_sstores(table: &GasTable, original: u8, values: &[u8]) -> (u64, i64) {
+        let (mut gas, mut refund, mut present) = (0, 0, original);
+        for &new in values {
+            let vals = vals(original, present, new);
+            gas += 2 * VERYLOW + table.sstore_cost(&vals, false);
+            refund += table.sstore_refund(&vals);
+            present = new;
+        }
+        (gas, refund)
+    }
+
+    /// Test vectors of EIP-2200 and EIP-3529 as `(original, values, used gas, refund)`.
+    ///
+    /// EIP-1283 was never activated on mainnet, its rules are superseded by EIP-2200.
+    const EIP2200_VECTORS: &[(u8, &[u8], u64, i64)] = &[
+        (0, &[0, 0], 1612, 0),
+        (0, &[0, 1], 20812, 0),
+        (0, &[1, 0], 20812, 19200),
+        (0, &[1, 2], 20812, 0),
+        (0, &[1, 1], 20812, 0),
+        (1, &[0, 0], 5812, 15000),
+        (1, &[0, 1], 5812, 4200),
+        (1, &[0, 2], 5812, 0),
+        (1, &[2, 0], 5812, 15000),
+        (1, &[2, 3], 5812, 0),
+        (1, &[2, 1], 5812, 4200),
+        (1, &[2, 2], 5812, 0),
+        (1, &[1, 0], 5812, 15000),
+        (1, &[1, 2], 5812, 0),
+        (1, &[1, 1], 1612, 0),
+        (0, &[1, 0, 1], 40818, 19200),
+        (1, &[0, 1, 0], 10818, 19200),
+    ];
+
+    const EIP3529_VECTORS: &[(u8, &[u8], u64, i64)] = &[
+        (0, &[0, 0], 212, 0),
+        (0, &[0, 1], 20112, 0),
+        (0, &[1, 0], 20112, 19900),
+        (0, &[1, 2], 20112, 0),
+        (0, &[1, 1], 20112, 0),
+        (1, &[0, 0], 3012, 4800),
+        (1, &[0, 1], 3012, 2800),
+        (1, &[0, 2], 3012, 0),
+        (1, &[2, 0], 3012, 4800),
+        (1, &[2, 3], 3012, 0),
+        (1, &[2, 1], 3012, 2800),
+        (1, &[2, 2], 3012, 0),
+        (1, &[1, 0], 3012, 4800),
+        (1, &[1, 2], 3012, 0),
+        (1, &[1, 1], 212, 0),
+        (0, &[1, 0, 1], 40118, 19900),
+        (1, &[0, 1, 0], 5918, 7600),
+    ];
+
+    #[test]
+    fn eip_sstore_vectors() {
+        for (spec_id, vectors) in [
+            (SpecId::ISTANBUL, EIP2200_VECTORS),
+            (SpecId::LONDON, EIP3529_VECTORS),
+        ] {
+            let table = GasTable::new(spec_id);
+            for &(original, values, gas, refund) in vectors {
+                assert_eq!(
+                    run_sstores(&table, original, values),
+                    (gas, refund),
+                    "{spec_id:?} original {original} values {values:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_schedule() {
+        let table = GasTable {
+            sload: 10,
+            cold_sload: Some(1_000),
+            ..GasTable::new(SpecId::CANCUN)
+        };
+        assert_eq!(table.sload_cost(true), 1_000);
+        assert_eq!(table.sload_cost(false), 10);
+        assert_eq!(table.sstore_cost(&vals(1, 2, 3), true), 1_010);
+    }
+}
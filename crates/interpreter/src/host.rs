@@ -1,38 +1,114 @@
+use crate::{gas::GasTable, InstructionResult};
 use core::ops::{Deref, DerefMut};
 use primitives::{Address, Bytes, Log, B256, U256};
+use std::vec::Vec;
 
+mod block_env_override;
 mod dummy;
+mod read_only;
+pub use block_env_override::BlockEnvOverride;
 pub use dummy::DummyHost;
+pub use read_only::ReadOnlyHost;
 use wiring::{default::EnvWiring, EvmWiring};
 
-/// EVM context host.
-pub trait Host {
+/// Read-only part of the EVM context host.
+///
+/// Reads never change the state, not even the warmth of accounts and storage slots: the
+/// `is_cold` flags report whether loading would warm them. Analyses that only need to read,
+/// like static analyzers and light simulators, can implement this half alone and run the
+/// instructions with a [`ReadOnlyHost`], with a compile-time guarantee that nothing is mutated.
+///
+/// `None` is returned if the state can not be read, hosts that load the state lazily only see
+/// what was already loaded through [`HostWrite`].
+pub trait HostRead {
     /// Chain specification.
     type EvmWiringT: EvmWiring;
 
     /// Returns a reference to the environment.
     fn env(&self) -> &EnvWiring<Self::EvmWiringT>;
 
-    /// Returns a mutable reference to the environment.
-    fn env_mut(&mut self) -> &mut EnvWiring<Self::EvmWiringT>;
-
-    /// Load an account code.
-    fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad>;
+    /// Get the account and if it is cold, following the EIP-7702 delegation.
+    fn account_delegated(&self, address: Address) -> Option<AccountLoad>;
 
     /// Get the block hash of the given block `number`.
-    fn block_hash(&mut self, number: u64) -> Option<B256>;
+    fn block_hash(&self, number: u64) -> Option<B256>;
 
     /// Get balance of `address` and if the account is cold.
-    fn balance(&mut self, address: Address) -> Option<StateLoad<U256>>;
+    fn balance(&self, address: Address) -> Option<StateLoad<U256>>;
 
     /// Get code of `address` and if the account is cold.
-    fn code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>>;
+    fn code(&self, address: Address) -> Option<Eip7702CodeLoad<Bytes>>;
 
     /// Get code hash of `address` and if the account is cold.
-    fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>>;
+    fn code_hash(&self, address: Address) -> Option<Eip7702CodeLoad<B256>>;
+
+    /// Get storage value of `address` at `index` and if the slot is cold.
+    fn sload(&self, address: Address, index: U256) -> Option<StateLoad<U256>>;
+
+    /// Get the transient storage value of `address` at `index`.
+    fn tload(&self, address: Address, index: U256) -> U256;
+
+    /// Returns the gas table that overrides the storage pricing of the spec, if any.
+    ///
+    /// Defaults to `None`, in which case [`GasTable::new`] of the spec is used.
+    fn gas_table(&self) -> Option<&GasTable> {
+        None
+    }
+
+    /// Returns the values that block information opcodes return instead of the environment.
+    ///
+    /// Defaults to `None`, in which case all values are read from the environment.
+    fn block_env_override(&self) -> Option<&BlockEnvOverride> {
+        None
+    }
+}
 
-    /// Get storage value of `address` at `index` and if the account is cold.
-    fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>>;
+/// Mutating part of the EVM context host.
+///
+/// Instructions load the state through this trait, which warms the loaded accounts and storage
+/// slots. The load methods default to the reads of [`HostRead`], for hosts that track no
+/// warmth. Instructions that change the state or create frames, like `SSTORE`, `CALL` and
+/// `CREATE`, require this trait.
+///
+/// The host enforces the static context: in a static call every state change fails with
+/// [`InstructionResult::StateChangeDuringStaticCall`], which halts the instruction. A failure
+/// to load the state is reported as [`InstructionResult::FatalExternalError`].
+pub trait HostWrite: HostRead {
+    /// Load the account, see [`HostRead::account_delegated`].
+    #[inline]
+    fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad> {
+        self.account_delegated(address)
+    }
+
+    /// Load the block hash, see [`HostRead::block_hash`].
+    #[inline]
+    fn load_block_hash(&mut self, number: u64) -> Option<B256> {
+        self.block_hash(number)
+    }
+
+    /// Load the balance, see [`HostRead::balance`].
+    #[inline]
+    fn load_balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
+        self.balance(address)
+    }
+
+    /// Load the code, see [`HostRead::code`].
+    #[inline]
+    fn load_code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+        self.code(address)
+    }
+
+    /// Load the code hash, see [`HostRead::code_hash`].
+    #[inline]
+    fn load_code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+        self.code_hash(address)
+    }
+
+    /// Load the storage value, see [`HostRead::sload`].
+    #[inline]
+    fn load_storage(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+        self.sload(address, index)
+    }
 
     /// Set storage value of account address at index.
     ///
@@ -42,25 +118,48 @@ pub trait Host {
         address: Address,
         index: U256,
         value: U256,
-    ) -> Option<StateLoad<SStoreResult>>;
-
-    /// Get the transient storage value of `address` at `index`.
-    fn tload(&mut self, address: Address, index: U256) -> U256;
+    ) -> Result<StateLoad<SStoreResult>, InstructionResult>;
 
     /// Set the transient storage value of `address` at `index`.
-    fn tstore(&mut self, address: Address, index: U256, value: U256);
+    fn tstore(
+        &mut self,
+        address: Address,
+        index: U256,
+        value: U256,
+    ) -> Result<(), InstructionResult>;
 
     /// Emit a log owned by `address` with given `LogData`.
-    fn log(&mut self, log: Log);
+    fn log(&mut self, log: Log) -> Result<(), InstructionResult>;
+
+    /// Returns an empty buffer for the topics of a log.
+    ///
+    /// Hosts that reuse allocations between transactions return a previously used buffer.
+    #[inline]
+    fn log_topics_buffer(&mut self) -> Vec<B256> {
+        Vec::new()
+    }
+
+    /// Returns an empty buffer for the data of a log, see [`HostWrite::log_topics_buffer`].
+    #[inline]
+    fn log_data_buffer(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
 
     /// Mark `address` to be deleted, with funds transferred to `target`.
     fn selfdestruct(
         &mut self,
         address: Address,
         target: Address,
-    ) -> Option<StateLoad<SelfDestructResult>>;
+    ) -> Result<StateLoad<SelfDestructResult>, InstructionResult>;
 }
 
+/// EVM context host.
+///
+/// Implemented for every type that implements both [`HostRead`] and [`HostWrite`].
+pub trait Host: HostWrite {}
+
+impl<T: HostWrite + ?Sized> Host for T {}
+
 /// Represents the result of an `sstore` operation.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -255,6 +354,8 @@ pub struct SelfDestructResult {
     pub had_value: bool,
     pub target_exists: bool,
     pub previously_destroyed: bool,
+    /// Balance of the target would overflow, selfdestruct was not performed.
+    pub balance_overflow: bool,
 }
 
 #[cfg(test)]
@@ -266,9 +367,12 @@ mod tests {
 
     fn assert_host<H: Host + ?Sized>() {}
 
+    fn assert_host_read<H: HostRead + ?Sized>() {}
+
     #[test]
     fn object_safety() {
         assert_host::<DummyHost<EthereumWiring<EmptyDB, ()>>>();
         assert_host::<dyn Host<EvmWiringT = EthereumWiring<EmptyDB, ()>>>();
+        assert_host_read::<dyn HostRead<EvmWiringT = EthereumWiring<EmptyDB, ()>>>();
     }
 }
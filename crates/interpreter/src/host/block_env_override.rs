@@ -0,0 +1,18 @@
+use primitives::{Address, U256};
+
+/// Values returned by block information opcodes instead of the environment.
+///
+/// Lets chains with different semantics, e.g. a constant difficulty, a zero coinbase or a block
+/// number derived from L1 block attributes, change what the opcodes return without replacing
+/// the instructions. Fields that are `None` are read from the environment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BlockEnvOverride {
+    /// Returned by `CHAINID`.
+    pub chain_id: Option<u64>,
+    /// Returned by `COINBASE`.
+    pub coinbase: Option<Address>,
+    /// Returned by `DIFFICULTY`, which is `PREVRANDAO` after the merge.
+    pub difficulty: Option<U256>,
+    /// Returned by `NUMBER`.
+    pub number: Option<U256>,
+}
@@ -1,4 +1,4 @@
-use crate::{Host, SStoreResult, SelfDestructResult};
+use crate::{HostRead, HostWrite, InstructionResult, SStoreResult, SelfDestructResult};
 use derive_where::derive_where;
 use primitives::{hash_map::Entry, Address, Bytes, HashMap, Log, B256, KECCAK_EMPTY, U256};
 use std::vec::Vec;
@@ -9,7 +9,7 @@ use wiring::{
 
 use super::{AccountLoad, Eip7702CodeLoad, StateLoad};
 
-/// A dummy [Host] implementation.
+/// A dummy [Host](crate::Host) implementation.
 #[derive_where(Clone, Debug, Default; EvmWiringT::Block, EvmWiringT::Transaction)]
 pub struct DummyHost<EvmWiringT>
 where
@@ -44,7 +44,7 @@ where
     }
 }
 
-impl<EvmWiringT> Host for DummyHost<EvmWiringT>
+impl<EvmWiringT> HostRead for DummyHost<EvmWiringT>
 where
     EvmWiringT: EvmWiring,
 {
@@ -56,37 +56,53 @@ where
     }
 
     #[inline]
-    fn env_mut(&mut self) -> &mut EnvWiring<Self::EvmWiringT> {
-        &mut self.env
-    }
-
-    #[inline]
-    fn load_account_delegated(&mut self, _address: Address) -> Option<AccountLoad> {
+    fn account_delegated(&self, _address: Address) -> Option<AccountLoad> {
         Some(AccountLoad::default())
     }
 
     #[inline]
-    fn block_hash(&mut self, _number: u64) -> Option<B256> {
+    fn block_hash(&self, _number: u64) -> Option<B256> {
         Some(B256::ZERO)
     }
 
     #[inline]
-    fn balance(&mut self, _address: Address) -> Option<StateLoad<U256>> {
+    fn balance(&self, _address: Address) -> Option<StateLoad<U256>> {
         Some(Default::default())
     }
 
     #[inline]
-    fn code(&mut self, _address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+    fn code(&self, _address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
         Some(Default::default())
     }
 
     #[inline]
-    fn code_hash(&mut self, _address: Address) -> Option<Eip7702CodeLoad<B256>> {
+    fn code_hash(&self, _address: Address) -> Option<Eip7702CodeLoad<B256>> {
         Some(Eip7702CodeLoad::new_not_delegated(KECCAK_EMPTY, false))
     }
 
     #[inline]
-    fn sload(&mut self, _address: Address, index: U256) -> Option<StateLoad<U256>> {
+    fn sload(&self, _address: Address, index: U256) -> Option<StateLoad<U256>> {
+        Some(match self.storage.get(&index) {
+            Some(value) => StateLoad::new(*value, false),
+            None => StateLoad::new(U256::ZERO, true),
+        })
+    }
+
+    #[inline]
+    fn tload(&self, _address: Address, index: U256) -> U256 {
+        self.transient_storage
+            .get(&index)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl<EvmWiringT> HostWrite for DummyHost<EvmWiringT>
+where
+    EvmWiringT: EvmWiring,
+{
+    #[inline]
+    fn load_storage(&mut self, _address: Address, index: U256) -> Option<StateLoad<U256>> {
         match self.storage.entry(index) {
             Entry::Occupied(entry) => Some(StateLoad::new(*entry.get(), false)),
             Entry::Vacant(entry) => {
@@ -102,9 +118,9 @@ where
         _address: Address,
         index: U256,
         value: U256,
-    ) -> Option<StateLoad<SStoreResult>> {
+    ) -> Result<StateLoad<SStoreResult>, InstructionResult> {
         let present = self.storage.insert(index, value);
-        Some(StateLoad {
+        Ok(StateLoad {
             data: SStoreResult {
                 original_value: U256::ZERO,
                 present_value: present.unwrap_or(U256::ZERO),
@@ -115,21 +131,20 @@ where
     }
 
     #[inline]
-    fn tload(&mut self, _address: Address, index: U256) -> U256 {
-        self.transient_storage
-            .get(&index)
-            .copied()
-            .unwrap_or_default()
-    }
-
-    #[inline]
-    fn tstore(&mut self, _address: Address, index: U256, value: U256) {
+    fn tstore(
+        &mut self,
+        _address: Address,
+        index: U256,
+        value: U256,
+    ) -> Result<(), InstructionResult> {
         self.transient_storage.insert(index, value);
+        Ok(())
     }
 
     #[inline]
-    fn log(&mut self, log: Log) {
-        self.log.push(log)
+    fn log(&mut self, log: Log) -> Result<(), InstructionResult> {
+        self.log.push(log);
+        Ok(())
     }
 
     #[inline]
@@ -137,7 +152,7 @@ where
         &mut self,
         _address: Address,
         _target: Address,
-    ) -> Option<StateLoad<SelfDestructResult>> {
-        Some(StateLoad::default())
+    ) -> Result<StateLoad<SelfDestructResult>, InstructionResult> {
+        Ok(StateLoad::default())
     }
 }
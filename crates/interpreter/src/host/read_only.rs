@@ -0,0 +1,112 @@
+use crate::{
+    gas::GasTable, AccountLoad, BlockEnvOverride, Eip7702CodeLoad, HostRead, HostWrite,
+    InstructionResult, SStoreResult, SelfDestructResult, StateLoad,
+};
+use primitives::{Address, Bytes, Log, B256, U256};
+use wiring::default::EnvWiring;
+
+/// [Host](crate::Host) that runs the instructions on a [`HostRead`].
+///
+/// Loads are the reads of the wrapped host and every state change fails with
+/// [`InstructionResult::StateChangeDuringStaticCall`]. Use it with
+/// [`read_only_instruction_table`](crate::instructions::read_only_instruction_table).
+#[derive(Debug)]
+pub struct ReadOnlyHost<'a, H: ?Sized>(pub &'a H);
+
+impl<H: ?Sized> Clone for ReadOnlyHost<'_, H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H: ?Sized> Copy for ReadOnlyHost<'_, H> {}
+
+impl<H: HostRead + ?Sized> HostRead for ReadOnlyHost<'_, H> {
+    type EvmWiringT = H::EvmWiringT;
+
+    #[inline]
+    fn env(&self) -> &EnvWiring<Self::EvmWiringT> {
+        self.0.env()
+    }
+
+    #[inline]
+    fn account_delegated(&self, address: Address) -> Option<AccountLoad> {
+        self.0.account_delegated(address)
+    }
+
+    #[inline]
+    fn block_hash(&self, number: u64) -> Option<B256> {
+        self.0.block_hash(number)
+    }
+
+    #[inline]
+    fn balance(&self, address: Address) -> Option<StateLoad<U256>> {
+        self.0.balance(address)
+    }
+
+    #[inline]
+    fn code(&self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+        self.0.code(address)
+    }
+
+    #[inline]
+    fn code_hash(&self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+        self.0.code_hash(address)
+    }
+
+    #[inline]
+    fn sload(&self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+        self.0.sload(address, index)
+    }
+
+    #[inline]
+    fn tload(&self, address: Address, index: U256) -> U256 {
+        self.0.tload(address, index)
+    }
+
+    #[inline]
+    fn gas_table(&self) -> Option<&GasTable> {
+        self.0.gas_table()
+    }
+
+    #[inline]
+    fn block_env_override(&self) -> Option<&BlockEnvOverride> {
+        self.0.block_env_override()
+    }
+}
+
+impl<H: HostRead + ?Sized> HostWrite for ReadOnlyHost<'_, H> {
+    #[inline]
+    fn sstore(
+        &mut self,
+        _address: Address,
+        _index: U256,
+        _value: U256,
+    ) -> Result<StateLoad<SStoreResult>, InstructionResult> {
+        Err(InstructionResult::StateChangeDuringStaticCall)
+    }
+
+    #[inline]
+    fn tstore(
+        &mut self,
+        _address: Address,
+        _index: U256,
+        _value: U256,
+    ) -> Result<(), InstructionResult> {
+        Err(InstructionResult::StateChangeDuringStaticCall)
+    }
+
+    #[inline]
+    fn log(&mut self, _log: Log) -> Result<(), InstructionResult> {
+        Err(InstructionResult::StateChangeDuringStaticCall)
+    }
+
+    #[inline]
+    fn selfdestruct(
+        &mut self,
+        _address: Address,
+        _target: Address,
+    ) -> Result<StateLoad<SelfDestructResult>, InstructionResult> {
+        Err(InstructionResult::StateChangeDuringStaticCall)
+    }
+}
@@ -76,7 +76,7 @@ pub enum InstructionResult {
     OverflowPayment,
     /// Error in precompiled contract execution.
     PrecompileError,
-    /// Nonce overflow.
+    /// Nonce of the creator would overflow, the creation is not started. See EIP-2681.
     NonceOverflow,
     /// Exceeded contract size limit during creation.
     CreateContractSizeLimit,
@@ -98,6 +98,13 @@ pub enum InstructionResult {
     EofAuxDataTooSmall,
     /// `EXT*CALL` target address needs to be padded with 0s.
     InvalidEXTCALLTarget,
+    /// Execution was interrupted by an execution guard, see `execution_guard_register`.
+    Interrupted,
+    /// Execution is suspended until the host provides data that is missing in the database.
+    ///
+    /// The instruction that needed the data is executed again when the frame is resumed, see
+    /// `suspendable_register`.
+    Suspended,
 }
 
 impl From<SuccessReason> for InstructionResult {
@@ -144,6 +151,9 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::EofAuxDataTooSmall => Self::EofAuxDataTooSmall,
             HaltReason::EOFFunctionStackOverflow => Self::EOFFunctionStackOverflow,
             HaltReason::InvalidEXTCALLTarget => Self::InvalidEXTCALLTarget,
+            HaltReason::ReturnContractInNotInitEOF => Self::ReturnContractInNotInitEOF,
+            HaltReason::EOFOpcodeDisabledInLegacy => Self::EOFOpcodeDisabledInLegacy,
+            HaltReason::Interrupted => Self::Interrupted,
         }
     }
 }
@@ -168,6 +178,7 @@ macro_rules! return_revert {
             | $crate::InstructionResult::InvalidEOFInitCode
             | $crate::InstructionResult::CreateInitCodeStartingEF00
             | $crate::InstructionResult::InvalidExtDelegateCallTarget
+            | $crate::InstructionResult::NonceOverflow
     };
 }
 
@@ -192,7 +203,6 @@ macro_rules! return_error {
             | $crate::InstructionResult::CreateCollision
             | $crate::InstructionResult::OverflowPayment
             | $crate::InstructionResult::PrecompileError
-            | $crate::InstructionResult::NonceOverflow
             | $crate::InstructionResult::CreateContractSizeLimit
             | $crate::InstructionResult::CreateContractStartingWithEF
             | $crate::InstructionResult::CreateInitCodeSizeLimit
@@ -203,6 +213,8 @@ macro_rules! return_error {
             | $crate::InstructionResult::EofAuxDataTooSmall
             | $crate::InstructionResult::EofAuxDataOverflow
             | $crate::InstructionResult::InvalidEXTCALLTarget
+            | $crate::InstructionResult::Interrupted
+            | $crate::InstructionResult::Suspended
     };
 }
 
@@ -316,8 +328,9 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::ReentrancySentryOOG => {
                 Self::Halt(HaltReason::OutOfGas(OutOfGasError::ReentrancySentry).into())
             }
-            InstructionResult::OpcodeNotFound | InstructionResult::ReturnContractInNotInitEOF => {
-                Self::Halt(HaltReason::OpcodeNotFound.into())
+            InstructionResult::OpcodeNotFound => Self::Halt(HaltReason::OpcodeNotFound.into()),
+            InstructionResult::ReturnContractInNotInitEOF => {
+                Self::Halt(HaltReason::ReturnContractInNotInitEOF.into())
             }
             InstructionResult::CallNotAllowedInsideStatic => {
                 Self::Halt(HaltReason::CallNotAllowedInsideStatic.into())
@@ -334,19 +347,23 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::CreateCollision => Self::Halt(HaltReason::CreateCollision.into()),
             InstructionResult::OverflowPayment => Self::Halt(HaltReason::OverflowPayment.into()), // Check for first call is done separately.
             InstructionResult::PrecompileError => Self::Halt(HaltReason::PrecompileError.into()),
-            InstructionResult::NonceOverflow => Self::Halt(HaltReason::NonceOverflow.into()),
-            InstructionResult::CreateContractSizeLimit
-            | InstructionResult::CreateContractStartingWithEF => {
+            InstructionResult::NonceOverflow => Self::Halt(HaltReason::NonceOverflow.into()), // not gonna happen for first call
+            InstructionResult::CreateContractSizeLimit => {
                 Self::Halt(HaltReason::CreateContractSizeLimit.into())
             }
+            InstructionResult::CreateContractStartingWithEF => {
+                Self::Halt(HaltReason::CreateContractStartingWithEF.into())
+            }
             InstructionResult::CreateInitCodeSizeLimit => {
                 Self::Halt(HaltReason::CreateInitCodeSizeLimit.into())
             }
             // TODO (EOF) add proper Revert subtype.
             InstructionResult::InvalidEOFInitCode => Self::Revert,
-            InstructionResult::FatalExternalError => Self::FatalExternalError,
+            InstructionResult::FatalExternalError | InstructionResult::Suspended => {
+                Self::FatalExternalError
+            }
             InstructionResult::EOFOpcodeDisabledInLegacy => {
-                Self::Halt(HaltReason::OpcodeNotFound.into())
+                Self::Halt(HaltReason::EOFOpcodeDisabledInLegacy.into())
             }
             InstructionResult::EOFFunctionStackOverflow => {
                 Self::Halt(HaltReason::EOFFunctionStackOverflow.into())
@@ -361,6 +378,7 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
             InstructionResult::InvalidEXTCALLTarget => {
                 Self::Halt(HaltReason::InvalidEXTCALLTarget.into())
             }
+            InstructionResult::Interrupted => Self::Halt(HaltReason::Interrupted.into()),
             InstructionResult::InvalidExtDelegateCallTarget => {
                 Self::Internal(InternalResult::InvalidExtDelegateCallTarget)
             }
@@ -370,7 +388,53 @@ impl<HaltReasonT: HaltReasonTrait> From<InstructionResult> for SuccessOrHalt<Hal
 
 #[cfg(test)]
 mod tests {
+    use super::SuccessOrHalt;
     use crate::InstructionResult;
+    use wiring::result::{HaltReason, OutOfGasError};
+
+    #[test]
+    fn halt_reasons_round_trip() {
+        let reasons = [
+            HaltReason::OutOfGas(OutOfGasError::Basic),
+            HaltReason::OutOfGas(OutOfGasError::MemoryLimit),
+            HaltReason::OutOfGas(OutOfGasError::Memory),
+            HaltReason::OutOfGas(OutOfGasError::Precompile),
+            HaltReason::OutOfGas(OutOfGasError::InvalidOperand),
+            HaltReason::OutOfGas(OutOfGasError::ReentrancySentry),
+            HaltReason::OpcodeNotFound,
+            HaltReason::InvalidFEOpcode,
+            HaltReason::InvalidJump,
+            HaltReason::NotActivated,
+            HaltReason::StackUnderflow,
+            HaltReason::StackOverflow,
+            HaltReason::OutOfOffset,
+            HaltReason::CreateCollision,
+            HaltReason::PrecompileError,
+            HaltReason::NonceOverflow,
+            HaltReason::CreateContractSizeLimit,
+            HaltReason::CreateContractStartingWithEF,
+            HaltReason::CreateInitCodeSizeLimit,
+            HaltReason::OverflowPayment,
+            HaltReason::StateChangeDuringStaticCall,
+            HaltReason::CallNotAllowedInsideStatic,
+            HaltReason::OutOfFunds,
+            HaltReason::CallTooDeep,
+            HaltReason::EofAuxDataOverflow,
+            HaltReason::EofAuxDataTooSmall,
+            HaltReason::EOFFunctionStackOverflow,
+            HaltReason::InvalidEXTCALLTarget,
+            HaltReason::ReturnContractInNotInitEOF,
+            HaltReason::EOFOpcodeDisabledInLegacy,
+            HaltReason::Interrupted,
+        ];
+        for reason in reasons {
+            assert_eq!(
+                SuccessOrHalt::<HaltReason>::from(InstructionResult::from(reason)),
+                SuccessOrHalt::Halt(reason),
+                "{reason}"
+            );
+        }
+    }
 
     #[test]
     fn all_results_are_covered() {
@@ -401,6 +465,7 @@ mod tests {
             InstructionResult::Revert,
             InstructionResult::CallTooDeep,
             InstructionResult::OutOfFunds,
+            InstructionResult::NonceOverflow,
         ];
 
         for result in revert_results {
@@ -427,11 +492,12 @@ mod tests {
             InstructionResult::CreateCollision,
             InstructionResult::OverflowPayment,
             InstructionResult::PrecompileError,
-            InstructionResult::NonceOverflow,
             InstructionResult::CreateContractSizeLimit,
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::Interrupted,
+            InstructionResult::Suspended,
         ];
 
         for result in error_results {
@@ -8,6 +8,8 @@ pub mod block_info;
 pub mod contract;
 pub mod control;
 pub mod data;
+#[cfg(feature = "superinstructions")]
+pub mod fused;
 pub mod host;
 pub mod i256;
 pub mod memory;
@@ -16,8 +18,8 @@ pub mod system;
 pub mod tx_info;
 pub mod utility;
 
-use crate::Host;
-use specification::hardfork::Spec;
+use crate::{Host, HostRead, ReadOnlyHost};
+use specification::hardfork::{Spec, SpecId};
 
 /// Returns the instruction function for the given opcode and spec.
 pub const fn instruction<H: Host + ?Sized, SPEC: Spec>(opcode: u8) -> crate::table::Instruction<H> {
@@ -57,9 +59,9 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[XOR as usize] = bitwise::bitxor;
     table[NOT as usize] = bitwise::not;
     table[BYTE as usize] = bitwise::byte;
-    table[SHL as usize] = bitwise::shl::<H, SPEC>;
-    table[SHR as usize] = bitwise::shr::<H, SPEC>;
-    table[SAR as usize] = bitwise::sar::<H, SPEC>;
+    table[SHL as usize] = bitwise::shl;
+    table[SHR as usize] = bitwise::shr;
+    table[SAR as usize] = bitwise::sar;
 
     table[KECCAK256 as usize] = system::keccak256;
 
@@ -77,8 +79,8 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[GASPRICE as usize] = tx_info::gasprice;
     table[EXTCODESIZE as usize] = host::extcodesize::<H, SPEC>;
     table[EXTCODECOPY as usize] = host::extcodecopy::<H, SPEC>;
-    table[RETURNDATASIZE as usize] = system::returndatasize::<H, SPEC>;
-    table[RETURNDATACOPY as usize] = system::returndatacopy::<H, SPEC>;
+    table[RETURNDATASIZE as usize] = system::returndatasize;
+    table[RETURNDATACOPY as usize] = system::returndatacopy;
     table[EXTCODEHASH as usize] = host::extcodehash::<H, SPEC>;
     table[BLOCKHASH as usize] = host::blockhash::<H, SPEC>;
     table[COINBASE as usize] = block_info::coinbase;
@@ -86,11 +88,11 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[NUMBER as usize] = block_info::block_number;
     table[DIFFICULTY as usize] = block_info::difficulty::<H, SPEC>;
     table[GASLIMIT as usize] = block_info::gaslimit;
-    table[CHAINID as usize] = block_info::chainid::<H, SPEC>;
-    table[SELFBALANCE as usize] = host::selfbalance::<H, SPEC>;
-    table[BASEFEE as usize] = block_info::basefee::<H, SPEC>;
-    table[BLOBHASH as usize] = tx_info::blob_hash::<H, SPEC>;
-    table[BLOBBASEFEE as usize] = block_info::blob_basefee::<H, SPEC>;
+    table[CHAINID as usize] = block_info::chainid;
+    table[SELFBALANCE as usize] = host::selfbalance;
+    table[BASEFEE as usize] = block_info::basefee;
+    table[BLOBHASH as usize] = tx_info::blob_hash;
+    table[BLOBBASEFEE as usize] = block_info::blob_basefee;
 
     table[POP as usize] = stack::pop;
     table[MLOAD as usize] = memory::mload;
@@ -104,11 +106,11 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[MSIZE as usize] = memory::msize;
     table[GAS as usize] = system::gas;
     table[JUMPDEST as usize] = control::jumpdest_or_nop;
-    table[TLOAD as usize] = host::tload::<H, SPEC>;
-    table[TSTORE as usize] = host::tstore::<H, SPEC>;
-    table[MCOPY as usize] = memory::mcopy::<H, SPEC>;
+    table[TLOAD as usize] = host::tload;
+    table[TSTORE as usize] = host::tstore;
+    table[MCOPY as usize] = memory::mcopy;
 
-    table[PUSH0 as usize] = stack::push0::<H, SPEC>;
+    table[PUSH0 as usize] = stack::push0;
     table[PUSH1 as usize] = stack::push::<1, H>;
     table[PUSH2 as usize] = stack::push::<2, H>;
     table[PUSH3 as usize] = stack::push::<3, H>;
@@ -213,18 +215,118 @@ pub const fn instruction_table<H: Host + ?Sized, SPEC: Spec>() -> [crate::table:
     table[EXTDELEGATECALL as usize] = contract::extdelegatecall::<H, SPEC>;
     table[STATICCALL as usize] = contract::static_call::<H, SPEC>;
     table[EXTSTATICCALL as usize] = contract::extstaticcall;
-    table[REVERT as usize] = control::revert::<H, SPEC>;
+    table[REVERT as usize] = control::revert;
     table[INVALID as usize] = control::invalid;
     table[SELFDESTRUCT as usize] = host::selfdestruct::<H, SPEC>;
+
+    // Opcodes introduced by later hardforks are not activated, so their instructions do not
+    // need to check the spec.
+    let activations = [
+        (DELEGATECALL, SpecId::HOMESTEAD),
+        (RETURNDATASIZE, SpecId::BYZANTIUM),
+        (RETURNDATACOPY, SpecId::BYZANTIUM),
+        (STATICCALL, SpecId::BYZANTIUM),
+        (REVERT, SpecId::BYZANTIUM),
+        (SHL, SpecId::CONSTANTINOPLE),
+        (SHR, SpecId::CONSTANTINOPLE),
+        (SAR, SpecId::CONSTANTINOPLE),
+        (EXTCODEHASH, SpecId::CONSTANTINOPLE),
+        (CREATE2, SpecId::PETERSBURG),
+        (CHAINID, SpecId::ISTANBUL),
+        (SELFBALANCE, SpecId::ISTANBUL),
+        (BASEFEE, SpecId::LONDON),
+        (PUSH0, SpecId::SHANGHAI),
+        (BLOBHASH, SpecId::CANCUN),
+        (BLOBBASEFEE, SpecId::CANCUN),
+        (TLOAD, SpecId::CANCUN),
+        (TSTORE, SpecId::CANCUN),
+        (MCOPY, SpecId::CANCUN),
+    ];
+    let mut i = 0;
+    while i < activations.len() {
+        let (opcode, spec_id) = activations[i];
+        if !SPEC::SPEC_ID.is_enabled_in(spec_id) {
+            table[opcode as usize] = control::not_activated;
+        }
+        i += 1;
+    }
     table
 }
 
+/// Returns the instruction table for a [`HostRead`], run with a [`ReadOnlyHost`].
+///
+/// Instructions that change the state or create frames, like `SSTORE`, `LOG0`, `CALL` and
+/// `CREATE`, halt with [`StateChangeDuringStaticCall`](crate::InstructionResult::StateChangeDuringStaticCall).
+pub const fn read_only_instruction_table<'a, H: HostRead + ?Sized, SPEC: Spec>(
+) -> [crate::table::Instruction<ReadOnlyHost<'a, H>>; 256] {
+    use bytecode::opcode::*;
+    let mut table = instruction_table::<ReadOnlyHost<'a, H>, SPEC>();
+
+    // Opcodes that are not activated keep halting with `NotActivated`.
+    let writes = [
+        (SSTORE, SpecId::FRONTIER),
+        (TSTORE, SpecId::CANCUN),
+        (LOG0, SpecId::FRONTIER),
+        (LOG1, SpecId::FRONTIER),
+        (LOG2, SpecId::FRONTIER),
+        (LOG3, SpecId::FRONTIER),
+        (LOG4, SpecId::FRONTIER),
+        (SELFDESTRUCT, SpecId::FRONTIER),
+        (CREATE, SpecId::FRONTIER),
+        (CREATE2, SpecId::PETERSBURG),
+        (CALL, SpecId::FRONTIER),
+        (CALLCODE, SpecId::FRONTIER),
+        (DELEGATECALL, SpecId::HOMESTEAD),
+        (STATICCALL, SpecId::BYZANTIUM),
+        (EOFCREATE, SpecId::FRONTIER),
+        (EXTCALL, SpecId::FRONTIER),
+        (EXTDELEGATECALL, SpecId::FRONTIER),
+        (EXTSTATICCALL, SpecId::FRONTIER),
+    ];
+    let mut i = 0;
+    while i < writes.len() {
+        let (opcode, spec_id) = writes[i];
+        if SPEC::SPEC_ID.is_enabled_in(spec_id) {
+            table[opcode as usize] = control::read_only;
+        }
+        i += 1;
+    }
+    table
+}
+
+/// Returns the gas used by `opcode` under the spec, excluding the `PUSH32`s of the inputs.
+///
+/// The first input is on top of the stack. Panics if the opcode fails.
+#[cfg(test)]
+pub(crate) fn opcode_gas(spec_id: SpecId, inputs: &[primitives::U256], opcode: u8) -> u64 {
+    use crate::Interpreter;
+    use std::vec::Vec;
+
+    let mut code = Vec::new();
+    for input in inputs.iter().rev() {
+        code.push(bytecode::opcode::PUSH32);
+        code.extend_from_slice(&input.to_be_bytes::<32>());
+    }
+    code.push(opcode);
+    let result = specification::spec_to_generic!(
+        spec_id,
+        Interpreter::run_standalone::<SPEC>(code.into(), Default::default(), 1_000_000)
+    );
+    assert!(
+        result.result.is_ok(),
+        "{opcode:#x} failed with {:?}",
+        result.result.result
+    );
+    result.result.gas.spent() - 3 * inputs.len() as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::DummyHost;
+    use crate::{Contract, DummyHost, InstructionResult, Interpreter};
     use bytecode::opcode::*;
-    use specification::hardfork::LatestSpec;
+    use primitives::U256;
+    use specification::hardfork::{CancunSpec, LatestSpec, LondonSpec};
     use wiring::DefaultEthereumWiring;
 
     #[test]
@@ -244,4 +346,58 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn not_activated_instructions() {
+        let london = instruction_table::<DummyHost<DefaultEthereumWiring>, LondonSpec>();
+        let cancun = instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        // Function pointers can't be compared reliably, run the instruction instead.
+        let not_activated = |table: &crate::table::InstructionTable<_>, opcode: u8| {
+            let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+            table[opcode as usize](&mut interpreter, &mut DummyHost::default());
+            interpreter.instruction_result == InstructionResult::NotActivated
+        };
+        for opcode in [BASEFEE, CHAINID, SHL] {
+            assert!(!not_activated(&london, opcode));
+        }
+        for opcode in [PUSH0, TLOAD, TSTORE, MCOPY, BLOBHASH, BLOBBASEFEE] {
+            assert!(not_activated(&london, opcode));
+            assert!(!not_activated(&cancun, opcode));
+        }
+
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+        london[PUSH0 as usize](&mut interpreter, &mut DummyHost::default());
+        assert_eq!(
+            interpreter.instruction_result,
+            InstructionResult::NotActivated
+        );
+        assert!(interpreter.stack.is_empty());
+    }
+
+    #[test]
+    fn read_only_host() {
+        let mut dummy = DummyHost::<DefaultEthereumWiring>::default();
+        dummy.storage.insert(U256::from(1), U256::from(2));
+        let table = read_only_instruction_table::<_, LatestSpec>();
+        let mut host = ReadOnlyHost(&dummy);
+
+        let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+        interpreter.stack.push(U256::from(3)).unwrap();
+        interpreter.stack.push(U256::from(1)).unwrap();
+        table[SLOAD as usize](&mut interpreter, &mut host);
+        assert_eq!(interpreter.stack.pop(), Ok(U256::from(2)));
+        // Reading a cold slot does not warm it.
+        table[SLOAD as usize](&mut interpreter, &mut host);
+        assert_eq!(interpreter.stack.pop(), Ok(U256::ZERO));
+        assert_eq!(dummy.storage.len(), 1);
+
+        for opcode in [SSTORE, LOG0, CALL, CREATE] {
+            let mut interpreter = Interpreter::new(Contract::default(), u64::MAX, false);
+            table[opcode as usize](&mut interpreter, &mut host);
+            assert_eq!(
+                interpreter.instruction_result,
+                InstructionResult::StateChangeDuringStaticCall
+            );
+        }
+    }
 }
@@ -96,3 +96,61 @@ pub fn signextend<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H
         *x = if bit { *x | !mask } else { *x & mask };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::opcode_gas;
+    use bytecode::opcode::*;
+    use primitives::U256;
+    use specification::hardfork::SpecId::{self, *};
+
+    const SPECS: [SpecId; 7] = [
+        FRONTIER, HOMESTEAD, BYZANTIUM, ISTANBUL, BERLIN, LONDON, CANCUN,
+    ];
+
+    #[test]
+    fn gas() {
+        // Yellow Paper, appendix G: W_verylow = 3, W_low = 5, W_mid = 8.
+        let ops = [
+            (ADD, 3),
+            (MUL, 5),
+            (SUB, 3),
+            (DIV, 5),
+            (SDIV, 5),
+            (MOD, 5),
+            (SMOD, 5),
+            (ADDMOD, 8),
+            (MULMOD, 8),
+            (SIGNEXTEND, 5),
+        ];
+        let inputs = [U256::from(7), U256::from(3), U256::from(5)];
+        for spec_id in SPECS {
+            for (op, gas) in ops {
+                assert_eq!(opcode_gas(spec_id, &inputs, op), gas, "{op:#x} {spec_id:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn exp_gas() {
+        // G_exp = 10 plus G_expbyte per byte of the exponent, raised from 10 to 50 by EIP-160.
+        let cases: [(SpecId, u64, u64); 8] = [
+            (FRONTIER, 0, 10),
+            (FRONTIER, 0xff, 10 + 10),
+            (FRONTIER, 0x100, 10 + 2 * 10),
+            (HOMESTEAD, 0xff, 10 + 10),
+            (SPURIOUS_DRAGON, 0, 10),
+            (SPURIOUS_DRAGON, 0xff, 10 + 50),
+            (SPURIOUS_DRAGON, 0x100, 10 + 2 * 50),
+            (CANCUN, 0xffff_ffff, 10 + 4 * 50),
+        ];
+        for (spec_id, exponent, gas) in cases {
+            let inputs = [U256::from(2), U256::from(exponent)];
+            assert_eq!(
+                opcode_gas(spec_id, &inputs, EXP),
+                gas,
+                "{exponent:#x} {spec_id:?}"
+            );
+        }
+    }
+}
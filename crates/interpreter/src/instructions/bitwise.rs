@@ -2,7 +2,6 @@ use super::i256::i256_cmp;
 use crate::{gas, Host, Interpreter};
 use core::cmp::Ordering;
 use primitives::U256;
-use specification::hardfork::Spec;
 
 pub fn lt<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
@@ -78,8 +77,7 @@ pub fn byte<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 }
 
 /// EIP-145: Bitwise shifting instructions in EVM
-pub fn shl<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, CONSTANTINOPLE);
+pub fn shl<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
     let shift = as_usize_saturated!(op1);
@@ -91,8 +89,7 @@ pub fn shl<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &
 }
 
 /// EIP-145: Bitwise shifting instructions in EVM
-pub fn shr<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, CONSTANTINOPLE);
+pub fn shr<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
     let shift = as_usize_saturated!(op1);
@@ -104,8 +101,7 @@ pub fn shr<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &
 }
 
 /// EIP-145: Bitwise shifting instructions in EVM
-pub fn sar<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, CONSTANTINOPLE);
+pub fn sar<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
     pop_top!(interpreter, op1, op2);
 
@@ -124,7 +120,6 @@ mod tests {
     use crate::instructions::bitwise::{byte, sar, shl, shr};
     use crate::{Contract, DummyHost, Interpreter};
     use primitives::{uint, U256};
-    use specification::hardfork::LatestSpec;
     use wiring::{default::Env, DefaultEthereumWiring};
 
     #[test]
@@ -202,7 +197,7 @@ mod tests {
             host.clear();
             push!(interpreter, test.value);
             push!(interpreter, test.shift);
-            shl::<DummyHost<DefaultEthereumWiring>, LatestSpec>(&mut interpreter, &mut host);
+            shl::<DummyHost<DefaultEthereumWiring>>(&mut interpreter, &mut host);
             pop!(interpreter, res);
             assert_eq!(res, test.expected);
         }
@@ -283,7 +278,7 @@ mod tests {
             host.clear();
             push!(interpreter, test.value);
             push!(interpreter, test.shift);
-            shr::<DummyHost<DefaultEthereumWiring>, LatestSpec>(&mut interpreter, &mut host);
+            shr::<DummyHost<DefaultEthereumWiring>>(&mut interpreter, &mut host);
             pop!(interpreter, res);
             assert_eq!(res, test.expected);
         }
@@ -389,7 +384,7 @@ mod tests {
             host.clear();
             push!(interpreter, test.value);
             push!(interpreter, test.shift);
-            sar::<DummyHost<DefaultEthereumWiring>, LatestSpec>(&mut interpreter, &mut host);
+            sar::<DummyHost<DefaultEthereumWiring>>(&mut interpreter, &mut host);
             pop!(interpreter, res);
             assert_eq!(res, test.expected);
         }
@@ -429,4 +424,26 @@ mod tests {
             assert_eq!(res, test.expected, "Failed at index: {}", test.index);
         }
     }
+
+    #[test]
+    fn gas() {
+        use crate::instructions::opcode_gas;
+        use bytecode::opcode::*;
+        use specification::hardfork::SpecId::*;
+
+        // W_verylow = 3 for comparison and bitwise logic operations.
+        let ops = [LT, GT, SLT, SGT, EQ, ISZERO, AND, OR, XOR, NOT, BYTE];
+        let inputs = [U256::from(1), U256::from(2)];
+        for spec_id in [FRONTIER, BYZANTIUM, BERLIN, CANCUN] {
+            for op in ops {
+                assert_eq!(opcode_gas(spec_id, &inputs, op), 3, "{op:#x} {spec_id:?}");
+            }
+        }
+        // EIP-145
+        for spec_id in [PETERSBURG, BERLIN, CANCUN] {
+            for op in [SHL, SHR, SAR] {
+                assert_eq!(opcode_gas(spec_id, &inputs, op), 3, "{op:#x} {spec_id:?}");
+            }
+        }
+    }
 }
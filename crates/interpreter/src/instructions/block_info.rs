@@ -1,54 +1,65 @@
-use crate::{gas, Host, Interpreter};
+use crate::{gas, HostRead, Interpreter};
 use primitives::U256;
 use specification::hardfork::{Spec, SpecId::*};
 use wiring::Block;
 
 /// EIP-1344: ChainID opcode
-pub fn chainid<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, ISTANBUL);
+pub fn chainid<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
-    push!(interpreter, U256::from(host.env().cfg.chain_id));
+    let chain_id = host
+        .block_env_override()
+        .and_then(|o| o.chain_id)
+        .unwrap_or(host.env().cfg.chain_id);
+    push!(interpreter, U256::from(chain_id));
 }
 
-pub fn coinbase<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn coinbase<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
-    push_b256!(interpreter, host.env().block.coinbase().into_word());
+    let coinbase = host
+        .block_env_override()
+        .and_then(|o| o.coinbase)
+        .unwrap_or(*host.env().block.coinbase());
+    push_b256!(interpreter, coinbase.into_word());
 }
 
-pub fn timestamp<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn timestamp<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(interpreter, *host.env().block.timestamp());
 }
 
-pub fn block_number<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn block_number<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
-    push!(interpreter, *host.env().block.number());
+    let number = host
+        .block_env_override()
+        .and_then(|o| o.number)
+        .unwrap_or(*host.env().block.number());
+    push!(interpreter, number);
 }
 
-pub fn difficulty<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn difficulty<H: HostRead + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
-    if SPEC::enabled(MERGE) {
+    if let Some(difficulty) = host.block_env_override().and_then(|o| o.difficulty) {
+        push!(interpreter, difficulty);
+    } else if SPEC::enabled(MERGE) {
         push_b256!(interpreter, *host.env().block.prevrandao().unwrap());
     } else {
         push!(interpreter, *host.env().block.difficulty());
     }
 }
 
-pub fn gaslimit<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+pub fn gaslimit<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(interpreter, *host.env().block.gas_limit());
 }
 
 /// EIP-3198: BASEFEE opcode
-pub fn basefee<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, LONDON);
+pub fn basefee<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(interpreter, *host.env().block.basefee());
 }
 
 /// EIP-7516: BLOBBASEFEE opcode
-pub fn blob_basefee<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, CANCUN);
+pub fn blob_basefee<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(
         interpreter,
@@ -6,8 +6,8 @@ use crate::{
     gas::{self, cost_per_word, EOF_CREATE_GAS, KECCAK256WORD, MIN_CALLEE_GAS},
     interpreter::Interpreter,
     interpreter_action::NewFrameAction,
-    CallInputs, CallScheme, CallValue, CreateInputs, EOFCreateInputs, Host, InstructionResult,
-    InterpreterAction, InterpreterResult, MAX_INITCODE_SIZE,
+    CallInput, CallInputs, CallScheme, CallValue, CreateInputs, EOFCreateInputs, Host,
+    InstructionResult, InterpreterAction, InterpreterResult, MAX_INITCODE_SIZE,
 };
 use bytecode::eof::{Eof, EofHeader};
 use core::cmp::max;
@@ -19,7 +19,6 @@ use wiring::default::CreateScheme;
 /// EOF Create instruction
 pub fn eofcreate<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     require_eof!(interpreter);
-    require_non_staticcall!(interpreter);
     gas!(interpreter, EOF_CREATE_GAS);
     let initcontainer_index = unsafe { *interpreter.instruction_pointer };
     pop!(interpreter, value, salt, data_offset, data_size);
@@ -146,19 +145,17 @@ pub fn return_contract<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &
     };
 }
 
-pub fn extcall_input(interpreter: &mut Interpreter) -> Option<Bytes> {
+pub fn extcall_input(interpreter: &mut Interpreter) -> Option<CallInput> {
     pop_ret!(interpreter, input_offset, input_size, None);
 
-    let return_memory_offset = resize_memory(interpreter, input_offset, input_size)?;
+    let input_range = resize_memory(interpreter, input_offset, input_size)?;
 
-    if return_memory_offset.is_empty() {
-        return Some(Bytes::new());
+    if input_range.is_empty() {
+        return Some(CallInput::default());
     }
 
-    Some(Bytes::copy_from_slice(
-        interpreter
-            .shared_memory
-            .slice_range(return_memory_offset.clone()),
+    Some(CallInput::SharedBuffer(
+        interpreter.shared_memory.global_range(input_range),
     ))
 }
 
@@ -250,6 +247,7 @@ pub fn extcall<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host
             value: CallValue::Transfer(value),
             scheme: CallScheme::ExtCall,
             is_static: interpreter.is_static,
+            depth: 0,
             is_eof: true,
             return_memory_offset: 0..0,
         })));
@@ -284,6 +282,7 @@ pub fn extdelegatecall<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpret
             value: CallValue::Apparent(interpreter.contract.call_value),
             scheme: CallScheme::ExtDelegateCall,
             is_static: interpreter.is_static,
+            depth: 0,
             is_eof: true,
             return_memory_offset: 0..0,
         })));
@@ -318,6 +317,7 @@ pub fn extstaticcall<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut
             value: CallValue::Transfer(U256::ZERO),
             scheme: CallScheme::ExtStaticCall,
             is_static: true,
+            depth: 0,
             is_eof: true,
             return_memory_offset: 0..0,
         })));
@@ -328,13 +328,6 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
     interpreter: &mut Interpreter,
     host: &mut H,
 ) {
-    require_non_staticcall!(interpreter);
-
-    // EIP-1014: Skinny CREATE2
-    if IS_CREATE2 {
-        check!(interpreter, PETERSBURG);
-    }
-
     pop!(interpreter, value, code_offset, len);
     let len = as_usize_or_fail!(interpreter, len);
 
@@ -389,6 +382,7 @@ pub fn create<const IS_CREATE2: bool, H: Host + ?Sized, SPEC: Spec>(
             value,
             init_code: code,
             gas_limit,
+            depth: 0,
         })));
     interpreter.instruction_result = InstructionResult::CallOrCreate;
 }
@@ -438,6 +432,7 @@ pub fn call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &
             value: CallValue::Transfer(value),
             scheme: CallScheme::Call,
             is_static: interpreter.is_static,
+            depth: 0,
             is_eof: false,
             return_memory_offset,
         })));
@@ -445,6 +440,10 @@ pub fn call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &
 }
 
 pub fn call_code<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
+    if host.env().cfg.is_callcode_disabled() {
+        interpreter.instruction_result = InstructionResult::NotActivated;
+        return;
+    }
     pop!(interpreter, local_gas_limit);
     pop_address!(interpreter, to);
     // max gas limit is not possible in real ethereum situation.
@@ -485,6 +484,7 @@ pub fn call_code<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
             value: CallValue::Transfer(value),
             scheme: CallScheme::CallCode,
             is_static: interpreter.is_static,
+            depth: 0,
             is_eof: false,
             return_memory_offset,
         })));
@@ -492,7 +492,6 @@ pub fn call_code<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
 }
 
 pub fn delegate_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, HOMESTEAD);
     pop!(interpreter, local_gas_limit);
     pop_address!(interpreter, to);
     // max gas limit is not possible in real ethereum situation.
@@ -525,6 +524,7 @@ pub fn delegate_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter
             value: CallValue::Apparent(interpreter.contract.call_value),
             scheme: CallScheme::DelegateCall,
             is_static: interpreter.is_static,
+            depth: 0,
             is_eof: false,
             return_memory_offset,
         })));
@@ -532,7 +532,6 @@ pub fn delegate_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter
 }
 
 pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, BYZANTIUM);
     pop!(interpreter, local_gas_limit);
     pop_address!(interpreter, to);
     // max gas limit is not possible in real ethereum situation.
@@ -564,8 +563,145 @@ pub fn static_call<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
             value: CallValue::Transfer(U256::ZERO),
             scheme: CallScheme::StaticCall,
             is_static: true,
+            depth: 0,
             is_eof: false,
             return_memory_offset,
         })));
     interpreter.instruction_result = InstructionResult::CallOrCreate;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{table::make_instruction_table, CallOutcome, DummyHost, Gas, SharedMemory};
+    use bytecode::{
+        opcode::{CALLCODE, EXTCALL, EXTDELEGATECALL, EXTSTATICCALL},
+        Bytecode,
+    };
+    use specification::hardfork::PragueSpec;
+    use wiring::DefaultEthereumWiring;
+
+    const TARGET: Address = Address::new([0xaa; 20]);
+
+    /// Executes the EXT*CALL `opcode` with an empty input and the given stack below the input
+    /// arguments, returns the interpreter.
+    fn step(opcode: u8, is_eof: bool, gas_limit: u64, stack: &[U256]) -> Interpreter {
+        step_with_target(opcode, is_eof, gas_limit, stack, TARGET.into_word().into())
+    }
+
+    fn step_with_target(
+        opcode: u8,
+        is_eof: bool,
+        gas_limit: u64,
+        stack: &[U256],
+        target: U256,
+    ) -> Interpreter {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
+        let mut host = DummyHost::default();
+
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([opcode].into()));
+        interp.is_eof = is_eof;
+        interp.gas = Gas::new(gas_limit);
+        for value in stack {
+            interp.stack.push(*value).unwrap();
+        }
+        // Input size, input offset and target address, there is no gas argument.
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(U256::ZERO).unwrap();
+        interp.stack.push(target).unwrap();
+        interp.step(&table, &mut host);
+        interp
+    }
+
+    fn call_inputs(interp: &Interpreter) -> &CallInputs {
+        match &interp.next_action {
+            InterpreterAction::NewFrame(NewFrameAction::Call(inputs)) => inputs,
+            action => panic!("expected a call, got {action:?}"),
+        }
+    }
+
+    #[test]
+    fn ext_calls() {
+        // Warm account access costs 100, the caller retains max(remaining / 64, 5000).
+        let interp = step(EXTCALL, true, 100_000, &[U256::from(10)]);
+        assert_eq!(interp.instruction_result, InstructionResult::CallOrCreate);
+        let inputs = call_inputs(&interp);
+        assert_eq!(inputs.scheme, CallScheme::ExtCall);
+        assert_eq!(inputs.target_address, TARGET);
+        assert_eq!(inputs.value, CallValue::Transfer(U256::from(10)));
+        assert_eq!(inputs.gas_limit, 100_000 - 100 - 9_000 - 5_000);
+        assert_eq!(interp.gas.remaining(), 5_000);
+        assert!(interp.stack.is_empty());
+
+        let interp = step(EXTDELEGATECALL, true, 1_000_000, &[]);
+        let inputs = call_inputs(&interp);
+        assert_eq!(inputs.scheme, CallScheme::ExtDelegateCall);
+        assert_eq!(inputs.bytecode_address, TARGET);
+        assert_eq!(inputs.gas_limit, 1_000_000 - 100 - (1_000_000 - 100) / 64);
+
+        let interp = step(EXTSTATICCALL, true, 100_000, &[]);
+        let inputs = call_inputs(&interp);
+        assert_eq!(inputs.scheme, CallScheme::ExtStaticCall);
+        assert!(inputs.is_static);
+    }
+
+    #[test]
+    fn ext_call_errors() {
+        let interp = step(EXTSTATICCALL, false, 100_000, &[]);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::EOFOpcodeDisabledInLegacy
+        );
+
+        // Target with non-zero upper bytes.
+        let interp = step_with_target(EXTSTATICCALL, true, 100_000, &[], U256::MAX);
+        assert_eq!(
+            interp.instruction_result,
+            InstructionResult::InvalidEXTCALLTarget
+        );
+
+        // Light failure if the callee would get less than MIN_CALLEE_GAS.
+        let interp = step(EXTSTATICCALL, true, 5_000, &[]);
+        assert_eq!(interp.instruction_result, InstructionResult::Continue);
+        assert_eq!(interp.stack.data(), &[U256::from(1)]);
+        assert_eq!(interp.next_action, InterpreterAction::None);
+    }
+
+    #[test]
+    fn ext_call_status_codes() {
+        for (result, status) in [
+            (InstructionResult::Return, 0),
+            (InstructionResult::Stop, 0),
+            (InstructionResult::Revert, 1),
+            (InstructionResult::OutOfFunds, 1),
+            (InstructionResult::CallTooDeep, 1),
+            (InstructionResult::OutOfGas, 2),
+            (InstructionResult::InvalidFEOpcode, 2),
+        ] {
+            let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([EXTCALL].into()));
+            interp.is_eof = true;
+            interp.insert_call_outcome(
+                &mut SharedMemory::new(),
+                CallOutcome::new(
+                    InterpreterResult::new(result, Bytes::new(), Gas::new(0)),
+                    0..0,
+                ),
+            );
+            assert_eq!(interp.stack.data(), &[U256::from(status)], "{result:?}");
+        }
+    }
+
+    #[test]
+    fn call_code_disabled() {
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, PragueSpec>();
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        host.env.cfg.disable_callcode = true;
+
+        let mut interp = Interpreter::new_bytecode(Bytecode::LegacyRaw([CALLCODE].into()));
+        interp.gas = Gas::new(100_000);
+        interp.step(&table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::NotActivated);
+        assert_eq!(interp.next_action, InterpreterAction::None);
+        assert_eq!(interp.gas.remaining(), 100_000);
+    }
+}
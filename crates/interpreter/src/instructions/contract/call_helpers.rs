@@ -1,19 +1,19 @@
-use crate::{gas, interpreter::Interpreter, AccountLoad};
+use crate::{gas, interpreter::Interpreter, AccountLoad, CallInput};
 use core::{cmp::min, ops::Range};
-use primitives::{Bytes, U256};
+use primitives::U256;
 use specification::hardfork::{Spec, SpecId::*};
 
 #[inline]
 pub fn get_memory_input_and_out_ranges(
     interpreter: &mut Interpreter,
-) -> Option<(Bytes, Range<usize>)> {
+) -> Option<(CallInput, Range<usize>)> {
     pop_ret!(interpreter, in_offset, in_len, out_offset, out_len, None);
 
     let in_range = resize_memory(interpreter, in_offset, in_len)?;
 
-    let mut input = Bytes::new();
+    let mut input = CallInput::default();
     if !in_range.is_empty() {
-        input = Bytes::copy_from_slice(interpreter.shared_memory.slice_range(in_range));
+        input = CallInput::SharedBuffer(interpreter.shared_memory.global_range(in_range));
     }
 
     let ret_range = resize_memory(interpreter, out_offset, out_len)?;
@@ -1,7 +1,6 @@
 use super::utility::{read_i16, read_u16};
 use crate::{gas, Host, InstructionResult, Interpreter, InterpreterResult};
 use primitives::{Bytes, U256};
-use specification::hardfork::Spec;
 
 pub fn rjump<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     require_eof!(interpreter);
@@ -180,8 +179,7 @@ pub fn ret<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 }
 
 /// EIP-140: REVERT instruction
-pub fn revert<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, BYZANTIUM);
+pub fn revert<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     return_inner(interpreter, InstructionResult::Revert);
 }
 
@@ -195,11 +193,22 @@ pub fn invalid<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     interpreter.instruction_result = InstructionResult::InvalidFEOpcode;
 }
 
+/// Opcode that is not activated in the current spec. This opcode halts the execution.
+pub fn not_activated<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
+    interpreter.instruction_result = InstructionResult::NotActivated;
+}
+
 /// Unknown opcode. This opcode halts the execution.
 pub fn unknown<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     interpreter.instruction_result = InstructionResult::OpcodeNotFound;
 }
 
+/// Opcode that changes the state or creates a frame in a read-only instruction table. This
+/// opcode halts the execution.
+pub fn read_only<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
+    interpreter.instruction_result = InstructionResult::StateChangeDuringStaticCall;
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
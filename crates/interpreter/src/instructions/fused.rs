@@ -0,0 +1,39 @@
+//! Super-instructions that execute common instruction sequences in a single dispatch.
+//!
+//! Every super-instruction behaves exactly like its instructions executed one after another,
+//! including gas and errors. They are only used by [`SpecializedTable::with_superinstructions`],
+//! which places them at the program counter of the first instruction of the sequence.
+//!
+//! [`SpecializedTable::with_superinstructions`]: crate::table::SpecializedTable::with_superinstructions
+
+use super::{control, memory, stack};
+use crate::{table::Instruction, Host, InstructionResult, Interpreter};
+
+/// Skips the opcode of the next instruction and executes it, if the previous one succeeded.
+#[inline(always)]
+fn then<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H, next: Instruction<H>) {
+    if interpreter.instruction_result != InstructionResult::Continue {
+        return;
+    }
+    // SAFETY: The sequence is part of the bytecode, so the opcode is in bounds.
+    interpreter.instruction_pointer = unsafe { interpreter.instruction_pointer.add(1) };
+    next(interpreter, host);
+}
+
+/// `PUSH1 offset MSTORE`
+pub fn push1_mstore<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+    stack::push::<1, H>(interpreter, host);
+    then(interpreter, host, memory::mstore::<H>);
+}
+
+/// `PUSH2 target JUMP`
+pub fn push2_jump<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+    stack::push::<2, H>(interpreter, host);
+    then(interpreter, host, control::jump::<H>);
+}
+
+/// `PUSH2 target JUMPI`
+pub fn push2_jumpi<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
+    stack::push::<2, H>(interpreter, host);
+    then(interpreter, host, control::jumpi::<H>);
+}
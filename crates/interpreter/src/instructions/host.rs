@@ -1,16 +1,14 @@
 use crate::{
-    gas::{self, warm_cold_cost, warm_cold_cost_with_delegation, CALL_STIPEND},
+    gas::{self, warm_cold_cost, warm_cold_cost_with_delegation, GasTable, CALL_STIPEND},
     interpreter::Interpreter,
-    Host, InstructionResult,
+    Host, HostRead, InstructionResult,
 };
-use core::cmp::min;
 use primitives::{Bytes, Log, LogData, B256, U256};
 use specification::hardfork::{Spec, SpecId::*};
-use std::vec::Vec;
 
 pub fn balance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    pop_address!(interpreter, address);
-    let Some(balance) = host.balance(address) else {
+    top_address!(interpreter, address, top);
+    let Some(balance) = host.load_balance(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -27,14 +25,13 @@ pub fn balance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host
             20
         }
     );
-    push!(interpreter, balance.data);
+    *top = balance.data;
 }
 
 /// EIP-1884: Repricing for trie-size-dependent opcodes
-pub fn selfbalance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, ISTANBUL);
+pub fn selfbalance<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::LOW);
-    let Some(balance) = host.balance(interpreter.contract.target_address) else {
+    let Some(balance) = host.load_balance(interpreter.contract.target_address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -42,8 +39,8 @@ pub fn selfbalance<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
 }
 
 pub fn extcodesize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    pop_address!(interpreter, address);
-    let Some(code) = host.code(address) else {
+    top_address!(interpreter, address, top);
+    let Some(code) = host.load_code(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -56,14 +53,13 @@ pub fn extcodesize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
         gas!(interpreter, 20);
     }
 
-    push!(interpreter, U256::from(code.len()));
+    *top = U256::from(code.len());
 }
 
 /// EIP-1052: EXTCODEHASH opcode
 pub fn extcodehash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, CONSTANTINOPLE);
-    pop_address!(interpreter, address);
-    let Some(code_hash) = host.code_hash(address) else {
+    top_address!(interpreter, address, top);
+    let Some(code_hash) = host.load_code_hash(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -75,14 +71,14 @@ pub fn extcodehash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
     } else {
         gas!(interpreter, 400);
     }
-    push_b256!(interpreter, code_hash);
+    *top = code_hash.into();
 }
 
 pub fn extcodecopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     pop_address!(interpreter, address);
     pop!(interpreter, memory_offset, code_offset, len_u256);
 
-    let Some(code) = host.code(address) else {
+    let Some(code) = host.load_code(address) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -97,7 +93,7 @@ pub fn extcodecopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
         return;
     }
     let memory_offset = as_usize_or_fail!(interpreter, memory_offset);
-    let code_offset = min(as_usize_saturated!(code_offset), code.len());
+    let code_offset = as_usize_saturated!(code_offset);
     resize_memory!(interpreter, memory_offset, len);
 
     // Note: this can't panic because we resized memory to fit.
@@ -111,7 +107,7 @@ pub fn blockhash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
     pop_top!(interpreter, number);
 
     let number_u64 = as_u64_saturated!(number);
-    let Some(hash) = host.block_hash(number_u64) else {
+    let Some(hash) = host.load_block_hash(number_u64) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
@@ -120,21 +116,26 @@ pub fn blockhash<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, ho
 
 pub fn sload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
     pop_top!(interpreter, index);
-    let Some(value) = host.sload(interpreter.contract.target_address, *index) else {
+    let Some(value) = host.load_storage(interpreter.contract.target_address, *index) else {
         interpreter.instruction_result = InstructionResult::FatalExternalError;
         return;
     };
-    gas!(interpreter, gas::sload_cost(SPEC::SPEC_ID, value.is_cold));
+    let gas_cost = match host.gas_table() {
+        Some(table) => table.sload_cost(value.is_cold),
+        None => gas::sload_cost(SPEC::SPEC_ID, value.is_cold),
+    };
+    gas!(interpreter, gas_cost);
     *index = value.data;
 }
 
 pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
-
     pop!(interpreter, index, value);
-    let Some(state_load) = host.sstore(interpreter.contract.target_address, index, value) else {
-        interpreter.instruction_result = InstructionResult::FatalExternalError;
-        return;
+    let state_load = match host.sstore(interpreter.contract.target_address, index, value) {
+        Ok(state_load) => state_load,
+        Err(result) => {
+            interpreter.instruction_result = result;
+            return;
+        }
     };
 
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
@@ -142,32 +143,32 @@ pub fn sstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
         interpreter.instruction_result = InstructionResult::ReentrancySentryOOG;
         return;
     }
+    let table = match host.gas_table() {
+        Some(table) => *table,
+        None => GasTable::new(SPEC::SPEC_ID),
+    };
     gas!(
         interpreter,
-        gas::sstore_cost(SPEC::SPEC_ID, &state_load.data, state_load.is_cold)
-    );
-    refund!(
-        interpreter,
-        gas::sstore_refund(SPEC::SPEC_ID, &state_load.data)
+        table.sstore_cost(&state_load.data, state_load.is_cold)
     );
+    refund!(interpreter, table.sstore_refund(&state_load.data));
 }
 
 /// EIP-1153: Transient storage opcodes
 /// Store value to transient storage
-pub fn tstore<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, CANCUN);
-    require_non_staticcall!(interpreter);
+pub fn tstore<H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::WARM_STORAGE_READ_COST);
 
     pop!(interpreter, index, value);
 
-    host.tstore(interpreter.contract.target_address, index, value);
+    if let Err(result) = host.tstore(interpreter.contract.target_address, index, value) {
+        interpreter.instruction_result = result;
+    }
 }
 
 /// EIP-1153: Transient storage opcodes
 /// Load value from transient storage
-pub fn tload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    check!(interpreter, CANCUN);
+pub fn tload<H: HostRead + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
     gas!(interpreter, gas::WARM_STORAGE_READ_COST);
 
     pop_top!(interpreter, index);
@@ -176,8 +177,6 @@ pub fn tload<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host:
 }
 
 pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
-
     pop!(interpreter, offset, len);
     let len = as_usize_or_fail!(interpreter, len);
     gas_or_fail!(interpreter, gas::log_cost(N as u8, len as u64));
@@ -186,7 +185,9 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
     } else {
         let offset = as_usize_or_fail!(interpreter, offset);
         resize_memory!(interpreter, offset, len);
-        Bytes::copy_from_slice(interpreter.shared_memory.slice(offset, len))
+        let mut data = host.log_data_buffer();
+        data.extend_from_slice(interpreter.shared_memory.slice(offset, len));
+        Bytes::from(data)
     };
 
     if interpreter.stack.len() < N {
@@ -194,7 +195,8 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
         return;
     }
 
-    let mut topics = Vec::with_capacity(N);
+    let mut topics = host.log_topics_buffer();
+    topics.reserve(N);
     for _ in 0..N {
         // SAFETY: stack bounds already checked few lines above
         topics.push(B256::from(unsafe { interpreter.stack.pop_unsafe() }));
@@ -205,17 +207,25 @@ pub fn log<const N: usize, H: Host + ?Sized>(interpreter: &mut Interpreter, host
         data: LogData::new(topics, data).expect("LogData should have <=4 topics"),
     };
 
-    host.log(log);
+    if let Err(result) = host.log(log) {
+        interpreter.instruction_result = result;
+    }
 }
 
 pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, host: &mut H) {
-    require_non_staticcall!(interpreter);
     pop_address!(interpreter, target);
 
-    let Some(res) = host.selfdestruct(interpreter.contract.target_address, target) else {
-        interpreter.instruction_result = InstructionResult::FatalExternalError;
-        return;
+    let res = match host.selfdestruct(interpreter.contract.target_address, target) {
+        Ok(res) => res,
+        Err(result) => {
+            interpreter.instruction_result = result;
+            return;
+        }
     };
+    if res.balance_overflow {
+        interpreter.instruction_result = InstructionResult::OverflowPayment;
+        return;
+    }
 
     // EIP-3529: Reduction in refunds
     if !SPEC::enabled(LONDON) && !res.previously_destroyed {
@@ -225,3 +235,47 @@ pub fn selfdestruct<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter,
 
     interpreter.instruction_result = InstructionResult::SelfDestruct;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::opcode_gas;
+    use bytecode::opcode::*;
+    use primitives::U256;
+    use specification::hardfork::SpecId::{self, *};
+
+    #[test]
+    fn storage_gas() {
+        // Slots of the host are unset and cold.
+        let cases: [(SpecId, u8, &[u64], u64); 14] = [
+            (FRONTIER, SLOAD, &[0], 50),
+            // EIP-150
+            (TANGERINE, SLOAD, &[0], 200),
+            // EIP-1884
+            (ISTANBUL, SLOAD, &[0], 800),
+            // EIP-2929: cold slot
+            (BERLIN, SLOAD, &[0], 2100),
+            (CANCUN, SLOAD, &[0], 2100),
+            // G_sset
+            (FRONTIER, SSTORE, &[0, 1], 20_000),
+            (ISTANBUL, SSTORE, &[0, 1], 20_000),
+            (BERLIN, SSTORE, &[0, 1], 20_000 + 2100),
+            // G_sreset for a no-op store before net gas metering.
+            (FRONTIER, SSTORE, &[0, 0], 5000),
+            (PETERSBURG, SSTORE, &[0, 0], 5000),
+            // EIP-2200: no-op store costs an `SLOAD`.
+            (ISTANBUL, SSTORE, &[0, 0], 800),
+            (BERLIN, SSTORE, &[0, 0], 100 + 2100),
+            // EIP-1153
+            (CANCUN, TLOAD, &[0], 100),
+            (CANCUN, TSTORE, &[0, 1], 100),
+        ];
+        for (spec_id, op, inputs, gas) in cases {
+            let inputs: std::vec::Vec<_> = inputs.iter().map(|i| U256::from(*i)).collect();
+            assert_eq!(
+                opcode_gas(spec_id, &inputs, op),
+                gas,
+                "{op:#x} {inputs:?} {spec_id:?}"
+            );
+        }
+    }
+}
@@ -1,16 +1,5 @@
 //! Utility macros to help implementing opcode instruction functions.
 
-/// Fails the instruction if the current call is static.
-#[macro_export]
-macro_rules! require_non_staticcall {
-    ($interp:expr) => {
-        if $interp.is_static {
-            $interp.instruction_result = $crate::InstructionResult::StateChangeDuringStaticCall;
-            return;
-        }
-    };
-}
-
 /// Error if the current call is executing EOF.
 #[macro_export]
 macro_rules! require_eof {
@@ -96,19 +85,13 @@ macro_rules! resize_memory {
     ($interp:expr, $offset:expr, $len:expr, $ret:expr) => {
         let new_size = $offset.saturating_add($len);
         if new_size > $interp.shared_memory.len() {
-            #[cfg(feature = "memory_limit")]
-            if $interp.shared_memory.limit_reached(new_size) {
-                $interp.instruction_result = $crate::InstructionResult::MemoryLimitOOG;
-                return $ret;
-            }
-
             // Note: we can't use `Interpreter` directly here because of potential double-borrows.
-            if !$crate::interpreter::resize_memory(
+            if let Err(result) = $crate::interpreter::resize_memory(
                 &mut $interp.shared_memory,
                 &mut $interp.gas,
                 new_size,
             ) {
-                $interp.instruction_result = $crate::InstructionResult::MemoryOOG;
+                $interp.instruction_result = result;
                 return $ret;
             }
         }
@@ -250,6 +233,16 @@ macro_rules! pop_top {
     };
 }
 
+/// Reads an `Address` from the top of the stack and returns a reference to the top of the
+/// stack, so the result can be written in its place. Fails the instruction if the stack is empty.
+#[macro_export]
+macro_rules! top_address {
+    ($interp:expr, $address:ident, $top:ident) => {
+        $crate::pop_top!($interp, $top);
+        let $address = ::primitives::Address::from_word(::primitives::B256::from(*$top));
+    };
+}
+
 /// Pushes `B256` values onto the stack. Fails the instruction if the stack is full.
 #[macro_export]
 macro_rules! push_b256 {
@@ -1,7 +1,6 @@
 use crate::{gas, Host, Interpreter};
 use core::cmp::max;
 use primitives::U256;
-use specification::hardfork::Spec;
 
 pub fn mload<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::VERYLOW);
@@ -33,8 +32,7 @@ pub fn msize<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
 }
 
 // EIP-5656: MCOPY - Memory copying instruction
-pub fn mcopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, CANCUN);
+pub fn mcopy<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     pop!(interpreter, dst, src, len);
 
     // into usize or fail
@@ -52,3 +50,37 @@ pub fn mcopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host:
     // copy memory in place
     interpreter.shared_memory.copy(dst, src, len);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::instructions::opcode_gas;
+    use bytecode::opcode::*;
+    use primitives::U256;
+    use specification::hardfork::SpecId::{self, *};
+
+    #[test]
+    fn gas() {
+        // W_verylow = 3, plus memory expansion of G_memory = 3 per word and words² / 512.
+        let cases: [(SpecId, u8, &[u64], u64); 10] = [
+            (FRONTIER, MLOAD, &[0], 3 + 3),
+            (FRONTIER, MSTORE, &[0, 1], 3 + 3),
+            (FRONTIER, MSTORE, &[32, 1], 3 + 2 * 3),
+            (FRONTIER, MSTORE, &[1024, 1], 3 + 33 * 3 + 33 * 33 / 512),
+            (FRONTIER, MSTORE8, &[0, 1], 3 + 3),
+            (FRONTIER, MSIZE, &[], 2),
+            (LONDON, MSTORE, &[0, 1], 3 + 3),
+            // EIP-5656: W_verylow and W_copy = 3 per copied word.
+            (CANCUN, MCOPY, &[0, 0, 32], 3 + 3 + 3),
+            (CANCUN, MCOPY, &[32, 0, 33], 3 + 2 * 3 + 3 * 3),
+            (CANCUN, MCOPY, &[0, 0, 0], 3),
+        ];
+        for (spec_id, op, inputs, gas) in cases {
+            let inputs: std::vec::Vec<_> = inputs.iter().map(|i| U256::from(*i)).collect();
+            assert_eq!(
+                opcode_gas(spec_id, &inputs, op),
+                gas,
+                "{op:#x} {inputs:?} {spec_id:?}"
+            );
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use crate::{gas, Host, InstructionResult, Interpreter};
+use crate::{gas, interpreter::copy_padded, Host, InstructionResult, Interpreter};
 use core::ptr;
 use primitives::{B256, KECCAK_EMPTY, U256};
-use specification::hardfork::Spec;
 
 pub fn keccak256<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     pop_top!(interpreter, offset, len_ptr);
@@ -104,8 +103,7 @@ pub fn calldatacopy<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut
 }
 
 /// EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
-pub fn returndatasize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, BYZANTIUM);
+pub fn returndatasize<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     gas!(interpreter, gas::BASE);
     push!(
         interpreter,
@@ -114,8 +112,7 @@ pub fn returndatasize<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interprete
 }
 
 /// EIP-211: New opcodes: RETURNDATASIZE and RETURNDATACOPY
-pub fn returndatacopy<H: Host + ?Sized, SPEC: Spec>(interpreter: &mut Interpreter, _host: &mut H) {
-    check!(interpreter, BYZANTIUM);
+pub fn returndatacopy<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &mut H) {
     pop!(interpreter, memory_offset, offset, len);
 
     let len = as_usize_or_fail!(interpreter, len);
@@ -150,16 +147,7 @@ pub fn returndataload<H: Host + ?Sized>(interpreter: &mut Interpreter, _host: &m
     let offset_usize = as_usize_saturated!(offset);
 
     let mut output = [0u8; 32];
-    if let Some(available) = interpreter
-        .return_data_buffer
-        .len()
-        .checked_sub(offset_usize)
-    {
-        let copy_len = available.min(32);
-        output[..copy_len].copy_from_slice(
-            &interpreter.return_data_buffer[offset_usize..offset_usize + copy_len],
-        );
-    }
+    copy_padded(&mut output, &interpreter.return_data_buffer, offset_usize);
 
     *offset = B256::from(output).into();
 }
@@ -190,9 +178,12 @@ pub fn memory_resize(
 mod test {
     use super::*;
     use crate::{table::make_instruction_table, DummyHost, Gas, InstructionResult};
-    use bytecode::opcode::{RETURNDATACOPY, RETURNDATALOAD};
+    use bytecode::opcode::{
+        CALLDATACOPY, CODECOPY, MSIZE, MSTORE, PUSH1, PUSH32, RETURN, RETURNDATACOPY,
+        RETURNDATALOAD,
+    };
     use bytecode::Bytecode;
-    use primitives::bytes;
+    use primitives::{bytes, Bytes};
     use specification::hardfork::PragueSpec;
     use wiring::DefaultEthereumWiring;
 
@@ -254,6 +245,43 @@ mod test {
         );
     }
 
+    /// Runs `COPY(dest: 0, offset, len: 64)` over memory filled with ones and returns
+    /// the memory.
+    fn copy_to_memory(copy: u8, offset: u8, calldata: &[u8]) -> Bytes {
+        let mut code = vec![PUSH32];
+        code.extend([0xff; 32]);
+        code.extend([PUSH1, 0x00, MSTORE, PUSH32]);
+        code.extend([0xff; 32]);
+        code.extend([PUSH1, 0x20, MSTORE]);
+        code.extend([PUSH1, 0x40, PUSH1, offset, PUSH1, 0x00, copy]);
+        code.extend([MSIZE, PUSH1, 0x00, RETURN]);
+        let result = Interpreter::run_standalone::<PragueSpec>(
+            code.into(),
+            Bytes::copy_from_slice(calldata),
+            100_000,
+        );
+        assert_eq!(result.result.result, InstructionResult::Return);
+        result.result.output
+    }
+
+    #[test]
+    fn copy_zero_fills_out_of_range() {
+        // Partially in range.
+        let memory = copy_to_memory(CALLDATACOPY, 2, &[1, 2, 3, 4]);
+        assert_eq!(memory[..2], [3, 4]);
+        assert_eq!(memory[2..], [0; 62]);
+        // Past the end.
+        let memory = copy_to_memory(CALLDATACOPY, 4, &[1, 2, 3, 4]);
+        assert_eq!(memory[..], [0; 64]);
+
+        // Code is 83 bytes long and ends with `MSIZE PUSH1 0x00 RETURN`.
+        let memory = copy_to_memory(CODECOPY, 83 - 4, &[]);
+        assert_eq!(memory[..4], [MSIZE, PUSH1, 0x00, RETURN]);
+        assert_eq!(memory[4..], [0; 60]);
+        let memory = copy_to_memory(CODECOPY, 0xff, &[]);
+        assert_eq!(memory[..], [0; 64]);
+    }
+
     #[test]
     fn returndatacopy() {
         let table = make_instruction_table::<_, PragueSpec>();
@@ -341,4 +369,41 @@ mod test {
         assert_eq!(interp.instruction_result, InstructionResult::Continue);
         assert_eq!(&interp.shared_memory.slice(0, 32), &[0u8; 32]);
     }
+
+    #[test]
+    fn gas() {
+        use crate::instructions::opcode_gas;
+        use bytecode::opcode::*;
+        use primitives::U256;
+        use specification::hardfork::SpecId::{self, *};
+
+        // W_base = 2 for environment information that needs no computation.
+        let base = [ADDRESS, CALLER, CALLVALUE, CALLDATASIZE, CODESIZE, GAS];
+        for spec_id in [FRONTIER, BERLIN, CANCUN] {
+            for op in base {
+                assert_eq!(opcode_gas(spec_id, &[], op), 2, "{op:#x} {spec_id:?}");
+            }
+        }
+        // EIP-211
+        assert_eq!(opcode_gas(BYZANTIUM, &[], RETURNDATASIZE), 2);
+
+        // G_keccak256 = 30 plus 6 per word, W_copy = 3 per word, plus memory expansion.
+        let cases: [(SpecId, u8, &[u64], u64); 7] = [
+            (FRONTIER, CALLDATALOAD, &[0], 3),
+            (FRONTIER, KECCAK256, &[0, 0], 30),
+            (FRONTIER, KECCAK256, &[0, 32], 30 + 6 + 3),
+            (CANCUN, KECCAK256, &[0, 33], 30 + 2 * 6 + 2 * 3),
+            (FRONTIER, CALLDATACOPY, &[0, 0, 32], 3 + 3 + 3),
+            (FRONTIER, CODECOPY, &[0, 0, 32], 3 + 3 + 3),
+            (CANCUN, CODECOPY, &[0, 0, 0], 3),
+        ];
+        for (spec_id, op, inputs, gas) in cases {
+            let inputs: std::vec::Vec<_> = inputs.iter().map(|i| U256::from(*i)).collect();
+            assert_eq!(
+                opcode_gas(spec_id, &inputs, op),
+                gas,
+                "{op:#x} {inputs:?} {spec_id:?}"
+            );
+        }
+    }
 }
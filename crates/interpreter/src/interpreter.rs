@@ -5,18 +5,25 @@ mod shared_memory;
 mod stack;
 
 pub use contract::Contract;
-pub use shared_memory::{num_words, SharedMemory, EMPTY_SHARED_MEMORY};
-pub use stack::{Stack, STACK_LIMIT};
+pub use shared_memory::{
+    copy_padded, num_words, MemoryMut, MemoryRef, SharedMemory, EMPTY_SHARED_MEMORY,
+};
+pub use stack::{Stack, StackRef, STACK_LIMIT};
 
 use crate::{
-    gas, push, push_b256, return_ok, return_revert, CallOutcome, CreateOutcome, FunctionStack, Gas,
-    Host, InstructionResult, InterpreterAction,
+    gas, push, push_b256, return_ok, return_revert,
+    table::{make_instruction_table, InstructionTable, SpecializedTable},
+    CallOutcome, CreateOutcome, DummyHost, FunctionStack, Gas, Host, InstructionResult,
+    InterpreterAction,
 };
 use bytecode::{Bytecode, Eof};
 use core::cmp::min;
-use primitives::{Bytes, U256};
+use primitives::{Address, Bytes, U256};
+use specification::hardfork::Spec;
 use std::borrow::ToOwned;
 use std::sync::Arc;
+use std::vec::Vec;
+use wiring::DefaultEthereumWiring;
 
 /// EVM bytecode interpreter.
 #[derive(Debug)]
@@ -152,6 +159,8 @@ impl Interpreter {
     /// - `Ok`: Pushes the address from `create_outcome` to the stack, updates gas costs, and records any gas refunds.
     /// - `Revert`: Pushes `U256::ZERO` to the stack and updates gas costs.
     /// - `FatalExternalError`: Sets the `instruction_result` to `InstructionResult::FatalExternalError`.
+    /// - `StateChangeDuringStaticCall`: The create was made in the static context, the
+    ///   interpreter halts with it.
     /// - `Default`: Pushes `U256::ZERO` to the stack.
     ///
     /// # Side Effects
@@ -186,6 +195,9 @@ impl Interpreter {
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_create_outcome");
             }
+            InstructionResult::StateChangeDuringStaticCall => {
+                self.instruction_result = InstructionResult::StateChangeDuringStaticCall;
+            }
             _ => {
                 push!(self, U256::ZERO);
             }
@@ -220,6 +232,9 @@ impl Interpreter {
             InstructionResult::FatalExternalError => {
                 panic!("Fatal external error in insert_eofcreate_outcome");
             }
+            InstructionResult::StateChangeDuringStaticCall => {
+                self.instruction_result = InstructionResult::StateChangeDuringStaticCall;
+            }
             _ => {
                 push!(self, U256::ZERO);
             }
@@ -248,6 +263,8 @@ impl Interpreter {
     /// - `return_revert!()`: Handles a revert by only updating the gas usage and shared memory.
     /// - `InstructionResult::FatalExternalError`: Sets the instruction result to a fatal external error.
     /// - Any other result: No specific action is taken.
+    ///
+    /// Calls with `out_len == 0` only set the return data buffer, memory is not touched.
     pub fn insert_call_outcome(
         &mut self,
         shared_memory: &mut SharedMemory,
@@ -335,6 +352,24 @@ impl Interpreter {
         &mut self.stack
     }
 
+    /// Returns a read-only view of the interpreter's stack.
+    #[inline]
+    pub fn stack_ref(&self) -> StackRef<'_> {
+        StackRef::from(&self.stack)
+    }
+
+    /// Returns a read-only view of the memory of the current call.
+    #[inline]
+    pub fn memory_ref(&self) -> MemoryRef<'_> {
+        MemoryRef::from(&self.shared_memory)
+    }
+
+    /// Returns a bounds checked mutable view of the memory of the current call.
+    #[inline]
+    pub fn memory_mut(&mut self) -> MemoryMut<'_> {
+        MemoryMut::from(&mut self.shared_memory)
+    }
+
     /// Returns the current program counter.
     #[inline]
     pub fn program_counter(&self) -> usize {
@@ -363,6 +398,50 @@ impl Interpreter {
         (instruction_table[opcode as usize])(self, host)
     }
 
+    /// Executes the interpreter with the instructions specialized for the current bytecode.
+    ///
+    /// Falls back to `fallback` for EOF code, or if `specialized` does not cover the bytecode.
+    /// A program counter that is not the start of an instruction in `specialized` is executed
+    /// with `fallback` as well.
+    pub fn run_specialized<H: Host + ?Sized>(
+        &mut self,
+        shared_memory: SharedMemory,
+        specialized: &SpecializedTable<H>,
+        fallback: &InstructionTable<H>,
+        host: &mut H,
+    ) -> InterpreterAction {
+        if self.is_eof || specialized.len() != self.bytecode.len() {
+            return self.run(shared_memory, fallback, host);
+        }
+        self.next_action = InterpreterAction::None;
+        self.shared_memory = shared_memory;
+        while self.instruction_result == InstructionResult::Continue {
+            let Some(block) = specialized.block(self.program_counter()) else {
+                self.step(fallback, host);
+                continue;
+            };
+            // Instructions inside of a block can only move to the next one, jumps end it.
+            for instruction in block {
+                self.instruction_pointer = unsafe { self.instruction_pointer.offset(1) };
+                instruction(self, host);
+                if self.instruction_result != InstructionResult::Continue {
+                    break;
+                }
+            }
+        }
+
+        if self.next_action.is_some() {
+            return core::mem::take(&mut self.next_action);
+        }
+        InterpreterAction::Return {
+            result: InterpreterResult {
+                result: self.instruction_result,
+                output: Bytes::new(),
+                gas: self.gas,
+            },
+        }
+    }
+
     /// Take memory and replace it with empty memory.
     pub fn take_memory(&mut self) -> SharedMemory {
         core::mem::replace(&mut self.shared_memory, EMPTY_SHARED_MEMORY)
@@ -400,10 +479,89 @@ impl Interpreter {
         }
     }
 
-    /// Resize the memory to the new size. Returns whether the gas was enough to resize the memory.
+    /// Executes the interpreter like [`Interpreter::run`], calling `should_interrupt` before
+    /// every instruction.
+    ///
+    /// Once `should_interrupt` returns `true`, the execution stops with
+    /// [`InstructionResult::Interrupted`].
+    pub fn run_interruptible<FN, H: Host + ?Sized, F>(
+        &mut self,
+        shared_memory: SharedMemory,
+        instruction_table: &[FN; 256],
+        host: &mut H,
+        mut should_interrupt: F,
+    ) -> InterpreterAction
+    where
+        FN: Fn(&mut Interpreter, &mut H),
+        F: FnMut() -> bool,
+    {
+        self.next_action = InterpreterAction::None;
+        self.shared_memory = shared_memory;
+        while self.instruction_result == InstructionResult::Continue {
+            if should_interrupt() {
+                self.instruction_result = InstructionResult::Interrupted;
+                break;
+            }
+            self.step(instruction_table, host);
+        }
+
+        if self.next_action.is_some() {
+            return core::mem::take(&mut self.next_action);
+        }
+        InterpreterAction::Return {
+            result: InterpreterResult {
+                result: self.instruction_result,
+                output: Bytes::new(),
+                gas: self.gas,
+            },
+        }
+    }
+
+    /// Executes legacy `bytecode` with `calldata` as input, without any state.
+    ///
+    /// Runs against a [`DummyHost`] with the default environment, so storage is empty and
+    /// only lives for the duration of the execution. Calls and creates are not executed, the
+    /// execution stops at them with [`InstructionResult::CallOrCreate`].
+    ///
+    /// Useful for evaluating code snippets without setting up a database.
+    pub fn run_standalone<SPEC: Spec>(
+        bytecode: Bytes,
+        calldata: Bytes,
+        gas_limit: u64,
+    ) -> StandaloneResult {
+        let contract = Contract::new(
+            calldata,
+            Bytecode::new_legacy(bytecode),
+            None,
+            Address::ZERO,
+            None,
+            Address::ZERO,
+            U256::ZERO,
+        );
+        let mut interpreter = Self::new(contract, gas_limit, false);
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, SPEC>();
+
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        let result = match interpreter.run(shared_memory, &table, &mut host) {
+            InterpreterAction::Return { result } => result,
+            _ => InterpreterResult::new(
+                interpreter.instruction_result,
+                Bytes::new(),
+                interpreter.gas,
+            ),
+        };
+        StandaloneResult {
+            result,
+            stack: core::mem::take(interpreter.stack.data_mut()),
+            memory: Bytes::copy_from_slice(interpreter.shared_memory.context_memory()),
+        }
+    }
+
+    /// Resize the memory to the new size, see [`resize_memory`].
     #[inline]
-    #[must_use]
-    pub fn resize_memory(&mut self, new_size: usize) -> bool {
+    pub fn resize_memory(&mut self, new_size: usize) -> Result<(), InstructionResult> {
         resize_memory(&mut self.shared_memory, &mut self.gas, new_size)
     }
 }
@@ -449,29 +607,94 @@ impl InterpreterResult {
     }
 }
 
-/// Resize the memory to the new size. Returns whether the gas was enough to resize the memory.
+/// The result of [`Interpreter::run_standalone`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StandaloneResult {
+    /// The result of the execution, with the output and gas.
+    pub result: InterpreterResult,
+    /// The final stack, the top of the stack is the last element.
+    pub stack: Vec<U256>,
+    /// The final memory.
+    pub memory: Bytes,
+}
+
+impl StandaloneResult {
+    /// Returns the output of the execution.
+    #[inline]
+    pub fn output(&self) -> &Bytes {
+        &self.result.output
+    }
+
+    /// Returns the gas used by the execution.
+    #[inline]
+    pub const fn gas_used(&self) -> u64 {
+        self.result.gas.spent()
+    }
+}
+
+/// Resize the memory to the new size.
+///
+/// Fails with [`InstructionResult::MemoryLimitOOG`] if the new size exceeds the memory limit of
+/// the `memory_limit` feature, with [`InstructionResult::Interrupted`] if it exceeds the
+/// [interrupt limit](SharedMemory::set_interrupt_limit), and with [`InstructionResult::MemoryOOG`]
+/// if the gas is not enough to pay for the expansion. Memory is not resized on failure.
 #[inline(never)]
 #[cold]
-#[must_use]
-pub fn resize_memory(memory: &mut SharedMemory, gas: &mut Gas, new_size: usize) -> bool {
+pub fn resize_memory(
+    memory: &mut SharedMemory,
+    gas: &mut Gas,
+    new_size: usize,
+) -> Result<(), InstructionResult> {
+    #[cfg(feature = "memory_limit")]
+    if memory.limit_reached(new_size) {
+        return Err(InstructionResult::MemoryLimitOOG);
+    }
+    if memory.interrupt_limit_reached(new_size) {
+        return Err(InstructionResult::Interrupted);
+    }
+
     let new_words = num_words(new_size as u64);
     let new_cost = gas::memory_gas(new_words);
     let current_cost = memory.current_expansion_cost();
     let cost = new_cost - current_cost;
-    let success = gas.record_cost(cost);
-    if success {
-        memory.resize((new_words as usize) * 32);
+    if !gas.record_cost(cost) {
+        return Err(InstructionResult::MemoryOOG);
     }
-    success
+    memory.resize((new_words as usize) * 32);
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{table::InstructionTable, DummyHost};
+    use bytecode::opcode::STOP;
     use specification::hardfork::CancunSpec;
     use wiring::DefaultEthereumWiring;
 
+    #[test]
+    fn resize_memory_interrupt_limit() {
+        let mut memory = SharedMemory::new();
+        memory.new_context();
+        memory.set_interrupt_limit(Some(64));
+        let mut gas = Gas::new(u64::MAX);
+        assert_eq!(resize_memory(&mut memory, &mut gas, 64), Ok(()));
+
+        // Checked before the memory is allocated and the gas is charged.
+        let spent = gas.spent();
+        assert_eq!(
+            resize_memory(&mut memory, &mut gas, 1 << 30),
+            Err(InstructionResult::Interrupted)
+        );
+        assert_eq!(memory.len(), 64);
+        assert_eq!(gas.spent(), spent);
+
+        // Clearing the memory removes the limit.
+        memory.clear();
+        memory.new_context();
+        assert_eq!(resize_memory(&mut memory, &mut gas, 128), Ok(()));
+    }
+
     #[test]
     fn object_safety() {
         let mut interp = Interpreter::new(Contract::default(), u64::MAX, false);
@@ -490,4 +713,279 @@ mod tests {
             >();
         let _ = interp.run(EMPTY_SHARED_MEMORY, table, host);
     }
+
+    #[test]
+    fn resize_memory_errors() {
+        let mut memory = SharedMemory::new();
+        memory.new_context();
+
+        let mut gas = Gas::new(3);
+        assert_eq!(resize_memory(&mut memory, &mut gas, 32), Ok(()));
+        assert_eq!(memory.len(), 32);
+        assert_eq!(
+            resize_memory(&mut memory, &mut gas, 64),
+            Err(InstructionResult::MemoryOOG)
+        );
+        assert_eq!(memory.len(), 32);
+
+        #[cfg(feature = "memory_limit")]
+        {
+            memory.set_memory_limit(64);
+            let mut gas = Gas::new(u64::MAX);
+            assert_eq!(resize_memory(&mut memory, &mut gas, 64), Ok(()));
+            assert_eq!(
+                resize_memory(&mut memory, &mut gas, 65),
+                Err(InstructionResult::MemoryLimitOOG)
+            );
+            assert_eq!(memory.len(), 64);
+        }
+    }
+
+    #[test]
+    fn ignored_call_output_is_not_written() {
+        let mut interp = Interpreter::new_bytecode(Bytecode::default());
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(32);
+
+        let output = Bytes::from_static(&[0xff; 32]);
+        let outcome = CallOutcome::new(
+            InterpreterResult::new(InstructionResult::Return, output.clone(), Gas::new(0)),
+            0..0,
+        );
+        interp.insert_call_outcome(&mut shared_memory, outcome);
+
+        assert_eq!(interp.return_data_buffer, output);
+        assert_eq!(shared_memory.slice(0, 32), &[0; 32]);
+        assert_eq!(interp.stack.data(), &[U256::from(1)]);
+    }
+
+    #[test]
+    fn specialized_matches_plain() {
+        use bytecode::opcode::{ADD, JUMP, JUMPDEST, PUSH1};
+
+        let bytecode = Bytecode::new_raw(
+            [
+                PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x08, JUMP, JUMPDEST, STOP,
+            ]
+            .into(),
+        );
+        let new_interp = || {
+            let contract = Contract::new(
+                Bytes::new(),
+                bytecode.clone(),
+                None,
+                primitives::Address::default(),
+                None,
+                primitives::Address::default(),
+                U256::ZERO,
+            );
+            Interpreter::new(contract, 100_000, false)
+        };
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+
+        let mut plain = new_interp();
+        let _ = plain.run(EMPTY_SHARED_MEMORY, &table, &mut host);
+
+        let mut specialized = new_interp();
+        let specialized_table = SpecializedTable::new(&specialized.bytecode, &table);
+        let _ =
+            specialized.run_specialized(EMPTY_SHARED_MEMORY, &specialized_table, &table, &mut host);
+
+        assert_eq!(specialized.instruction_result, InstructionResult::Stop);
+        assert_eq!(specialized.instruction_result, plain.instruction_result);
+        assert_eq!(specialized.stack.data(), plain.stack.data());
+        assert_eq!(specialized.gas, plain.gas);
+    }
+
+    #[test]
+    fn specialized_blocks() {
+        use bytecode::opcode::{DUP1, JUMPDEST, JUMPI, PUSH1, SUB, SWAP1};
+
+        // Counts down from 3 to 0 in a loop.
+        let code = [
+            PUSH1, 0x03, JUMPDEST, PUSH1, 0x01, SWAP1, SUB, DUP1, PUSH1, 0x02, JUMPI, STOP,
+        ];
+        let contract = Contract::new(
+            Bytes::new(),
+            Bytecode::new_raw(code.into()),
+            None,
+            primitives::Address::default(),
+            None,
+            primitives::Address::default(),
+            U256::ZERO,
+        );
+        let mut interp = Interpreter::new(contract, 100_000, false);
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let specialized = SpecializedTable::new(&interp.bytecode, &table);
+
+        // `PUSH1`, then the loop body up to `JUMPI`, then `STOP` and the padding.
+        assert_eq!(specialized.block(0).map(<[_]>::len), Some(1));
+        assert_eq!(specialized.block(1).map(<[_]>::len), None);
+        assert_eq!(specialized.block(2).map(<[_]>::len), Some(7));
+        assert_eq!(specialized.block(7).map(<[_]>::len), Some(3));
+        assert_eq!(
+            specialized.block(11).map(<[_]>::len),
+            Some(1 + interp.bytecode.len() - 12)
+        );
+
+        let _ = interp.run_specialized(EMPTY_SHARED_MEMORY, &specialized, &table, &mut host);
+        assert_eq!(interp.instruction_result, InstructionResult::Stop);
+        assert_eq!(interp.stack.data(), &[U256::ZERO]);
+    }
+
+    /// Runs the code with the plain table and with fused super-instructions.
+    #[cfg(feature = "superinstructions")]
+    fn run_superinstructions(
+        code: &[u8],
+        table: &crate::table::InstructionTable<DummyHost<DefaultEthereumWiring>>,
+    ) -> (Interpreter, Interpreter) {
+        let bytecode = Bytecode::new_raw(Bytes::copy_from_slice(code));
+        let new_interp = || {
+            let contract = Contract::new(
+                Bytes::new(),
+                bytecode.clone(),
+                None,
+                primitives::Address::default(),
+                None,
+                primitives::Address::default(),
+                U256::ZERO,
+            );
+            Interpreter::new(contract, 100_000, false)
+        };
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+
+        let mut plain = new_interp();
+        let _ = plain.run(SharedMemory::new(), table, &mut host);
+
+        let mut fused = new_interp();
+        let fused_table = SpecializedTable::with_superinstructions(&fused.bytecode, table);
+        let _ = fused.run_specialized(SharedMemory::new(), &fused_table, table, &mut host);
+
+        assert_eq!(fused.instruction_result, plain.instruction_result);
+        assert_eq!(fused.gas, plain.gas);
+        (plain, fused)
+    }
+
+    #[test]
+    #[cfg(feature = "superinstructions")]
+    fn superinstructions_match_plain() {
+        use crate::{instructions::fused, table::Instruction};
+        use bytecode::opcode::{INVALID, JUMP, JUMPDEST, JUMPI, MSTORE, PUSH1, PUSH2};
+
+        let code = [
+            PUSH1, 0x20, PUSH1, 0x00, MSTORE, PUSH1, 0x01, PUSH2, 0x00, 0x0c, JUMPI, INVALID,
+            JUMPDEST, PUSH2, 0x00, 0x11, JUMP, JUMPDEST, STOP,
+        ];
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let (_, fused) = run_superinstructions(&code, &table);
+
+        assert_eq!(fused.instruction_result, InstructionResult::Stop);
+        assert_eq!(
+            fused.shared_memory.slice(0, 32),
+            &U256::from(0x20).to_be_bytes::<32>()
+        );
+
+        let fused_table = SpecializedTable::with_superinstructions(&code, &table);
+        let push1_mstore: Instruction<DummyHost<DefaultEthereumWiring>> = fused::push1_mstore;
+        assert!(core::ptr::fn_addr_eq(
+            fused_table.block(2).unwrap()[0],
+            push1_mstore
+        ));
+        // The fused instruction dispatches the `MSTORE` too.
+        assert!(fused_table.block(4).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "superinstructions")]
+    fn superinstructions_loop() {
+        use bytecode::opcode::{DUP1, JUMPDEST, JUMPI, PUSH1, PUSH2, SUB, SWAP1};
+
+        // Counts down from 3 to 0 in a loop that ends with a fused `PUSH2 JUMPI`.
+        let code = [
+            PUSH1, 0x03, JUMPDEST, PUSH1, 0x01, SWAP1, SUB, DUP1, PUSH2, 0x00, 0x02, JUMPI, STOP,
+        ];
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        let (plain, fused) = run_superinstructions(&code, &table);
+        assert_eq!(fused.instruction_result, InstructionResult::Stop);
+        assert_eq!(fused.stack.data(), plain.stack.data());
+    }
+
+    #[test]
+    #[cfg(feature = "superinstructions")]
+    fn superinstructions_invalid_jump() {
+        use bytecode::opcode::{JUMP, JUMPI, PUSH1, PUSH2};
+
+        let table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        // Destination is a STOP, not a JUMPDEST.
+        let (_, fused) = run_superinstructions(&[PUSH2, 0x00, 0x04, JUMP, STOP], &table);
+        assert_eq!(fused.instruction_result, InstructionResult::InvalidJump);
+
+        let code = [PUSH1, 0x01, PUSH2, 0x00, 0x06, JUMPI, STOP];
+        let (_, fused) = run_superinstructions(&code, &table);
+        assert_eq!(fused.instruction_result, InstructionResult::InvalidJump);
+    }
+
+    #[test]
+    #[cfg(feature = "superinstructions")]
+    fn superinstructions_keep_custom_instructions() {
+        use bytecode::opcode::{MSTORE, PUSH1};
+
+        let mut table =
+            crate::table::make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+        table[MSTORE as usize] = |interpreter, _| {
+            interpreter.instruction_result = InstructionResult::Revert;
+        };
+        let code = [PUSH1, 0x20, PUSH1, 0x00, MSTORE, STOP];
+        let (_, fused) = run_superinstructions(&code, &table);
+        assert_eq!(fused.instruction_result, InstructionResult::Revert);
+    }
+
+    #[test]
+    fn run_standalone() {
+        use bytecode::opcode::{ADD, CALLDATALOAD, DUP1, MSTORE, PUSH1, RETURN};
+
+        // Returns the first calldata word plus one, leaving a copy of it on the stack.
+        let bytecode = Bytes::from_static(&[
+            PUSH1,
+            0x00,
+            CALLDATALOAD,
+            PUSH1,
+            0x01,
+            ADD,
+            DUP1,
+            PUSH1,
+            0x00,
+            MSTORE,
+            PUSH1,
+            0x20,
+            PUSH1,
+            0x00,
+            RETURN,
+        ]);
+        let calldata = Bytes::from(U256::from(41).to_be_bytes_vec());
+        let result = Interpreter::run_standalone::<CancunSpec>(bytecode, calldata, 100_000);
+
+        assert_eq!(result.result.result, InstructionResult::Return);
+        assert_eq!(result.output()[..], U256::from(42).to_be_bytes::<32>());
+        assert_eq!(result.memory[..], U256::from(42).to_be_bytes::<32>());
+        assert_eq!(result.stack, [U256::from(42)]);
+        // Nine verylow cost opcodes and one word of memory expansion.
+        assert_eq!(result.gas_used(), 9 * 3 + 3);
+    }
+
+    #[test]
+    fn run_standalone_out_of_gas() {
+        let bytecode = Bytes::from_static(&[bytecode::opcode::PUSH1, 0x00]);
+        let result = Interpreter::run_standalone::<CancunSpec>(bytecode, Bytes::new(), 2);
+        assert_eq!(result.result.result, InstructionResult::OutOfGas);
+        assert!(result.output().is_empty());
+    }
 }
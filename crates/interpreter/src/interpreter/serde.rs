@@ -90,6 +90,11 @@ impl<'de> Deserialize<'de> for Interpreter {
         if program_counter >= bytecode.len() {
             return Err(serde::de::Error::custom("program_counter out of bounds"));
         }
+        if is_eof != contract.bytecode.is_eof() {
+            return Err(serde::de::Error::custom(
+                "is_eof does not match contract bytecode",
+            ));
+        }
 
         // SAFETY: range of program_counter checked above
         let instruction_pointer = unsafe { bytecode.as_ptr().add(program_counter) };
@@ -115,6 +120,14 @@ impl<'de> Deserialize<'de> for Interpreter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{table::make_instruction_table, DummyHost};
+    use bytecode::{
+        opcode::{MSTORE, PUSH1, RETURN},
+        Bytecode,
+    };
+    use primitives::{Address, U256};
+    use specification::hardfork::CancunSpec;
+    use wiring::DefaultEthereumWiring;
 
     #[test]
     fn test_serde() {
@@ -123,4 +136,46 @@ mod tests {
         let de: Interpreter = bincode::deserialize(&serialized).unwrap();
         assert_eq!(interp.program_counter(), de.program_counter());
     }
+
+    #[test]
+    fn resume_from_snapshot() {
+        let bytecode = Bytecode::new_raw(
+            [
+                PUSH1, 0x2a, PUSH1, 0x00, MSTORE, PUSH1, 0x20, PUSH1, 0x00, RETURN,
+            ]
+            .into(),
+        );
+        let contract = Contract::new(
+            Bytes::new(),
+            bytecode,
+            None,
+            Address::default(),
+            None,
+            Address::default(),
+            U256::ZERO,
+        );
+        let mut host = DummyHost::<DefaultEthereumWiring>::default();
+        let table = make_instruction_table::<DummyHost<DefaultEthereumWiring>, CancunSpec>();
+
+        let mut interp = Interpreter::new(contract, 100_000, false);
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        interp.shared_memory = shared_memory;
+        // Pause after MSTORE.
+        for _ in 0..3 {
+            interp.step(&table, &mut host);
+        }
+
+        let snapshot = bincode::serialize(&interp).unwrap();
+        let mut resumed: Interpreter = bincode::deserialize(&snapshot).unwrap();
+        assert_eq!(resumed.program_counter(), interp.program_counter());
+        assert_eq!(resumed.gas, interp.gas);
+        assert_eq!(resumed.stack, interp.stack);
+        assert_eq!(resumed.shared_memory, interp.shared_memory);
+
+        let memory = interp.take_memory();
+        let expected = interp.run(memory, &table, &mut host);
+        let memory = resumed.take_memory();
+        assert_eq!(resumed.run(memory, &table, &mut host), expected);
+    }
 }
@@ -7,7 +7,7 @@ use std::vec::Vec;
 /// A [SharedMemory] instance should always be obtained using
 /// the `new` static method to ensure memory safety.
 #[derive(Clone, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SharedMemory {
     /// The underlying buffer.
     buffer: Vec<u8>,
@@ -19,6 +19,8 @@ pub struct SharedMemory {
     /// Memory limit. See [`CfgEnv`](wiring::default::CfgEnv).
     #[cfg(feature = "memory_limit")]
     memory_limit: u64,
+    /// Memory limit of an execution guard. See [`SharedMemory::set_interrupt_limit`].
+    interrupt_limit: Option<usize>,
 }
 
 /// Empty shared memory.
@@ -30,6 +32,7 @@ pub const EMPTY_SHARED_MEMORY: SharedMemory = SharedMemory {
     last_checkpoint: 0,
     #[cfg(feature = "memory_limit")]
     memory_limit: u64::MAX,
+    interrupt_limit: None,
 };
 
 impl fmt::Debug for SharedMemory {
@@ -66,6 +69,7 @@ impl SharedMemory {
             last_checkpoint: 0,
             #[cfg(feature = "memory_limit")]
             memory_limit: u64::MAX,
+            interrupt_limit: None,
         }
     }
 
@@ -82,6 +86,33 @@ impl SharedMemory {
         }
     }
 
+    /// Sets the upper bound for allocation size.
+    #[cfg(feature = "memory_limit")]
+    #[inline]
+    pub fn set_memory_limit(&mut self, memory_limit: u64) {
+        self.memory_limit = memory_limit;
+    }
+
+    /// Sets the upper bound for the size of the memory of all contexts, above which a resize
+    /// fails with [`InstructionResult::Interrupted`](crate::InstructionResult::Interrupted).
+    ///
+    /// Used by execution guards. The limit is removed by [`SharedMemory::clear`].
+    #[inline]
+    pub fn set_interrupt_limit(&mut self, interrupt_limit: Option<usize>) {
+        self.interrupt_limit = interrupt_limit;
+    }
+
+    /// Clears the memory and all contexts.
+    ///
+    /// The allocated buffer is kept, so the memory can be reused by the next execution.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.checkpoints.clear();
+        self.last_checkpoint = 0;
+        self.interrupt_limit = None;
+    }
+
     /// Returns `true` if the `new_size` for the current context memory will
     /// make the shared buffer length exceed the `memory_limit`.
     #[cfg(feature = "memory_limit")]
@@ -90,6 +121,14 @@ impl SharedMemory {
         self.last_checkpoint.saturating_add(new_size) as u64 > self.memory_limit
     }
 
+    /// Returns `true` if the `new_size` for the current context memory will
+    /// make the shared buffer length exceed the interrupt limit.
+    #[inline]
+    pub fn interrupt_limit_reached(&self, new_size: usize) -> bool {
+        self.interrupt_limit
+            .is_some_and(|limit| self.last_checkpoint.saturating_add(new_size) > limit)
+    }
+
     /// Prepares the shared memory for a new context.
     #[inline]
     pub fn new_context(&mut self) {
@@ -157,6 +196,24 @@ impl SharedMemory {
         }
     }
 
+    /// Converts a range of the current context memory to a range of the whole buffer.
+    ///
+    /// The converted range stays valid for child contexts, see
+    /// [`global_slice_range`](Self::global_slice_range).
+    #[inline]
+    pub fn global_range(&self, range: Range<usize>) -> Range<usize> {
+        range.start + self.last_checkpoint..range.end + self.last_checkpoint
+    }
+
+    /// Returns a byte slice of the whole buffer, not limited to the current context.
+    ///
+    /// The range can come from another context, so it is not covered by the memory limit
+    /// checks of the current one. Returns an empty slice if it is out of bounds.
+    #[inline]
+    pub fn global_slice_range(&self, range: Range<usize>) -> &[u8] {
+        self.buffer.get(range).unwrap_or_default()
+    }
+
     /// Returns a byte slice of the memory region at the given offset.
     ///
     /// # Panics
@@ -251,28 +308,17 @@ impl SharedMemory {
     /// Set memory from data. Our memory offset+len is expected to be correct but we
     /// are doing bound checks on data/data_offeset/len and zeroing parts that is not copied.
     ///
+    /// Data is copied straight into the resized memory and the tail past the end of the data
+    /// is zeroed in the same slice, so large copies like `CODECOPY` of a whole contract do
+    /// a single bounds check and no intermediate allocation.
+    ///
     /// # Panics
     ///
     /// Panics if memory is out of bounds.
     #[inline]
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn set_data(&mut self, memory_offset: usize, data_offset: usize, len: usize, data: &[u8]) {
-        if data_offset >= data.len() {
-            // nullify all memory slots
-            self.slice_mut(memory_offset, len).fill(0);
-            return;
-        }
-        let data_end = min(data_offset + len, data.len());
-        let data_len = data_end - data_offset;
-        debug_assert!(data_offset < data.len() && data_end <= data.len());
-        let data = unsafe { data.get_unchecked(data_offset..data_end) };
-        self.slice_mut(memory_offset, data_len)
-            .copy_from_slice(data);
-
-        // nullify rest of memory slots
-        // SAFETY: Memory is assumed to be valid, and it is commented where this assumption is made.
-        self.slice_mut(memory_offset + data_len, len - data_len)
-            .fill(0);
+        copy_padded(self.slice_mut(memory_offset, len), data, data_offset);
     }
 
     /// Copies elements from one part of the memory to another part of itself.
@@ -305,6 +351,210 @@ impl SharedMemory {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SharedMemory {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct SharedMemoryDe {
+            buffer: Vec<u8>,
+            checkpoints: Vec<usize>,
+            last_checkpoint: usize,
+            #[cfg(feature = "memory_limit")]
+            memory_limit: u64,
+            #[serde(default)]
+            interrupt_limit: Option<usize>,
+        }
+
+        let SharedMemoryDe {
+            buffer,
+            checkpoints,
+            last_checkpoint,
+            #[cfg(feature = "memory_limit")]
+            memory_limit,
+            interrupt_limit,
+        } = SharedMemoryDe::deserialize(deserializer)?;
+
+        // Memory is accessed unchecked, checkpoints must be ordered and in bounds of the buffer.
+        if checkpoints.windows(2).any(|w| w[0] > w[1])
+            || checkpoints.last().is_some_and(|last| *last > buffer.len())
+        {
+            return Err(serde::de::Error::custom("memory checkpoints out of bounds"));
+        }
+        if last_checkpoint != checkpoints.last().copied().unwrap_or_default() {
+            return Err(serde::de::Error::custom(
+                "last checkpoint does not match checkpoints",
+            ));
+        }
+
+        Ok(Self {
+            buffer,
+            checkpoints,
+            last_checkpoint,
+            #[cfg(feature = "memory_limit")]
+            memory_limit,
+            interrupt_limit,
+        })
+    }
+}
+
+/// Read-only view of the memory of the current context of a [SharedMemory].
+///
+/// Accessors are bounds checked and return `None` instead of panicking, so inspectors can
+/// read memory operands of the current instruction before the memory is expanded.
+///
+/// Memory of the EVM is zero-expanded: an instruction that accesses bytes past the end grows
+/// the memory to the next multiple of 32 bytes and the new bytes read as zero. Tracers that
+/// look at operands before the instruction is executed can use [`MemoryRef::word_padded`] and
+/// [`MemoryRef::effective_len`] to see what the instruction will see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MemoryRef<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MemoryRef<'a> {
+    /// Returns the length of the memory in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether the memory is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the bytes in the given range, or `None` if it is out of bounds.
+    #[inline]
+    pub fn slice(&self, range: Range<usize>) -> Option<&'a [u8]> {
+        self.data.get(range)
+    }
+
+    /// Returns the 32 bytes at the given offset, or `None` if they are out of bounds.
+    #[inline]
+    pub fn word(&self, offset: usize) -> Option<B256> {
+        let end = offset.checked_add(32)?;
+        self.slice(offset..end).map(B256::from_slice)
+    }
+
+    /// Returns the 32 bytes at the given offset, where bytes past the end read as zero.
+    ///
+    /// This is the value `MLOAD` returns after the memory is expanded.
+    #[inline]
+    pub fn word_padded(&self, offset: usize) -> B256 {
+        let mut word = B256::ZERO;
+        copy_padded(&mut word[..], self.data, offset);
+        word
+    }
+
+    /// Returns the length of the memory after `len` bytes at `offset` are accessed, or `None`
+    /// if the end overflows.
+    ///
+    /// Accessing zero bytes never expands the memory.
+    #[inline]
+    pub fn effective_len(&self, offset: usize, len: usize) -> Option<usize> {
+        if len == 0 {
+            return Some(self.len());
+        }
+        let end = offset.checked_add(len)?.checked_next_multiple_of(32)?;
+        Some(end.max(self.len()))
+    }
+
+    /// Returns the whole memory.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+impl<'a> From<&'a SharedMemory> for MemoryRef<'a> {
+    #[inline]
+    fn from(memory: &'a SharedMemory) -> Self {
+        Self {
+            data: memory.context_memory(),
+        }
+    }
+}
+
+/// Mutable view of the memory of the current context of a [SharedMemory].
+///
+/// Like [MemoryRef], accessors are bounds checked and return `None` instead of panicking. The
+/// view can not expand the memory, writes past the end fail and leave the memory unchanged.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct MemoryMut<'a> {
+    data: &'a mut [u8],
+}
+
+impl<'a> MemoryMut<'a> {
+    /// Returns the length of the memory in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether the memory is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a read-only view of the memory.
+    #[inline]
+    pub fn as_memory_ref(&self) -> MemoryRef<'_> {
+        MemoryRef { data: self.data }
+    }
+
+    /// Returns the 32 bytes at the given offset, or `None` if they are out of bounds.
+    #[inline]
+    pub fn word(&self, offset: usize) -> Option<B256> {
+        self.as_memory_ref().word(offset)
+    }
+
+    /// Returns the mutable bytes in the given range, or `None` if it is out of bounds.
+    #[inline]
+    pub fn slice_mut(&mut self, range: Range<usize>) -> Option<&mut [u8]> {
+        self.data.get_mut(range)
+    }
+
+    /// Writes the bytes at the given offset, or returns `None` if they are out of bounds.
+    #[inline]
+    pub fn set(&mut self, offset: usize, value: &[u8]) -> Option<()> {
+        let end = offset.checked_add(value.len())?;
+        self.slice_mut(offset..end)?.copy_from_slice(value);
+        Some(())
+    }
+
+    /// Writes the 32 bytes at the given offset, or returns `None` if they are out of bounds.
+    #[inline]
+    pub fn set_word(&mut self, offset: usize, value: &B256) -> Option<()> {
+        self.set(offset, &value[..])
+    }
+
+    /// Copies the bytes in the `src` range to `dst`, like `MCOPY`, or returns `None` if either
+    /// range is out of bounds.
+    #[inline]
+    pub fn copy_within(&mut self, src: Range<usize>, dst: usize) -> Option<()> {
+        let dst_end = dst.checked_add(src.len())?;
+        if src.start > src.end || src.end > self.len() || dst_end > self.len() {
+            return None;
+        }
+        self.data.copy_within(src, dst);
+        Some(())
+    }
+}
+
+impl<'a> From<&'a mut SharedMemory> for MemoryMut<'a> {
+    #[inline]
+    fn from(memory: &'a mut SharedMemory) -> Self {
+        Self {
+            data: memory.context_memory_mut(),
+        }
+    }
+}
+
 /// Returns number of words what would fit to provided number of bytes,
 /// i.e. it rounds up the number bytes to number of words.
 #[inline]
@@ -312,10 +562,122 @@ pub const fn num_words(len: u64) -> u64 {
     len.saturating_add(31) / 32
 }
 
+/// Fills `dst` with the bytes of `src` starting at `src_offset`, zero-filling the part of
+/// `dst` that is past the end of `src`.
+///
+/// This is the copy semantics of the `*COPY` and `*LOAD` opcodes: reading past the end of the
+/// source is not an error, any offset is valid and yields zeros.
+#[inline]
+pub fn copy_padded(dst: &mut [u8], src: &[u8], src_offset: usize) {
+    let src = src.get(src_offset..).unwrap_or_default();
+    let copy_len = min(src.len(), dst.len());
+    let (copied, padding) = dst.split_at_mut(copy_len);
+    copied.copy_from_slice(&src[..copy_len]);
+    padding.fill(0);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn memory_ref() {
+        let mut memory = SharedMemory::new();
+        memory.resize(32);
+        memory.set_u256(0, U256::from(0xabcd));
+        memory.new_context();
+        memory.resize(64);
+        memory.set_byte(63, 0xff);
+
+        // Only the memory of the current context is visible.
+        let view = MemoryRef::from(&memory);
+        assert_eq!(view.len(), 64);
+        assert_eq!(view.word(32).unwrap()[31], 0xff);
+        assert_eq!(view.word(33), None);
+        assert_eq!(view.word(usize::MAX), None);
+        assert_eq!(view.slice(60..64), Some(&[0, 0, 0, 0xff][..]));
+        assert_eq!(view.slice(60..65), None);
+
+        memory.free_context();
+        let view = MemoryRef::from(&memory);
+        assert_eq!(view.word(0), Some(U256::from(0xabcd).into()));
+    }
+
+    #[test]
+    fn memory_ref_expansion() {
+        let mut memory = SharedMemory::new();
+        memory.resize(32);
+        memory.set_byte(31, 0xff);
+        let view = MemoryRef::from(&memory);
+
+        assert_eq!(view.word_padded(0)[31], 0xff);
+        assert_eq!(view.word_padded(31)[0], 0xff);
+        assert_eq!(view.word_padded(31)[1..], [0; 31]);
+        assert_eq!(view.word_padded(usize::MAX), B256::ZERO);
+
+        assert_eq!(view.effective_len(0, 32), Some(32));
+        assert_eq!(view.effective_len(32, 1), Some(64));
+        assert_eq!(view.effective_len(1000, 0), Some(32));
+        assert_eq!(view.effective_len(usize::MAX, 1), None);
+    }
+
+    #[test]
+    fn memory_mut() {
+        let mut memory = SharedMemory::new();
+        memory.resize(64);
+        let mut view = MemoryMut::from(&mut memory);
+
+        let word = B256::with_last_byte(0xab);
+        assert_eq!(view.set_word(0, &word), Some(()));
+        assert_eq!(view.set_word(33, &word), None);
+        assert_eq!(view.set(usize::MAX, &[1]), None);
+        assert_eq!(view.copy_within(0..32, 32), Some(()));
+        assert_eq!(view.copy_within(0..33, 32), None);
+        assert_eq!(view.word(32), Some(word));
+
+        // Failed writes leave the memory unchanged.
+        assert_eq!(memory.len(), 64);
+        assert_eq!(memory.get_word(0), word);
+        assert_eq!(memory.get_word(32), word);
+    }
+
+    #[test]
+    fn global_slice_range() {
+        let mut memory = SharedMemory::new();
+        memory.resize(32);
+        memory.set(0, &[1, 2, 3]);
+        memory.new_context();
+
+        let range = memory.global_range(0..2);
+        assert_eq!(memory.global_slice_range(0..2), &[1, 2]);
+        assert_eq!(memory.global_slice_range(range.clone()), &[] as &[u8]);
+        memory.resize(32);
+        assert_eq!(memory.global_slice_range(range), &[0, 0]);
+        // Out of bounds ranges read as empty.
+        assert_eq!(memory.global_slice_range(60..70), &[] as &[u8]);
+        assert_eq!(
+            memory.global_slice_range(usize::MAX - 1..usize::MAX),
+            &[] as &[u8]
+        );
+    }
+
+    #[test]
+    fn test_copy_padded() {
+        let src = [1, 2, 3, 4];
+        let mut dst = [0xff; 6];
+        copy_padded(&mut dst, &src, 0);
+        assert_eq!(dst, [1, 2, 3, 4, 0, 0]);
+        copy_padded(&mut dst, &src, 3);
+        assert_eq!(dst, [4, 0, 0, 0, 0, 0]);
+        copy_padded(&mut dst, &src, 4);
+        assert_eq!(dst, [0; 6]);
+        dst = [0xff; 6];
+        copy_padded(&mut dst, &src, usize::MAX);
+        assert_eq!(dst, [0; 6]);
+        copy_padded(&mut dst[..2], &src, 1);
+        assert_eq!(dst, [2, 3, 0, 0, 0, 0]);
+    }
+
     #[test]
     fn test_num_words() {
         assert_eq!(num_words(0), 0);
@@ -401,4 +763,45 @@ mod tests {
         assert_eq!(shared_memory.len(), 64);
         assert_eq!(shared_memory.buffer.get(0..64), Some(&[0_u8; 64] as &[u8]));
     }
+
+    #[test]
+    fn clear() {
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(32);
+        shared_memory.set_u256(0, U256::MAX);
+        shared_memory.new_context();
+        shared_memory.resize(8 * 1024);
+        let capacity = shared_memory.buffer.capacity();
+
+        shared_memory.clear();
+        assert_eq!(shared_memory.buffer.len(), 0);
+        assert_eq!(shared_memory.buffer.capacity(), capacity);
+        assert_eq!(shared_memory.checkpoints.len(), 0);
+        assert_eq!(shared_memory.last_checkpoint, 0);
+
+        // Reused memory is zeroed.
+        shared_memory.new_context();
+        shared_memory.resize(32);
+        assert_eq!(shared_memory.get_u256(0), U256::ZERO);
+    }
+
+    #[test]
+    fn set_data() {
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(64);
+        shared_memory.slice_mut(0, 64).fill(0xff);
+
+        // Tail past the end of the data is zeroed.
+        shared_memory.set_data(0, 1, 8, &[1, 2, 3, 4]);
+        assert_eq!(shared_memory.slice(0, 9), &[2, 3, 4, 0, 0, 0, 0, 0, 0xff]);
+
+        // Offset past the end of the data zeroes everything.
+        shared_memory.set_data(16, 10, 8, &[1, 2, 3, 4]);
+        assert_eq!(shared_memory.slice(16, 9), &[0, 0, 0, 0, 0, 0, 0, 0, 0xff]);
+
+        shared_memory.set_data(32, 0, 4, &[1, 2, 3, 4, 5]);
+        assert_eq!(shared_memory.slice(32, 5), &[1, 2, 3, 4, 0xff]);
+    }
 }
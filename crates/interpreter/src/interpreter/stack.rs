@@ -374,6 +374,65 @@ impl Stack {
     }
 }
 
+/// Read-only view of a [Stack].
+///
+/// Lets inspectors read the operands of the current instruction without being able to
+/// modify the stack. Items are indexed from the top of the stack, the top is at index `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StackRef<'a> {
+    data: &'a [U256],
+}
+
+impl<'a> StackRef<'a> {
+    /// Returns the number of items on the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether the stack is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the `n`th item from the top of the stack, or `None` if the stack has fewer
+    /// items.
+    #[inline]
+    pub fn peek(&self, n: usize) -> Option<U256> {
+        self.data.iter().rev().nth(n).copied()
+    }
+
+    /// Returns the top `N` items, top of the stack first, or `None` if the stack has fewer
+    /// items.
+    ///
+    /// For example `peek_n::<2>()` at `SSTORE` returns the key and the value.
+    #[inline]
+    pub fn peek_n<const N: usize>(&self) -> Option<[U256; N]> {
+        let top = self.data.get(self.data.len().checked_sub(N)?..)?;
+        Some(core::array::from_fn(|i| top[N - 1 - i]))
+    }
+
+    /// Returns the items from the top to the bottom of the stack.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &'a U256> + ExactSizeIterator {
+        self.data.iter().rev()
+    }
+
+    /// Returns the items, the bottom of the stack first.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [U256] {
+        self.data
+    }
+}
+
+impl<'a> From<&'a Stack> for StackRef<'a> {
+    #[inline]
+    fn from(stack: &'a Stack) -> Self {
+        Self { data: &stack.data }
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for Stack {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -397,6 +456,23 @@ impl<'de> serde::Deserialize<'de> for Stack {
 mod tests {
     use super::*;
 
+    #[test]
+    fn stack_ref() {
+        let mut stack = Stack::new();
+        for i in 1..=3 {
+            stack.push(U256::from(i)).unwrap();
+        }
+        let view = StackRef::from(&stack);
+        assert_eq!(view.len(), 3);
+        assert_eq!(view.peek(0), Some(U256::from(3)));
+        assert_eq!(view.peek(2), Some(U256::from(1)));
+        assert_eq!(view.peek(3), None);
+        assert_eq!(view.peek_n::<2>(), Some([U256::from(3), U256::from(2)]));
+        assert_eq!(view.peek_n::<4>(), None);
+        assert_eq!(view.iter().next(), Some(&U256::from(3)));
+        assert_eq!(view.as_slice(), stack.data().as_slice());
+    }
+
     fn run(f: impl FnOnce(&mut Stack)) {
         let mut stack = Stack::new();
         // fill capacity with non-zero values
@@ -1,3 +1,4 @@
+use crate::SharedMemory;
 use core::ops::Range;
 use primitives::{Address, Bytes, U256};
 
@@ -6,12 +7,17 @@ use primitives::{Address, Bytes, U256};
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallInputs {
     /// The call data of the call.
-    pub input: Bytes,
+    ///
+    /// Calls made by the interpreter reference the memory of the caller, see [`CallInput`].
+    pub input: CallInput,
     /// The return memory offset where the output of the call is written.
     ///
     /// In EOF, this range is invalid as EOF calls do not write output to memory.
     pub return_memory_offset: Range<usize>,
     /// The gas limit of the call.
+    ///
+    /// This is the gas the new frame gets, after the 63/64 rule of
+    /// [EIP-150](https://eips.ethereum.org/EIPS/eip-150) and including the call stipend.
     pub gas_limit: u64,
     /// The account address of bytecode that is going to be executed.
     ///
@@ -37,6 +43,11 @@ pub struct CallInputs {
     pub scheme: CallScheme,
     /// Whether the call is a static call, or is initiated inside a static call.
     pub is_static: bool,
+    /// Call depth of the new frame, the first frame of the transaction has depth 1.
+    ///
+    /// Matches the depth of the journaled state inside of the frame. Instructions leave it at
+    /// zero, it is set by the EVM before the frame is created.
+    pub depth: u64,
     /// Whether the call is initiated from EOF bytecode.
     pub is_eof: bool,
 }
@@ -124,6 +135,77 @@ impl CallScheme {
     }
 }
 
+/// Call data of a call.
+///
+/// Call data of sub calls is kept in the memory of the caller until it is needed, so calls
+/// that end before execution (e.g. transfers to accounts without code or failed value
+/// transfers) don't copy it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CallInput {
+    /// Range of the [`SharedMemory`] buffer, see [`SharedMemory::global_slice_range`].
+    ///
+    /// The range is valid while the caller frame is alive.
+    SharedBuffer(Range<usize>),
+    /// Owned call data.
+    Bytes(Bytes),
+}
+
+impl Default for CallInput {
+    #[inline]
+    fn default() -> Self {
+        Self::Bytes(Bytes::new())
+    }
+}
+
+impl From<Bytes> for CallInput {
+    #[inline]
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl CallInput {
+    /// Returns the length of the call data.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::SharedBuffer(range) => range.len(),
+            Self::Bytes(bytes) => bytes.len(),
+        }
+    }
+
+    /// Returns `true` if the call data is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the call data as a slice, reading the shared buffer if needed.
+    ///
+    /// Shared buffer range that is out of bounds of the memory reads as empty.
+    #[inline]
+    pub fn as_slice<'a>(&'a self, shared_memory: &'a SharedMemory) -> &'a [u8] {
+        match self {
+            Self::SharedBuffer(range) => shared_memory.global_slice_range(range.clone()),
+            Self::Bytes(bytes) => bytes,
+        }
+    }
+
+    /// Returns the owned call data, copying it out of the shared buffer if needed.
+    ///
+    /// Shared buffer range that is out of bounds of the memory reads as empty.
+    #[inline]
+    pub fn bytes(&self, shared_memory: &SharedMemory) -> Bytes {
+        match self {
+            Self::SharedBuffer(range) => {
+                Bytes::copy_from_slice(shared_memory.global_slice_range(range.clone()))
+            }
+            Self::Bytes(bytes) => bytes.clone(),
+        }
+    }
+}
+
 /// Call value.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -14,7 +14,14 @@ pub struct CreateInputs {
     /// The init code of the contract.
     pub init_code: Bytes,
     /// The gas limit of the call.
+    ///
+    /// This is the gas the new frame gets, after the 63/64 rule of
+    /// [EIP-150](https://eips.ethereum.org/EIPS/eip-150).
     pub gas_limit: u64,
+    /// Call depth of the new frame, the first frame of the transaction has depth 1.
+    ///
+    /// Instructions leave it at zero, it is set by the EVM before the frame is created.
+    pub depth: u64,
 }
 
 impl CreateInputs {
@@ -30,16 +30,17 @@ pub mod table;
 pub use function_stack::{FunctionReturnFrame, FunctionStack};
 pub use gas::Gas;
 pub use host::{
-    AccountLoad, DummyHost, Eip7702CodeLoad, Host, SStoreResult, SelfDestructResult, StateLoad,
+    AccountLoad, BlockEnvOverride, DummyHost, Eip7702CodeLoad, Host, HostRead, HostWrite,
+    ReadOnlyHost, SStoreResult, SelfDestructResult, StateLoad,
 };
 pub use instruction_result::*;
 pub use interpreter::{
-    num_words, Contract, Interpreter, InterpreterResult, SharedMemory, Stack, EMPTY_SHARED_MEMORY,
-    STACK_LIMIT,
+    num_words, Contract, Interpreter, InterpreterResult, MemoryMut, MemoryRef, SharedMemory, Stack,
+    StackRef, StandaloneResult, EMPTY_SHARED_MEMORY, STACK_LIMIT,
 };
 pub use interpreter_action::{
-    CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome, EOFCreateInputs,
-    EOFCreateKind, InterpreterAction, NewFrameAction,
+    CallInput, CallInputs, CallOutcome, CallScheme, CallValue, CreateInputs, CreateOutcome,
+    EOFCreateInputs, EOFCreateKind, InterpreterAction, NewFrameAction,
 };
 pub use specification::constants::{MAX_CODE_SIZE, MAX_INITCODE_SIZE};
 pub use table::Instruction;
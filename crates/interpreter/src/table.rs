@@ -1,8 +1,9 @@
 #![allow(clippy::wrong_self_convention)]
 
 use crate::{instructions::control, instructions::instruction, Host, Interpreter};
+use bytecode::opcode::{JUMP, JUMPDEST, JUMPI, PUSH1, PUSH32};
 use specification::hardfork::Spec;
-use std::boxed::Box;
+use std::{boxed::Box, vec, vec::Vec};
 
 /// EVM opcode function signature.
 pub type Instruction<H> = fn(&mut Interpreter, &mut H);
@@ -130,6 +131,145 @@ impl<'a, H: Host + ?Sized + 'a> InstructionTables<'a, H> {
     }
 }
 
+/// Instruction table specialized for a single legacy bytecode.
+///
+/// The code is split into basic blocks that start at a `JUMPDEST` or after a `JUMP` or
+/// `JUMPI`, and the instructions of every block are stored in order, without the push data.
+/// The interpreter looks up the program counter only when it enters a block and runs the
+/// rest of the block without reading the opcodes. Build it once for a hot contract and reuse
+/// it with [`Interpreter::run_specialized`].
+pub struct SpecializedTable<H: ?Sized> {
+    /// Instructions of the code in order.
+    instructions: Box<[Instruction<H>]>,
+    /// For every byte offset, the range of `instructions` from the instruction at the offset
+    /// to the end of its basic block. Empty for push data.
+    blocks: Box<[(u32, u32)]>,
+}
+
+impl<H: ?Sized> core::fmt::Debug for SpecializedTable<H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SpecializedTable")
+            .field("len", &self.blocks.len())
+            .field("instructions", &self.instructions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: Host + ?Sized> SpecializedTable<H> {
+    /// Creates specialized table for the padded bytecode that the interpreter executes.
+    pub fn new(bytecode: &[u8], table: &InstructionTable<H>) -> Self {
+        Self::build(bytecode, table, |_, _| None)
+    }
+
+    /// Creates specialized table that executes common instruction sequences in a single
+    /// dispatch, see [`fused`](crate::instructions::fused).
+    ///
+    /// Fused sequences are `PUSH1 MSTORE`, `PUSH2 JUMP` and `PUSH2 JUMPI`. Super-instructions
+    /// call the mainnet instructions, so a sequence is only fused if `table` holds the mainnet
+    /// versions of all of its instructions. Custom versions are dispatched one by one.
+    #[cfg(feature = "superinstructions")]
+    pub fn with_superinstructions(bytecode: &[u8], table: &InstructionTable<H>) -> Self {
+        use crate::instructions::{fused, memory, stack};
+        use bytecode::opcode::{MSTORE, PUSH2};
+        use core::ptr::fn_addr_eq;
+
+        // Custom instructions have a different address. Identical code folding can only merge
+        // functions with the same behavior, so a match is always safe to fuse.
+        let is_mainnet =
+            |op: u8, instruction: Instruction<H>| fn_addr_eq(table[op as usize], instruction);
+        let push1 = is_mainnet(PUSH1, stack::push::<1, H>);
+        let push2 = is_mainnet(PUSH2, stack::push::<2, H>);
+        let mstore = push1 && is_mainnet(MSTORE, memory::mstore::<H>);
+        let jump = push2 && is_mainnet(JUMP, control::jump::<H>);
+        let jumpi = push2 && is_mainnet(JUMPI, control::jumpi::<H>);
+
+        Self::build(bytecode, table, |op, next| match (op, next) {
+            (PUSH1, MSTORE) if mstore => Some(fused::push1_mstore),
+            (PUSH2, JUMP) if jump => Some(fused::push2_jump),
+            (PUSH2, JUMPI) if jumpi => Some(fused::push2_jumpi),
+            _ => None,
+        })
+    }
+
+    /// Creates specialized table, `fuse` returns the instruction that executes an opcode and
+    /// the opcode after it.
+    fn build(
+        bytecode: &[u8],
+        table: &InstructionTable<H>,
+        fuse: impl Fn(u8, u8) -> Option<Instruction<H>>,
+    ) -> Self {
+        let next_pc = |pc: usize, op: u8| {
+            if (PUSH1..=PUSH32).contains(&op) {
+                pc + 1 + (op - PUSH1 + 1) as usize
+            } else {
+                pc + 1
+            }
+        };
+
+        // Offsets, last opcodes and instructions of the dispatches. The first opcode of a fused
+        // sequence is a push, so the last one decides where the basic block ends.
+        let mut ops = Vec::new();
+        let mut pc = 0;
+        while let Some(&op) = bytecode.get(pc) {
+            let next = next_pc(pc, op);
+            match bytecode
+                .get(next)
+                .and_then(|&next_op| Some((next_op, fuse(op, next_op)?)))
+            {
+                Some((next_op, fused)) => {
+                    ops.push((pc, next_op, fused));
+                    pc = next_pc(next, next_op);
+                }
+                None => {
+                    ops.push((pc, op, table[op as usize]));
+                    pc = next;
+                }
+            }
+        }
+
+        // Walk back to find the end of the basic block of every instruction.
+        let mut blocks = vec![(0, 0); bytecode.len()];
+        let mut end = ops.len() as u32;
+        for (i, &(pc, op, _)) in ops.iter().enumerate().rev() {
+            let i = i as u32;
+            if op == JUMP || op == JUMPI {
+                end = i + 1;
+            }
+            blocks[pc] = (i, end);
+            if op == JUMPDEST {
+                end = i;
+            }
+        }
+
+        Self {
+            instructions: ops.iter().map(|&(_, _, instruction)| instruction).collect(),
+            blocks: blocks.into(),
+        }
+    }
+
+    /// Returns the number of byte offsets covered by this table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns the instructions from the given program counter to the end of its basic block.
+    ///
+    /// Returns `None` if the program counter is not the start of an instruction.
+    #[inline]
+    pub fn block(&self, pc: usize) -> Option<&[Instruction<H>]> {
+        let &(start, end) = self.blocks.get(pc)?;
+        let block = &self.instructions[start as usize..end as usize];
+        (!block.is_empty()).then_some(block)
+    }
+}
+
 /// Make instruction table.
 #[inline]
 pub const fn make_instruction_table<H: Host + ?Sized, SPEC: Spec>() -> InstructionTable<H> {
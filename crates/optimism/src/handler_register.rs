@@ -1,6 +1,7 @@
 //! Handler related to Optimism chain
 
 use crate::{
+    l1block::L1BlockInfoCacheKey,
     optimism_spec_to_generic,
     transaction::{
         deposit::DepositTransaction, error::OpTransactionError, OpTransactionType, OpTxTrait,
@@ -9,7 +10,6 @@ use crate::{
     OptimismHaltReason, OptimismSpec, OptimismSpecId,
 };
 use crate::{BASE_FEE_RECIPIENT, L1_FEE_RECIPIENT};
-use core::ops::Mul;
 use revm::{
     database_interface::Database,
     handler::{
@@ -31,7 +31,7 @@ use revm::{
     },
     Context, ContextPrecompiles, FrameResult,
 };
-use std::sync::Arc;
+use std::{sync::Arc, vec::Vec};
 
 pub fn optimism_handle_register<EvmWiringT>(handler: &mut EvmHandler<'_, EvmWiringT>)
 where
@@ -51,7 +51,8 @@ where
         handler.pre_execution.deduct_caller = Arc::new(deduct_caller::<EvmWiringT, SPEC>);
         // Refund is calculated differently then mainnet.
         handler.execution.last_frame_return = Arc::new(last_frame_return::<EvmWiringT, SPEC>);
-        handler.post_execution.refund = Arc::new(refund::<EvmWiringT, SPEC>);
+        handler.post_execution.refund_cap = Arc::new(refund_cap::<EvmWiringT, SPEC>);
+        handler.post_execution.calldata_floor = Arc::new(calldata_floor::<EvmWiringT, SPEC>);
         handler.post_execution.reward_beneficiary =
             Arc::new(reward_beneficiary::<EvmWiringT, SPEC>);
         // In case of halt of deposit transaction return Error.
@@ -170,15 +171,12 @@ pub fn last_frame_return<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     Ok(())
 }
 
-/// Record Eip-7702 refund and calculate final refund.
+/// Calculate final refund.
 #[inline]
-pub fn refund<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
+pub fn refund_cap<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     context: &mut Context<EvmWiringT>,
     gas: &mut Gas,
-    eip7702_refund: i64,
 ) {
-    gas.record_refund(eip7702_refund);
-
     let env = context.evm.inner.env();
     let is_deposit = env.tx.tx_type() == OpTransactionType::Deposit;
     let is_regolith = SPEC::optimism_enabled(OptimismSpecId::REGOLITH);
@@ -190,6 +188,18 @@ pub fn refund<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     }
 }
 
+/// Apply the calldata floor, deposit transactions are exempt.
+#[inline]
+pub fn calldata_floor<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
+    context: &mut Context<EvmWiringT>,
+    gas: &mut Gas,
+) {
+    if context.evm.inner.env.tx.tx_type() == OpTransactionType::Deposit {
+        return;
+    }
+    mainnet::calldata_floor::<EvmWiringT, SPEC>(context, gas);
+}
+
 /// Load precompiles for Optimism chain.
 #[inline]
 pub fn load_precompiles<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
@@ -217,19 +227,46 @@ pub fn load_precompiles<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
 #[inline]
 pub fn load_accounts<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     context: &mut Context<EvmWiringT>,
+) -> EVMResultGeneric<(), EvmWiringT> {
+    load_l1_block_info::<EvmWiringT, SPEC>(context)?;
+    mainnet::load_accounts::<EvmWiringT, SPEC>(context)
+}
+
+/// Loads the L1 block info of the current L2 block, reusing the cached one if it is still valid.
+fn load_l1_block_info<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
+    context: &mut Context<EvmWiringT>,
 ) -> EVMResultGeneric<(), EvmWiringT> {
     // the L1-cost fee is only computed for Optimism non-deposit transactions.
 
     if context.evm.env.tx.tx_type() != OpTransactionType::Deposit {
-        let l1_block_info =
-            super::L1BlockInfo::try_fetch(&mut context.evm.inner.db, SPEC::OPTIMISM_SPEC_ID)
-                .map_err(EVMError::Database)?;
+        let block = &context.evm.env.block;
+        let key = L1BlockInfoCacheKey {
+            spec_id: SPEC::OPTIMISM_SPEC_ID,
+            number: *block.number(),
+            timestamp: *block.timestamp(),
+            prevrandao: block.prevrandao().copied(),
+        };
+        // L1 block info only changes once per L2 block, reuse it if it was already fetched.
+        let is_cached = context
+            .evm
+            .chain
+            .l1_block_info()
+            .is_some_and(|info| info.cached_for.as_ref() == Some(&key));
 
-        // storage l1 block info for later use.
-        *context.evm.chain.l1_block_info_mut() = Some(l1_block_info);
-    }
+        if !is_cached {
+            let mut l1_block_info =
+                super::L1BlockInfo::try_fetch(&mut context.evm.inner.db, SPEC::OPTIMISM_SPEC_ID)
+                    .map_err(EVMError::Database)?;
+            l1_block_info.cached_for = Some(key);
 
-    mainnet::load_accounts::<EvmWiringT, SPEC>(context)
+            // storage l1 block info for later use.
+            *context.evm.chain.l1_block_info_mut() = Some(l1_block_info);
+        }
+    } else if let Some(l1_block_info) = context.evm.chain.l1_block_info_mut() {
+        // Deposits, like the L1 attributes deposit, can update the L1 block contract.
+        l1_block_info.cached_for = None;
+    }
+    Ok(())
 }
 
 /// Deduct max balance from caller
@@ -254,7 +291,12 @@ pub fn deduct_caller<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
     if is_deposit {
         let tx = context.evm.inner.env.tx.deposit();
         if let Some(mint) = tx.mint() {
-            caller_account.info.balance += U256::from(mint);
+            let Some(balance) = caller_account.info.balance.checked_add(U256::from(mint)) else {
+                return Err(EVMError::Transaction(
+                    OpTransactionError::DepositMintOverflow,
+                ));
+            };
+            caller_account.info.balance = balance;
         }
     }
 
@@ -333,7 +375,12 @@ pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
             .load_account(L1_FEE_RECIPIENT, &mut context.evm.inner.db)
             .map_err(EVMError::Database)?;
         l1_fee_vault_account.mark_touch();
-        l1_fee_vault_account.info.balance += l1_cost;
+        let Some(balance) = l1_fee_vault_account.info.balance.checked_add(l1_cost) else {
+            return Err(EVMError::Custom(
+                "[OPTIMISM] L1 fee vault balance overflow.".into(),
+            ));
+        };
+        l1_fee_vault_account.info.balance = balance;
 
         // Send the base fee of the transaction to the Base Fee Vault.
         let mut base_fee_vault_account = context
@@ -343,13 +390,20 @@ pub fn reward_beneficiary<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
             .load_account(BASE_FEE_RECIPIENT, &mut context.evm.inner.db)
             .map_err(EVMError::Database)?;
         base_fee_vault_account.mark_touch();
-        base_fee_vault_account.info.balance += context
+        let Some(balance) = context
             .evm
             .inner
             .env
             .block
             .basefee()
-            .mul(U256::from(gas.spent() - gas.refunded() as u64));
+            .checked_mul(U256::from(gas.spent() - gas.refunded() as u64))
+            .and_then(|base_fee| base_fee_vault_account.info.balance.checked_add(base_fee))
+        else {
+            return Err(EVMError::Custom(
+                "[OPTIMISM] Base fee vault balance overflow.".into(),
+            ));
+        };
+        base_fee_vault_account.info.balance = balance;
     }
     Ok(())
 }
@@ -433,6 +487,7 @@ pub fn end<EvmWiringT: OptimismWiring, SPEC: OptimismSpec>(
                     gas_used,
                 },
                 state,
+                selfdestruct_legs: Vec::new(),
             })
         } else {
             Err(err)
@@ -480,7 +535,7 @@ mod tests {
             0..0,
         ));
         last_frame_return::<TestEmptyOpWiring, SPEC>(&mut ctx, &mut first_frame).unwrap();
-        refund::<TestEmptyOpWiring, SPEC>(&mut ctx, first_frame.gas_mut(), 0);
+        refund_cap::<TestEmptyOpWiring, SPEC>(&mut ctx, first_frame.gas_mut());
         *first_frame.gas()
     }
 
@@ -569,6 +624,83 @@ mod tests {
         assert_eq!(gas.refunded(), 0);
     }
 
+    #[test]
+    fn test_load_accounts_caches_l1_block_info_per_block() {
+        let mut db = InMemoryDB::default();
+        db.insert_account_storage(crate::L1_BLOCK_CONTRACT, U256::from(1), U256::from(10))
+            .unwrap();
+
+        let mut context = Context::<TestMemOpWiring>::new_with_db(db);
+        context.evm.inner.env.block.number = U256::from(1);
+        let cached = |context: &mut Context<TestMemOpWiring>, spec_id| {
+            let block = &context.evm.inner.env.block;
+            *context.evm.chain.l1_block_info_mut() = Some(L1BlockInfo {
+                l1_base_fee: U256::from(5),
+                cached_for: Some(L1BlockInfoCacheKey {
+                    spec_id,
+                    number: block.number,
+                    timestamp: block.timestamp,
+                    prevrandao: block.prevrandao,
+                }),
+                ..Default::default()
+            });
+        };
+        cached(&mut context, OptimismSpecId::BEDROCK);
+
+        // Same block, cached info is reused.
+        load_accounts::<TestMemOpWiring, BedrockSpec>(&mut context).unwrap();
+        let l1_block_info = context.evm.chain.l1_block_info().unwrap();
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(5));
+        // The cache key is not compared.
+        assert_eq!(
+            *l1_block_info,
+            L1BlockInfo {
+                l1_base_fee: U256::from(5),
+                ..Default::default()
+            }
+        );
+
+        // Other spec, info is fetched from state.
+        load_accounts::<TestMemOpWiring, RegolithSpec>(&mut context).unwrap();
+        let l1_block_info = context.evm.chain.l1_block_info().unwrap();
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(10));
+
+        // Other block of the same number, info is fetched from state.
+        cached(&mut context, OptimismSpecId::BEDROCK);
+        context.evm.inner.env.block.timestamp = U256::from(2);
+        load_accounts::<TestMemOpWiring, BedrockSpec>(&mut context).unwrap();
+        let l1_block_info = context.evm.chain.l1_block_info().unwrap();
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(10));
+
+        // New block, info is fetched from state.
+        cached(&mut context, OptimismSpecId::BEDROCK);
+        context.evm.inner.env.block.number = U256::from(2);
+        load_accounts::<TestMemOpWiring, BedrockSpec>(&mut context).unwrap();
+        let l1_block_info = context.evm.chain.l1_block_info().unwrap();
+        assert_eq!(
+            l1_block_info.cached_for.as_ref().map(|key| key.number),
+            Some(U256::from(2))
+        );
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(10));
+
+        // Deposit can update the L1 block contract, info is fetched again in the same block.
+        context
+            .evm
+            .inner
+            .db
+            .insert_account_storage(crate::L1_BLOCK_CONTRACT, U256::from(1), U256::from(20))
+            .unwrap();
+        let tx = core::mem::replace(
+            &mut context.evm.inner.env.tx,
+            OpTransaction::Deposit(TxDeposit::default()),
+        );
+        load_l1_block_info::<TestMemOpWiring, BedrockSpec>(&mut context).unwrap();
+        context.evm.inner.env.tx = tx;
+        load_accounts::<TestMemOpWiring, BedrockSpec>(&mut context).unwrap();
+        let l1_block_info = context.evm.chain.l1_block_info().unwrap();
+        assert_eq!(l1_block_info.l1_base_fee, U256::from(20));
+    }
+
     #[test]
     fn test_commit_mint_value() {
         let caller = Address::ZERO;
@@ -613,6 +745,33 @@ mod tests {
         assert_eq!(account.info.balance, U256::from(1010));
     }
 
+    #[test]
+    fn test_mint_value_overflow() {
+        let caller = Address::ZERO;
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            caller,
+            AccountInfo {
+                balance: U256::MAX,
+                ..Default::default()
+            },
+        );
+        let mut context = Context::<TestMemOpWiring>::new_with_db(db);
+        *context.evm.chain.l1_block_info_mut() = Some(L1BlockInfo::default());
+        context.evm.inner.env.tx = OpTransaction::Deposit(TxDeposit {
+            mint: Some(1),
+            source_hash: B256::ZERO,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            deduct_caller::<TestMemOpWiring, RegolithSpec>(&mut context),
+            Err(EVMError::Transaction(
+                OpTransactionError::DepositMintOverflow
+            ))
+        );
+    }
+
     #[test]
     fn test_remove_l1_cost_non_deposit() {
         let caller = Address::ZERO;
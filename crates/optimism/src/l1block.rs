@@ -2,7 +2,7 @@ use crate::fast_lz::flz_compress_len;
 use core::ops::Mul;
 use revm::{
     database_interface::Database,
-    primitives::{address, Address, U256},
+    primitives::{address, Address, B256, U256},
 };
 
 use super::OptimismSpecId;
@@ -51,7 +51,14 @@ pub const L1_BLOCK_CONTRACT: Address = address!("4200000000000000000000000000000
 /// uint64 _sequenceNumber, bytes32 _batcherHash, uint256 _l1FeeOverhead, uint256 _l1FeeScalar)
 ///
 /// For now, we only care about the fields necessary for L1 cost calculation.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+///
+/// The values only change with the L1 attributes deposit at the start of each L2 block, so the
+/// info can be fetched once per block and reused by sequencers and fee estimators to price any
+/// number of transactions with [`L1BlockInfo::calculate_tx_l1_cost`]. The optimism handler
+/// caches it until the next deposit transaction, block or spec. The cache can not see a change
+/// of the database, reset the info with `l1_block_info_mut` when the state of the same block
+/// changes.
+#[derive(Clone, Debug, Default)]
 pub struct L1BlockInfo {
     /// The base fee of the L1 origin block.
     pub l1_base_fee: U256,
@@ -65,6 +72,34 @@ pub struct L1BlockInfo {
     pub l1_blob_base_fee_scalar: Option<U256>,
     /// True if Ecotone is activated, but the L1 fee scalars have not yet been set.
     pub(crate) empty_scalars: bool,
+    /// L2 block and spec the handler cached this info for, `None` if it is not cached.
+    pub(crate) cached_for: Option<L1BlockInfoCacheKey>,
+}
+
+impl PartialEq for L1BlockInfo {
+    fn eq(&self, other: &Self) -> bool {
+        // The cache key is not part of the info.
+        self.l1_base_fee == other.l1_base_fee
+            && self.l1_fee_overhead == other.l1_fee_overhead
+            && self.l1_base_fee_scalar == other.l1_base_fee_scalar
+            && self.l1_blob_base_fee == other.l1_blob_base_fee
+            && self.l1_blob_base_fee_scalar == other.l1_blob_base_fee_scalar
+            && self.empty_scalars == other.empty_scalars
+    }
+}
+
+impl Eq for L1BlockInfo {}
+
+/// The L2 block and spec a cached [`L1BlockInfo`] was fetched for.
+///
+/// The block env does not contain the hash of the block, the timestamp and the prevrandao of the
+/// L1 origin tell apart blocks of the same number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct L1BlockInfoCacheKey {
+    pub(crate) spec_id: OptimismSpecId,
+    pub(crate) number: U256,
+    pub(crate) timestamp: U256,
+    pub(crate) prevrandao: Option<B256>,
 }
 
 impl L1BlockInfo {
@@ -121,6 +156,7 @@ impl L1BlockInfo {
                 l1_blob_base_fee_scalar: Some(l1_blob_base_fee_scalar),
                 empty_scalars,
                 l1_fee_overhead,
+                cached_for: None,
             })
         }
     }
@@ -15,8 +15,8 @@ pub mod transaction;
 pub mod wiring;
 
 pub use handler_register::{
-    deduct_caller, end, last_frame_return, load_accounts, load_precompiles,
-    optimism_handle_register, output, refund, reward_beneficiary, validate_env,
+    calldata_floor, deduct_caller, end, last_frame_return, load_accounts, load_precompiles,
+    optimism_handle_register, output, refund_cap, reward_beneficiary, validate_env,
     validate_tx_against_state,
 };
 pub use l1block::{L1BlockInfo, BASE_FEE_RECIPIENT, L1_BLOCK_CONTRACT, L1_FEE_RECIPIENT};
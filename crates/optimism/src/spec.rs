@@ -35,6 +35,7 @@ pub enum OptimismSpecId {
     GRANITE = 23,
     PRAGUE = 24,
     PRAGUE_EOF = 25,
+    OSAKA = 26,
     #[default]
     LATEST = u8::MAX,
 }
@@ -86,6 +87,7 @@ impl OptimismSpecId {
             | OptimismSpecId::GRANITE => SpecId::CANCUN,
             OptimismSpecId::PRAGUE => SpecId::PRAGUE,
             OptimismSpecId::PRAGUE_EOF => SpecId::PRAGUE_EOF,
+            OptimismSpecId::OSAKA => SpecId::OSAKA,
             OptimismSpecId::LATEST => SpecId::LATEST,
         }
     }
@@ -120,6 +122,7 @@ impl From<SpecId> for OptimismSpecId {
             SpecId::CANCUN => Self::CANCUN,
             SpecId::PRAGUE => Self::PRAGUE,
             SpecId::PRAGUE_EOF => Self::PRAGUE_EOF,
+            SpecId::OSAKA => Self::OSAKA,
             SpecId::LATEST => Self::LATEST,
         }
     }
@@ -167,6 +170,7 @@ impl From<&str> for OptimismSpecId {
             id::CANCUN => Self::CANCUN,
             id::PRAGUE => Self::PRAGUE,
             id::PRAGUE_EOF => Self::PRAGUE_EOF,
+            id::OSAKA => Self::OSAKA,
             id::BEDROCK => Self::BEDROCK,
             id::REGOLITH => Self::REGOLITH,
             id::CANYON => Self::CANYON,
@@ -200,7 +204,8 @@ impl From<OptimismSpecId> for &'static str {
             | OptimismSpecId::SHANGHAI
             | OptimismSpecId::CANCUN
             | OptimismSpecId::PRAGUE
-            | OptimismSpecId::PRAGUE_EOF => value.into_eth_spec_id().into(),
+            | OptimismSpecId::PRAGUE_EOF
+            | OptimismSpecId::OSAKA => value.into_eth_spec_id().into(),
             OptimismSpecId::BEDROCK => id::BEDROCK,
             OptimismSpecId::REGOLITH => id::REGOLITH,
             OptimismSpecId::CANYON => id::CANYON,
@@ -258,6 +263,7 @@ spec!(SHANGHAI, ShanghaiSpec);
 spec!(CANCUN, CancunSpec);
 spec!(PRAGUE, PragueSpec);
 spec!(PRAGUE_EOF, PragueEofSpec);
+spec!(OSAKA, OsakaSpec);
 
 spec!(LATEST, LatestSpec);
 
@@ -336,6 +342,10 @@ macro_rules! optimism_spec_to_generic {
                 use $crate::PragueEofSpec as SPEC;
                 $e
             }
+            $crate::OptimismSpecId::OSAKA => {
+                use $crate::OsakaSpec as SPEC;
+                $e
+            }
             $crate::OptimismSpecId::BEDROCK => {
                 use $crate::BedrockSpec as SPEC;
                 $e
@@ -574,6 +584,10 @@ mod tests {
             OptimismSpecId::PRAGUE_EOF,
             assert_eq!(SPEC::OPTIMISM_SPEC_ID, OptimismSpecId::PRAGUE_EOF)
         );
+        optimism_spec_to_generic!(
+            OptimismSpecId::OSAKA,
+            assert_eq!(SPEC::OPTIMISM_SPEC_ID, OptimismSpecId::OSAKA)
+        );
         optimism_spec_to_generic!(
             OptimismSpecId::LATEST,
             assert_eq!(SPEC::OPTIMISM_SPEC_ID, OptimismSpecId::LATEST)
@@ -72,6 +72,8 @@ impl<T: Transaction> Transaction for OpTransaction<T> {
 
     type Eip7702 = T::Eip7702;
 
+    type Custom = TxDeposit;
+
     fn tx_type(&self) -> Self::TransactionType {
         match self {
             Self::Base { tx, .. } => OpTransactionType::Base(tx.tx_type().into()),
@@ -141,6 +143,13 @@ impl<T: Transaction> Transaction for OpTransaction<T> {
         };
         tx.eip7702()
     }
+
+    fn custom(&self) -> &Self::Custom {
+        let Self::Deposit(deposit) = self else {
+            panic!("Not a deposit transaction")
+        };
+        deposit
+    }
 }
 
 impl<T: Transaction> OpTxTrait for OpTransaction<T> {
@@ -1,6 +1,6 @@
 use revm::{
     primitives::{Address, Bytes, TxKind, B256, U256},
-    transaction::CommonTxFields,
+    transaction::{CommonTxFields, CustomTx},
 };
 
 pub trait DepositTransaction: CommonTxFields {
@@ -58,6 +58,21 @@ impl CommonTxFields for TxDeposit {
     }
 }
 
+/// Deposits have no fee fields, their gas is bought on L1.
+impl CustomTx for TxDeposit {
+    fn kind(&self) -> TxKind {
+        self.to
+    }
+
+    fn max_fee(&self) -> u128 {
+        0
+    }
+
+    fn effective_gas_price(&self, base_fee: U256) -> U256 {
+        base_fee
+    }
+}
+
 impl DepositTransaction for TxDeposit {
     fn source_hash(&self) -> B256 {
         self.source_hash
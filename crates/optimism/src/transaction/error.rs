@@ -39,6 +39,11 @@ pub enum OpTransactionError {
     /// are cause for non-inclusion, so a special [crate::OptimismHaltReason] variant was introduced to handle this
     /// case for failed deposit transactions.
     HaltedDepositPostRegolith,
+    /// Adding the `mint` value of a deposit transaction overflows the balance of the caller.
+    ///
+    /// Like any other error of a deposit transaction, it bubbles up into a
+    /// [crate::OptimismHaltReason::FailedDeposit] error in the `revm` handler.
+    DepositMintOverflow,
 }
 
 impl TransactionError for OpTransactionError {}
@@ -59,6 +64,9 @@ impl Display for OpTransactionError {
                     "deposit transaction halted post-regolith; error will be bubbled up to main return handler"
                 )
             }
+            Self::DepositMintOverflow => {
+                write!(f, "deposit mint value overflows the caller balance")
+            }
         }
     }
 }
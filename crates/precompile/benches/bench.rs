@@ -6,6 +6,8 @@ use revm_precompile::{
         pair::{ISTANBUL_PAIR_BASE, ISTANBUL_PAIR_PER_POINT},
         run_add, run_pair,
     },
+    hash::{ripemd160_run, sha256_run},
+    identity::identity_run,
     kzg_point_evaluation::run,
     secp256k1::ec_recover_run,
     Bytes,
@@ -147,9 +149,29 @@ pub fn benchmark_crypto_precompiles(c: &mut Criterion) {
     });
 }
 
+/// Benchmarks the hash and identity precompiles with inputs of different sizes.
+///
+/// SHA-256 uses the SHA extensions of the CPU when they are available.
+pub fn benchmark_hash_precompiles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Hash Precompile benchmarks");
+
+    for size in [32, 1024, 32 * 1024] {
+        let input = Bytes::from(vec![0xab; size]);
+        group.bench_function(format!("sha256 precompile | {size} bytes"), |b| {
+            b.iter(|| black_box(sha256_run(&input, u64::MAX).unwrap()))
+        });
+        group.bench_function(format!("ripemd160 precompile | {size} bytes"), |b| {
+            b.iter(|| black_box(ripemd160_run(&input, u64::MAX).unwrap()))
+        });
+        group.bench_function(format!("identity precompile | {size} bytes"), |b| {
+            b.iter(|| black_box(identity_run(&input, u64::MAX).unwrap()))
+        });
+    }
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default();
-    targets = benchmark_crypto_precompiles
+    targets = benchmark_crypto_precompiles, benchmark_hash_precompiles
 }
 criterion_main!(benches);
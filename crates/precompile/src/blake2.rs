@@ -134,3 +134,48 @@ pub mod algo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_blake2(
+            rounds in 0u32..=64,
+            data in proptest::collection::vec(proptest::num::u8::ANY, INPUT_LENGTH - 5),
+            f in proptest::num::u8::ANY,
+            gas_limit in 0u64..=64,
+        ) {
+            let mut input = Vec::with_capacity(INPUT_LENGTH);
+            input.extend_from_slice(&rounds.to_be_bytes());
+            input.extend_from_slice(&data);
+            input.push(f);
+            let result = crate::run_raw(&FUN.0, &input, gas_limit).unwrap();
+            if rounds as u64 > gas_limit {
+                proptest::prop_assert_eq!(result, Err(PrecompileError::OutOfGas.into()));
+            } else if f > 1 {
+                proptest::prop_assert_eq!(
+                    result,
+                    Err(PrecompileError::Blake2WrongFinalIndicatorFlag.into())
+                );
+            } else {
+                let output = result.unwrap();
+                proptest::prop_assert_eq!(output.gas_used, rounds as u64);
+                proptest::prop_assert_eq!(output.bytes.len(), 64);
+            }
+        }
+
+        #[test]
+        fn fuzz_blake2_length(
+            input in proptest::collection::vec(proptest::num::u8::ANY, 0..=2 * INPUT_LENGTH),
+        ) {
+            proptest::prop_assume!(input.len() != INPUT_LENGTH);
+            proptest::prop_assert_eq!(
+                crate::run_raw(&FUN.0, &input, u64::MAX).unwrap(),
+                Err(PrecompileError::Blake2WrongLength.into())
+            );
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use crate::{
     utilities::{bool_to_bytes32, right_pad},
-    Address, Precompile, PrecompileError, PrecompileOutput, PrecompileResult,
-    PrecompileWithAddress,
+    Address, CryptoProvider, DefaultCrypto, Precompile, PrecompileError, PrecompileOutput,
+    PrecompileResult, PrecompileWithAddress,
 };
+#[cfg(feature = "bn")]
 use bn::{AffineG1, AffineG2, Fq, Fq2, Group, Gt, G1, G2};
+#[cfg(feature = "bn")]
 use std::vec::Vec;
 
 pub mod add {
@@ -94,6 +96,7 @@ pub const PAIR_ELEMENT_LEN: usize = 64 + 128;
 /// # Panics
 ///
 /// Panics if the input is not at least 32 bytes long.
+#[cfg(feature = "bn")]
 #[inline]
 pub fn read_fq(input: &[u8]) -> Result<Fq, PrecompileError> {
     Fq::from_slice(&input[..32]).map_err(|_| PrecompileError::Bn128FieldPointNotAMember)
@@ -104,6 +107,7 @@ pub fn read_fq(input: &[u8]) -> Result<Fq, PrecompileError> {
 /// # Panics
 ///
 /// Panics if the input is not at least 64 bytes long.
+#[cfg(feature = "bn")]
 #[inline]
 pub fn read_point(input: &[u8]) -> Result<G1, PrecompileError> {
     let px = read_fq(&input[0..32])?;
@@ -112,6 +116,7 @@ pub fn read_point(input: &[u8]) -> Result<G1, PrecompileError> {
 }
 
 /// Creates a new `G1` point from the given `x` and `y` coordinates.
+#[cfg(feature = "bn")]
 pub fn new_g1_point(px: Fq, py: Fq) -> Result<G1, PrecompileError> {
     if px == Fq::zero() && py == Fq::zero() {
         Ok(G1::zero())
@@ -123,40 +128,46 @@ pub fn new_g1_point(px: Fq, py: Fq) -> Result<G1, PrecompileError> {
 }
 
 pub fn run_add(input: &[u8], gas_cost: u64, gas_limit: u64) -> PrecompileResult {
+    run_add_with(&DefaultCrypto, input, gas_cost, gas_limit)
+}
+
+/// Runs `ADD` with the given crypto provider.
+pub fn run_add_with(
+    crypto: &dyn CryptoProvider,
+    input: &[u8],
+    gas_cost: u64,
+    gas_limit: u64,
+) -> PrecompileResult {
     if gas_cost > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
     }
 
     let input = right_pad::<ADD_INPUT_LEN>(input);
+    let (p1, p2) = input.split_at(64);
 
-    let p1 = read_point(&input[..64])?;
-    let p2 = read_point(&input[64..])?;
-
-    let mut output = [0u8; 64];
-    if let Some(sum) = AffineG1::from_jacobian(p1 + p2) {
-        sum.x().to_big_endian(&mut output[..32]).unwrap();
-        sum.y().to_big_endian(&mut output[32..]).unwrap();
-    }
+    let output = crypto.bn128_add(p1.try_into().unwrap(), p2.try_into().unwrap())?;
     Ok(PrecompileOutput::new(gas_cost, output.into()))
 }
 
 pub fn run_mul(input: &[u8], gas_cost: u64, gas_limit: u64) -> PrecompileResult {
+    run_mul_with(&DefaultCrypto, input, gas_cost, gas_limit)
+}
+
+/// Runs `MUL` with the given crypto provider.
+pub fn run_mul_with(
+    crypto: &dyn CryptoProvider,
+    input: &[u8],
+    gas_cost: u64,
+    gas_limit: u64,
+) -> PrecompileResult {
     if gas_cost > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
     }
 
     let input = right_pad::<MUL_INPUT_LEN>(input);
+    let (p, scalar) = input.split_at(64);
 
-    let p = read_point(&input[..64])?;
-
-    // `Fr::from_slice` can only fail when the length is not 32.
-    let fr = bn::Fr::from_slice(&input[64..96]).unwrap();
-
-    let mut output = [0u8; 64];
-    if let Some(mul) = AffineG1::from_jacobian(p * fr) {
-        mul.x().to_big_endian(&mut output[..32]).unwrap();
-        mul.y().to_big_endian(&mut output[32..]).unwrap();
-    }
+    let output = crypto.bn128_mul(p.try_into().unwrap(), scalar.try_into().unwrap())?;
     Ok(PrecompileOutput::new(gas_cost, output.into()))
 }
 
@@ -166,7 +177,26 @@ pub fn run_pair(
     pair_base_cost: u64,
     gas_limit: u64,
 ) -> PrecompileResult {
-    let gas_used = (input.len() / PAIR_ELEMENT_LEN) as u64 * pair_per_point_cost + pair_base_cost;
+    run_pair_with(
+        &DefaultCrypto,
+        input,
+        pair_per_point_cost,
+        pair_base_cost,
+        gas_limit,
+    )
+}
+
+/// Runs `PAIR` with the given crypto provider.
+pub fn run_pair_with(
+    crypto: &dyn CryptoProvider,
+    input: &[u8],
+    pair_per_point_cost: u64,
+    pair_base_cost: u64,
+    gas_limit: u64,
+) -> PrecompileResult {
+    let gas_used = ((input.len() / PAIR_ELEMENT_LEN) as u64)
+        .saturating_mul(pair_per_point_cost)
+        .saturating_add(pair_base_cost);
     if gas_used > gas_limit {
         return Err(PrecompileError::OutOfGas.into());
     }
@@ -175,53 +205,88 @@ pub fn run_pair(
         return Err(PrecompileError::Bn128PairLength.into());
     }
 
-    let success = if input.is_empty() {
-        true
-    } else {
-        let elements = input.len() / PAIR_ELEMENT_LEN;
-
-        let mut points = Vec::with_capacity(elements);
-
-        // read points
-        for idx in 0..elements {
-            let read_fq_at = |n: usize| {
-                debug_assert!(n < PAIR_ELEMENT_LEN / 32);
-                let start = idx * PAIR_ELEMENT_LEN + n * 32;
-                // SAFETY: We're reading `6 * 32 == PAIR_ELEMENT_LEN` bytes from `input[idx..]`
-                // per iteration. This is guaranteed to be in-bounds.
-                let slice = unsafe { input.get_unchecked(start..start + 32) };
-                Fq::from_slice(slice).map_err(|_| PrecompileError::Bn128FieldPointNotAMember)
-            };
-            let ax = read_fq_at(0)?;
-            let ay = read_fq_at(1)?;
-            let bay = read_fq_at(2)?;
-            let bax = read_fq_at(3)?;
-            let bby = read_fq_at(4)?;
-            let bbx = read_fq_at(5)?;
-
-            let a = new_g1_point(ax, ay)?;
-            let b = {
-                let ba = Fq2::new(bax, bay);
-                let bb = Fq2::new(bbx, bby);
-                // TODO: check whether or not we need these zero checks
-                if ba.is_zero() && bb.is_zero() {
-                    G2::zero()
-                } else {
-                    G2::from(
-                        AffineG2::new(ba, bb)
-                            .map_err(|_| PrecompileError::Bn128AffineGFailedToCreate)?,
-                    )
-                }
-            };
+    let success = input.is_empty() || crypto.bn128_pairing_check(input)?;
+    Ok(PrecompileOutput::new(gas_used, bool_to_bytes32(success)))
+}
 
-            points.push((a, b));
-        }
+/// Adds two uncompressed G1 points, used by [`DefaultCrypto`](crate::DefaultCrypto).
+#[cfg(feature = "bn")]
+pub fn g1_point_add(p1: &[u8; 64], p2: &[u8; 64]) -> Result<[u8; 64], PrecompileError> {
+    let p1 = read_point(p1)?;
+    let p2 = read_point(p2)?;
+
+    let mut output = [0u8; 64];
+    if let Some(sum) = AffineG1::from_jacobian(p1 + p2) {
+        sum.x().to_big_endian(&mut output[..32]).unwrap();
+        sum.y().to_big_endian(&mut output[32..]).unwrap();
+    }
+    Ok(output)
+}
 
-        let mul = bn::pairing_batch(&points);
+/// Multiplies an uncompressed G1 point by a scalar, used by
+/// [`DefaultCrypto`](crate::DefaultCrypto).
+#[cfg(feature = "bn")]
+pub fn g1_point_mul(p: &[u8; 64], scalar: &[u8; 32]) -> Result<[u8; 64], PrecompileError> {
+    let p = read_point(p)?;
 
-        mul == Gt::one()
-    };
-    Ok(PrecompileOutput::new(gas_used, bool_to_bytes32(success)))
+    // `Fr::from_slice` can only fail when the length is not 32.
+    let fr = bn::Fr::from_slice(scalar).unwrap();
+
+    let mut output = [0u8; 64];
+    if let Some(mul) = AffineG1::from_jacobian(p * fr) {
+        mul.x().to_big_endian(&mut output[..32]).unwrap();
+        mul.y().to_big_endian(&mut output[32..]).unwrap();
+    }
+    Ok(output)
+}
+
+/// Checks whether the product of the pairings of the `(G1, G2)` elements is one, used by
+/// [`DefaultCrypto`](crate::DefaultCrypto).
+///
+/// The input length must be a multiple of [`PAIR_ELEMENT_LEN`].
+#[cfg(feature = "bn")]
+pub fn pairing_check(input: &[u8]) -> Result<bool, PrecompileError> {
+    debug_assert_eq!(input.len() % PAIR_ELEMENT_LEN, 0);
+    let elements = input.len() / PAIR_ELEMENT_LEN;
+
+    let mut points = Vec::with_capacity(elements);
+
+    // read points
+    for idx in 0..elements {
+        let read_fq_at = |n: usize| {
+            debug_assert!(n < PAIR_ELEMENT_LEN / 32);
+            let start = idx * PAIR_ELEMENT_LEN + n * 32;
+            // SAFETY: We're reading `6 * 32 == PAIR_ELEMENT_LEN` bytes from `input[idx..]`
+            // per iteration. This is guaranteed to be in-bounds.
+            let slice = unsafe { input.get_unchecked(start..start + 32) };
+            Fq::from_slice(slice).map_err(|_| PrecompileError::Bn128FieldPointNotAMember)
+        };
+        let ax = read_fq_at(0)?;
+        let ay = read_fq_at(1)?;
+        let bay = read_fq_at(2)?;
+        let bax = read_fq_at(3)?;
+        let bby = read_fq_at(4)?;
+        let bbx = read_fq_at(5)?;
+
+        let a = new_g1_point(ax, ay)?;
+        let b = {
+            let ba = Fq2::new(bax, bay);
+            let bb = Fq2::new(bbx, bby);
+            // TODO: check whether or not we need these zero checks
+            if ba.is_zero() && bb.is_zero() {
+                G2::zero()
+            } else {
+                G2::from(
+                    AffineG2::new(ba, bb)
+                        .map_err(|_| PrecompileError::Bn128AffineGFailedToCreate)?,
+                )
+            }
+        };
+
+        points.push((a, b));
+    }
+
+    Ok(bn::pairing_batch(&points) == Gt::one())
 }
 
 #[cfg(test)]
@@ -235,6 +300,7 @@ mod tests {
         PrecompileError, PrecompileErrors,
     };
     use primitives::hex;
+    use std::vec;
 
     use super::*;
 
@@ -528,4 +594,35 @@ mod tests {
             Err(PrecompileErrors::Error(PrecompileError::Bn128PairLength))
         ));
     }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_bn128(
+            address in 6u64..=8,
+            input in proptest::collection::vec(proptest::num::u8::ANY, 0..=2 * PAIR_ELEMENT_LEN),
+            gas_limit in 0u64..200_000,
+        ) {
+            match crate::run_raw(&crate::u64_to_address(address), &input, gas_limit).unwrap() {
+                Ok(output) => {
+                    proptest::prop_assert!(output.gas_used <= gas_limit);
+                    proptest::prop_assert_eq!(output.bytes.len(), if address == 8 { 32 } else { 64 });
+                }
+                Err(err) => proptest::prop_assert!(matches!(err, PrecompileErrors::Error(_))),
+            }
+        }
+
+        #[test]
+        fn fuzz_bn128_pair_length(
+            elements in 0usize..=2,
+            byte in proptest::num::u8::ANY,
+        ) {
+            // Input of whole elements never fails on the length.
+            let input = vec![byte; elements * PAIR_ELEMENT_LEN];
+            let result = run_pair(&input, BYZANTIUM_PAIR_PER_POINT, BYZANTIUM_PAIR_BASE, u64::MAX);
+            proptest::prop_assert!(!matches!(
+                result,
+                Err(PrecompileErrors::Error(PrecompileError::Bn128PairLength))
+            ));
+        }
+    }
 }
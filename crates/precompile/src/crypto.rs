@@ -0,0 +1,310 @@
+//! Pluggable cryptography used by the precompiles.
+//!
+//! Precompiles run on a [`CryptoProvider`] instead of a crypto library directly, so zkVM and
+//! embedded users can replace the implementations with accelerated or provable ones without
+//! forking the crate. The static precompile sets use [`DefaultCrypto`]; [`precompiles`] returns
+//! the same precompiles running on another provider, to be added to the precompiles of an EVM.
+//!
+//! The default implementations are behind the `sha2`, `ripemd`, `k256` (or `secp256k1`) and
+//! `bn` features, all enabled by `default-crypto`. With a feature disabled, the matching default
+//! method returns a fatal error, so a provider must implement it.
+use crate::{
+    bn128, hash, secp256k1, u64_to_address, Precompile, PrecompileErrors, PrecompileResult,
+    PrecompileSpecId, PrecompileWithAddress, StatefulPrecompile,
+};
+use core::fmt::Debug;
+use primitives::{alloy_primitives::B512, Bytes, B256};
+use std::{sync::Arc, vec::Vec};
+use wiring::default::CfgEnv;
+
+/// Cryptographic primitives used by the precompiles.
+///
+/// Every method has a default implementation backed by the crate dependencies, see
+/// [`DefaultCrypto`], so a provider only needs to override the primitives it accelerates.
+///
+/// Errors are returned as they are by the precompile, a fatal error aborts the transaction.
+pub trait CryptoProvider: Debug + Send + Sync {
+    /// Computes the SHA-256 hash of the input.
+    fn sha256(&self, input: &[u8]) -> Result<[u8; 32], PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "sha2")] {
+                use sha2::Digest;
+                Ok(sha2::Sha256::digest(input).into())
+            } else {
+                let _ = input;
+                Err(disabled("sha2"))
+            }
+        }
+    }
+
+    /// Computes the RIPEMD-160 hash of the input.
+    fn ripemd160(&self, input: &[u8]) -> Result<[u8; 20], PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "ripemd")] {
+                use ripemd::Digest;
+                Ok(ripemd::Ripemd160::digest(input).into())
+            } else {
+                let _ = input;
+                Err(disabled("ripemd"))
+            }
+        }
+    }
+
+    /// Recovers the signer of the message hash and returns the keccak hash of its public key
+    /// with the first 12 bytes zeroed.
+    ///
+    /// Returns `None` if the signature is invalid.
+    fn secp256k1_ecrecover(
+        &self,
+        sig: &B512,
+        recid: u8,
+        msg: &B256,
+    ) -> Result<Option<B256>, PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "secp256k1", feature = "k256"))] {
+                Ok(secp256k1::ecrecover(sig, recid, msg).ok())
+            } else {
+                let _ = (sig, recid, msg);
+                Err(disabled("k256"))
+            }
+        }
+    }
+
+    /// Adds two uncompressed alt_bn128 G1 points.
+    fn bn128_add(&self, p1: &[u8; 64], p2: &[u8; 64]) -> Result<[u8; 64], PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "bn")] {
+                Ok(bn128::g1_point_add(p1, p2)?)
+            } else {
+                let _ = (p1, p2);
+                Err(disabled("bn"))
+            }
+        }
+    }
+
+    /// Multiplies an uncompressed alt_bn128 G1 point by a scalar.
+    fn bn128_mul(&self, p: &[u8; 64], scalar: &[u8; 32]) -> Result<[u8; 64], PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "bn")] {
+                Ok(bn128::g1_point_mul(p, scalar)?)
+            } else {
+                let _ = (p, scalar);
+                Err(disabled("bn"))
+            }
+        }
+    }
+
+    /// Checks whether the product of the pairings of the `(G1, G2)` elements is one.
+    ///
+    /// The input is not empty and its length is a multiple of [`bn128::PAIR_ELEMENT_LEN`].
+    fn bn128_pairing_check(&self, input: &[u8]) -> Result<bool, PrecompileErrors> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "bn")] {
+                Ok(bn128::pairing_check(input)?)
+            } else {
+                let _ = input;
+                Err(disabled("bn"))
+            }
+        }
+    }
+}
+
+/// Error returned by a default method whose backend feature is disabled.
+#[cfg(not(all(
+    feature = "sha2",
+    feature = "ripemd",
+    any(feature = "secp256k1", feature = "k256"),
+    feature = "bn"
+)))]
+fn disabled(feature: &str) -> PrecompileErrors {
+    PrecompileErrors::Fatal {
+        msg: std::format!(
+            "`{feature}` feature is not enabled and the crypto provider does not implement it"
+        ),
+    }
+}
+
+/// Provider that uses the crate dependencies.
+///
+/// Signature recovery uses `secp256k1` if the feature of the same name is enabled and `k256`
+/// otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DefaultCrypto;
+
+impl CryptoProvider for DefaultCrypto {}
+
+/// Precompile function that runs on a [`CryptoProvider`].
+pub type CryptoPrecompileFn = fn(
+    crypto: &dyn CryptoProvider,
+    input: &Bytes,
+    gas_limit: u64,
+    cfg: &CfgEnv,
+) -> PrecompileResult;
+
+/// Precompile that runs a [`CryptoPrecompileFn`] on a shared [`CryptoProvider`].
+#[derive(Clone, Debug)]
+pub struct CryptoPrecompile {
+    crypto: Arc<dyn CryptoProvider>,
+    run: CryptoPrecompileFn,
+}
+
+impl CryptoPrecompile {
+    /// Creates a new precompile running `run` on `crypto`.
+    pub fn new(crypto: Arc<dyn CryptoProvider>, run: CryptoPrecompileFn) -> Self {
+        Self { crypto, run }
+    }
+}
+
+impl StatefulPrecompile for CryptoPrecompile {
+    fn call(&self, bytes: &Bytes, gas_limit: u64, env: &CfgEnv) -> PrecompileResult {
+        (self.run)(&*self.crypto, bytes, gas_limit, env)
+    }
+}
+
+/// Returns the precompiles of the spec that use cryptography, running on `crypto`.
+///
+/// Extending a precompile set with them replaces the versions that use [`DefaultCrypto`].
+pub fn precompiles(
+    spec: PrecompileSpecId,
+    crypto: Arc<dyn CryptoProvider>,
+) -> Vec<PrecompileWithAddress> {
+    use bn128::{add, mul, pair};
+
+    let mut runs: Vec<(u64, CryptoPrecompileFn)> = Vec::new();
+    runs.extend([
+        (
+            1,
+            (|c, i, g, _| secp256k1::ec_recover_run_with(c, i, g)) as CryptoPrecompileFn,
+        ),
+        (2, |c, i, g, _| hash::sha256_run_with(c, i, g)),
+        (3, |c, i, g, _| hash::ripemd160_run_with(c, i, g)),
+    ]);
+    if spec >= PrecompileSpecId::ISTANBUL {
+        runs.extend([
+            (
+                6,
+                (|c, i, g, _| bn128::run_add_with(c, i, add::ISTANBUL_ADD_GAS_COST, g))
+                    as CryptoPrecompileFn,
+            ),
+            (7, |c, i, g, _| {
+                bn128::run_mul_with(c, i, mul::ISTANBUL_MUL_GAS_COST, g)
+            }),
+            (8, |c, i, g, _| {
+                bn128::run_pair_with(
+                    c,
+                    i,
+                    pair::ISTANBUL_PAIR_PER_POINT,
+                    pair::ISTANBUL_PAIR_BASE,
+                    g,
+                )
+            }),
+        ]);
+    } else if spec >= PrecompileSpecId::BYZANTIUM {
+        runs.extend([
+            (
+                6,
+                (|c, i, g, _| bn128::run_add_with(c, i, add::BYZANTIUM_ADD_GAS_COST, g))
+                    as CryptoPrecompileFn,
+            ),
+            (7, |c, i, g, _| {
+                bn128::run_mul_with(c, i, mul::BYZANTIUM_MUL_GAS_COST, g)
+            }),
+            (8, |c, i, g, _| {
+                bn128::run_pair_with(
+                    c,
+                    i,
+                    pair::BYZANTIUM_PAIR_PER_POINT,
+                    pair::BYZANTIUM_PAIR_BASE,
+                    g,
+                )
+            }),
+        ]);
+    }
+    #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+    if spec >= PrecompileSpecId::CANCUN {
+        runs.push((0x0A, crate::kzg_point_evaluation::run_with));
+    }
+
+    runs.into_iter()
+        .map(|(address, run)| {
+            PrecompileWithAddress(
+                u64_to_address(address),
+                Precompile::Stateful(Arc::new(CryptoPrecompile::new(crypto.clone(), run))),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Precompiles;
+    use primitives::hex;
+
+    #[derive(Debug)]
+    struct ZeroHash;
+
+    impl CryptoProvider for ZeroHash {
+        fn sha256(&self, _input: &[u8]) -> Result<[u8; 32], PrecompileErrors> {
+            Ok([0; 32])
+        }
+    }
+
+    #[test]
+    fn default_hashes() {
+        assert_eq!(
+            DefaultCrypto.sha256(b"abc"),
+            Ok(hex!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+            ))
+        );
+        assert_eq!(
+            DefaultCrypto.ripemd160(b"abc"),
+            Ok(hex!("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"))
+        );
+    }
+
+    #[test]
+    fn default_methods() {
+        // Only the overridden method changes.
+        assert_eq!(ZeroHash.sha256(b"abc"), Ok([0; 32]));
+        assert_eq!(ZeroHash.ripemd160(b"abc"), DefaultCrypto.ripemd160(b"abc"));
+        assert_eq!(ZeroHash.bn128_add(&[0; 64], &[0; 64]), Ok([0; 64]));
+    }
+
+    #[test]
+    fn provider_precompiles() {
+        let crypto: Arc<dyn CryptoProvider> = Arc::new(ZeroHash);
+        let mut berlin = Precompiles::berlin().clone();
+        berlin.extend(precompiles(PrecompileSpecId::BERLIN, crypto));
+        assert_eq!(berlin.len(), Precompiles::berlin().len());
+
+        let env = CfgEnv::default();
+        let input = Bytes::from_static(b"abc");
+        let sha256 = berlin.get(&u64_to_address(2)).unwrap();
+        assert_eq!(
+            sha256.call_ref(&input, 100, &env).unwrap().bytes,
+            Bytes::from([0; 32])
+        );
+        // The static set is unchanged.
+        let sha256 = Precompiles::berlin().get(&u64_to_address(2)).unwrap();
+        assert_ne!(
+            sha256.call_ref(&input, 100, &env).unwrap().bytes,
+            Bytes::from([0; 32])
+        );
+    }
+
+    #[test]
+    fn precompiles_by_spec() {
+        let crypto: Arc<dyn CryptoProvider> = Arc::new(DefaultCrypto);
+        assert_eq!(
+            precompiles(PrecompileSpecId::HOMESTEAD, crypto.clone()).len(),
+            3
+        );
+        assert_eq!(
+            precompiles(PrecompileSpecId::BYZANTIUM, crypto.clone()).len(),
+            6
+        );
+        assert_eq!(precompiles(PrecompileSpecId::BERLIN, crypto).len(), 6);
+    }
+}
@@ -1,9 +1,9 @@
 use super::calc_linear_cost_u32;
 use crate::{
-    Precompile, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
+    CryptoProvider, DefaultCrypto, Precompile, PrecompileError, PrecompileOutput, PrecompileResult,
+    PrecompileWithAddress,
 };
 use primitives::Bytes;
-use sha2::Digest;
 
 pub const SHA256: PrecompileWithAddress =
     PrecompileWithAddress(crate::u64_to_address(2), Precompile::Standard(sha256_run));
@@ -20,11 +20,20 @@ pub const RIPEMD160: PrecompileWithAddress = PrecompileWithAddress(
 /// - [Solidity Documentation on Mathematical and Cryptographic Functions](https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions)
 /// - [Address 0x02](https://etherscan.io/address/0000000000000000000000000000000000000002)
 pub fn sha256_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    sha256_run_with(&DefaultCrypto, input, gas_limit)
+}
+
+/// Computes the SHA-256 hash of the input data with the given crypto provider.
+pub fn sha256_run_with(
+    crypto: &dyn CryptoProvider,
+    input: &Bytes,
+    gas_limit: u64,
+) -> PrecompileResult {
     let cost = calc_linear_cost_u32(input.len(), 60, 12);
     if cost > gas_limit {
         Err(PrecompileError::OutOfGas.into())
     } else {
-        let output = sha2::Sha256::digest(input);
+        let output = crypto.sha256(input)?;
         Ok(PrecompileOutput::new(cost, output.to_vec().into()))
     }
 }
@@ -36,15 +45,21 @@ pub fn sha256_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
 /// - [Solidity Documentation on Mathematical and Cryptographic Functions](https://docs.soliditylang.org/en/develop/units-and-global-variables.html#mathematical-and-cryptographic-functions)
 /// - [Address 03](https://etherscan.io/address/0000000000000000000000000000000000000003)
 pub fn ripemd160_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    ripemd160_run_with(&DefaultCrypto, input, gas_limit)
+}
+
+/// Computes the RIPEMD-160 hash of the input data with the given crypto provider.
+pub fn ripemd160_run_with(
+    crypto: &dyn CryptoProvider,
+    input: &Bytes,
+    gas_limit: u64,
+) -> PrecompileResult {
     let gas_used = calc_linear_cost_u32(input.len(), 600, 120);
     if gas_used > gas_limit {
         Err(PrecompileError::OutOfGas.into())
     } else {
-        let mut hasher = ripemd::Ripemd160::new();
-        hasher.update(input);
-
         let mut output = [0u8; 32];
-        hasher.finalize_into((&mut output[12..]).into());
+        output[12..].copy_from_slice(&crypto.ripemd160(input)?);
         Ok(PrecompileOutput::new(gas_used, output.to_vec().into()))
     }
 }
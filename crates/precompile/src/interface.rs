@@ -25,6 +25,12 @@ impl PrecompileOutput {
     }
 }
 
+/// Precompile function that takes the input and the gas limit.
+///
+/// Precompiles compute their cost from the input length or a fixed size header and return
+/// [`PrecompileError::OutOfGas`] before doing work proportional to the input, so a call with
+/// a large input and little gas fails fast. Work that depends on the content, like the modexp
+/// exponent, is priced from the header before the operands are read.
 pub type StandardPrecompileFn = fn(&Bytes, u64) -> PrecompileResult;
 pub type EnvPrecompileFn = fn(&Bytes, u64, env: &CfgEnv) -> PrecompileResult;
 
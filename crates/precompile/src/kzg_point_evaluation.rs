@@ -1,5 +1,6 @@
 use crate::{
-    Address, Precompile, PrecompileError, PrecompileOutput, PrecompileResult, PrecompileWithAddress,
+    Address, CryptoProvider, DefaultCrypto, Precompile, PrecompileError, PrecompileErrors,
+    PrecompileOutput, PrecompileResult, PrecompileWithAddress,
 };
 cfg_if::cfg_if! {
     if #[cfg(feature = "c-kzg")] {
@@ -9,7 +10,6 @@ cfg_if::cfg_if! {
     }
 }
 use primitives::{hex_literal::hex, Bytes};
-use sha2::{Digest, Sha256};
 use wiring::default::CfgEnv;
 
 pub const POINT_EVALUATION: PrecompileWithAddress =
@@ -34,6 +34,16 @@ pub const RETURN_VALUE: &[u8; 64] = &hex!(
 /// |     32         | 32  | 32  |     48     |   48  |
 /// with z and y being padded 32 byte big endian values
 pub fn run(input: &Bytes, gas_limit: u64, cfg: &CfgEnv) -> PrecompileResult {
+    run_with(&DefaultCrypto, input, gas_limit, cfg)
+}
+
+/// Run kzg point evaluation precompile with the given crypto provider.
+pub fn run_with(
+    crypto: &dyn CryptoProvider,
+    input: &Bytes,
+    gas_limit: u64,
+    cfg: &CfgEnv,
+) -> PrecompileResult {
     if gas_limit < GAS_COST {
         return Err(PrecompileError::OutOfGas.into());
     }
@@ -46,7 +56,7 @@ pub fn run(input: &Bytes, gas_limit: u64, cfg: &CfgEnv) -> PrecompileResult {
     // Verify commitment matches versioned_hash
     let versioned_hash = &input[..32];
     let commitment = &input[96..144];
-    if kzg_to_versioned_hash(commitment) != versioned_hash {
+    if kzg_to_versioned_hash(crypto, commitment)? != versioned_hash {
         return Err(PrecompileError::BlobMismatchedVersion.into());
     }
 
@@ -65,10 +75,13 @@ pub fn run(input: &Bytes, gas_limit: u64, cfg: &CfgEnv) -> PrecompileResult {
 
 /// `VERSIONED_HASH_VERSION_KZG ++ sha256(commitment)[1..]`
 #[inline]
-pub fn kzg_to_versioned_hash(commitment: &[u8]) -> [u8; 32] {
-    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+pub fn kzg_to_versioned_hash(
+    crypto: &dyn CryptoProvider,
+    commitment: &[u8],
+) -> Result<[u8; 32], PrecompileErrors> {
+    let mut hash = crypto.sha256(commitment)?;
     hash[0] = VERSIONED_HASH_VERSION_KZG;
-    hash
+    Ok(hash)
 }
 
 #[inline]
@@ -111,8 +124,9 @@ mod tests {
         // test data from: https://github.com/ethereum/c-kzg-4844/blob/main/tests/verify_kzg_proof/kzg-mainnet/verify_kzg_proof_case_correct_proof_31ebd010e6098750/data.yaml
 
         let commitment = hex!("8f59a8d2a1a625a17f3fea0fe5eb8c896db3764f3185481bc22f91b4aaffcca25f26936857bc3a7c2539ea8ec3a952b7").to_vec();
-        let mut versioned_hash = Sha256::digest(&commitment).to_vec();
-        versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+        let versioned_hash = kzg_to_versioned_hash(&DefaultCrypto, &commitment)
+            .unwrap()
+            .to_vec();
         let z = hex!("73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000").to_vec();
         let y = hex!("1522a4a7f34e1ea350ae07c29c96c7e79655aa926122e95fe69fcbd932ca49e9").to_vec();
         let proof = hex!("a62ad71d14c5719385c0686f1871430475bf3a00f0aa3f7b8dd99a9abc2160744faf0070725e00b60ad9a026a15b1a8c").to_vec();
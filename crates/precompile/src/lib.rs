@@ -12,6 +12,7 @@ pub mod blake2;
 #[cfg(feature = "blst")]
 pub mod bls12_381;
 pub mod bn128;
+pub mod crypto;
 pub mod fatal_precompile;
 pub mod hash;
 pub mod identity;
@@ -24,6 +25,7 @@ pub mod secp256k1;
 pub mod secp256r1;
 pub mod utilities;
 
+pub use crypto::{CryptoPrecompile, CryptoPrecompileFn, CryptoProvider, DefaultCrypto};
 pub use fatal_precompile::fatal_precompile;
 
 pub use interface::*;
@@ -38,6 +40,21 @@ use cfg_if::cfg_if;
 use core::hash::Hash;
 use once_cell::race::OnceBox;
 use std::{boxed::Box, vec::Vec};
+use wiring::default::CfgEnv;
+
+/// Runs the precompile at the given address with the precompiles of the latest spec and the
+/// default [`CfgEnv`].
+///
+/// Returns `None` if there is no precompile at the address. Used as the entry point for
+/// fuzzing: any input must return a result or an error, never panic.
+pub fn run_raw(address: &Address, input: &[u8], gas_limit: u64) -> Option<PrecompileResult> {
+    let precompile = Precompiles::latest().get(address)?;
+    Some(precompile.call_ref(
+        &Bytes::copy_from_slice(input),
+        gas_limit,
+        &CfgEnv::default(),
+    ))
+}
 
 pub fn calc_linear_cost_u32(len: usize, base: u64, word: u64) -> u64 {
     (len as u64 + 32 - 1) / 32 * word + base
@@ -291,7 +308,7 @@ impl PrecompileSpecId {
             ISTANBUL | MUIR_GLACIER => Self::ISTANBUL,
             BERLIN | LONDON | ARROW_GLACIER | GRAY_GLACIER | MERGE | SHANGHAI => Self::BERLIN,
             CANCUN => Self::CANCUN,
-            PRAGUE | PRAGUE_EOF => Self::PRAGUE,
+            PRAGUE | PRAGUE_EOF | OSAKA => Self::PRAGUE,
             LATEST => Self::LATEST,
         }
     }
@@ -308,3 +325,21 @@ pub const fn u64_to_address(x: u64) -> Address {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_inputs_without_gas_fail() {
+        // Valid length for most precompiles that take a multiple of a fixed size.
+        let input = Bytes::from(vec![0xff; 128 * 160 * 384]);
+        for (address, precompile) in Precompiles::latest().inner() {
+            let result = precompile.call_ref(&input, 0, &CfgEnv::default());
+            assert!(
+                matches!(result, Err(PrecompileErrors::Error(_))),
+                "{address}: {result:?}"
+            );
+        }
+    }
+}
@@ -29,7 +29,8 @@ pub fn berlin_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     })
 }
 
-pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
+/// Returns `None` if the iteration count overflows.
+pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> Option<u64> {
     let mut iteration_count: u64 = 0;
 
     if exp_length <= 32 && exp_highp.is_zero() {
@@ -37,16 +38,21 @@ pub fn calculate_iteration_count(exp_length: u64, exp_highp: &U256) -> u64 {
     } else if exp_length <= 32 {
         iteration_count = exp_highp.bit_len() as u64 - 1;
     } else if exp_length > 32 {
-        iteration_count = (8u64.saturating_mul(exp_length - 32))
-            .saturating_add(max(1, exp_highp.bit_len() as u64) - 1);
+        iteration_count = 8u64
+            .checked_mul(exp_length - 32)?
+            .checked_add(max(1, exp_highp.bit_len() as u64) - 1)?;
     }
 
-    max(iteration_count, 1)
+    Some(max(iteration_count, 1))
 }
 
+/// Runs modexp with the given gas calculation.
+///
+/// `calc_gas` returns `None` if the gas cost overflows, which fails with
+/// [`PrecompileError::OutOfGas`].
 pub fn run_inner<F>(input: &[u8], gas_limit: u64, min_gas: u64, calc_gas: F) -> PrecompileResult
 where
-    F: FnOnce(u64, u64, u64, &U256) -> u64,
+    F: FnOnce(u64, u64, u64, &U256) -> Option<u64>,
 {
     // If there is no minimum gas, return error.
     if min_gas > gas_limit {
@@ -97,13 +103,18 @@ where
     };
 
     // Check if we have enough gas.
-    let gas_cost = calc_gas(base_len as u64, exp_len as u64, mod_len as u64, &exp_highp);
-    if gas_cost > gas_limit {
-        return Err(PrecompileError::OutOfGas.into());
-    }
+    let gas_cost = match calc_gas(base_len as u64, exp_len as u64, mod_len as u64, &exp_highp) {
+        Some(gas_cost) if gas_cost <= gas_limit => gas_cost,
+        _ => return Err(PrecompileError::OutOfGas.into()),
+    };
 
     // Padding is needed if the input does not contain all 3 values.
-    let input_len = base_len.saturating_add(exp_len).saturating_add(mod_len);
+    let Some(input_len) = base_len
+        .checked_add(exp_len)
+        .and_then(|len| len.checked_add(mod_len))
+    else {
+        return Err(PrecompileError::OutOfGas.into());
+    };
     let input = right_pad_vec(input, input_len);
     let (base, input) = input.split_at(base_len);
     let (exponent, modulus) = input.split_at(exp_len);
@@ -119,7 +130,13 @@ where
     ))
 }
 
-pub fn byzantium_gas_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp: &U256) -> u64 {
+/// Returns `None` if the gas cost overflows.
+pub fn byzantium_gas_calc(
+    base_len: u64,
+    exp_len: u64,
+    mod_len: u64,
+    exp_highp: &U256,
+) -> Option<u64> {
     // output of this function is bounded by 2^128
     fn mul_complexity(x: u64) -> U256 {
         if x <= 64 {
@@ -135,20 +152,21 @@ pub fn byzantium_gas_calc(base_len: u64, exp_len: u64, mod_len: u64, exp_highp:
     }
 
     let mul = mul_complexity(core::cmp::max(mod_len, base_len));
-    let iter_count = U256::from(calculate_iteration_count(exp_len, exp_highp));
+    let iter_count = U256::from(calculate_iteration_count(exp_len, exp_highp)?);
     // mul * iter_count bounded by 2^195 < 2^256 (no overflow)
     let gas = (mul * iter_count) / U256::from(20);
-    gas.saturating_to()
+    u64::try_from(gas).ok()
 }
 
 // Calculate gas cost according to EIP 2565:
 // https://eips.ethereum.org/EIPS/eip-2565
+// Returns `None` if the gas cost overflows.
 pub fn berlin_gas_calc(
     base_length: u64,
     exp_length: u64,
     mod_length: u64,
     exp_highp: &U256,
-) -> u64 {
+) -> Option<u64> {
     fn calculate_multiplication_complexity(base_length: u64, mod_length: u64) -> U256 {
         let max_length = max(base_length, mod_length);
         let mut words = max_length / 8;
@@ -160,15 +178,17 @@ pub fn berlin_gas_calc(
     }
 
     let multiplication_complexity = calculate_multiplication_complexity(base_length, mod_length);
-    let iteration_count = calculate_iteration_count(exp_length, exp_highp);
+    let iteration_count = calculate_iteration_count(exp_length, exp_highp)?;
     let gas = (multiplication_complexity * U256::from(iteration_count)) / U256::from(3);
-    max(200, gas.saturating_to())
+    Some(max(200, u64::try_from(gas).ok()?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::PrecompileErrors;
     use primitives::hex;
+    use proptest::strategy::Strategy;
     use std::vec::Vec;
 
     struct Test {
@@ -382,4 +402,62 @@ mod tests {
         let expected: Vec<u8> = Vec::new();
         assert_eq!(res.bytes, expected)
     }
+
+    #[test]
+    fn test_modexp_gas_overflow() {
+        let mut input = Vec::new();
+        for len in [0u64, 1 << 40, 1 << 32] {
+            input.extend_from_slice(&U256::from(len).to_be_bytes::<32>());
+        }
+        let input = Bytes::from(input);
+        assert_eq!(berlin_gas_calc(0, 1 << 40, 1 << 32, &U256::ZERO), None);
+        for run in [byzantium_run, berlin_run] {
+            assert_eq!(run(&input, u64::MAX), Err(PrecompileError::OutOfGas.into()));
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_modexp(
+            base_len in 0usize..=64,
+            exp_len in 0usize..=64,
+            mod_len in 0usize..=64,
+            data in proptest::collection::vec(proptest::num::u8::ANY, 0..256),
+            gas_limit in 0u64..100_000,
+        ) {
+            let mut input = Vec::new();
+            for len in [base_len, exp_len, mod_len] {
+                input.extend_from_slice(&U256::from(len).to_be_bytes::<32>());
+            }
+            input.extend_from_slice(&data);
+            match crate::run_raw(&BERLIN.0, &input, gas_limit).unwrap() {
+                Ok(output) => {
+                    proptest::prop_assert!(output.gas_used <= gas_limit);
+                    proptest::prop_assert_eq!(output.bytes.len(), mod_len);
+                }
+                Err(err) => proptest::prop_assert_eq!(err, PrecompileError::OutOfGas.into()),
+            }
+        }
+
+        #[test]
+        fn fuzz_modexp_header(
+            lens in proptest::array::uniform3(proptest::prop_oneof![
+                (0u64..=1_024).prop_map(U256::from),
+                proptest::num::u64::ANY.prop_map(U256::from),
+                proptest::strategy::Just(U256::MAX),
+            ]),
+            data in proptest::collection::vec(proptest::num::u8::ANY, 0..=128),
+            gas_limit in 0u64..1_000_000,
+        ) {
+            // Lengths that can't be allocated are rejected by the gas limit.
+            let mut input = Vec::new();
+            for len in lens {
+                input.extend_from_slice(&len.to_be_bytes::<32>());
+            }
+            input.extend_from_slice(&data);
+            let result = crate::run_raw(&BERLIN.0, &input, gas_limit).unwrap();
+            let is_fatal = matches!(result, Err(PrecompileErrors::Fatal { .. }));
+            proptest::prop_assert!(!is_fatal);
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    utilities::right_pad, Precompile, PrecompileError, PrecompileOutput, PrecompileResult,
-    PrecompileWithAddress,
+    utilities::right_pad, CryptoProvider, DefaultCrypto, Precompile, PrecompileError,
+    PrecompileOutput, PrecompileResult, PrecompileWithAddress,
 };
 use primitives::{alloy_primitives::B512, Bytes, B256};
 
@@ -9,9 +9,10 @@ pub const ECRECOVER: PrecompileWithAddress = PrecompileWithAddress(
     Precompile::Standard(ec_recover_run),
 );
 
+#[cfg(any(feature = "secp256k1", feature = "k256"))]
 pub use self::secp256k1::ecrecover;
 
-#[cfg(not(feature = "secp256k1"))]
+#[cfg(all(not(feature = "secp256k1"), feature = "k256"))]
 #[allow(clippy::module_inception)]
 mod secp256k1 {
     use k256::ecdsa::{Error, RecoveryId, Signature, VerifyingKey};
@@ -53,6 +54,7 @@ mod secp256k1 {
     };
 
     // Silence the unused crate dependency warning.
+    #[cfg(feature = "k256")]
     use k256 as _;
 
     pub fn ecrecover(sig: &B512, recid: u8, msg: &B256) -> Result<B256, secp256k1::Error> {
@@ -70,6 +72,15 @@ mod secp256k1 {
 }
 
 pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    ec_recover_run_with(&DefaultCrypto, input, gas_limit)
+}
+
+/// Recovers the signer address with the given crypto provider.
+pub fn ec_recover_run_with(
+    crypto: &dyn CryptoProvider,
+    input: &Bytes,
+    gas_limit: u64,
+) -> PrecompileResult {
     const ECRECOVER_BASE: u64 = 3_000;
 
     if ECRECOVER_BASE > gas_limit {
@@ -87,8 +98,40 @@ pub fn ec_recover_run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
     let recid = input[63] - 27;
     let sig = <&B512>::try_from(&input[64..128]).unwrap();
 
-    let out = secp256k1::ecrecover(sig, recid, msg)
+    let out = crypto
+        .secp256k1_ecrecover(sig, recid, msg)?
         .map(|o| o.to_vec().into())
         .unwrap_or_default();
     Ok(PrecompileOutput::new(ECRECOVER_BASE, out))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest::proptest! {
+        #[test]
+        fn fuzz_ecrecover(
+            input in proptest::collection::vec(proptest::num::u8::ANY, 0..=160),
+            v in 26u8..=29,
+            gas_limit in 0u64..6_000,
+        ) {
+            let mut input = input;
+            if input.len() >= 64 {
+                // Hit valid and invalid recovery ids more often.
+                input[32..63].fill(0);
+                input[63] = v;
+            }
+            match crate::run_raw(&ECRECOVER.0, &input, gas_limit).unwrap() {
+                Ok(output) => {
+                    proptest::prop_assert_eq!(output.gas_used, 3_000);
+                    proptest::prop_assert!(output.bytes.is_empty() || output.bytes.len() == 32);
+                }
+                Err(err) => {
+                    proptest::prop_assert!(gas_limit < 3_000);
+                    proptest::prop_assert_eq!(err, PrecompileError::OutOfGas.into());
+                }
+            }
+        }
+    }
+}
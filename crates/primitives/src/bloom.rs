@@ -0,0 +1,36 @@
+use alloy_primitives::{Bloom, Log};
+
+/// Computes the bloom filter of the logs, as used in receipts and block headers.
+///
+/// Each log adds its address and topics to the filter.
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+    let mut bloom = Bloom::ZERO;
+    for log in logs {
+        bloom.accrue_log(log);
+    }
+    bloom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, BloomInput, Bytes};
+
+    #[test]
+    fn bloom_of_logs() {
+        assert_eq!(logs_bloom(&[] as &[Log]), Bloom::ZERO);
+
+        let topic = b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let log = Log::new_unchecked(
+            address!("dac17f958d2ee523a2206206994597c13d831ec7"),
+            std::vec![topic],
+            Bytes::new(),
+        );
+        let bloom = logs_bloom([&log]);
+        assert!(bloom.contains_log(&log));
+        assert!(bloom.contains_input(BloomInput::Raw(log.address.as_slice())));
+        assert!(bloom.contains_input(BloomInput::Raw(topic.as_slice())));
+        // Data is not part of the filter.
+        assert!(!bloom.contains_input(BloomInput::Raw(&[1, 2, 3])));
+    }
+}
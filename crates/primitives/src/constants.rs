@@ -21,6 +21,16 @@ pub const BLOCKHASH_SERVE_WINDOW: usize = 8192;
 /// This is named `HISTORY_STORAGE_ADDRESS` in the EIP.
 pub const BLOCKHASH_STORAGE_ADDRESS: Address = address!("25a219378dad9b3503c8268c9ca836a52427a4fb");
 
+/// EIP-4788: Beacon block root in the EVM
+///
+/// The address of the beacon roots contract.
+pub const BEACON_ROOTS_ADDRESS: Address = address!("000f3df6d732807ef1319fb7b8bb8522d0beac02");
+
+/// EIP-4788: Beacon block root in the EVM
+///
+/// Length of the ring buffers that store timestamps and roots in the beacon roots contract.
+pub const BEACON_ROOTS_HISTORY_BUFFER_LENGTH: u64 = 8191;
+
 /// The address of precompile 3, which is handled specially in a few places.
 pub const PRECOMPILE3: Address =
     Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
@@ -0,0 +1,93 @@
+use alloy_primitives::{keccak256, Address, B256};
+
+/// Returns the address of a contract created by `caller` with `CREATE` at `nonce`.
+///
+/// The address is the last 20 bytes of `keccak256(rlp([caller, nonce]))`.
+#[inline]
+pub fn create_address(caller: Address, nonce: u64) -> Address {
+    caller.create(nonce)
+}
+
+/// Returns the address of a contract created by `caller` with `CREATE2`.
+///
+/// The address is the last 20 bytes of `keccak256(0xff ++ caller ++ salt ++ init_code_hash)`,
+/// see [EIP-1014](https://eips.ethereum.org/EIPS/eip-1014).
+#[inline]
+pub fn create2_address(caller: Address, salt: B256, init_code_hash: B256) -> Address {
+    caller.create2(salt, init_code_hash)
+}
+
+/// Returns the first salt of `salts` for which the `CREATE2` address of `caller` and
+/// `init_code_hash` satisfies `predicate`, together with the address.
+///
+/// Used for salt mining, e.g. to find a vanity address or an address with leading zero bytes.
+/// Only the salt is written for every candidate, the rest of the hashed preimage is reused.
+pub fn mine_create2_salt(
+    caller: Address,
+    init_code_hash: B256,
+    salts: impl IntoIterator<Item = B256>,
+    mut predicate: impl FnMut(&Address) -> bool,
+) -> Option<(B256, Address)> {
+    let mut preimage = [0u8; 85];
+    preimage[0] = 0xff;
+    preimage[1..21].copy_from_slice(caller.as_slice());
+    preimage[53..85].copy_from_slice(init_code_hash.as_slice());
+
+    salts.into_iter().find_map(|salt| {
+        preimage[21..53].copy_from_slice(salt.as_slice());
+        let address = Address::from_word(keccak256(preimage));
+        predicate(&address).then_some((salt, address))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, U256};
+
+    #[test]
+    fn create_addresses() {
+        let caller = address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        assert_eq!(
+            create_address(caller, 0),
+            address!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")
+        );
+        assert_eq!(
+            create_address(caller, 1),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")
+        );
+
+        // Example 5 of EIP-1014.
+        assert_eq!(
+            create2_address(
+                address!("00000000000000000000000000000000deadbeef"),
+                b256!("00000000000000000000000000000000000000000000000000000000cafebabe"),
+                keccak256([0xde, 0xad, 0xbe, 0xef]),
+            ),
+            address!("60f3f640a8508fc6a86d45df051962668e1e8ac7")
+        );
+    }
+
+    #[test]
+    fn mines_salt() {
+        let caller = address!("00000000000000000000000000000000deadbeef");
+        let init_code_hash = keccak256([0x00]);
+        let salts = (0u64..).map(|i| B256::from(U256::from(i)));
+
+        let (salt, address) =
+            mine_create2_salt(caller, init_code_hash, salts.clone(), |a| a[0] == 0).unwrap();
+        assert_eq!(address[0], 0);
+        assert_eq!(address, create2_address(caller, salt, init_code_hash));
+        // The first matching salt is returned.
+        assert!(salts.take_while(|s| *s != salt).all(|s| create2_address(
+            caller,
+            s,
+            init_code_hash
+        )[0] != 0));
+
+        assert_eq!(
+            mine_create2_salt(caller, init_code_hash, [], |_| true),
+            None
+        );
+    }
+}
@@ -4,12 +4,21 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc as std;
+
+mod bloom;
 mod constants;
+mod create_address;
+mod revert_reason;
+pub use bloom::logs_bloom;
 pub use constants::*;
+pub use create_address::{create2_address, create_address, mine_create2_salt};
+pub use revert_reason::{RevertReason, ERROR_SELECTOR, PANIC_SELECTOR};
 
 pub use alloy_primitives::{
     self, address, b256, bytes, fixed_bytes, hex, hex_literal, keccak256, ruint, uint, Address,
-    Bytes, FixedBytes, Log, LogData, TxKind, B256, I256, U256,
+    Bloom, BloomInput, Bytes, FixedBytes, Log, LogData, TxKind, B256, I256, U256,
 };
 
 pub use alloy_primitives::map::{self, hash_map, hash_set, HashMap, HashSet};
@@ -0,0 +1,154 @@
+use alloy_primitives::{Bytes, U256};
+use core::fmt;
+use std::string::String;
+
+/// Selector of the Solidity `Error(string)` revert, used by `revert("...")` and `require`.
+pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of the Solidity `Panic(uint256)` revert, used by compiler inserted checks.
+pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decoded output of a reverted call.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RevertReason {
+    /// `Error(string)` with the revert message.
+    Error(String),
+    /// `Panic(uint256)` with the panic code, see [`RevertReason::panic_description`].
+    Panic(U256),
+    /// Any other revert data, like Solidity custom errors or malformed `Error` and `Panic`
+    /// encodings.
+    Custom(Bytes),
+}
+
+impl RevertReason {
+    /// Decodes the output of a reverted call.
+    ///
+    /// Returns `None` if the output is empty, for example for `revert()` without a reason.
+    pub fn decode(output: &[u8]) -> Option<Self> {
+        if output.is_empty() {
+            return None;
+        }
+        let (selector, data) = output.split_at(output.len().min(4));
+        let decoded = if selector == ERROR_SELECTOR {
+            decode_error(data)
+        } else if selector == PANIC_SELECTOR {
+            decode_panic(data)
+        } else {
+            None
+        };
+        Some(decoded.unwrap_or_else(|| Self::Custom(Bytes::copy_from_slice(output))))
+    }
+
+    /// Returns the description of the Solidity panic code, `None` for other reasons and
+    /// unknown codes.
+    pub fn panic_description(&self) -> Option<&'static str> {
+        let Self::Panic(code) = self else {
+            return None;
+        };
+        let description = match u8::try_from(*code).ok()? {
+            0x00 => "generic compiler inserted panic",
+            0x01 => "assertion failed",
+            0x11 => "arithmetic underflow or overflow",
+            0x12 => "division or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "invalid storage byte array encoding",
+            0x31 => "pop on empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory",
+            0x51 => "call to zero-initialized function",
+            _ => return None,
+        };
+        Some(description)
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(message) => f.write_str(message),
+            Self::Panic(code) => match self.panic_description() {
+                Some(description) => write!(f, "panic: {description} (0x{code:02x})"),
+                None => write!(f, "panic: unknown code 0x{code:02x}"),
+            },
+            Self::Custom(data) => write!(f, "custom error {data}"),
+        }
+    }
+}
+
+/// Decodes the ABI encoded string argument of `Error(string)`.
+fn decode_error(data: &[u8]) -> Option<RevertReason> {
+    let offset = usize::try_from(read_word(data, 0)?).ok()?;
+    let len = usize::try_from(read_word(data, offset)?).ok()?;
+    let start = offset.checked_add(32)?;
+    let message = data.get(start..start.checked_add(len)?)?;
+    String::from_utf8(message.to_vec())
+        .ok()
+        .map(RevertReason::Error)
+}
+
+/// Decodes the `uint256` argument of `Panic(uint256)`.
+fn decode_panic(data: &[u8]) -> Option<RevertReason> {
+    read_word(data, 0).map(RevertReason::Panic)
+}
+
+/// Reads the ABI word at the given offset.
+fn read_word(data: &[u8], offset: usize) -> Option<U256> {
+    let word = data.get(offset..offset.checked_add(32)?)?;
+    Some(U256::from_be_slice(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn error_reason() {
+        // `revert("Not enough Ether provided.")`
+        let output = hex!(
+            "08c379a0"
+            "0000000000000000000000000000000000000000000000000000000000000020"
+            "000000000000000000000000000000000000000000000000000000000000001a"
+            "4e6f7420656e6f7567682045746865722070726f76696465642e000000000000"
+        );
+        let reason = RevertReason::decode(&output).unwrap();
+        assert_eq!(
+            reason,
+            RevertReason::Error("Not enough Ether provided.".into())
+        );
+        assert_eq!(reason.to_string(), "Not enough Ether provided.");
+
+        // Message longer than the data.
+        let reason = RevertReason::decode(&output[..output.len() - 8]).unwrap();
+        assert!(matches!(reason, RevertReason::Custom(_)));
+    }
+
+    #[test]
+    fn panic_reason() {
+        let output = hex!(
+            "4e487b71"
+            "0000000000000000000000000000000000000000000000000000000000000011"
+        );
+        let reason = RevertReason::decode(&output).unwrap();
+        assert_eq!(reason, RevertReason::Panic(U256::from(0x11)));
+        assert_eq!(
+            reason.to_string(),
+            "panic: arithmetic underflow or overflow (0x11)"
+        );
+    }
+
+    #[test]
+    fn other_reasons() {
+        assert_eq!(RevertReason::decode(&[]), None);
+        // Custom error `InsufficientBalance()`.
+        let output = hex!("f4d678b8");
+        assert_eq!(
+            RevertReason::decode(&output),
+            Some(RevertReason::Custom(Bytes::copy_from_slice(&output)))
+        );
+        assert_eq!(
+            RevertReason::decode(&[0x08, 0xc3]),
+            Some(RevertReason::Custom(Bytes::from_static(&[0x08, 0xc3])))
+        );
+    }
+}
@@ -4,10 +4,11 @@ use criterion::{
 use database::BenchmarkDB;
 use interpreter::{table::make_instruction_table, SharedMemory, EMPTY_SHARED_MEMORY};
 use revm::{
-    bytecode::Bytecode,
+    bytecode::{opcode, Bytecode},
     interpreter::{Contract, DummyHost, Interpreter},
-    primitives::{address, bytes, hex, Bytes, TxKind, U256},
+    primitives::{address, bytes, hex, Address, Bytes, TxKind, U256},
     specification::hardfork::BerlinSpec,
+    state::{Account, AccountInfo, EvmState, EvmStorageSlot},
     wiring::EthereumWiring,
     Evm,
 };
@@ -81,6 +82,215 @@ fn transfer(c: &mut Criterion) {
     g.finish();
 }
 
+/// Calls an account without code with large call data in a loop.
+///
+/// Call data of such calls is never copied out of the memory of the caller.
+fn calldata(c: &mut Criterion) {
+    let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+            [
+                opcode::JUMPDEST,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH2,
+                0x80,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0xee,
+                opcode::GAS,
+                opcode::CALL,
+                opcode::POP,
+                opcode::PUSH1,
+                0x00,
+                opcode::JUMP,
+            ]
+            .into(),
+        )))
+        .modify_tx_env(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000000001");
+            tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+            tx.gas_limit = 1_000_000;
+        })
+        .build();
+
+    let mut g = c.benchmark_group("calldata");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+    bench_transact(&mut g, &mut evm);
+    g.finish();
+}
+
+/// Hashes 1KiB of memory with `KECCAK256` in a loop.
+///
+/// Build with the `asm-keccak` feature to use the assembly implementation.
+fn keccak(c: &mut Criterion) {
+    let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+            [
+                opcode::JUMPDEST,
+                opcode::PUSH2,
+                0x04,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::KECCAK256,
+                opcode::POP,
+                opcode::PUSH1,
+                0x00,
+                opcode::JUMP,
+            ]
+            .into(),
+        )))
+        .modify_tx_env(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000000001");
+            tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+            tx.gas_limit = 1_000_000;
+        })
+        .build();
+
+    let mut g = c.benchmark_group("keccak");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+    bench_transact(&mut g, &mut evm);
+    g.finish();
+}
+
+/// Copies the code of a large contract to memory in a loop, with a zero padded tail.
+fn codecopy(c: &mut Criterion) {
+    for (name, copy) in [
+        ("codecopy", &[opcode::CODECOPY][..]),
+        ("extcodecopy", &[opcode::ADDRESS, opcode::EXTCODECOPY][..]),
+    ] {
+        // Copy starts at the second byte, so the last byte of memory is padding.
+        let mut code = [
+            opcode::JUMPDEST,
+            opcode::PUSH2,
+            0x60,
+            0x00,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x00,
+        ]
+        .to_vec();
+        code.extend_from_slice(copy);
+        code.extend_from_slice(&[opcode::PUSH1, 0x00, opcode::JUMP]);
+        // Largest code allowed by EIP-170.
+        code.resize(0x6000, opcode::STOP);
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(code.into())))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+
+        let mut g = c.benchmark_group(name);
+        g.noise_threshold(0.03)
+            .warm_up_time(Duration::from_secs(1))
+            .measurement_time(Duration::from_secs(3))
+            .sample_size(10);
+        bench_transact(&mut g, &mut evm);
+        g.finish();
+    }
+}
+
+/// Measures calls that each need a new journal.
+///
+/// Compare runs with and without the `tx-arena` feature to see the cost of the
+/// allocations.
+fn checkpoints(c: &mut Criterion) {
+    let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+            [
+                opcode::JUMPDEST,
+                opcode::PUSH1,
+                0x01,
+                opcode::PUSH1,
+                0x00,
+                opcode::TSTORE,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0x00,
+                opcode::PUSH1,
+                0xee,
+                opcode::PUSH1,
+                0x00,
+                opcode::CALL,
+                opcode::POP,
+                opcode::PUSH1,
+                0x00,
+                opcode::JUMP,
+            ]
+            .into(),
+        )))
+        .modify_tx_env(|tx| {
+            tx.caller = address!("0000000000000000000000000000000000000001");
+            tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+            tx.gas_limit = 1_000_000;
+        })
+        .build();
+
+    let mut g = c.benchmark_group("checkpoints");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+    bench_transact(&mut g, &mut evm);
+    g.finish();
+}
+
+/// Measures the hasher overhead of the state maps.
+///
+/// Compare runs with and without the `map-fxhash` feature to see the cost of
+/// deterministic iteration order.
+fn state_map(c: &mut Criterion) {
+    let mut g = c.benchmark_group("state_map");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+
+    g.bench_function("insert_iterate", |b| {
+        b.iter(|| {
+            let mut state = EvmState::default();
+            for i in 0..1000u64 {
+                let mut account = Account::from(AccountInfo::from_balance(U256::from(i)));
+                for slot in 0..10u64 {
+                    account
+                        .storage
+                        .insert(U256::from(slot), EvmStorageSlot::new(U256::from(i)));
+                }
+                state.insert(Address::from_word(U256::from(i).into()), account);
+            }
+            state
+                .values()
+                .flat_map(|account| account.storage.values())
+                .fold(U256::ZERO, |acc, slot| acc + slot.present_value)
+        })
+    });
+    g.finish();
+}
+
 fn bench_transact(
     g: &mut BenchmarkGroup<'_, WallTime>,
     evm: &mut Evm<'_, EthereumWiring<BenchmarkDB, ()>>,
@@ -132,6 +342,11 @@ criterion_group!(
     analysis,
     snailtracer,
     transfer,
+    calldata,
+    keccak,
+    codecopy,
+    checkpoints,
+    state_map,
 );
 criterion_main!(benches);
 
@@ -0,0 +1,84 @@
+//! Router contract that makes many calls and ignores their output.
+//!
+//! Run with `cargo bench -p revm --bench router`.
+use criterion::{criterion_group, criterion_main, Criterion};
+use database::BenchmarkDB;
+use revm::{
+    bytecode::{opcode, Bytecode},
+    primitives::{address, TxKind},
+    wiring::EthereumWiring,
+    Evm,
+};
+use std::time::Duration;
+
+/// Returns a contract that calls itself until it runs out of gas, the inner calls return
+/// 1 KiB.
+///
+/// The output range of the calls is `out_len` KiB.
+fn router_code(out_len: u8) -> Bytecode {
+    Bytecode::new_raw(
+        [
+            // Calls with input are calls of the callee.
+            opcode::CALLDATASIZE,
+            opcode::PUSH1,
+            0x17,
+            opcode::JUMPI,
+            // Router loop.
+            opcode::JUMPDEST,
+            opcode::PUSH2,
+            out_len * 4,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x01,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::ADDRESS,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::PUSH1,
+            0x04,
+            opcode::JUMP,
+            // Callee.
+            opcode::JUMPDEST,
+            opcode::PUSH2,
+            0x04,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::RETURN,
+        ]
+        .into(),
+    )
+}
+
+/// `ignored` passes an empty output range to `CALL`, so only the return data buffer is set.
+/// `written` copies the output to memory after every call.
+fn router(c: &mut Criterion) {
+    let mut g = c.benchmark_group("router");
+    g.noise_threshold(0.03)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3))
+        .sample_size(10);
+
+    for (name, out_len) in [("ignored", 0), ("written", 1)] {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(router_code(out_len)))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        g.bench_function(name, |b| b.iter(|| evm.transact().unwrap()));
+    }
+    g.finish();
+}
+
+criterion_group!(benches, router);
+criterion_main!(benches);
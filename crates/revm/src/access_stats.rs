@@ -0,0 +1,119 @@
+use state::EvmState;
+
+/// Account and storage access statistics of a transaction.
+///
+/// Recorded by the [`JournaledState`](crate::JournaledState) and available with
+/// [`Evm::last_access_stats`](crate::Evm::last_access_stats) after the transaction. Tools that
+/// generate access lists or look for gas optimizations can use it to see how much of the gas
+/// went to cold accesses and how many of the written slots actually changed.
+///
+/// Loads done by the handler, like the caller and beneficiary loads, are counted as well.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateAccessStats {
+    /// Number of account loads that were cold.
+    pub cold_account_loads: u64,
+    /// Number of account loads that were warm.
+    pub warm_account_loads: u64,
+    /// Number of storage slot loads that were cold, including the loads of `SSTORE`.
+    pub cold_storage_loads: u64,
+    /// Number of storage slot loads that were warm, including the loads of `SSTORE`.
+    pub warm_storage_loads: u64,
+    /// Number of storage writes.
+    pub storage_writes: u64,
+    /// Number of unique accounts that were loaded.
+    pub accounts: usize,
+    /// Number of unique storage slots that were loaded.
+    pub storage_slots: usize,
+    /// Number of storage slots whose value differs from the original one at the end of the
+    /// transaction.
+    ///
+    /// Slots that were written and restored to their original value are not counted.
+    pub changed_storage_slots: usize,
+}
+
+impl StateAccessStats {
+    /// Records an account load.
+    #[inline]
+    pub fn record_account_load(&mut self, is_cold: bool) {
+        if is_cold {
+            self.cold_account_loads += 1;
+        } else {
+            self.warm_account_loads += 1;
+        }
+    }
+
+    /// Records a storage slot load.
+    #[inline]
+    pub fn record_storage_load(&mut self, is_cold: bool) {
+        if is_cold {
+            self.cold_storage_loads += 1;
+        } else {
+            self.warm_storage_loads += 1;
+        }
+    }
+
+    /// Records the unique accounts and storage slots of the final state of the transaction.
+    pub fn record_state(&mut self, state: &EvmState) {
+        self.accounts = state.len();
+        self.storage_slots = state.values().map(|account| account.storage.len()).sum();
+        self.changed_storage_slots = state
+            .values()
+            .flat_map(|account| account.storage.values())
+            .filter(|slot| slot.is_changed())
+            .count();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use bytecode::{
+        opcode::{POP, PUSH1, SLOAD, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, TxKind};
+    use wiring::EthereumWiring;
+
+    #[test]
+    fn records_access_stats() {
+        let bytecode = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, SLOAD, POP, PUSH1, 0x00, SLOAD, POP, PUSH1, 0x01, PUSH1, 0x01, SSTORE,
+                PUSH1, 0x00, PUSH1, 0x02, SSTORE, STOP,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.coinbase = address!("00000000000000000000000000000000000000cb")
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        assert_eq!(evm.last_access_stats(), &StateAccessStats::default());
+
+        assert!(evm.transact().unwrap().result.is_success());
+        let stats = evm.last_access_stats().clone();
+        assert_eq!(stats.cold_storage_loads, 3);
+        assert_eq!(stats.warm_storage_loads, 1);
+        assert_eq!(stats.storage_writes, 2);
+        // Caller, contract and coinbase.
+        assert_eq!(stats.accounts, 3);
+        assert!(stats.cold_account_loads > 0);
+        assert_eq!(stats.storage_slots, 3);
+        // Writing zero to the zero slot is not a change.
+        assert_eq!(stats.changed_storage_slots, 1);
+
+        // Statistics are recorded per transaction.
+        evm.transact().unwrap();
+        assert_eq!(evm.last_access_stats(), &stats);
+    }
+}
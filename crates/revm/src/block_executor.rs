@@ -0,0 +1,607 @@
+use crate::Evm;
+use core::fmt::Debug;
+use database::{states::bundle_state::BundleRetention, BundleState, State, TransitionState};
+use database_interface::{Database, DatabaseCommit};
+use primitives::{
+    logs_bloom, Address, Bloom, Log, BEACON_ROOTS_ADDRESS, BEACON_ROOTS_HISTORY_BUFFER_LENGTH, U256,
+};
+use specification::hardfork::SpecId;
+use state::{Account, EvmState, EvmStorageSlot};
+use std::{boxed::Box, format, vec::Vec};
+use transaction::TransactionType;
+use wiring::{
+    default::{block::BlockEnv, TxEnv},
+    result::{EVMError, InvalidTransaction, ResultAndState},
+    EthereumWiring, Transaction,
+};
+
+/// Wiring used by [`BlockExecutor`].
+pub type BlockExecutorWiring<DB, EXT> = EthereumWiring<State<DB>, EXT>;
+
+/// Error returned by [`BlockExecutor`].
+pub type BlockExecutionError<DBError> = EVMError<DBError, InvalidTransaction>;
+
+/// Callback called with the receipt and state of each executed transaction.
+type OnTransaction<'a> = Box<dyn FnMut(&Receipt, &EvmState) + 'a>;
+
+/// Receipt of a transaction executed by [`BlockExecutor`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Receipt {
+    /// Type of the transaction.
+    pub tx_type: TransactionType,
+    /// Whether the transaction was successful.
+    pub success: bool,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+    /// Gas used by the block up to and including this transaction.
+    pub cumulative_gas_used: u64,
+    /// Logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// Bloom filter of the logs.
+    pub logs_bloom: Bloom,
+}
+
+/// Output of the executed block.
+#[derive(Debug)]
+pub struct BlockExecutionOutput {
+    /// Receipts of the executed transactions.
+    pub receipts: Vec<Receipt>,
+    /// Gas used by the block.
+    pub gas_used: u64,
+    /// State changes of the executed blocks with one revert per block.
+    pub bundle_state: BundleState,
+}
+
+impl BlockExecutionOutput {
+    /// Returns the bloom filter of all logs in the block.
+    pub fn logs_bloom(&self) -> Bloom {
+        let mut bloom = Bloom::ZERO;
+        for receipt in &self.receipts {
+            bloom.accrue_bloom(&receipt.logs_bloom);
+        }
+        bloom
+    }
+}
+
+/// Executes transactions of a block on top of a shared [`State`].
+///
+/// Every transaction is committed to the state before the next one is executed, block gas is
+/// accumulated and a [`Receipt`] is generated for each of them. Rewards and withdrawals are
+/// applied as balance increments and [`BlockExecutor::finish`] returns the [`BundleState`] of
+/// the whole block.
+///
+/// Consecutive blocks can be executed with [`BlockExecutor::next_block`], the bundle then
+/// accumulates the changes of all of them and keeps the reverts of each block, so a node can
+/// write the plain state and the changesets once and still unwind single blocks.
+///
+/// Receipts can be streamed while the block executes with [`BlockExecutor::on_transaction`].
+///
+/// System calls that are done once per block, like the EIP-4788 beacon root, are applied by
+/// the executor and not by the transactions, see [`BlockExecutor::apply_beacon_root`].
+pub struct BlockExecutor<'a, DB: Database, EXT: Debug> {
+    evm: Evm<'a, BlockExecutorWiring<DB, EXT>>,
+    cumulative_gas_used: u64,
+    beacon_root_applied: bool,
+    receipts: Vec<Receipt>,
+    on_transaction: Option<OnTransaction<'a>>,
+}
+
+impl<DB: Database, EXT: Debug> Debug for BlockExecutor<'_, DB, EXT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BlockExecutor")
+            .field("cumulative_gas_used", &self.cumulative_gas_used)
+            .field("receipts", &self.receipts)
+            .field("on_transaction", &self.on_transaction.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, DB: Database, EXT: Debug> BlockExecutor<'a, DB, EXT> {
+    /// Creates new block executor.
+    ///
+    /// Block and configuration are taken from the environment of the given [`Evm`]. Transition
+    /// tracking is enabled on the state if it is not already.
+    pub fn new(mut evm: Evm<'a, BlockExecutorWiring<DB, EXT>>) -> Self {
+        let state = evm.db_mut();
+        if state.transition_state.is_none() {
+            state.transition_state = Some(TransitionState::default());
+        }
+        Self {
+            evm,
+            cumulative_gas_used: 0,
+            beacon_root_applied: false,
+            receipts: Vec::new(),
+            on_transaction: None,
+        }
+    }
+
+    /// Sets the callback that is called after every executed transaction with its receipt and
+    /// the state it changed, before the state is committed.
+    ///
+    /// Lets indexers process logs while the block executes instead of after
+    /// [`BlockExecutor::finish`]. Replaces the previous callback.
+    pub fn on_transaction(mut self, callback: impl FnMut(&Receipt, &EvmState) + 'a) -> Self {
+        self.on_transaction = Some(Box::new(callback));
+        self
+    }
+
+    /// Returns the wrapped [`Evm`].
+    pub fn evm(&self) -> &Evm<'a, BlockExecutorWiring<DB, EXT>> {
+        &self.evm
+    }
+
+    /// Returns the mutable wrapped [`Evm`].
+    pub fn evm_mut(&mut self) -> &mut Evm<'a, BlockExecutorWiring<DB, EXT>> {
+        &mut self.evm
+    }
+
+    /// Returns gas used by the executed transactions.
+    pub fn cumulative_gas_used(&self) -> u64 {
+        self.cumulative_gas_used
+    }
+
+    /// Returns receipts of the executed transactions.
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Stores the parent beacon block root of the block in the beacon roots contract.
+    ///
+    /// EIP-4788: this is the system call that is done once per block before its first
+    /// transaction. It is applied by [`BlockExecutor::execute_transaction`],
+    /// [`BlockExecutor::apply_block_reward`] and [`BlockExecutor::apply_withdrawals`], a block
+    /// without any of them has to call it explicitly. Further calls in the same block do nothing.
+    ///
+    /// Nothing is written before Cancun, if the root is not set or if the contract has no code.
+    pub fn apply_beacon_root(&mut self) -> Result<(), BlockExecutionError<DB::Error>> {
+        if self.beacon_root_applied {
+            return Ok(());
+        }
+        let block = self.evm.block();
+        let (root, timestamp) = (block.parent_beacon_block_root, block.timestamp);
+        if let (true, Some(root)) = (self.evm.spec_id().is_enabled_in(SpecId::CANCUN), root) {
+            let state = self.evm.db_mut();
+            let info = state
+                .basic(BEACON_ROOTS_ADDRESS)
+                .map_err(EVMError::Database)?
+                .unwrap_or_default();
+            if !info.is_empty_code_hash() {
+                let timestamp_index = timestamp % U256::from(BEACON_ROOTS_HISTORY_BUFFER_LENGTH);
+                let root_index = timestamp_index + U256::from(BEACON_ROOTS_HISTORY_BUFFER_LENGTH);
+                let mut account = Account::from(info);
+                for (key, value) in [
+                    (timestamp_index, timestamp),
+                    (root_index, U256::from_be_bytes(root.0)),
+                ] {
+                    let original = state
+                        .storage(BEACON_ROOTS_ADDRESS, key)
+                        .map_err(EVMError::Database)?;
+                    account
+                        .storage
+                        .insert(key, EvmStorageSlot::new_changed(original, value));
+                }
+                account.mark_touch();
+                state.commit(EvmState::from_iter([(BEACON_ROOTS_ADDRESS, account)]));
+            }
+        }
+        self.beacon_root_applied = true;
+        Ok(())
+    }
+
+    /// Executes the transaction, commits its state and returns its receipt.
+    ///
+    /// Transaction is rejected if its gas limit exceeds the gas left in the block.
+    pub fn execute_transaction(
+        &mut self,
+        tx: TxEnv,
+    ) -> Result<&Receipt, BlockExecutionError<DB::Error>> {
+        self.apply_beacon_root()?;
+        let block_gas_limit = self.evm.block().gas_limit.saturating_to::<u64>();
+        if tx.gas_limit > block_gas_limit.saturating_sub(self.cumulative_gas_used) {
+            return Err(EVMError::Transaction(
+                InvalidTransaction::CallerGasLimitMoreThanBlock,
+            ));
+        }
+
+        let tx_type = tx.tx_type();
+        *self.evm.tx_mut() = tx;
+        let ResultAndState { result, state, .. } = self.evm.transact()?;
+
+        self.cumulative_gas_used += result.gas_used();
+        let logs = result.logs().to_vec();
+        let logs_bloom = logs_bloom(&logs);
+        self.receipts.push(Receipt {
+            tx_type,
+            success: result.is_success(),
+            gas_used: result.gas_used(),
+            cumulative_gas_used: self.cumulative_gas_used,
+            logs,
+            logs_bloom,
+        });
+        let receipt = self.receipts.last().unwrap();
+        if let Some(callback) = &mut self.on_transaction {
+            callback(receipt, &state);
+        }
+        self.evm.db_mut().commit(state);
+        Ok(receipt)
+    }
+
+    /// Executes all transactions, stopping at the first error.
+    pub fn execute_transactions(
+        &mut self,
+        txs: impl IntoIterator<Item = TxEnv>,
+    ) -> Result<(), BlockExecutionError<DB::Error>> {
+        for tx in txs {
+            self.execute_transaction(tx)?;
+        }
+        Ok(())
+    }
+
+    /// Increments the balance of the block beneficiary by the block reward, in wei.
+    ///
+    /// Fails without changing the state if the balance would overflow.
+    pub fn apply_block_reward(
+        &mut self,
+        reward: u128,
+    ) -> Result<(), BlockExecutionError<DB::Error>> {
+        self.apply_beacon_root()?;
+        let coinbase = self.evm.block().coinbase;
+        self.increment_balance(coinbase, reward)
+    }
+
+    /// Applies withdrawals given as recipient and amount in gwei.
+    ///
+    /// Stops at the first withdrawal that would overflow the balance of its recipient.
+    pub fn apply_withdrawals(
+        &mut self,
+        withdrawals: impl IntoIterator<Item = (Address, u64)>,
+    ) -> Result<(), BlockExecutionError<DB::Error>> {
+        self.apply_beacon_root()?;
+        for (address, amount) in withdrawals {
+            self.increment_balance(address, amount as u128 * 1_000_000_000)?;
+        }
+        Ok(())
+    }
+
+    /// Increments the balance of the account, failing if it would overflow.
+    fn increment_balance(
+        &mut self,
+        address: Address,
+        amount: u128,
+    ) -> Result<(), BlockExecutionError<DB::Error>> {
+        let state = self.evm.db_mut();
+        let balance = state
+            .basic(address)
+            .map_err(EVMError::Database)?
+            .unwrap_or_default()
+            .balance;
+        if balance.checked_add(U256::from(amount)).is_none() {
+            return Err(EVMError::Custom(format!(
+                "balance of {address} overflows when incremented by {amount}"
+            )));
+        }
+        state
+            .increment_balances([(address, amount)])
+            .map_err(EVMError::Database)
+    }
+
+    /// Finishes the current block and starts the next one with the given block environment.
+    ///
+    /// Changes of the finished block are merged into the bundle of the state together with
+    /// their reverts. Returns the receipts of the finished block.
+    pub fn next_block(&mut self, block: BlockEnv) -> Vec<Receipt> {
+        self.evm
+            .db_mut()
+            .merge_transitions(BundleRetention::Reverts);
+        *self.evm.block_mut() = block;
+        self.cumulative_gas_used = 0;
+        self.beacon_root_applied = false;
+        core::mem::take(&mut self.receipts)
+    }
+
+    /// Finishes the block and returns receipts and the bundle state.
+    ///
+    /// Reverts are kept in the bundle so the block can be unwound. If previous blocks were
+    /// finished with [`BlockExecutor::next_block`], the bundle contains their changes too.
+    pub fn finish(mut self) -> BlockExecutionOutput {
+        let state = self.evm.db_mut();
+        state.merge_transitions(BundleRetention::Reverts);
+        BlockExecutionOutput {
+            receipts: self.receipts,
+            gas_used: self.cumulative_gas_used,
+            bundle_state: state.take_bundle(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{LOG0, NUMBER, PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::{InMemoryDB, OriginalValuesKnown, RevertToSlot};
+    use primitives::{address, TxKind, B256};
+    use state::AccountInfo;
+
+    #[test]
+    fn executes_block() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("0000000000000000000000000000000000001000");
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+        let withdrawal = address!("00000000000000000000000000000000000000ee");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000_000)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [
+                    PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, LOG0,
+                ]
+                .into(),
+            )),
+        );
+        let state = State::builder().with_database(db).build();
+
+        let evm = Evm::<BlockExecutorWiring<InMemoryDB, ()>>::builder()
+            .with_db(state)
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.coinbase = coinbase;
+                block.gas_limit = U256::from(100_000);
+            })
+            .build();
+        let mut executor = BlockExecutor::new(evm);
+
+        let tx = |nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(contract),
+            gas_limit: 50_000,
+            nonce,
+            ..Default::default()
+        };
+        let receipt = executor.execute_transaction(tx(0)).unwrap().clone();
+        assert!(receipt.success);
+        assert_eq!(receipt.logs.len(), 1);
+        assert!(receipt.logs_bloom.contains_log(&receipt.logs[0]));
+
+        // Second transaction reuses the committed state.
+        let second = executor.execute_transaction(tx(1)).unwrap();
+        assert_eq!(
+            second.cumulative_gas_used,
+            receipt.gas_used + second.gas_used
+        );
+        assert!(second.gas_used < receipt.gas_used);
+
+        // Not enough gas left in the block.
+        assert_eq!(
+            executor.execute_transaction(tx(2)).unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)
+        );
+
+        executor.apply_block_reward(2).unwrap();
+        executor.apply_withdrawals([(withdrawal, 1)]).unwrap();
+
+        let output = executor.finish();
+        assert_eq!(output.receipts.len(), 2);
+        assert_eq!(output.logs_bloom(), receipt.logs_bloom);
+        assert_eq!(
+            output
+                .bundle_state
+                .account(&withdrawal)
+                .unwrap()
+                .info
+                .as_ref()
+                .unwrap()
+                .balance,
+            U256::from(1_000_000_000)
+        );
+        assert_eq!(
+            output
+                .bundle_state
+                .account(&coinbase)
+                .unwrap()
+                .info
+                .as_ref()
+                .unwrap()
+                .balance,
+            U256::from(2)
+        );
+        assert_eq!(
+            output
+                .bundle_state
+                .account(&contract)
+                .unwrap()
+                .storage_slot(U256::ZERO),
+            Some(U256::from(1))
+        );
+    }
+
+    #[test]
+    fn applies_beacon_root_once_per_block() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let root = B256::repeat_byte(0x42);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000_000)));
+        db.insert_account_info(
+            BEACON_ROOTS_ADDRESS,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([STOP].into())),
+        );
+        let block = |timestamp| BlockEnv {
+            timestamp: U256::from(timestamp),
+            gas_limit: U256::from(1_000_000),
+            parent_beacon_block_root: Some(root),
+            ..Default::default()
+        };
+        let tx = |nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(Address::ZERO),
+            gas_limit: 50_000,
+            nonce,
+            ..Default::default()
+        };
+
+        let evm = Evm::<BlockExecutorWiring<InMemoryDB, ()>>::builder()
+            .with_db(State::builder().with_database(db).build())
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::CANCUN)
+            .with_block_env(block(10))
+            .build();
+        let mut states = Vec::new();
+        let mut executor = BlockExecutor::new(evm).on_transaction(|_, state| {
+            states.push(state.contains_key(&BEACON_ROOTS_ADDRESS));
+        });
+        for nonce in 0..2 {
+            // Transactions don't pay for the system call.
+            assert_eq!(
+                executor.execute_transaction(tx(nonce)).unwrap().gas_used,
+                21_000
+            );
+        }
+        executor.next_block(block(20));
+        // Block without transactions.
+        executor.apply_beacon_root().unwrap();
+        executor.apply_beacon_root().unwrap();
+
+        let bundle = executor.finish().bundle_state;
+        let account = bundle.account(&BEACON_ROOTS_ADDRESS).unwrap();
+        for timestamp in [10, 20] {
+            let index = U256::from(timestamp);
+            assert_eq!(account.storage_slot(index), Some(index));
+            assert_eq!(
+                account.storage_slot(index + U256::from(BEACON_ROOTS_HISTORY_BUFFER_LENGTH)),
+                Some(U256::from_be_bytes(root.0))
+            );
+        }
+        assert_eq!(states, [false, false]);
+    }
+
+    #[test]
+    fn streams_receipts() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("0000000000000000000000000000000000001000");
+
+        // Stores the block number in slot 0.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000_000)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([NUMBER, PUSH1, 0x00, SSTORE].into())),
+        );
+        let evm = Evm::<BlockExecutorWiring<InMemoryDB, ()>>::builder()
+            .with_db(State::builder().with_database(db).build())
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.number = U256::from(7);
+                block.gas_limit = U256::from(1_000_000);
+            })
+            .build();
+
+        let mut streamed = Vec::new();
+        let mut executor = BlockExecutor::new(evm).on_transaction(|receipt, state| {
+            let slot = state[&contract].storage[&U256::ZERO].present_value();
+            streamed.push((receipt.cumulative_gas_used, slot));
+        });
+        for nonce in 0..2 {
+            executor
+                .execute_transaction(TxEnv {
+                    caller,
+                    transact_to: TxKind::Call(contract),
+                    gas_limit: 50_000,
+                    nonce,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        let receipts = executor.finish().receipts;
+
+        assert_eq!(
+            streamed,
+            receipts
+                .iter()
+                .map(|receipt| (receipt.cumulative_gas_used, U256::from(7)))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn executes_consecutive_blocks() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let contract = address!("0000000000000000000000000000000000001000");
+
+        // Stores the block number in slot 0.
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000_000)));
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([NUMBER, PUSH1, 0x00, SSTORE].into())),
+        );
+        let block = |number| BlockEnv {
+            number: U256::from(number),
+            gas_limit: U256::from(1_000_000),
+            ..Default::default()
+        };
+        let tx = |nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(contract),
+            gas_limit: 50_000,
+            nonce,
+            ..Default::default()
+        };
+
+        let evm = Evm::<BlockExecutorWiring<InMemoryDB, ()>>::builder()
+            .with_db(State::builder().with_database(db).build())
+            .with_default_ext_ctx()
+            .with_block_env(block(1))
+            .build();
+        let mut executor = BlockExecutor::new(evm);
+        executor.execute_transaction(tx(0)).unwrap();
+        let receipts = executor.next_block(block(2));
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(executor.cumulative_gas_used(), 0);
+        executor.execute_transaction(tx(1)).unwrap();
+
+        let mut bundle = executor.finish().bundle_state;
+        assert_eq!(bundle.reverts.len(), 2);
+        assert_eq!(
+            bundle.account(&contract).unwrap().storage_slot(U256::ZERO),
+            Some(U256::from(2))
+        );
+
+        let (changeset, reverts) = bundle.to_plain_state_and_reverts(OriginalValuesKnown::Yes);
+        let storage = changeset
+            .storage
+            .iter()
+            .find(|storage| storage.address == contract)
+            .unwrap();
+        assert_eq!(storage.storage, [(U256::ZERO, U256::from(2))]);
+        // Slot values before each block.
+        let slot_revert = |block: usize| {
+            reverts.storage[block]
+                .iter()
+                .find(|revert| revert.address == contract)
+                .unwrap()
+                .storage_revert
+                .clone()
+        };
+        assert_eq!(
+            slot_revert(0),
+            [(U256::ZERO, RevertToSlot::Some(U256::ZERO))]
+        );
+        assert_eq!(
+            slot_revert(1),
+            [(U256::ZERO, RevertToSlot::Some(U256::from(1)))]
+        );
+
+        // Unwind the second block.
+        assert!(bundle.revert_latest());
+        assert_eq!(bundle.reverts.len(), 1);
+        assert_eq!(
+            bundle.account(&contract).unwrap().storage_slot(U256::ZERO),
+            Some(U256::from(1))
+        );
+    }
+}
@@ -1,7 +1,12 @@
-use crate::{handler::register, Context, Evm, EvmContext, EvmWiring, Handler};
+use crate::{
+    execution_guard_register, handler::register, ChainSpec, Context, Evm, EvmContext, EvmWiring,
+    ExecutionGuard, Handler,
+};
 use core::marker::PhantomData;
 use database_interface::EmptyDB;
-use std::boxed::Box;
+use precompile::CryptoProvider;
+use specification::hardfork::SpecId;
+use std::{boxed::Box, sync::Arc};
 use transaction::Transaction;
 use wiring::{
     default::{CfgEnv, EnvWiring},
@@ -347,6 +352,18 @@ impl<'a, BuilderStage, EvmWiringT: EvmWiring> EvmBuilder<'a, BuilderStage, EvmWi
         self
     }
 
+    /// Appends a handler register that enforces the limits of the [`ExecutionGuard`].
+    pub fn with_execution_guard(self, guard: ExecutionGuard) -> Self {
+        self.append_handler_register_box(execution_guard_register(guard))
+    }
+
+    /// Appends a handler register that runs the cryptographic precompiles on `crypto`.
+    ///
+    /// See [`register::crypto_provider_register`].
+    pub fn with_crypto_provider(self, crypto: Arc<dyn CryptoProvider>) -> Self {
+        self.append_handler_register_box(register::crypto_provider_register(crypto))
+    }
+
     /// Allows modification of Evm Database.
     pub fn modify_db(mut self, f: impl FnOnce(&mut EvmWiringT::Database)) -> Self {
         f(self.database.as_mut().unwrap());
@@ -461,6 +478,33 @@ where
     }
 }
 
+impl<'a, BuilderStage, EvmWiringT> EvmBuilder<'a, BuilderStage, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<
+        Hardfork = SpecId,
+        Transaction: Transaction<TransactionError: From<InvalidTransaction>>,
+    >,
+{
+    /// Sets the chain settings of the configuration environment and the specification id of
+    /// the chain preset, other settings of the configuration environment are kept, see
+    /// [`ChainSpec::apply_cfg`].
+    ///
+    /// With the `secp256r1` feature, appends `p256_verify_register` if the chain has the
+    /// `P256VERIFY` precompile.
+    ///
+    /// # Note
+    ///
+    /// Same as [`Self::with_spec_id`], this reapplies all handle registers.
+    pub fn with_chain_spec(mut self, chain_spec: &ChainSpec) -> Self {
+        chain_spec.apply_cfg(&mut self.env.as_mut().unwrap().cfg);
+        #[cfg(feature = "secp256r1")]
+        if chain_spec.p256_verify {
+            self = self.append_handler_register(register::p256_verify_register);
+        }
+        self.with_spec_id(chain_spec.spec_id)
+    }
+}
+
 #[cfg(test)]
 mod test {
     extern crate alloc;
@@ -665,6 +709,7 @@ mod test {
     //             &self,
     //             _input: &Bytes,
     //             _gas_limit: u64,
+    //             _is_static: bool,
     //             _context: &mut InnerEvmContext<TestEvmWiring>,
     //         ) -> PrecompileResult {
     //             Ok(PrecompileOutput::new(10, Bytes::new()))
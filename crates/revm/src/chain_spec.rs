@@ -0,0 +1,200 @@
+use precompile::{PrecompileSpecId, Precompiles};
+use specification::hardfork::SpecId;
+use wiring::default::CfgEnv;
+
+/// Configuration preset of a known chain.
+///
+/// Bundles the chain id, the fork and the [`CfgEnv`] settings that otherwise need to be
+/// assembled by hand. Presets use the latest fork of the chain that is activated and
+/// supported by this version of revm. Use [`EvmBuilder::with_chain_spec`] to apply a preset.
+///
+/// [`EvmBuilder::with_chain_spec`]: crate::EvmBuilder::with_chain_spec
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChainSpec {
+    /// Name of the chain.
+    pub name: &'static str,
+    /// Chain id, see EIP-155.
+    pub chain_id: u64,
+    /// Ethereum fork whose rules the chain follows.
+    pub spec_id: SpecId,
+    /// Whether the chain is an OP stack chain.
+    ///
+    /// OP stack chains need the wiring and handler of the `revm-optimism` crate to charge
+    /// the L1 data fee and for the OP specific precompile changes, [`Self::spec_id`] and
+    /// [`Self::p256_verify`] only cover the Ethereum rules and precompiles they inherit.
+    pub optimism: bool,
+    /// Whether the transaction gas limit is allowed to exceed the block gas limit.
+    ///
+    /// Only takes effect with the `optional_block_gas_limit` feature.
+    pub disable_block_gas_limit: bool,
+    /// Whether the chain has the `P256VERIFY` precompile of RIP-7212, OP stack chains
+    /// activated it with Fjord.
+    ///
+    /// Only takes effect with the `secp256r1` feature.
+    pub p256_verify: bool,
+}
+
+impl ChainSpec {
+    /// Ethereum mainnet.
+    pub const MAINNET: Self = Self {
+        name: "mainnet",
+        chain_id: 1,
+        spec_id: SpecId::PRAGUE,
+        optimism: false,
+        disable_block_gas_limit: false,
+        p256_verify: false,
+    };
+
+    /// Sepolia testnet.
+    pub const SEPOLIA: Self = Self {
+        name: "sepolia",
+        chain_id: 11155111,
+        spec_id: SpecId::PRAGUE,
+        optimism: false,
+        disable_block_gas_limit: false,
+        p256_verify: false,
+    };
+
+    /// OP mainnet.
+    pub const OP_MAINNET: Self = Self {
+        name: "optimism",
+        chain_id: 10,
+        spec_id: SpecId::PRAGUE,
+        optimism: true,
+        disable_block_gas_limit: false,
+        p256_verify: true,
+    };
+
+    /// Base mainnet.
+    pub const BASE: Self = Self {
+        name: "base",
+        chain_id: 8453,
+        spec_id: SpecId::PRAGUE,
+        optimism: true,
+        disable_block_gas_limit: false,
+        p256_verify: true,
+    };
+
+    /// Arbitrum One in compatibility mode.
+    ///
+    /// Executes with Ethereum gas rules: the L1 calldata component of the Arbitrum gas price
+    /// is not charged and, as ArbOS enforces its own limits, the block gas limit is not checked.
+    pub const ARBITRUM_COMPAT: Self = Self {
+        name: "arbitrum",
+        chain_id: 42161,
+        spec_id: SpecId::PRAGUE,
+        optimism: false,
+        disable_block_gas_limit: true,
+        p256_verify: false,
+    };
+
+    /// All presets.
+    pub const ALL: &'static [Self] = &[
+        Self::MAINNET,
+        Self::SEPOLIA,
+        Self::OP_MAINNET,
+        Self::BASE,
+        Self::ARBITRUM_COMPAT,
+    ];
+
+    /// Returns the preset with the given chain id.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|spec| spec.chain_id == chain_id)
+            .copied()
+    }
+
+    /// Returns the configuration environment of the chain.
+    pub fn cfg_env(&self) -> CfgEnv {
+        let mut cfg = CfgEnv::default();
+        self.apply_cfg(&mut cfg);
+        cfg
+    }
+
+    /// Sets the chain settings of the configuration environment, other settings are kept.
+    pub fn apply_cfg(&self, cfg: &mut CfgEnv) {
+        cfg.chain_id = self.chain_id;
+        #[cfg(feature = "optional_block_gas_limit")]
+        {
+            cfg.disable_block_gas_limit = self.disable_block_gas_limit;
+        }
+    }
+
+    /// Returns the precompiles of [`Self::spec_id`] and the `P256VERIFY` precompile if the
+    /// chain has it.
+    pub fn precompiles(&self) -> Precompiles {
+        #[allow(unused_mut)]
+        let mut precompiles =
+            Precompiles::new(PrecompileSpecId::from_spec_id(self.spec_id)).clone();
+        #[cfg(feature = "secp256r1")]
+        if self.p256_verify {
+            precompiles.extend([precompile::secp256r1::P256VERIFY]);
+        }
+        precompiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use database_interface::EmptyDB;
+    use wiring::EthereumWiring;
+
+    #[test]
+    fn from_chain_id() {
+        assert_eq!(ChainSpec::from_chain_id(1), Some(ChainSpec::MAINNET));
+        assert_eq!(ChainSpec::from_chain_id(8453), Some(ChainSpec::BASE));
+        assert_eq!(ChainSpec::from_chain_id(0), None);
+    }
+
+    #[test]
+    fn cfg_env() {
+        for spec in ChainSpec::ALL {
+            assert_eq!(spec.cfg_env().chain_id, spec.chain_id);
+        }
+        #[cfg(feature = "optional_block_gas_limit")]
+        assert!(ChainSpec::ARBITRUM_COMPAT
+            .cfg_env()
+            .is_block_gas_limit_disabled());
+        assert!(!ChainSpec::MAINNET.cfg_env().is_block_gas_limit_disabled());
+    }
+
+    #[test]
+    fn with_chain_spec() {
+        let evm = Evm::<EthereumWiring<EmptyDB, ()>>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::FRONTIER)
+            .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(1))
+            .with_chain_spec(&ChainSpec::SEPOLIA)
+            .build();
+        assert_eq!(evm.spec_id(), SpecId::PRAGUE);
+        assert_eq!(evm.context.evm.env.cfg.chain_id, 11155111);
+        // Other settings are kept.
+        assert_eq!(evm.context.evm.env.cfg.limit_contract_code_size, Some(1));
+    }
+
+    #[cfg(feature = "secp256r1")]
+    #[test]
+    fn p256_verify() {
+        let address = precompile::secp256r1::P256VERIFY.0;
+        assert!(ChainSpec::BASE.precompiles().contains(&address));
+        assert!(!ChainSpec::MAINNET.precompiles().contains(&address));
+
+        for (chain_spec, expected) in [(ChainSpec::OP_MAINNET, true), (ChainSpec::MAINNET, false)] {
+            let mut evm = Evm::<EthereumWiring<EmptyDB, ()>>::builder()
+                .with_default_db()
+                .with_default_ext_ctx()
+                .with_chain_spec(&chain_spec)
+                .build();
+            let precompiles = evm.handler.pre_execution.load_precompiles();
+            assert_eq!(precompiles.contains(&address), expected);
+            // The register follows spec changes.
+            evm.modify_spec_id(SpecId::CANCUN);
+            let precompiles = evm.handler.pre_execution.load_precompiles();
+            assert_eq!(precompiles.contains(&address), expected);
+        }
+    }
+}
@@ -1,7 +1,9 @@
+mod address_scheme;
 mod context_precompiles;
 pub(crate) mod evm_context;
 mod inner_evm_context;
 
+pub use address_scheme::AddressScheme;
 pub use context_precompiles::{
     ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile, ContextStatefulPrecompileArc,
     ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
@@ -10,14 +12,18 @@ use derive_where::derive_where;
 pub use evm_context::EvmContext;
 pub use inner_evm_context::InnerEvmContext;
 
-use crate::EvmWiring;
+use crate::{EvmWiring, JournalError};
+use bytecode::{Bytecode, EOF_MAGIC_BYTES, EOF_MAGIC_HASH};
 use database_interface::{Database, EmptyDB};
 use interpreter::{
-    as_u64_saturated, AccountLoad, Eip7702CodeLoad, Host, SStoreResult, SelfDestructResult,
-    StateLoad,
+    as_u64_saturated, gas::GasTable, AccountLoad, BlockEnvOverride, Eip7702CodeLoad, HostRead,
+    HostWrite, InstructionResult, SStoreResult, SelfDestructResult, StateLoad,
 };
-use primitives::{Address, Bytes, Log, B256, BLOCK_HASH_HISTORY, U256};
-use std::boxed::Box;
+use primitives::{Address, Bytes, Log, B256, BLOCK_HASH_HISTORY, KECCAK_EMPTY, U256};
+use state::{Account, AccountStatus};
+#[cfg(feature = "tx-arena")]
+use std::vec::Vec;
+use std::{borrow::Cow, boxed::Box};
 use wiring::{default::EnvWiring, Block, EthereumWiring};
 
 /// Main Context structure that contains both EvmContext and External context.
@@ -78,7 +84,9 @@ impl<EvmWiringT: EvmWiring> ContextWithEvmWiring<EvmWiringT> {
     }
 }
 
-impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
+/// Reads only see the state already loaded in the transaction, as the database can only be read
+/// mutably. Everything else returns `None`.
+impl<EvmWiringT: EvmWiring> HostRead for Context<EvmWiringT> {
     type EvmWiringT = EvmWiringT;
 
     /// Returns reference to Environment.
@@ -87,33 +95,111 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
         &self.evm.env
     }
 
-    fn env_mut(&mut self) -> &mut EnvWiring<EvmWiringT> {
-        &mut self.evm.env
+    fn account_delegated(&self, address: Address) -> Option<AccountLoad> {
+        let account = self.loaded_account(address)?;
+        let code = loaded_code(account.data)?;
+        let mut account_load = AccountLoad {
+            is_empty: account.state_clear_aware_is_empty(self.evm.journaled_state.spec),
+            load: Eip7702CodeLoad::new_not_delegated((), account.is_cold),
+        };
+        if let Bytecode::Eip7702(code) = &*code {
+            let delegated = self.loaded_account(code.address())?;
+            account_load.load.set_delegate_load(delegated.is_cold);
+        }
+        Some(account_load)
     }
 
-    fn block_hash(&mut self, requested_number: u64) -> Option<B256> {
-        let block_number = as_u64_saturated!(*self.env().block.number());
+    fn block_hash(&self, requested_number: u64) -> Option<B256> {
+        let block_number = as_u64_saturated!(*self.evm.env.block.number());
+        match block_number.checked_sub(requested_number) {
+            Some(diff) if diff != 0 && diff <= BLOCK_HASH_HISTORY => None,
+            _ => Some(B256::ZERO),
+        }
+    }
 
-        let Some(diff) = block_number.checked_sub(requested_number) else {
-            return Some(B256::ZERO);
+    fn balance(&self, address: Address) -> Option<StateLoad<U256>> {
+        self.loaded_account(address)
+            .map(|account| account.map(|account| account.info.balance))
+    }
+
+    fn code(&self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+        let account = self.loaded_account(address)?;
+        let code = loaded_code(account.data)?;
+        let bytes = |code: &Bytecode| {
+            if code.is_eof() {
+                EOF_MAGIC_BYTES.clone()
+            } else {
+                code.original_bytes()
+            }
         };
+        if let Bytecode::Eip7702(code) = &*code {
+            let delegated = self.loaded_account(code.address())?;
+            let delegated_code = loaded_code(delegated.data)?;
+            return Some(Eip7702CodeLoad::new(
+                StateLoad::new(bytes(&delegated_code), account.is_cold),
+                delegated.is_cold,
+            ));
+        }
+        Some(Eip7702CodeLoad::new_not_delegated(
+            bytes(&code),
+            account.is_cold,
+        ))
+    }
 
-        // blockhash should push zero if number is same as current block number.
-        if diff == 0 {
-            return Some(B256::ZERO);
+    fn code_hash(&self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+        let account = self.loaded_account(address)?;
+        let code = loaded_code(account.data)?;
+        let hash = |account: &Account, code: &Bytecode| {
+            if account.is_empty() {
+                B256::ZERO
+            } else if code.is_eof() {
+                EOF_MAGIC_HASH
+            } else {
+                account.info.code_hash
+            }
+        };
+        if let Bytecode::Eip7702(code) = &*code {
+            let delegated = self.loaded_account(code.address())?;
+            let delegated_code = loaded_code(delegated.data)?;
+            return Some(Eip7702CodeLoad::new(
+                StateLoad::new(hash(delegated.data, &delegated_code), account.is_cold),
+                delegated.is_cold,
+            ));
         }
+        Some(Eip7702CodeLoad::new_not_delegated(
+            hash(account.data, &code),
+            account.is_cold,
+        ))
+    }
 
-        if diff <= BLOCK_HASH_HISTORY {
-            return self
-                .evm
-                .block_hash(requested_number)
-                .map_err(|e| self.evm.error = Err(e))
-                .ok();
+    fn sload(&self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+        let account = self.loaded_account(address)?.data;
+        match account.storage.get(&index) {
+            Some(slot) => Some(StateLoad::new(slot.present_value, slot.is_cold)),
+            None if account.is_created() => Some(StateLoad::new(U256::ZERO, true)),
+            None => None,
         }
+    }
 
-        Some(B256::ZERO)
+    fn tload(&self, address: Address, index: U256) -> U256 {
+        self.evm
+            .journaled_state
+            .transient_storage
+            .get(&(address, index))
+            .copied()
+            .unwrap_or_default()
     }
 
+    fn gas_table(&self) -> Option<&GasTable> {
+        self.evm.gas_table.as_ref()
+    }
+
+    fn block_env_override(&self) -> Option<&BlockEnvOverride> {
+        self.evm.block_env_override.as_ref()
+    }
+}
+
+impl<EvmWiringT: EvmWiring> HostWrite for Context<EvmWiringT> {
     fn load_account_delegated(&mut self, address: Address) -> Option<AccountLoad> {
         self.evm
             .load_account_delegated(address)
@@ -121,28 +207,35 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
             .ok()
     }
 
-    fn balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
+    fn load_block_hash(&mut self, requested_number: u64) -> Option<B256> {
+        self.evm
+            .recent_block_hash(requested_number)
+            .map_err(|e| self.evm.error = Err(e))
+            .ok()
+    }
+
+    fn load_balance(&mut self, address: Address) -> Option<StateLoad<U256>> {
         self.evm
             .balance(address)
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
 
-    fn code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
+    fn load_code(&mut self, address: Address) -> Option<Eip7702CodeLoad<Bytes>> {
         self.evm
             .code(address)
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
 
-    fn code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
+    fn load_code_hash(&mut self, address: Address) -> Option<Eip7702CodeLoad<B256>> {
         self.evm
             .code_hash(address)
             .map_err(|e| self.evm.error = Err(e))
             .ok()
     }
 
-    fn sload(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
+    fn load_storage(&mut self, address: Address, index: U256) -> Option<StateLoad<U256>> {
         self.evm
             .sload(address, index)
             .map_err(|e| self.evm.error = Err(e))
@@ -154,35 +247,132 @@ impl<EvmWiringT: EvmWiring> Host for Context<EvmWiringT> {
         address: Address,
         index: U256,
         value: U256,
-    ) -> Option<StateLoad<SStoreResult>> {
+    ) -> Result<StateLoad<SStoreResult>, InstructionResult> {
         self.evm
             .sstore(address, index, value)
-            .map_err(|e| self.evm.error = Err(e))
-            .ok()
+            .map_err(|e| self.journal_error(e))
+    }
+
+    fn tstore(
+        &mut self,
+        address: Address,
+        index: U256,
+        value: U256,
+    ) -> Result<(), InstructionResult> {
+        self.evm
+            .tstore(address, index, value)
+            .map_err(|_| InstructionResult::StateChangeDuringStaticCall)
     }
 
-    fn tload(&mut self, address: Address, index: U256) -> U256 {
-        self.evm.tload(address, index)
+    fn log(&mut self, log: Log) -> Result<(), InstructionResult> {
+        self.evm
+            .journaled_state
+            .log(log)
+            .map_err(|_| InstructionResult::StateChangeDuringStaticCall)
     }
 
-    fn tstore(&mut self, address: Address, index: U256, value: U256) {
-        self.evm.tstore(address, index, value)
+    #[cfg(feature = "tx-arena")]
+    fn log_topics_buffer(&mut self) -> Vec<B256> {
+        self.evm.journaled_state.arena.take_topics()
     }
 
-    fn log(&mut self, log: Log) {
-        self.evm.journaled_state.log(log);
+    #[cfg(feature = "tx-arena")]
+    fn log_data_buffer(&mut self) -> Vec<u8> {
+        self.evm.journaled_state.arena.take_data()
     }
 
     fn selfdestruct(
         &mut self,
         address: Address,
         target: Address,
-    ) -> Option<StateLoad<SelfDestructResult>> {
+    ) -> Result<StateLoad<SelfDestructResult>, InstructionResult> {
         self.evm
             .inner
             .journaled_state
             .selfdestruct(address, target, &mut self.evm.inner.db)
-            .map_err(|e| self.evm.error = Err(e))
-            .ok()
+            .map_err(|e| self.journal_error(e))
+    }
+}
+
+/// Returns the code of a loaded account, `None` if it was not loaded yet.
+fn loaded_code(account: &Account) -> Option<Cow<'_, Bytecode>> {
+    match &account.info.code {
+        Some(code) => Some(Cow::Borrowed(code)),
+        None if account.info.code_hash == KECCAK_EMPTY => Some(Cow::Owned(Bytecode::default())),
+        None => None,
+    }
+}
+
+impl<EvmWiringT: EvmWiring> Context<EvmWiringT> {
+    /// Returns the account if it is loaded in the journal, and if it is cold.
+    fn loaded_account(&self, address: Address) -> Option<StateLoad<&Account>> {
+        let account = self.evm.journaled_state.state.get(&address)?;
+        Some(StateLoad::new(
+            account,
+            account.status.contains(AccountStatus::Cold),
+        ))
+    }
+
+    /// Converts the error of a state change to the result of the instruction, a database error
+    /// is kept in the context.
+    fn journal_error(
+        &mut self,
+        error: JournalError<<EvmWiringT::Database as Database>::Error>,
+    ) -> InstructionResult {
+        match error {
+            JournalError::StateChangeDuringStaticCall => {
+                InstructionResult::StateChangeDuringStaticCall
+            }
+            // Only returned when reverting to a snapshot.
+            JournalError::InvalidSnapshot => InstructionResult::FatalExternalError,
+            JournalError::Database(e) => {
+                self.evm.error = Err(e);
+                InstructionResult::FatalExternalError
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::address;
+
+    #[test]
+    fn host_read_sees_loaded_state() {
+        let mut context = Context::default();
+        let address = address!("0000000000000000000000000000000000000001");
+
+        assert_eq!(context.balance(address), None);
+        assert_eq!(context.sload(address, U256::from(1)), None);
+        assert!(context
+            .evm
+            .journaled_state
+            .journal
+            .last()
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            context.load_balance(address),
+            Some(StateLoad::new(U256::ZERO, true))
+        );
+        assert_eq!(
+            context.load_storage(address, U256::from(1)),
+            Some(StateLoad::new(U256::ZERO, true))
+        );
+        // Loaded state is now warm.
+        assert_eq!(
+            context.balance(address),
+            Some(StateLoad::new(U256::ZERO, false))
+        );
+        assert_eq!(
+            context.sload(address, U256::from(1)),
+            Some(StateLoad::new(U256::ZERO, false))
+        );
+        assert_eq!(
+            context.code_hash(address).map(|load| load.state_load.data),
+            Some(B256::ZERO)
+        );
     }
 }
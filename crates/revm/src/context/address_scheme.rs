@@ -0,0 +1,31 @@
+use primitives::{create2_address, create_address, Address, B256};
+
+/// Derivation of the addresses of created contracts.
+///
+/// Loaded for every transaction by the `load_address_scheme` handle of the pre execution, so
+/// chains that derive addresses differently, like some zk-rollups, can replace it with
+/// [`address_scheme_register`](crate::handler::register::address_scheme_register).
+/// Defaults to the keccak-based derivation of Ethereum.
+#[derive(Clone, Copy, Debug)]
+pub struct AddressScheme {
+    /// Address of a contract created with `CREATE` or by a create transaction, from the caller
+    /// and its nonce.
+    pub create: fn(caller: Address, nonce: u64) -> Address,
+    /// Address of a contract created with `CREATE2`, from the caller, the salt and the hash of
+    /// the init code.
+    pub create2: fn(caller: Address, salt: B256, init_code_hash: B256) -> Address,
+}
+
+impl AddressScheme {
+    /// Address derivation of Ethereum, see [`create_address`] and [`create2_address`].
+    pub const ETHEREUM: Self = Self {
+        create: create_address,
+        create2: create2_address,
+    };
+}
+
+impl Default for AddressScheme {
+    fn default() -> Self {
+        Self::ETHEREUM
+    }
+}
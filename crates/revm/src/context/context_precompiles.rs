@@ -102,6 +102,9 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
 
     /// Call precompile and executes it. Returns the result of the precompile execution.
     ///
+    /// `is_static` is passed to context precompiles, which must not change the state if it is
+    /// set.
+    ///
     /// Returns `None` if the precompile does not exist.
     #[inline]
     pub fn call(
@@ -109,6 +112,7 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
         address: &Address,
         bytes: &Bytes,
         gas_limit: u64,
+        is_static: bool,
         evmctx: &mut InnerEvmContext<EvmWiringT>,
     ) -> Option<PrecompileResult> {
         Some(match self.inner {
@@ -117,8 +121,12 @@ impl<EvmWiringT: EvmWiring> ContextPrecompiles<EvmWiringT> {
             }
             PrecompilesCow::Owned(ref mut owned) => match owned.get_mut(address)? {
                 ContextPrecompile::Ordinary(p) => p.call(bytes, gas_limit, &evmctx.env.cfg),
-                ContextPrecompile::ContextStateful(p) => p.call(bytes, gas_limit, evmctx),
-                ContextPrecompile::ContextStatefulMut(p) => p.call_mut(bytes, gas_limit, evmctx),
+                ContextPrecompile::ContextStateful(p) => {
+                    p.call(bytes, gas_limit, is_static, evmctx)
+                }
+                ContextPrecompile::ContextStatefulMut(p) => {
+                    p.call_mut(bytes, gas_limit, is_static, evmctx)
+                }
             },
         })
     }
@@ -183,22 +191,30 @@ impl<EvmWiringT: EvmWiring> Default for PrecompilesCow<EvmWiringT> {
 
 /// Context aware stateful precompile trait. It is used to create
 /// a arc precompile in [`ContextPrecompile`].
+///
+/// If `is_static` is set the precompile is called inside a static frame and must not change
+/// the state. Changes are reverted and the call fails with
+/// [`InstructionResult::StateChangeDuringStaticCall`](interpreter::InstructionResult::StateChangeDuringStaticCall).
 pub trait ContextStatefulPrecompile<EvmWiringT: EvmWiring>: Sync + Send {
     fn call(
         &self,
         bytes: &Bytes,
         gas_limit: u64,
+        is_static: bool,
         evmctx: &mut InnerEvmContext<EvmWiringT>,
     ) -> PrecompileResult;
 }
 
 /// Context aware mutable stateful precompile trait. It is used to create
 /// a boxed precompile in [`ContextPrecompile`].
+///
+/// See [`ContextStatefulPrecompile`] for the meaning of `is_static`.
 pub trait ContextStatefulPrecompileMut<EvmWiringT: EvmWiring>: DynClone + Send + Sync {
     fn call_mut(
         &mut self,
         bytes: &Bytes,
         gas_limit: u64,
+        is_static: bool,
         evmctx: &mut InnerEvmContext<EvmWiringT>,
     ) -> PrecompileResult;
 }
@@ -221,6 +237,9 @@ impl<EvmWiringT: EvmWiring> From<Precompile> for ContextPrecompile<EvmWiringT> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use database_interface::EmptyDB;
+    use precompile::PrecompileOutput;
+    use primitives::{keccak256, B256, U256};
     use wiring::DefaultEthereumWiring;
 
     #[test]
@@ -239,4 +258,51 @@ mod tests {
         assert!(matches!(precompiles.inner, PrecompilesCow::Owned(_)));
         assert!(precompiles.contains(&custom_address));
     }
+
+    #[test]
+    fn test_precompile_block_access() {
+        /// Returns the current block number and the hash of the requested block.
+        struct BlockOracle;
+
+        impl ContextStatefulPrecompile<DefaultEthereumWiring> for BlockOracle {
+            fn call(
+                &self,
+                bytes: &Bytes,
+                _gas_limit: u64,
+                _is_static: bool,
+                evmctx: &mut InnerEvmContext<DefaultEthereumWiring>,
+            ) -> PrecompileResult {
+                let number = u64::from_be_bytes(bytes[..8].try_into().unwrap());
+                let hash = evmctx.recent_block_hash(number).unwrap();
+                let mut output = evmctx.block().number.to_be_bytes_vec();
+                output.extend_from_slice(hash.as_slice());
+                Ok(PrecompileOutput::new(0, output.into()))
+            }
+        }
+
+        let address = Address::with_last_byte(0xff);
+        let mut precompiles = ContextPrecompiles::<DefaultEthereumWiring>::default();
+        precompiles.extend([(
+            address,
+            ContextPrecompile::ContextStateful(Arc::new(BlockOracle)),
+        )]);
+        let mut evmctx = InnerEvmContext::<DefaultEthereumWiring>::new(EmptyDB::default());
+        evmctx.env.block.number = U256::from(300);
+
+        let mut call = |number: u64| {
+            let input = Bytes::copy_from_slice(&number.to_be_bytes());
+            let output = precompiles
+                .call(&address, &input, 0, false, &mut evmctx)
+                .unwrap()
+                .unwrap()
+                .bytes;
+            assert_eq!(U256::from_be_slice(&output[..32]), U256::from(300));
+            B256::from_slice(&output[32..])
+        };
+        assert_eq!(call(299), keccak256(b"299"));
+        assert_eq!(call(44), keccak256(b"44"));
+        // Outside of the window of the `BLOCKHASH` opcode.
+        assert_eq!(call(43), B256::ZERO);
+        assert_eq!(call(300), B256::ZERO);
+    }
 }
@@ -1,20 +1,24 @@
-use super::inner_evm_context::InnerEvmContext;
-use crate::{ContextPrecompiles, EvmWiring, FrameOrResult, CALL_STACK_LIMIT};
+use super::{inner_evm_context::InnerEvmContext, AddressScheme};
+use crate::{
+    handler::{mainnet, TransferHandle},
+    ContextPrecompiles, EvmWiring, FrameOrResult, CALL_STACK_LIMIT,
+};
 use bytecode::{Bytecode, Eof, EOF_MAGIC_BYTES};
 use core::ops::{Deref, DerefMut};
 use database_interface::Database;
 use derive_where::derive_where;
 use interpreter::CallValue;
 use interpreter::{
-    return_ok, CallInputs, Contract, CreateInputs, EOFCreateInputs, EOFCreateKind, Gas,
-    InstructionResult, Interpreter, InterpreterResult,
+    gas::GasTable, return_ok, BlockEnvOverride, CallInputs, Contract, CreateInputs,
+    EOFCreateInputs, EOFCreateKind, Gas, InstructionResult, Interpreter, InterpreterResult,
+    SharedMemory,
 };
 use precompile::PrecompileErrors;
-use primitives::{keccak256, Address, Bytes, B256};
+use primitives::{keccak256, Address, Bytes, B256, U256};
 use specification::hardfork::SpecId::{self, *};
 use std::{boxed::Box, sync::Arc};
 use wiring::{
-    default::{CreateScheme, EnvWiring},
+    default::{AnalysisKind, CreateScheme, EnvWiring},
     result::{EVMError, EVMResultGeneric},
     Transaction,
 };
@@ -26,6 +30,14 @@ pub struct EvmContext<EvmWiringT: EvmWiring> {
     pub inner: InnerEvmContext<EvmWiringT>,
     /// Precompiles that are available for evm.
     pub precompiles: ContextPrecompiles<EvmWiringT>,
+    /// Gas table that overrides the storage pricing of the spec.
+    pub gas_table: Option<GasTable>,
+    /// Derivation of the addresses of created contracts.
+    pub address_scheme: AddressScheme,
+    /// Values returned by block information opcodes instead of the environment.
+    pub block_env_override: Option<BlockEnvOverride>,
+    /// Value transfer of calls and creates, loaded from the execution handler.
+    pub transfer: TransferHandle<EvmWiringT>,
 }
 
 impl<EvmWiringT: EvmWiring> Deref for EvmContext<EvmWiringT> {
@@ -51,6 +63,10 @@ where
         Self {
             inner: InnerEvmContext::new(db),
             precompiles: ContextPrecompiles::default(),
+            gas_table: None,
+            address_scheme: AddressScheme::default(),
+            block_env_override: None,
+            transfer: mainnet::transfer,
         }
     }
 }
@@ -65,6 +81,10 @@ where
         Self {
             inner: InnerEvmContext::new_with_env(db, env),
             precompiles: ContextPrecompiles::default(),
+            gas_table: None,
+            address_scheme: AddressScheme::default(),
+            block_env_override: None,
+            transfer: mainnet::transfer,
         }
     }
 
@@ -81,6 +101,10 @@ where
         EvmContext {
             inner: self.inner.with_db(db),
             precompiles: ContextPrecompiles::default(),
+            gas_table: None,
+            address_scheme: AddressScheme::default(),
+            block_env_override: None,
+            transfer: mainnet::transfer,
         }
     }
 
@@ -101,10 +125,11 @@ where
         address: &Address,
         input_data: &Bytes,
         gas: Gas,
+        is_static: bool,
     ) -> EVMResultGeneric<Option<InterpreterResult>, EvmWiringT> {
         let Some(outcome) =
             self.precompiles
-                .call(address, input_data, gas.limit(), &mut self.inner)
+                .call(address, input_data, gas.limit(), is_static, &mut self.inner)
         else {
             return Ok(None);
         };
@@ -137,10 +162,13 @@ where
     }
 
     /// Make call frame
+    ///
+    /// Call data that references the shared memory is copied only if the call is executed.
     #[inline]
     pub fn make_call_frame(
         &mut self,
         inputs: &CallInputs,
+        shared_memory: &SharedMemory,
     ) -> EVMResultGeneric<FrameOrResult, EvmWiringT> {
         let gas = Gas::new(inputs.gas_limit);
 
@@ -169,6 +197,9 @@ where
 
         // Create subroutine checkpoint
         let checkpoint = self.journaled_state.checkpoint();
+        if inputs.is_static {
+            self.journaled_state.enter_static_context();
+        }
 
         // Touch address. For "EIP-158 State Clear", this will erase empty accounts.
         match inputs.value {
@@ -181,16 +212,9 @@ where
             CallValue::Transfer(value) => {
                 // Transfer value from caller to called account. As value get transferred
                 // target gets touched.
-                if let Some(result) = self
-                    .inner
-                    .journaled_state
-                    .transfer(
-                        &inputs.caller,
-                        &inputs.target_address,
-                        value,
-                        &mut self.inner.db,
-                    )
-                    .map_err(EVMError::Database)?
+                if let Some(result) =
+                    (self.transfer)(&mut self.inner, inputs.caller, inputs.target_address, value)
+                        .map_err(EVMError::Database)?
                 {
                     self.journaled_state.checkpoint_revert(checkpoint);
                     return return_result(result);
@@ -199,7 +223,27 @@ where
             _ => {}
         };
 
-        if let Some(result) = self.call_precompile(&inputs.bytecode_address, &inputs.input, gas)? {
+        // Call data is copied out of the shared memory only for precompiles. The caller already
+        // paid for the memory it spans, which covers the copy.
+        let precompile_result = if self.precompiles.contains(&inputs.bytecode_address) {
+            self.call_precompile(
+                &inputs.bytecode_address,
+                &inputs.input.bytes(shared_memory),
+                gas,
+                inputs.is_static,
+            )?
+        } else {
+            None
+        };
+        if let Some(mut result) = precompile_result {
+            // Context precompiles have access to the journal and could change the state
+            // in a static call.
+            if self.journaled_state.is_static_context()
+                && self.journaled_state.has_state_changes_since(&checkpoint)
+            {
+                result.result = InstructionResult::StateChangeDuringStaticCall;
+                result.output = Bytes::new();
+            }
             if matches!(result.result, return_ok!()) {
                 self.journaled_state.checkpoint_commit();
             } else {
@@ -210,11 +254,15 @@ where
                 inputs.return_memory_offset.clone(),
             ))
         } else {
-            let account = self
+            let analysis = self.inner.env.cfg.perf_analyse_created_bytecodes;
+            let mut account = self
                 .inner
                 .journaled_state
                 .load_code(inputs.bytecode_address, &mut self.inner.db)
                 .map_err(EVMError::Database)?;
+            if analysis == AnalysisKind::Check {
+                analyse_in_place(&mut account.info.code);
+            }
 
             let code_hash = account.info.code_hash();
             let mut bytecode = account.info.code.clone().unwrap_or_default();
@@ -232,19 +280,23 @@ where
             }
 
             if let Bytecode::Eip7702(eip7702_bytecode) = bytecode {
-                bytecode = self
+                let mut delegated = self
                     .inner
                     .journaled_state
                     .load_code(eip7702_bytecode.delegated_address, &mut self.inner.db)
-                    .map_err(EVMError::Database)?
-                    .info
-                    .code
-                    .clone()
-                    .unwrap_or_default();
+                    .map_err(EVMError::Database)?;
+                if analysis == AnalysisKind::Check {
+                    analyse_in_place(&mut delegated.info.code);
+                }
+                bytecode = delegated.info.code.clone().unwrap_or_default();
             }
 
-            let contract =
-                Contract::new_with_context(inputs.input.clone(), bytecode, Some(code_hash), inputs);
+            let contract = Contract::new_with_context(
+                inputs.input.bytes(shared_memory),
+                bytecode,
+                Some(code_hash),
+                inputs,
+            );
             // Create interpreter and executes call and push new CallStackFrame.
             Ok(FrameOrResult::new_call_frame(
                 inputs.return_memory_offset.clone(),
@@ -272,6 +324,11 @@ where
             ))
         };
 
+        // Creating a contract changes the state, the creating frame halts in the static context.
+        if self.journaled_state.is_static_context() {
+            return return_error(InstructionResult::StateChangeDuringStaticCall);
+        }
+
         // Check depth
         if self.journaled_state.depth() > CALL_STACK_LIMIT {
             return return_error(InstructionResult::CallTooDeep);
@@ -290,21 +347,19 @@ where
             return return_error(InstructionResult::OutOfFunds);
         }
 
-        // Increase nonce of caller and check if it overflows
-        let old_nonce;
-        if let Some(nonce) = self.journaled_state.inc_nonce(inputs.caller) {
-            old_nonce = nonce - 1;
-        } else {
-            return return_error(InstructionResult::Return);
-        }
+        // Increase nonce of caller and check if it overflows. See EIP-2681.
+        let Some(nonce) = self.journaled_state.inc_nonce(inputs.caller) else {
+            return return_error(InstructionResult::NonceOverflow);
+        };
+        let old_nonce = nonce - 1;
 
         // Create address
         let mut init_code_hash = B256::ZERO;
         let created_address = match inputs.scheme {
-            CreateScheme::Create => inputs.caller.create(old_nonce),
+            CreateScheme::Create => (self.address_scheme.create)(inputs.caller, old_nonce),
             CreateScheme::Create2 { salt } => {
                 init_code_hash = keccak256(&inputs.init_code);
-                inputs.caller.create2(salt.to_be_bytes(), init_code_hash)
+                (self.address_scheme.create2)(inputs.caller, salt.into(), init_code_hash)
             }
         };
 
@@ -316,11 +371,11 @@ where
         // warm load account.
         self.load_account(created_address)?;
 
-        // create account, transfer funds and make the journal checkpoint.
+        // create account and make the journal checkpoint.
         let checkpoint = match self.journaled_state.create_account_checkpoint(
             inputs.caller,
             created_address,
-            inputs.value,
+            U256::ZERO,
             spec_id,
         ) {
             Ok(checkpoint) => checkpoint,
@@ -329,6 +384,19 @@ where
             }
         };
 
+        // transfer funds to the created account.
+        if !inputs.value.is_zero() {
+            if let Some(result) = (self.transfer)(
+                &mut self.inner,
+                inputs.caller,
+                created_address,
+                inputs.value,
+            )? {
+                self.journaled_state.checkpoint_revert(checkpoint);
+                return return_error(result);
+            }
+        }
+
         let bytecode = Bytecode::new_legacy(inputs.init_code.clone());
 
         let contract = Contract::new(
@@ -366,6 +434,11 @@ where
             ))
         };
 
+        // Creating a contract changes the state, the creating frame halts in the static context.
+        if self.journaled_state.is_static_context() {
+            return return_error(InstructionResult::StateChangeDuringStaticCall);
+        }
+
         let (input, initcode, created_address) = match &inputs.kind {
             EOFCreateKind::Opcode {
                 initcode,
@@ -388,9 +461,9 @@ where
 
                 // Use nonce from tx to calculate address.
                 let tx = self.env.tx.common_fields();
-                let create_address = tx.caller().create(tx.nonce());
+                let created_address = (self.address_scheme.create)(tx.caller(), tx.nonce());
 
-                (input, eof, Some(create_address))
+                (input, eof, Some(created_address))
             }
         };
 
@@ -407,14 +480,14 @@ where
             return return_error(InstructionResult::OutOfFunds);
         }
 
-        // Increase nonce of caller and check if it overflows
+        // Increase nonce of caller and check if it overflows. See EIP-2681.
         let Some(nonce) = self.journaled_state.inc_nonce(inputs.caller) else {
-            // can't happen on mainnet.
-            return return_error(InstructionResult::Return);
+            return return_error(InstructionResult::NonceOverflow);
         };
         let old_nonce = nonce - 1;
 
-        let created_address = created_address.unwrap_or_else(|| inputs.caller.create(old_nonce));
+        let created_address = created_address
+            .unwrap_or_else(|| (self.address_scheme.create)(inputs.caller, old_nonce));
 
         // created address is not allowed to be a precompile.
         if self.precompiles.contains(&created_address) {
@@ -424,11 +497,11 @@ where
         // Load account so it needs to be marked as warm for access list.
         self.load_account(created_address)?;
 
-        // create account, transfer funds and make the journal checkpoint.
+        // create account and make the journal checkpoint.
         let checkpoint = match self.journaled_state.create_account_checkpoint(
             inputs.caller,
             created_address,
-            inputs.value,
+            U256::ZERO,
             spec_id,
         ) {
             Ok(checkpoint) => checkpoint,
@@ -437,6 +510,19 @@ where
             }
         };
 
+        // transfer funds to the created account.
+        if !inputs.value.is_zero() {
+            if let Some(result) = (self.transfer)(
+                &mut self.inner,
+                inputs.caller,
+                created_address,
+                inputs.value,
+            )? {
+                self.journaled_state.checkpoint_revert(checkpoint);
+                return return_error(result);
+            }
+        }
+
         let contract = Contract::new(
             input.clone(),
             // fine to clone as it is Bytes.
@@ -460,6 +546,13 @@ where
     }
 }
 
+/// Replaces raw legacy bytecode with its analysis, so later frames don't analyse it again.
+fn analyse_in_place(code: &mut Option<Bytecode>) {
+    if let Some(code @ Bytecode::LegacyRaw(_)) = code {
+        *code = core::mem::take(code).into_analyzed();
+    }
+}
+
 /// Test utilities for the [`EvmContext`].
 #[cfg(any(test, feature = "test-utils"))]
 pub(crate) mod test_utils {
@@ -467,7 +560,7 @@ pub(crate) mod test_utils {
     use crate::journaled_state::JournaledState;
     use database::CacheDB;
     use database_interface::EmptyDB;
-    use interpreter::CallScheme;
+    use interpreter::{CallInput, CallScheme};
     use primitives::{address, HashSet, B256, U256};
     use specification::hardfork::SpecId;
     use state::AccountInfo;
@@ -478,7 +571,7 @@ pub(crate) mod test_utils {
     /// Creates `CallInputs` that calls a provided contract address from the mock caller.
     pub fn create_mock_call_inputs(to: Address) -> CallInputs {
         CallInputs {
-            input: Bytes::new(),
+            input: CallInput::default(),
             gas_limit: 0,
             bytecode_address: to,
             target_address: to,
@@ -487,6 +580,7 @@ pub(crate) mod test_utils {
             scheme: CallScheme::Call,
             is_eof: false,
             is_static: false,
+            depth: 1,
             return_memory_offset: 0..0,
         }
     }
@@ -527,6 +621,10 @@ pub(crate) mod test_utils {
                 error: Ok(()),
             },
             precompiles: ContextPrecompiles::default(),
+            gas_table: None,
+            address_scheme: AddressScheme::default(),
+            block_env_override: None,
+            transfer: mainnet::transfer,
         }
     }
 
@@ -544,6 +642,10 @@ pub(crate) mod test_utils {
                 error: Ok(()),
             },
             precompiles: ContextPrecompiles::default(),
+            gas_table: None,
+            address_scheme: AddressScheme::default(),
+            block_env_override: None,
+            transfer: mainnet::transfer,
         }
     }
 }
@@ -551,10 +653,12 @@ pub(crate) mod test_utils {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Frame, JournalEntry};
+    use crate::{ContextPrecompile, ContextStatefulPrecompileMut, Frame, JournalEntry};
     use bytecode::Bytecode;
     use database::CacheDB;
     use database_interface::EmptyDB;
+    use interpreter::{CallInput, EMPTY_SHARED_MEMORY};
+    use precompile::{PrecompileError, PrecompileOutput, PrecompileResult};
     use primitives::{address, U256};
     use state::AccountInfo;
     use std::boxed::Box;
@@ -572,7 +676,7 @@ mod tests {
         context.journaled_state.depth = CALL_STACK_LIMIT as usize + 1;
         let contract = address!("dead10000000000000000000000000000001dead");
         let call_inputs = test_utils::create_mock_call_inputs(contract);
-        let res = context.make_call_frame(&call_inputs);
+        let res = context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY);
         let Ok(FrameOrResult::Result(err)) = res else {
             panic!("Expected FrameOrResult::Result");
         };
@@ -594,7 +698,7 @@ mod tests {
         let contract = address!("dead10000000000000000000000000000001dead");
         let mut call_inputs = test_utils::create_mock_call_inputs(contract);
         call_inputs.value = CallValue::Transfer(U256::from(1));
-        let res = evm_context.make_call_frame(&call_inputs);
+        let res = evm_context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY);
         let Ok(FrameOrResult::Result(result)) = res else {
             panic!("Expected FrameOrResult::Result");
         };
@@ -617,7 +721,7 @@ mod tests {
             create_cache_db_evm_context_with_balance::<CacheEthWiring>(Box::new(env), cdb, bal);
         let contract = address!("dead10000000000000000000000000000001dead");
         let call_inputs = test_utils::create_mock_call_inputs(contract);
-        let res = context.make_call_frame(&call_inputs);
+        let res = context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY);
         let Ok(FrameOrResult::Result(result)) = res else {
             panic!("Expected FrameOrResult::Result");
         };
@@ -643,11 +747,188 @@ mod tests {
         );
         let mut evm_context =
             create_cache_db_evm_context_with_balance::<CacheEthWiring>(Box::new(env), cdb, bal);
-        let call_inputs = test_utils::create_mock_call_inputs(contract);
-        let res = evm_context.make_call_frame(&call_inputs);
+        // Call data is read from the memory of the caller.
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(32);
+        shared_memory.set(0, &[1, 2, 3]);
+        let mut call_inputs = test_utils::create_mock_call_inputs(contract);
+        call_inputs.input = CallInput::SharedBuffer(shared_memory.global_range(1..3));
+        let res = evm_context.make_call_frame(&call_inputs, &shared_memory);
         let Ok(FrameOrResult::Frame(Frame::Call(call_frame))) = res else {
             panic!("Expected FrameOrResult::Frame(Frame::Call(..))");
         };
         assert_eq!(call_frame.return_memory_range, 0..0,);
+        assert_eq!(
+            call_frame.frame_data.interpreter.contract.input,
+            Bytes::from_static(&[2, 3])
+        );
+    }
+
+    #[test]
+    fn test_make_call_frame_shared_buffer_input() {
+        #[derive(Clone)]
+        struct Echo;
+
+        impl ContextStatefulPrecompileMut<DefaultEthereumWiring> for Echo {
+            fn call_mut(
+                &mut self,
+                bytes: &Bytes,
+                _gas_limit: u64,
+                _is_static: bool,
+                _evmctx: &mut InnerEvmContext<DefaultEthereumWiring>,
+            ) -> PrecompileResult {
+                Ok(PrecompileOutput::new(0, bytes.clone()))
+            }
+        }
+
+        let env = EnvWiring::<DefaultEthereumWiring>::default();
+        let mut context = test_utils::create_empty_evm_context::<DefaultEthereumWiring>(
+            Box::new(env),
+            EmptyDB::default(),
+        );
+        let precompile = Address::with_last_byte(0xff);
+        context.precompiles.extend([(
+            precompile,
+            ContextPrecompile::ContextStatefulMut(Box::new(Echo)),
+        )]);
+        let mut shared_memory = SharedMemory::new();
+        shared_memory.new_context();
+        shared_memory.resize(32);
+        shared_memory.set(0, &[1, 2, 3]);
+
+        // Precompiles get the call data out of the memory of the caller.
+        let mut call_inputs = test_utils::create_mock_call_inputs(precompile);
+        call_inputs.input = CallInput::SharedBuffer(shared_memory.global_range(1..3));
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_call_frame(&call_inputs, &shared_memory)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().output,
+            Bytes::from_static(&[2, 3])
+        );
+
+        // Out of bounds range reads as empty call data instead of panicking.
+        call_inputs.input = CallInput::SharedBuffer(shared_memory.global_range(64..96));
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_call_frame(&call_inputs, &shared_memory)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(result.interpreter_result().output, Bytes::new());
+
+        // Call to an account without code ends before the call data is read.
+        let mut call_inputs = test_utils::create_mock_call_inputs(address!(
+            "dead10000000000000000000000000000001dead"
+        ));
+        call_inputs.input = CallInput::SharedBuffer(shared_memory.global_range(1..3));
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_call_frame(&call_inputs, &shared_memory)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(result.interpreter_result().result, InstructionResult::Stop);
+        assert_eq!(result.interpreter_result().output, Bytes::new());
+    }
+
+    #[test]
+    fn test_make_call_frame_analysis_kind() {
+        type CacheEthWiring = EthereumWiring<CacheDB<EmptyDB>, ()>;
+        let contract = address!("dead10000000000000000000000000000001dead");
+        let code = |analysis| {
+            let mut env = EnvWiring::<CacheEthWiring>::default();
+            env.cfg.perf_analyse_created_bytecodes = analysis;
+            let mut cdb = CacheDB::new(EmptyDB::default());
+            let by = Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x60, 0x00]));
+            cdb.insert_account_info(contract, AccountInfo::from_bytecode(by));
+            let mut evm_context = create_cache_db_evm_context_with_balance::<CacheEthWiring>(
+                Box::new(env),
+                cdb,
+                U256::ZERO,
+            );
+            let call_inputs = test_utils::create_mock_call_inputs(contract);
+            let res = evm_context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY);
+            assert!(matches!(res, Ok(FrameOrResult::Frame(_))));
+            evm_context.journaled_state.state[&contract]
+                .info
+                .code
+                .clone()
+                .unwrap()
+        };
+        // Only `Check` keeps the analysis of executed code.
+        assert!(matches!(code(AnalysisKind::Raw), Bytecode::LegacyRaw(_)));
+        assert!(matches!(
+            code(AnalysisKind::Analyse),
+            Bytecode::LegacyRaw(_)
+        ));
+        assert!(matches!(
+            code(AnalysisKind::Check),
+            Bytecode::LegacyAnalyzed(_)
+        ));
+    }
+
+    #[test]
+    fn test_make_call_frame_static_precompile() {
+        #[derive(Clone)]
+        struct Counter;
+
+        impl ContextStatefulPrecompileMut<DefaultEthereumWiring> for Counter {
+            fn call_mut(
+                &mut self,
+                _bytes: &Bytes,
+                _gas_limit: u64,
+                is_static: bool,
+                evmctx: &mut InnerEvmContext<DefaultEthereumWiring>,
+            ) -> PrecompileResult {
+                let address = Address::with_last_byte(0xff);
+                let count = evmctx.journaled_state.tload(address, U256::ZERO);
+                evmctx
+                    .journaled_state
+                    .tstore(address, U256::ZERO, count + U256::from(1))
+                    .map_err(|e| PrecompileError::Other(format!("{e:?}")))?;
+                Ok(PrecompileOutput::new(0, Bytes::from(vec![is_static as u8])))
+            }
+        }
+
+        let env = EnvWiring::<DefaultEthereumWiring>::default();
+        let mut context = test_utils::create_empty_evm_context::<DefaultEthereumWiring>(
+            Box::new(env),
+            EmptyDB::default(),
+        );
+        let address = Address::with_last_byte(0xff);
+        context.precompiles.extend([(
+            address,
+            ContextPrecompile::ContextStatefulMut(Box::new(Counter)),
+        )]);
+        let mut call_inputs = test_utils::create_mock_call_inputs(address);
+
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::Return
+        );
+        assert_eq!(result.interpreter_result().output, Bytes::from_static(&[0]));
+
+        // The journal rejects changes in a static call.
+        call_inputs.is_static = true;
+        let Ok(FrameOrResult::Result(result)) =
+            context.make_call_frame(&call_inputs, &EMPTY_SHARED_MEMORY)
+        else {
+            panic!("Expected FrameOrResult::Result");
+        };
+        assert_eq!(
+            result.interpreter_result().result,
+            InstructionResult::PrecompileError
+        );
+        assert_eq!(
+            context.journaled_state.tload(address, U256::ZERO),
+            U256::from(1)
+        );
     }
 }
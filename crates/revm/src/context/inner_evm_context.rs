@@ -1,22 +1,22 @@
-use crate::{journaled_state::JournaledState, JournalCheckpoint};
+use crate::{journaled_state::JournaledState, JournalCheckpoint, JournalError};
 use bytecode::{Bytecode, Eof, EOF_MAGIC_BYTES, EOF_MAGIC_HASH};
 use database_interface::Database;
 use derive_where::derive_where;
 use interpreter::{
-    gas, return_ok, AccountLoad, Eip7702CodeLoad, InstructionResult, InterpreterResult,
-    SStoreResult, SelfDestructResult, StateLoad,
+    as_u64_saturated, gas, return_ok, AccountLoad, Eip7702CodeLoad, InstructionResult,
+    InterpreterResult, SStoreResult, SelfDestructResult, StateLoad,
 };
-use primitives::{Address, Bytes, HashSet, B256, U256};
+use primitives::{Address, Bytes, HashSet, B256, BLOCK_HASH_HISTORY, U256};
 use specification::hardfork::{
     Spec,
     SpecId::{self, *},
 };
-use state::Account;
+use state::{Account, EvmStorageSlot};
 use std::{boxed::Box, sync::Arc};
 use transaction::AccessListTrait;
 use wiring::{
     default::{AnalysisKind, CfgEnv, EnvWiring},
-    EvmWiring, Transaction,
+    Block, EvmWiring, Transaction,
 };
 
 /// EVM contexts contains data that EVM needs for execution.
@@ -107,6 +107,32 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         Ok(())
     }
 
+    /// Loads the accounts and storage slots of the [`WarmSet`](wiring::default::WarmSet) from
+    /// the environment, making them warm.
+    #[inline]
+    pub fn load_warm_set(&mut self) -> Result<(), <EvmWiringT::Database as Database>::Error> {
+        for (address, slots) in self.env.warm_set.accounts.iter() {
+            self.journaled_state.initial_account_load(
+                *address,
+                slots.iter().copied(),
+                &mut self.db,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Apply state overrides from the environment.
+    ///
+    /// See [`JournaledState::set_state_override`].
+    #[inline]
+    pub fn apply_state_override(
+        &mut self,
+    ) -> Result<(), <EvmWiringT::Database as Database>::Error> {
+        self.journaled_state
+            .set_state_override(self.env.state_override.clone());
+        Ok(())
+    }
+
     /// Return environment.
     #[inline]
     pub fn env(&mut self) -> &mut EnvWiring<EvmWiringT> {
@@ -118,6 +144,12 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         &self.env.cfg
     }
 
+    /// Returns reference to the block environment.
+    #[inline]
+    pub fn block(&self) -> &EvmWiringT::Block {
+        &self.env.block
+    }
+
     /// Returns the error by replacing it with `Ok(())`, if any.
     #[inline]
     pub fn take_error(&mut self) -> Result<(), <EvmWiringT::Database as Database>::Error> {
@@ -133,6 +165,22 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         self.db.block_hash(number)
     }
 
+    /// Fetch block hash the same way as the `BLOCKHASH` opcode.
+    ///
+    /// Only hashes of the [`BLOCK_HASH_HISTORY`] blocks before the current one are fetched from
+    /// the database, zero is returned for all other blocks.
+    #[inline]
+    pub fn recent_block_hash(
+        &mut self,
+        number: u64,
+    ) -> Result<B256, <EvmWiringT::Database as Database>::Error> {
+        let block_number = as_u64_saturated!(*self.env.block.number());
+        match block_number.checked_sub(number) {
+            Some(diff) if diff != 0 && diff <= BLOCK_HASH_HISTORY => self.db.block_hash(number),
+            _ => Ok(B256::ZERO),
+        }
+    }
+
     /// Mark account as touched as only touched accounts will be added to state.
     #[inline]
     pub fn touch(&mut self, address: &Address) {
@@ -273,6 +321,13 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         self.journaled_state.sload(address, index, &mut self.db)
     }
 
+    /// Returns original and present value of a storage slot that was already loaded in this
+    /// transaction, see [`JournaledState::storage_slot`].
+    #[inline]
+    pub fn storage_slot(&self, address: Address, index: U256) -> Option<&EvmStorageSlot> {
+        self.journaled_state.storage_slot(address, index)
+    }
+
     /// Storage change of storage slot, before storing `sload` will be called for that slot.
     #[inline]
     pub fn sstore(
@@ -280,7 +335,8 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         address: Address,
         index: U256,
         value: U256,
-    ) -> Result<StateLoad<SStoreResult>, <EvmWiringT::Database as Database>::Error> {
+    ) -> Result<StateLoad<SStoreResult>, JournalError<<EvmWiringT::Database as Database>::Error>>
+    {
         self.journaled_state
             .sstore(address, index, value, &mut self.db)
     }
@@ -293,7 +349,12 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
 
     /// Stores transient storage value.
     #[inline]
-    pub fn tstore(&mut self, address: Address, index: U256, value: U256) {
+    pub fn tstore(
+        &mut self,
+        address: Address,
+        index: U256,
+        value: U256,
+    ) -> Result<(), JournalError> {
         self.journaled_state.tstore(address, index, value)
     }
 
@@ -303,7 +364,10 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
         &mut self,
         address: Address,
         target: Address,
-    ) -> Result<StateLoad<SelfDestructResult>, <EvmWiringT::Database as Database>::Error> {
+    ) -> Result<
+        StateLoad<SelfDestructResult>,
+        JournalError<<EvmWiringT::Database as Database>::Error>,
+    > {
         self.journaled_state
             .selfdestruct(address, target, &mut self.db)
     }
@@ -354,12 +418,23 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
     }
 
     /// Handles call return.
+    ///
+    /// Fails the call with [`InstructionResult::StateChangeDuringStaticCall`] if it entered the
+    /// static context and changed the state.
     #[inline]
     pub fn call_return(
         &mut self,
-        interpreter_result: &InterpreterResult,
+        interpreter_result: &mut InterpreterResult,
         journal_checkpoint: JournalCheckpoint,
     ) {
+        if matches!(interpreter_result.result, return_ok!())
+            && self
+                .journaled_state
+                .static_context_violated(&journal_checkpoint)
+        {
+            interpreter_result.result = InstructionResult::StateChangeDuringStaticCall;
+            interpreter_result.output = Bytes::new();
+        }
         // revert changes or not.
         if matches!(interpreter_result.result, return_ok!()) {
             self.journaled_state.checkpoint_commit();
@@ -419,7 +494,9 @@ impl<EvmWiringT: EvmWiring> InnerEvmContext<EvmWiringT> {
 
         // Do analysis of bytecode straight away.
         let bytecode = match self.env.cfg.perf_analyse_created_bytecodes {
-            AnalysisKind::Raw => Bytecode::new_legacy(interpreter_result.output.clone()),
+            AnalysisKind::Raw | AnalysisKind::Check => {
+                Bytecode::new_legacy(interpreter_result.output.clone())
+            }
             AnalysisKind::Analyse => {
                 Bytecode::new_legacy(interpreter_result.output.clone()).into_analyzed()
             }
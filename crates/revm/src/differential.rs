@@ -0,0 +1,306 @@
+use crate::{Database, Evm, EvmWiring};
+use primitives::{Address, Log, U256};
+use state::{AccountInfo, EvmState};
+use std::{collections::BTreeSet, vec::Vec};
+use wiring::{
+    result::{EVMError, EVMErrorForChain, EVMResultGeneric, ExecutionResult},
+    HaltReasonTrait,
+};
+
+/// First difference between the executions of a transaction by two EVMs.
+///
+/// See [`diff_transaction`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Divergence<HaltReasonT: HaltReasonTrait, ErrorT> {
+    /// Only one of the executions returned an error, or the errors differ.
+    Error {
+        left: Option<ErrorT>,
+        right: Option<ErrorT>,
+    },
+    /// Executions have a different outcome, halt reason or output.
+    Result {
+        left: ExecutionResult<HaltReasonT>,
+        right: ExecutionResult<HaltReasonT>,
+    },
+    /// Executions used a different amount of gas.
+    GasUsed { left: u64, right: u64 },
+    /// Executions emitted different logs.
+    Logs { left: Vec<Log>, right: Vec<Log> },
+    /// Account has a different balance, nonce or code after the executions.
+    ///
+    /// `None` if the account does not exist.
+    Account {
+        address: Address,
+        left: Option<AccountInfo>,
+        right: Option<AccountInfo>,
+    },
+    /// Storage slot has a different value after the executions.
+    Storage {
+        address: Address,
+        slot: U256,
+        left: U256,
+        right: U256,
+    },
+}
+
+/// Divergence of two EVMs with the given wiring.
+pub type DivergenceForChain<EvmWiringT> =
+    Divergence<<EvmWiringT as wiring::EvmWiring>::HaltReason, EVMErrorForChain<EvmWiringT>>;
+
+/// Executes the transaction with both EVMs and returns the first divergence of their outcome,
+/// gas, logs or post state, or `None` if they agree.
+///
+/// The EVMs are expected to start from the same state, usually clones of the same database,
+/// and to differ in the spec id or in handler registers that, for example, replace
+/// instructions. This is meant to validate a new hardfork or an optimized instruction against
+/// the existing one. Nothing is committed. Accounts and slots that were loaded by only one of
+/// the EVMs are read from the database of the other one.
+///
+/// Returns an error if the database fails while the post states are compared.
+pub fn diff_transaction<EvmWiringT>(
+    left: &mut Evm<'_, EvmWiringT>,
+    right: &mut Evm<'_, EvmWiringT>,
+    tx: &EvmWiringT::Transaction,
+) -> EVMResultGeneric<Option<DivergenceForChain<EvmWiringT>>, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<Transaction: Clone>,
+    EVMErrorForChain<EvmWiringT>: PartialEq,
+{
+    *left.tx_mut() = tx.clone();
+    *right.tx_mut() = tx.clone();
+    let (left_output, right_output) = match (left.transact(), right.transact()) {
+        (Ok(left), Ok(right)) => (left, right),
+        (Err(left), Err(right)) if left == right => return Ok(None),
+        (left, right) => {
+            return Ok(Some(Divergence::Error {
+                left: left.err(),
+                right: right.err(),
+            }))
+        }
+    };
+
+    let (left_result, right_result) = (&left_output.result, &right_output.result);
+    if strip_gas_and_logs(left_result.clone()) != strip_gas_and_logs(right_result.clone()) {
+        return Ok(Some(Divergence::Result {
+            left: left_output.result,
+            right: right_output.result,
+        }));
+    }
+    if left_result.gas_used() != right_result.gas_used() {
+        return Ok(Some(Divergence::GasUsed {
+            left: left_result.gas_used(),
+            right: right_result.gas_used(),
+        }));
+    }
+    if left_result.logs() != right_result.logs() {
+        return Ok(Some(Divergence::Logs {
+            left: left_output.result.into_logs(),
+            right: right_output.result.into_logs(),
+        }));
+    }
+
+    let addresses: BTreeSet<Address> = left_output
+        .state
+        .keys()
+        .chain(right_output.state.keys())
+        .copied()
+        .collect();
+    for address in addresses {
+        let left_info = account_info(left, &left_output.state, address)?;
+        let right_info = account_info(right, &right_output.state, address)?;
+        if left_info != right_info {
+            return Ok(Some(Divergence::Account {
+                address,
+                left: left_info,
+                right: right_info,
+            }));
+        }
+        if left_info.is_none() {
+            continue;
+        }
+
+        let slots: BTreeSet<U256> = [&left_output.state, &right_output.state]
+            .into_iter()
+            .filter_map(|state| state.get(&address))
+            .flat_map(|account| account.changed_storage_slots().map(|(slot, _)| *slot))
+            .collect();
+        for slot in slots {
+            let left_value = storage(left, &left_output.state, address, slot)?;
+            let right_value = storage(right, &right_output.state, address, slot)?;
+            if left_value != right_value {
+                return Ok(Some(Divergence::Storage {
+                    address,
+                    slot,
+                    left: left_value,
+                    right: right_value,
+                }));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Zeroes the gas and removes the logs, leaving the outcome and the output.
+fn strip_gas_and_logs<HaltReasonT: HaltReasonTrait>(
+    mut result: ExecutionResult<HaltReasonT>,
+) -> ExecutionResult<HaltReasonT> {
+    match &mut result {
+        ExecutionResult::Success {
+            gas_used,
+            gas_refunded,
+            logs,
+            ..
+        } => {
+            *gas_used = 0;
+            *gas_refunded = 0;
+            logs.clear();
+        }
+        ExecutionResult::Revert { gas_used, .. } | ExecutionResult::Halt { gas_used, .. } => {
+            *gas_used = 0
+        }
+    }
+    result
+}
+
+/// Returns the account after the execution, from the database if it was not loaded.
+fn account_info<EvmWiringT: EvmWiring>(
+    evm: &mut Evm<'_, EvmWiringT>,
+    state: &EvmState,
+    address: Address,
+) -> EVMResultGeneric<Option<AccountInfo>, EvmWiringT> {
+    match state.get(&address) {
+        Some(account)
+            if account.is_selfdestructed()
+                || account.is_state_cleared()
+                || (account.is_loaded_as_not_existing() && !account.is_touched()) =>
+        {
+            Ok(None)
+        }
+        Some(account) => Ok(Some(account.info.copy_without_code())),
+        None => Ok(evm
+            .db_mut()
+            .basic(address)
+            .map_err(EVMError::Database)?
+            .map(|info| info.copy_without_code())),
+    }
+}
+
+/// Returns the storage slot after the execution, from the database if it was not loaded.
+fn storage<EvmWiringT: EvmWiring>(
+    evm: &mut Evm<'_, EvmWiringT>,
+    state: &EvmState,
+    address: Address,
+    slot: U256,
+) -> EVMResultGeneric<U256, EvmWiringT> {
+    match state
+        .get(&address)
+        .and_then(|account| account.storage.get(&slot))
+    {
+        Some(value) => Ok(value.present_value),
+        None => evm
+            .db_mut()
+            .storage(address, slot)
+            .map_err(EVMError::Database),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{PUSH0, PUSH1, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, TxKind};
+    use specification::hardfork::SpecId;
+    use wiring::{default::TxEnv, result::HaltReason, EthereumWiring};
+
+    type TestWiring = EthereumWiring<BenchmarkDB, ()>;
+
+    fn evm<'a>(code: &[u8], spec_id: SpecId) -> Evm<'a, TestWiring> {
+        Evm::<TestWiring>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                code.to_vec().into(),
+            )))
+            .with_default_ext_ctx()
+            .with_spec_id(spec_id)
+            .build()
+    }
+
+    fn tx() -> TxEnv {
+        TxEnv {
+            caller: address!("0000000000000000000000000000000000000001"),
+            transact_to: TxKind::Call(Address::ZERO),
+            gas_limit: 100_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_spec() {
+        let code = [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let mut left = evm(&code, SpecId::CANCUN);
+        let mut right = evm(&code, SpecId::CANCUN);
+        assert_eq!(diff_transaction(&mut left, &mut right, &tx()), Ok(None));
+    }
+
+    #[test]
+    fn spec_divergence() {
+        // PUSH0 is invalid before Shanghai.
+        let code = [PUSH0, STOP];
+        let mut left = evm(&code, SpecId::MERGE);
+        let mut right = evm(&code, SpecId::SHANGHAI);
+        let divergence = diff_transaction(&mut left, &mut right, &tx()).unwrap();
+        assert!(matches!(
+            divergence,
+            Some(Divergence::Result {
+                left: ExecutionResult::Halt {
+                    reason: HaltReason::NotActivated,
+                    ..
+                },
+                right: ExecutionResult::Success { .. },
+            })
+        ));
+
+        // EIP-2929 made the cold slot access more expensive.
+        let code = [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let mut left = evm(&code, SpecId::ISTANBUL);
+        let mut right = evm(&code, SpecId::BERLIN);
+        assert_eq!(
+            diff_transaction(&mut left, &mut right, &tx()),
+            Ok(Some(Divergence::GasUsed {
+                left: 41_006,
+                right: 43_106,
+            }))
+        );
+    }
+
+    #[test]
+    fn instruction_divergence() {
+        let code = [PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP];
+        let mut left = evm(&code, SpecId::CANCUN);
+        // SSTORE that stores one more than asked, at the same cost.
+        let mut right = evm(&code, SpecId::CANCUN)
+            .modify()
+            .append_handler_register(|handler| {
+                handler
+                    .instruction_table
+                    .update_boxed(SSTORE, |prev, interpreter, host| {
+                        let value = interpreter.stack.peek(1).unwrap();
+                        interpreter.stack.set(1, value + U256::from(1)).unwrap();
+                        prev(interpreter, host)
+                    });
+            })
+            .build();
+        assert_eq!(
+            diff_transaction(&mut left, &mut right, &tx()),
+            Ok(Some(Divergence::Storage {
+                address: Address::ZERO,
+                slot: U256::ZERO,
+                left: U256::from(1),
+                right: U256::from(2),
+            }))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Evm, EvmWiring};
+use interpreter::gas::CALL_STIPEND;
+use specification::{eip7825, experimental::ExperimentalEip, hardfork::SpecId};
+use wiring::{
+    default::TxEnv,
+    result::{EVMError, EVMResultGeneric, ExecutionResult},
+    Block, HaltReasonTrait,
+};
+
+/// Result of [`Evm::estimate_gas`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GasEstimate<HaltReasonT: HaltReasonTrait> {
+    /// Minimal gas limit at which the transaction succeeds.
+    Success(u64),
+    /// The transaction does not succeed with the highest gas limit.
+    Failure(ExecutionResult<HaltReasonT>),
+}
+
+impl<EvmWiringT: EvmWiring<Transaction = TxEnv>> Evm<'_, EvmWiringT> {
+    /// Estimates the minimal gas limit at which the transaction succeeds.
+    ///
+    /// The transaction is executed with the gas limit of the block, or the gas limit of
+    /// `tx_env` or the EIP-7825 cap if they are lower. If it succeeds, the minimal gas limit is searched with a binary
+    /// search. Because of the 1/64th rule of EIP-150 and the stipends of calls, the minimal gas
+    /// limit can be above the gas used. The first guess of the search is therefore the gas used
+    /// and refunded plus the call stipend, increased by 64/63.
+    ///
+    /// Errors of the transaction at a lower gas limit, like a gas limit below the intrinsic
+    /// gas, count as failures of the transaction. Other errors are returned.
+    ///
+    /// Nothing is committed. Wrap the database in the `CacheDB` of `revm-database` to read the
+    /// state only once for all executions. Afterwards the transaction of the EVM is `tx_env`
+    /// with the estimated gas limit.
+    pub fn estimate_gas(
+        &mut self,
+        tx_env: TxEnv,
+    ) -> EVMResultGeneric<GasEstimate<EvmWiringT::HaltReason>, EvmWiringT> {
+        let block_gas_limit = self.block().gas_limit().saturating_to::<u64>();
+        let mut hi = tx_env.gas_limit.min(block_gas_limit);
+        if Into::<SpecId>::into(self.spec_id()).is_eip_enabled(ExperimentalEip::Eip7825) {
+            hi = hi.min(eip7825::TX_GAS_LIMIT_CAP);
+        }
+        *self.tx_mut() = tx_env;
+
+        self.tx_mut().gas_limit = hi;
+        let (gas_used, gas_refunded) = match self.transact()?.result {
+            ExecutionResult::Success {
+                gas_used,
+                gas_refunded,
+                ..
+            } => (gas_used, gas_refunded),
+            result => return Ok(GasEstimate::Failure(result)),
+        };
+
+        // The gas limit can not be below the gas used.
+        let mut lo = gas_used - 1;
+        let optimistic = (gas_used + gas_refunded + CALL_STIPEND) * 64 / 63;
+        if optimistic < hi {
+            if self.succeeds_with(optimistic)? {
+                hi = optimistic;
+            } else {
+                lo = optimistic;
+            }
+        }
+        while lo + 1 < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.succeeds_with(mid)? {
+                hi = mid;
+            } else {
+                lo = mid;
+            }
+        }
+
+        self.tx_mut().gas_limit = hi;
+        Ok(GasEstimate::Success(hi))
+    }
+
+    /// Returns whether the transaction succeeds with the gas limit.
+    fn succeeds_with(&mut self, gas_limit: u64) -> EVMResultGeneric<bool, EvmWiringT> {
+        self.tx_mut().gas_limit = gas_limit;
+        match self.transact() {
+            Ok(result) => Ok(result.result.is_success()),
+            Err(EVMError::Transaction(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{PUSH1, REVERT, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, Address, TxKind};
+    use wiring::{result::SuccessReason, EthereumWiring};
+
+    fn evm(code: &[u8]) -> Evm<'static, EthereumWiring<BenchmarkDB, ()>> {
+        Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw(
+                code.to_vec().into(),
+            )))
+            .with_default_ext_ctx()
+            .build()
+    }
+
+    fn tx() -> TxEnv {
+        TxEnv {
+            caller: address!("0000000000000000000000000000000000000001"),
+            transact_to: TxKind::Call(Address::ZERO),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn estimates_minimal_gas_limit() {
+        // No-op SSTORE uses less gas than the call stipend it requires, see EIP-2200.
+        let mut evm = evm(&[PUSH1, 0x00, PUSH1, 0x00, SSTORE, STOP]);
+        let GasEstimate::Success(estimate) = evm.estimate_gas(tx()).unwrap() else {
+            panic!("transaction fails");
+        };
+        assert_eq!(evm.tx().gas_limit, estimate);
+
+        let result = evm.transact().unwrap().result;
+        assert!(matches!(
+            result,
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                ..
+            }
+        ));
+        assert!(estimate > result.gas_used());
+
+        evm.tx_mut().gas_limit = estimate - 1;
+        assert!(!evm.transact().unwrap().result.is_success());
+    }
+
+    #[test]
+    fn estimate_below_tx_gas_limit_cap() {
+        let mut evm = evm(&[PUSH1, 0x00, PUSH1, 0x00, SSTORE, STOP]);
+        evm.modify_spec_id(SpecId::OSAKA);
+        let GasEstimate::Success(estimate) = evm.estimate_gas(tx()).unwrap() else {
+            panic!("transaction fails");
+        };
+        assert!(estimate <= eip7825::TX_GAS_LIMIT_CAP);
+        assert!(evm.transact().unwrap().result.is_success());
+    }
+
+    #[test]
+    fn returns_failure() {
+        let mut evm = evm(&[PUSH1, 0x00, PUSH1, 0x00, REVERT]);
+        assert!(matches!(
+            evm.estimate_gas(tx()).unwrap(),
+            GasEstimate::Failure(ExecutionResult::Revert { .. })
+        ));
+    }
+}
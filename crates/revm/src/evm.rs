@@ -2,11 +2,14 @@ use crate::{
     builder::{EvmBuilder, SetGenericStage},
     handler::Handler,
     Context, ContextWithEvmWiring, EvmContext, EvmWiring, Frame, FrameOrResult, FrameResult,
-    InnerEvmContext,
+    InnerEvmContext, JournalError, JournalSnapshot, RevertPolicy,
 };
 use core::fmt::{self, Debug};
 use database_interface::{Database, DatabaseCommit};
-use interpreter::{Host, InterpreterAction, NewFrameAction, SharedMemory};
+use interpreter::{
+    HostRead, InstructionResult, InterpreterAction, NewFrameAction, SharedMemory,
+    EMPTY_SHARED_MEMORY,
+};
 use std::{boxed::Box, vec::Vec};
 use wiring::{
     default::{CfgEnv, EnvWiring},
@@ -25,6 +28,8 @@ pub struct Evm<'a, EvmWiringT: EvmWiring> {
     /// Handler is a component of the of EVM that contains all the logic. Handler contains specification id
     /// and it different depending on the specified fork.
     pub handler: Handler<'a, EvmWiringT, Context<EvmWiringT>>,
+    /// Memory buffer shared by the call frames, reused between executions.
+    shared_memory: SharedMemory,
 }
 
 impl<EvmWiringT> Debug for Evm<'_, EvmWiringT>
@@ -42,10 +47,20 @@ where
 
 impl<EvmWiringT: EvmWiring<Database: DatabaseCommit>> Evm<'_, EvmWiringT> {
     /// Commit the changes to the database.
+    ///
+    /// Returns an error instead of committing a failed transaction whose state was kept by
+    /// [`RevertPolicy::Keep`], as that state is not valid.
     pub fn transact_commit(
         &mut self,
     ) -> EVMResultGeneric<ExecutionResult<EvmWiringT::HaltReason>, EvmWiringT> {
-        let ResultAndState { result, state } = self.transact()?;
+        let revert_policy = self.handler.post_execution().revert_policy(&self.context);
+        let ResultAndState { result, state, .. } = self.transact()?;
+        if revert_policy == RevertPolicy::Keep && !result.is_success() {
+            return Err(EVMError::Custom(
+                "state of a failed transaction kept by `RevertPolicy::Keep` can not be committed"
+                    .into(),
+            ));
+        }
         self.context.evm.db.commit(state);
         Ok(result)
     }
@@ -72,7 +87,11 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
             .evm
             .journaled_state
             .set_spec_id(handler.spec_id.into());
-        Evm { context, handler }
+        Evm {
+            context,
+            handler,
+            shared_memory: EMPTY_SHARED_MEMORY,
+        }
     }
 
     /// Allow for evm setting to be modified by feeding current evm
@@ -89,60 +108,117 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
                     external,
                 },
             handler,
+            ..
         } = self;
         EvmBuilder::<'a>::new_with(db, external, env, handler)
     }
 
     /// Runs main call loop.
+    ///
+    /// The memory buffer is given back to the EVM when the loop ends, also on errors, and is
+    /// reused by the next execution.
     #[inline]
     pub fn run_the_loop(
         &mut self,
         first_frame: Frame,
     ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        let mut call_loop = self.start_the_loop(first_frame, false);
+        let result = self.continue_the_loop(&mut call_loop);
+        self.end_the_loop(call_loop);
+        result
+    }
+
+    /// Creates the call loop with the first frame on the call stack.
+    ///
+    /// A resumable loop keeps its frames when data is missing in the database, see
+    /// [`CallLoop::is_suspended`].
+    pub(crate) fn start_the_loop(&mut self, first_frame: Frame, resumable: bool) -> CallLoop {
+        #[cfg(feature = "tx-arena")]
+        let mut call_stack: Vec<Frame> = self.context.evm.journaled_state.arena.take_call_stack();
+        #[cfg(not(feature = "tx-arena"))]
         let mut call_stack: Vec<Frame> = Vec::with_capacity(1025);
         call_stack.push(first_frame);
 
+        // Reuse the buffer of the previous execution, frames borrow it through checkpoints.
+        let mut shared_memory = core::mem::replace(&mut self.shared_memory, EMPTY_SHARED_MEMORY);
         #[cfg(feature = "memory_limit")]
-        let mut shared_memory =
-            SharedMemory::new_with_memory_limit(self.context.evm.env.cfg.memory_limit);
-        #[cfg(not(feature = "memory_limit"))]
-        let mut shared_memory = SharedMemory::new();
+        shared_memory.set_memory_limit(self.context.evm.env.cfg.memory_limit);
 
         shared_memory.new_context();
 
-        // Peek the last stack frame.
-        let mut stack_frame = call_stack.last_mut().unwrap();
+        CallLoop {
+            call_stack,
+            shared_memory,
+            pending: None,
+            resumable,
+        }
+    }
+
+    /// Gives the buffers of the call loop back for the next execution.
+    ///
+    /// Must be called on every exit of the loop. If the loop ended with an error, the memory
+    /// contexts of the frames that did not return are freed here.
+    pub(crate) fn end_the_loop(&mut self, mut call_loop: CallLoop) {
+        call_loop.shared_memory.clear();
+        self.shared_memory = call_loop.shared_memory;
+        #[cfg(feature = "tx-arena")]
+        self.context
+            .evm
+            .journaled_state
+            .arena
+            .give_call_stack(call_loop.call_stack);
+    }
+
+    /// Runs the call loop until the first frame returns.
+    ///
+    /// A suspended resumable loop continues with the instruction or the frame creation that
+    /// missed the data.
+    pub(crate) fn continue_the_loop(
+        &mut self,
+        call_loop: &mut CallLoop,
+    ) -> EVMResultGeneric<FrameResult, EvmWiringT> {
+        if let Some(frame) = call_loop.call_stack.last_mut() {
+            let interpreter = frame.interpreter_mut();
+            if interpreter.instruction_result == InstructionResult::Suspended {
+                interpreter.instruction_result = InstructionResult::Continue;
+            }
+        }
 
         loop {
-            // Execute the frame.
-            let next_action =
-                self.handler
-                    .execute_frame(stack_frame, &mut shared_memory, &mut self.context)?;
+            let next_action = match call_loop.pending.take() {
+                Some(action) => InterpreterAction::NewFrame(action),
+                None => {
+                    // Execute the frame.
+                    let stack_frame = call_loop
+                        .call_stack
+                        .last_mut()
+                        .expect("call loop has a frame");
+                    let next_action = self.handler.execute_frame(
+                        stack_frame,
+                        &mut call_loop.shared_memory,
+                        &mut self.context,
+                    )?;
 
-            // Take error and break the loop, if any.
-            // This error can be set in the Interpreter when it interacts with the context.
-            self.context.evm.take_error().map_err(EVMError::Database)?;
+                    // Take error and break the loop, if any.
+                    // This error can be set in the Interpreter when it interacts with the context.
+                    self.context.evm.take_error().map_err(EVMError::Database)?;
+                    next_action
+                }
+            };
 
-            let exec = &mut self.handler.execution;
             let frame_or_result = match next_action {
-                InterpreterAction::NewFrame(NewFrameAction::Call(inputs)) => {
-                    exec.call(&mut self.context, inputs)?
-                }
-                InterpreterAction::NewFrame(NewFrameAction::Create(inputs)) => {
-                    exec.create(&mut self.context, inputs)?
-                }
-                InterpreterAction::NewFrame(NewFrameAction::EOFCreate(inputs)) => {
-                    exec.eofcreate(&mut self.context, inputs)?
-                }
+                InterpreterAction::NewFrame(action) => self.make_frame(call_loop, action)?,
                 InterpreterAction::Return { result } => {
                     // free memory context.
-                    shared_memory.free_context();
+                    call_loop.shared_memory.free_context();
 
                     // pop last frame from the stack and consume it to create FrameResult.
-                    let returned_frame = call_stack
+                    let returned_frame = call_loop
+                        .call_stack
                         .pop()
                         .expect("We just returned from Interpreter frame");
 
+                    let exec = &mut self.handler.execution;
                     let ctx = &mut self.context;
                     FrameOrResult::Result(match returned_frame {
                         Frame::Call(frame) => {
@@ -164,22 +240,22 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
             // handle result
             match frame_or_result {
                 FrameOrResult::Frame(frame) => {
-                    shared_memory.new_context();
-                    call_stack.push(frame);
-                    stack_frame = call_stack.last_mut().unwrap();
+                    call_loop.shared_memory.new_context();
+                    call_loop.call_stack.push(frame);
                 }
                 FrameOrResult::Result(result) => {
-                    let Some(top_frame) = call_stack.last_mut() else {
+                    let Some(stack_frame) = call_loop.call_stack.last_mut() else {
                         // Break the loop if there are no more frames.
                         return Ok(result);
                     };
-                    stack_frame = top_frame;
+                    let exec = &mut self.handler.execution;
                     let ctx = &mut self.context;
+                    let shared_memory = &mut call_loop.shared_memory;
                     // Insert result to the top frame.
                     match result {
                         FrameResult::Call(outcome) => {
                             // return_call
-                            exec.insert_call_outcome(ctx, stack_frame, &mut shared_memory, outcome)?
+                            exec.insert_call_outcome(ctx, stack_frame, shared_memory, outcome)?
                         }
                         FrameResult::Create(outcome) => {
                             // return_create
@@ -194,6 +270,72 @@ impl<'a, EvmWiringT: EvmWiring> Evm<'a, EvmWiringT> {
             }
         }
     }
+
+    /// Creates the frame of a call or create of the top frame.
+    ///
+    /// In a resumable loop a database error reverts the journal to the state before the
+    /// creation and keeps the action, so the frame is created again when the loop continues.
+    fn make_frame(
+        &mut self,
+        call_loop: &mut CallLoop,
+        action: NewFrameAction,
+    ) -> EVMResultGeneric<FrameOrResult, EvmWiringT> {
+        let retry = call_loop
+            .resumable
+            .then(|| (self.context.evm.journaled_state.snapshot(), action.clone()));
+
+        let exec = &mut self.handler.execution;
+        let ctx = &mut self.context;
+        let frame_or_result = match action {
+            NewFrameAction::Call(mut inputs) => {
+                inputs.depth = ctx.evm.journaled_state.depth() + 1;
+                exec.call(ctx, inputs, &call_loop.shared_memory)
+            }
+            NewFrameAction::Create(mut inputs) => {
+                inputs.depth = ctx.evm.journaled_state.depth() + 1;
+                exec.create(ctx, inputs)
+            }
+            NewFrameAction::EOFCreate(inputs) => exec.eofcreate(ctx, inputs),
+        };
+
+        match (frame_or_result, retry) {
+            (Err(EVMError::Database(e)), Some((snapshot, action))) => {
+                ctx.evm.journaled_state.rewind_to(snapshot);
+                call_loop.pending = Some(action);
+                Err(EVMError::Database(e))
+            }
+            (frame_or_result, _) => frame_or_result,
+        }
+    }
+}
+
+/// Call loop of a transaction, see [`Evm::run_the_loop`].
+#[derive(Debug)]
+pub(crate) struct CallLoop {
+    /// Frames that have not returned yet, the last one is executed.
+    call_stack: Vec<Frame>,
+    /// Memory of the frames.
+    shared_memory: SharedMemory,
+    /// Action of the top frame whose frame is not created yet.
+    pending: Option<NewFrameAction>,
+    /// Whether the loop is kept intact when data is missing in the database.
+    resumable: bool,
+}
+
+impl CallLoop {
+    /// Returns `true` if the loop stopped because data is missing in the database and can be
+    /// continued once the data is provided.
+    ///
+    /// The instruction that missed the data is only executed again if it is wrapped by
+    /// [`suspendable_register`](crate::suspendable_register), otherwise the frame already
+    /// failed and the loop can not be continued.
+    #[cfg(any(test, feature = "suspendable"))]
+    pub(crate) fn is_suspended(&self) -> bool {
+        self.pending.is_some()
+            || self.call_stack.last().is_some_and(|frame| {
+                frame.interpreter().instruction_result == InstructionResult::Suspended
+            })
+    }
 }
 
 impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
@@ -214,7 +356,7 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
     }
 
     /// Calls clear handle of post execution to clear the state for next execution.
-    fn clear(&mut self) {
+    pub(crate) fn clear(&mut self) {
         self.handler.post_execution().clear(&mut self.context);
     }
 
@@ -230,7 +372,13 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
             .inspect_err(|_| {
                 self.clear();
             })?;
-        let output = self.transact_preverified_inner(initial_gas_spend);
+        self.handler
+            .pre_execution()
+            .apply_state_override(&mut self.context)
+            .inspect_err(|_| {
+                self.clear();
+            })?;
+        let output = self.transact_preverified_inner(initial_gas_spend, false);
         let output = self.handler.post_execution().end(&mut self.context, output);
         self.clear();
         output
@@ -238,12 +386,16 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
 
     /// Pre verify transaction inner.
     #[inline]
-    fn preverify_transaction_inner(&mut self) -> EVMResultGeneric<u64, EvmWiringT> {
+    pub(crate) fn preverify_transaction_inner(&mut self) -> EVMResultGeneric<u64, EvmWiringT> {
         self.handler.validation().env(&self.context.evm.env)?;
         let initial_gas_spend = self
             .handler
             .validation()
             .initial_tx_gas(&self.context.evm.env)?;
+        // state overrides need to be applied before caller is checked.
+        self.handler
+            .pre_execution()
+            .apply_state_override(&mut self.context)?;
         self.handler
             .validation()
             .tx_against_state(&mut self.context)?;
@@ -259,12 +411,35 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
             self.clear();
         })?;
 
-        let output = self.transact_preverified_inner(initial_gas_spend);
+        let output = self.transact_preverified_inner(initial_gas_spend, false);
         let output = self.handler.post_execution().end(&mut self.context, output);
         self.clear();
         output
     }
 
+    /// Transact transaction in a static context, like the `STATICCALL` of an `eth_call`.
+    ///
+    /// The first call frame of the transaction is static, so any state change or log of the
+    /// call or its subcalls halts it with `StateChangeDuringStaticCall`, including a value
+    /// transfer of the transaction. Create transactions fail with [`EVMError::Custom`]. The
+    /// first frame is made static by the `first_frame_readonly` handle of the
+    /// [`ExecutionHandler`](crate::handler::ExecutionHandler).
+    ///
+    /// Only the [`ExecutionResult`] is returned, the journaled state is discarded, so nothing
+    /// of the execution can be committed.
+    pub fn transact_readonly(
+        &mut self,
+    ) -> EVMResultGeneric<ExecutionResult<EvmWiringT::HaltReason>, EvmWiringT> {
+        let initial_gas_spend = self.preverify_transaction_inner().inspect_err(|_| {
+            self.clear();
+        })?;
+
+        let output = self.transact_preverified_inner(initial_gas_spend, true);
+        let output = self.handler.post_execution().end(&mut self.context, output);
+        self.clear();
+        output.map(|result_and_state| result_and_state.result)
+    }
+
     /// Returns the reference of Env configuration
     #[inline]
     pub fn cfg(&self) -> &CfgEnv {
@@ -313,6 +488,39 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         &mut self.context.evm.env.block
     }
 
+    /// Returns the account and storage access statistics of the last finalized transaction.
+    #[cfg(feature = "access-stats")]
+    #[inline]
+    pub fn last_access_stats(&self) -> &crate::StateAccessStats {
+        &self.context.evm.journaled_state.last_access_stats
+    }
+
+    /// Returns the read set and write set of the last finalized transaction.
+    #[cfg(feature = "read-write-set")]
+    #[inline]
+    pub fn last_read_write_set(&self) -> &crate::ReadWriteSet {
+        &self.context.evm.journaled_state.last_read_write_set
+    }
+
+    /// Makes a snapshot of the journaled state, see [`JournaledState::snapshot`].
+    ///
+    /// The journal holds the changes of the transaction that is executed, e.g. a suspended
+    /// one, and is cleared when the transaction ends.
+    ///
+    /// [`JournaledState::snapshot`]: crate::JournaledState::snapshot
+    #[inline]
+    pub fn snapshot(&mut self) -> JournalSnapshot {
+        self.context.evm.journaled_state.snapshot()
+    }
+
+    /// Reverts the journaled state to the snapshot, see [`JournaledState::revert_to`].
+    ///
+    /// [`JournaledState::revert_to`]: crate::JournaledState::revert_to
+    #[inline]
+    pub fn revert_to(&mut self, snapshot: &JournalSnapshot) -> Result<(), JournalError> {
+        self.context.evm.journaled_state.revert_to(snapshot)
+    }
+
     /// Modify spec id, this will create new EVM that matches this spec id.
     pub fn modify_spec_id(&mut self, spec_id: EvmWiringT::Hardfork) {
         self.context.evm.journaled_state.set_spec_id(spec_id.into());
@@ -347,8 +555,34 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         ContextWithEvmWiring::new(self.context, self.handler.spec_id)
     }
 
-    /// Transact pre-verified transaction.
-    fn transact_preverified_inner(&mut self, initial_gas_spend: u64) -> EVMResult<EvmWiringT> {
+    /// Transact pre-verified transaction, read-only if `read_only` is set.
+    fn transact_preverified_inner(
+        &mut self,
+        initial_gas_spend: u64,
+        read_only: bool,
+    ) -> EVMResult<EvmWiringT> {
+        let (first_frame_or_result, eip7702_gas_refund) =
+            self.start_transaction(initial_gas_spend, read_only)?;
+
+        // Starts the main running loop or return the result.
+        let result = match first_frame_or_result {
+            FrameOrResult::Frame(first_frame) => self.run_the_loop(first_frame)?,
+            FrameOrResult::Result(result) => result,
+        };
+
+        self.finish_transaction(result, eip7702_gas_refund)
+    }
+
+    /// Runs the pre-execution of the pre-verified transaction and creates its first frame.
+    ///
+    /// Returns the frame, or the result if no code is executed, and the EIP-7702 gas refund.
+    /// If `read_only` is set, the first frame action goes through the `first_frame_readonly`
+    /// handle.
+    pub(crate) fn start_transaction(
+        &mut self,
+        initial_gas_spend: u64,
+        read_only: bool,
+    ) -> EVMResultGeneric<(FrameOrResult, i64), EvmWiringT> {
         let ctx = &mut self.context;
         let pre_exec = self.handler.pre_execution();
 
@@ -359,6 +593,18 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         let precompiles = pre_exec.load_precompiles();
         ctx.evm.set_precompiles(precompiles);
 
+        // load gas table overrides.
+        ctx.evm.gas_table = pre_exec.load_gas_table();
+
+        // load address derivation of created contracts.
+        ctx.evm.address_scheme = pre_exec.load_address_scheme();
+
+        // load values of block information opcodes.
+        ctx.evm.block_env_override = pre_exec.load_block_env_override();
+
+        // load value transfer of calls and creates.
+        ctx.evm.transfer = self.handler.execution().transfer;
+
         // deduce caller balance with its limit.
         pre_exec.deduct_caller(ctx)?;
 
@@ -367,25 +613,34 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
         // apply EIP-7702 auth list.
         let eip7702_gas_refund = pre_exec.apply_eip7702_auth_list(ctx)? as i64;
 
+        // set what happens to the changes if the transaction fails.
+        ctx.evm.journaled_state.revert_policy = self.handler.post_execution().revert_policy(ctx);
+
         // start execution
         let exec = self.handler.execution();
 
         // create first frame action
-        let first_frame_action = exec.first_frame_creation(ctx, gas_limit)?;
+        let mut first_frame_action = exec.first_frame_creation(ctx, gas_limit)?;
+        if read_only {
+            first_frame_action = exec.first_frame_readonly(first_frame_action)?;
+        }
 
         // call handler to create first frame.
         let first_frame_or_result = match first_frame_action {
-            NewFrameAction::Call(inputs) => exec.call(ctx, inputs)?,
+            NewFrameAction::Call(inputs) => exec.call(ctx, inputs, &EMPTY_SHARED_MEMORY)?,
             NewFrameAction::Create(inputs) => exec.create(ctx, inputs)?,
             NewFrameAction::EOFCreate(inputs) => exec.eofcreate(ctx, inputs)?,
         };
 
-        // Starts the main running loop or return the result.
-        let mut result = match first_frame_or_result {
-            FrameOrResult::Frame(first_frame) => self.run_the_loop(first_frame)?,
-            FrameOrResult::Result(result) => result,
-        };
+        Ok((first_frame_or_result, eip7702_gas_refund))
+    }
 
+    /// Runs the post-execution of the transaction with the result of its first frame.
+    pub(crate) fn finish_transaction(
+        &mut self,
+        mut result: FrameResult,
+        eip7702_gas_refund: i64,
+    ) -> EVMResult<EvmWiringT> {
         let ctx = &mut self.context;
 
         // handle output of call/create calls.
@@ -394,8 +649,11 @@ impl<EvmWiringT: EvmWiring> Evm<'_, EvmWiringT> {
             .last_frame_return(ctx, &mut result)?;
 
         let post_exec = self.handler.post_execution();
-        // calculate final refund and add EIP-7702 refund to gas.
+        // add EIP-7702 refund to gas and calculate final refund.
         post_exec.refund(ctx, result.gas_mut(), eip7702_gas_refund);
+        post_exec.refund_cap(ctx, result.gas_mut());
+        // EIP-7623: raise the gas used to the calldata floor.
+        post_exec.calldata_floor(ctx, result.gas_mut());
         // Reimburse the caller
         post_exec.reimburse_caller(ctx, result.gas())?;
         // Reward beneficiary
@@ -410,17 +668,150 @@ mod tests {
 
     use super::*;
     use bytecode::{
-        opcode::{PUSH1, SSTORE},
+        opcode::{
+            ADDRESS, CALL, CREATE, DIFFICULTY, GAS, INVALID, ISZERO, JUMPDEST, JUMPI, MSTORE8, POP,
+            PUSH1, PUSH2, RETURN, SELFDESTRUCT, SLOAD, SSTORE, STOP,
+        },
         Bytecode,
     };
-    use database::BenchmarkDB;
-    use primitives::{address, TxKind, U256};
+    use database::{BenchmarkDB, InMemoryDB};
+    use database_interface::DatabaseRef;
+    use primitives::{
+        address, Address, HashMap, TxKind, B256, BEACON_ROOTS_ADDRESS, KECCAK_EMPTY, U256,
+    };
     use specification::{
         eip7702::{Authorization, RecoveredAuthorization, Signature},
         hardfork::SpecId,
     };
+    use state::AccountInfo;
+    use std::sync::Arc;
     use transaction::TransactionType;
-    use wiring::EthereumWiring;
+    use wiring::{
+        default::AccountOverride,
+        result::{HaltReason, InvalidTransaction, SelfdestructLeg},
+        EthereumWiring,
+    };
+
+    #[test]
+    #[cfg(feature = "std")]
+    // Handler slots are `Arc<dyn Fn>` without `Send + Sync` bounds.
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn max_call_depth_on_small_stack() {
+        // Calls itself with all the gas until the call depth limit is reached.
+        let bytecode = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, ADDRESS, GAS,
+                CALL, STOP,
+            ]
+            .into(),
+        );
+
+        // Frames are kept on the call stack of the loop, not on the stack of the host.
+        let max_depth = std::thread::Builder::new()
+            .stack_size(1024 * 1024)
+            .spawn(move || {
+                let mut evm = Evm::<EthereumWiring<BenchmarkDB, u64>>::builder()
+                    .with_db(BenchmarkDB::new_bytecode(bytecode))
+                    .with_external_context(0)
+                    // The gas limit is above the EIP-7825 cap.
+                    .with_spec_id(SpecId::PRAGUE)
+                    .modify_tx_env(|tx| {
+                        tx.caller = address!("0000000000000000000000000000000000000001");
+                        tx.transact_to = TxKind::Call(Address::ZERO);
+                        tx.gas_limit = 1_000_000_000_000;
+                    })
+                    .append_handler_register(|handler| {
+                        let prev_handle = handler.execution.call.clone();
+                        handler.execution.call = Arc::new(move |ctx, inputs, shared_memory| {
+                            ctx.external = ctx.external.max(ctx.evm.journaled_state.depth());
+                            prev_handle(ctx, inputs, shared_memory)
+                        });
+                    })
+                    .build();
+                assert!(evm.transact().unwrap().result.is_success());
+                evm.context.external
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+        // The call of the deepest frame fails with `CallTooDeep`.
+        assert_eq!(max_depth, CALL_STACK_LIMIT + 1);
+    }
+
+    #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
+    fn shared_memory_is_freed_on_error() {
+        let bytecode = Bytecode::new_legacy(
+            [
+                PUSH1, 0x01, PUSH1, 0x00, MSTORE8, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1,
+                0x00, PUSH1, 0x00, ADDRESS, GAS, CALL, STOP,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(|handler| {
+                let prev_handle = handler.execution.call.clone();
+                handler.execution.call = Arc::new(move |ctx, inputs, shared_memory| {
+                    if inputs.depth > 1 {
+                        return Err(EVMError::Custom("sub call".into()));
+                    }
+                    prev_handle(ctx, inputs, shared_memory)
+                });
+            })
+            .build();
+
+        // The sub call fails after the first frame wrote to memory.
+        assert!(matches!(evm.transact(), Err(EVMError::Custom(_))));
+        assert!(evm.shared_memory.is_empty());
+    }
+
+    #[test]
+    fn transact_readonly() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let build = |code: &[u8], transact_to| {
+            Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    code.to_vec().into(),
+                )))
+                .with_default_ext_ctx()
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = transact_to;
+                    tx.gas_limit = 100_000;
+                })
+                .build()
+        };
+
+        let mut evm = build(&[PUSH1, 0x00, SLOAD, STOP], TxKind::Call(Address::ZERO));
+        assert!(evm.transact_readonly().unwrap().is_success());
+        // Value transfer of the transaction.
+        evm.tx_mut().value = U256::from(1);
+        assert!(!evm.transact_readonly().unwrap().is_success());
+
+        let mut evm = build(
+            &[PUSH1, 0x01, PUSH1, 0x00, SSTORE, STOP],
+            TxKind::Call(Address::ZERO),
+        );
+        assert_eq!(
+            evm.transact_readonly().unwrap(),
+            ExecutionResult::Halt {
+                reason: HaltReason::StateChangeDuringStaticCall,
+                gas_used: 100_000,
+            }
+        );
+        // Only the read-only transaction is static.
+        assert!(evm.transact().unwrap().result.is_success());
+
+        let mut evm = build(&[STOP], TxKind::Create);
+        assert!(matches!(evm.transact_readonly(), Err(EVMError::Custom(_))));
+    }
 
     #[test]
     fn sanity_eip7702_tx() {
@@ -462,4 +853,826 @@ mod tests {
             U256::from(1)
         );
     }
+
+    #[test]
+    fn eip7702_invalid_authorizations_are_skipped() {
+        let wrong_chain = address!("0000000000000000000000000000000000000100");
+        let wrong_nonce = address!("0000000000000000000000000000000000000200");
+        let any_chain = address!("0000000000000000000000000000000000000300");
+        let authorization = |chain_id: u64, nonce, authority| {
+            RecoveredAuthorization::new_unchecked(
+                Authorization {
+                    chain_id: U256::from(chain_id),
+                    address: Address::ZERO,
+                    nonce,
+                }
+                .into_signed(Signature::test_signature()),
+                Some(authority),
+            )
+        };
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_spec_id(SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.tx_type = TransactionType::Eip7702;
+                tx.gas_limit = 200_000;
+                tx.authorization_list = vec![
+                    authorization(2, 0, wrong_chain),
+                    authorization(1, 1, wrong_nonce),
+                    authorization(0, 0, any_chain),
+                ]
+                .into();
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(wrong_chain);
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        // Every authorization is charged, none of the authorities existed so nothing is refunded.
+        assert_eq!(result.gas_used(), 21_000 + 3 * 25_000);
+        for skipped in [wrong_chain, wrong_nonce] {
+            assert_eq!(state[&skipped].info.code_hash, KECCAK_EMPTY);
+            assert_eq!(state[&skipped].info.nonce, 0);
+        }
+        assert_eq!(
+            state[&any_chain].info.code,
+            Some(Bytecode::new_eip7702(Address::ZERO))
+        );
+        assert_eq!(state[&any_chain].info.nonce, 1);
+    }
+
+    #[test]
+    fn state_override() {
+        let caller = address!("0000000000000000000000000000000000000002");
+        let contract = address!("0000000000000000000000000000000000001000");
+        let code = Bytecode::new_legacy([PUSH1, 0x00, SLOAD, PUSH1, 0x01, SSTORE].into());
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_env(|env| {
+                env.state_override.insert(
+                    caller,
+                    AccountOverride::default().with_balance(U256::from(1_000_000)),
+                );
+                env.state_override.insert(
+                    contract,
+                    AccountOverride::default()
+                        .with_code(code)
+                        .with_state_diff(HashMap::from_iter([(U256::ZERO, U256::from(7))])),
+                );
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(1);
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&caller].info.balance,
+            U256::from(1_000_000 - result.gas_used())
+        );
+        assert_eq!(
+            state[&contract].storage[&U256::from(1)].present_value,
+            U256::from(7)
+        );
+    }
+
+    #[test]
+    fn state_override_is_not_committed() {
+        let caller = address!("0000000000000000000000000000000000000002");
+        let contract = address!("0000000000000000000000000000000000001000");
+        // Copies slot 0 to slot 1 and reverts if slot 2 is set.
+        let code = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, SLOAD, PUSH1, 0x01, SSTORE, PUSH1, 0x02, SLOAD, ISZERO, PUSH1, 0x0e,
+                JUMPI, INVALID, JUMPDEST, STOP,
+            ]
+            .into(),
+        );
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+        db.insert_account_storage(contract, U256::from(3), U256::from(9))
+            .unwrap();
+        let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_env(|env| {
+                env.state_override.insert(
+                    caller,
+                    AccountOverride::default().with_balance(U256::from(1_000_000)),
+                );
+                env.state_override.insert(
+                    contract,
+                    AccountOverride::default()
+                        .with_state(HashMap::from_iter([(U256::ZERO, U256::from(7))])),
+                );
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        // Only the change of the transaction is in the state, overridden slot is not.
+        assert_eq!(
+            state[&contract]
+                .storage
+                .iter()
+                .map(|(key, slot)| (*key, slot.present_value))
+                .collect::<Vec<_>>(),
+            [(U256::from(1), U256::from(7))]
+        );
+        assert!(!state[&contract].is_created());
+
+        evm.transact_commit().unwrap();
+        let db = &evm.context.evm.db;
+        assert_eq!(db.storage_ref(contract, U256::ZERO), Ok(U256::ZERO));
+        assert_eq!(db.storage_ref(contract, U256::from(1)), Ok(U256::from(7)));
+        // Full storage override does not clear the storage in the database.
+        assert_eq!(db.storage_ref(contract, U256::from(3)), Ok(U256::from(9)));
+
+        // Overrides of a failed transaction are not committed either.
+        evm = evm
+            .modify()
+            .modify_env(|env| {
+                env.state_override.insert(
+                    contract,
+                    AccountOverride::default().with_state_diff(HashMap::from_iter([
+                        (U256::ZERO, U256::from(5)),
+                        (U256::from(2), U256::from(1)),
+                    ])),
+                );
+            })
+            .modify_tx_env(|tx| tx.nonce = 1)
+            .build();
+        assert!(!evm.transact_commit().unwrap().is_success());
+        let db = &evm.context.evm.db;
+        assert_eq!(db.storage_ref(contract, U256::ZERO), Ok(U256::ZERO));
+        assert_eq!(db.storage_ref(contract, U256::from(2)), Ok(U256::ZERO));
+    }
+
+    #[test]
+    fn typed_create_tx_errors() {
+        for (tx_type, error) in [
+            (
+                TransactionType::Eip4844,
+                InvalidTransaction::BlobCreateTransaction,
+            ),
+            (
+                TransactionType::Eip7702,
+                InvalidTransaction::Eip7702CreateTransaction,
+            ),
+        ] {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .with_default_ext_ctx()
+                .modify_tx_env(|tx| {
+                    tx.tx_type = tx_type;
+                    tx.transact_to = TxKind::Create;
+                })
+                .build();
+            assert_eq!(evm.transact().unwrap_err(), EVMError::Transaction(error));
+        }
+    }
+
+    #[test]
+    fn custom_tx_type() {
+        // Routed to `CustomTx`, which prices the transaction like a legacy one.
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.tx_type = TransactionType::Custom;
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(1);
+            })
+            .build();
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&address!("0000000000000000000000000000000000000001")]
+                .info
+                .balance,
+            U256::from(10_000_000 - result.gas_used())
+        );
+    }
+
+    #[test]
+    fn warm_set() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let code = Bytecode::new_legacy([PUSH1, 0x00, SLOAD].into());
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        let cold_gas = result.gas_used();
+
+        // Slot read by the previous transaction is warm.
+        evm.context.evm.env.warm_set.extend_from_state(&state);
+        assert!(evm.context.evm.env.warm_set.accounts[&Address::ZERO].contains(&U256::ZERO));
+        let result = evm.transact().unwrap().result;
+        assert_eq!(cold_gas - result.gas_used(), 2_000);
+    }
+
+    #[test]
+    fn selfdestruct_legs() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let code = Bytecode::new_legacy([PUSH1, 0x01, SELFDESTRUCT].into());
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let ResultAndState {
+            result,
+            selfdestruct_legs,
+            ..
+        } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let balance = U256::from(10_000_000);
+        assert_eq!(
+            selfdestruct_legs,
+            [
+                SelfdestructLeg::Debit {
+                    address: Address::ZERO,
+                    target: caller,
+                    balance,
+                },
+                SelfdestructLeg::Credit {
+                    address: Address::ZERO,
+                    target: caller,
+                    balance,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_of_finished_transaction() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, PUSH1, 0x00, SSTORE].into(),
+            )))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let snapshot = evm.snapshot();
+        assert_eq!(evm.revert_to(&snapshot), Ok(()));
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.revert_to(&snapshot),
+            Err(crate::JournalError::InvalidSnapshot)
+        );
+    }
+
+    #[test]
+    fn beacon_root_not_written_by_transactions() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_spec_id(SpecId::CANCUN)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_env(|env| {
+                env.block.timestamp = U256::from(10);
+                env.block.parent_beacon_block_root = Some(B256::repeat_byte(0x42));
+                env.state_override.insert(
+                    BEACON_ROOTS_ADDRESS,
+                    AccountOverride::default()
+                        .with_code(Bytecode::new_legacy([PUSH1, 0x00, SLOAD].into())),
+                );
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        // EIP-4788 system call is done once per block by the `BlockExecutor`.
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let changed_slots = state
+            .get(&BEACON_ROOTS_ADDRESS)
+            .map_or(0, |account| account.changed_storage_slots().count());
+        assert_eq!(changed_slots, 0);
+    }
+
+    #[test]
+    fn nonce_overflow() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        // Stores 1 to slot 0 if CREATE failed.
+        let code = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, CREATE, ISZERO, PUSH1, 0x00, SSTORE,
+            ]
+            .into(),
+        );
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_default_ext_ctx()
+            .modify_env(|env| {
+                env.state_override.insert(
+                    Address::ZERO,
+                    AccountOverride::default().with_nonce(u64::MAX),
+                );
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let contract = &state[&Address::ZERO];
+        assert_eq!(contract.info.nonce, u64::MAX);
+        assert_eq!(contract.storage[&U256::ZERO].present_value, U256::from(1));
+
+        // Transaction with the maximum nonce is rejected.
+        evm = evm
+            .modify()
+            .modify_env(|env| {
+                env.state_override.clear();
+                env.state_override
+                    .insert(caller, AccountOverride::default().with_nonce(u64::MAX));
+            })
+            .modify_tx_env(|tx| tx.nonce = u64::MAX)
+            .build();
+        assert_eq!(
+            evm.transact().unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::NonceOverflowInTransaction)
+        );
+    }
+
+    #[test]
+    fn create_collision() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let created = caller.create(0);
+        for account in [
+            AccountOverride::default().with_nonce(1),
+            AccountOverride::default().with_code(Bytecode::new_legacy([PUSH1, 0x00].into())),
+        ] {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .with_default_ext_ctx()
+                .modify_env(|env| {
+                    env.state_override.insert(created, account);
+                })
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Create;
+                    tx.gas_limit = 100_000;
+                })
+                .build();
+
+            // Collision consumes all gas.
+            assert_eq!(
+                evm.transact().unwrap().result,
+                ExecutionResult::Halt {
+                    reason: HaltReason::CreateCollision,
+                    gas_used: 100_000,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn created_account_nonce() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        // EIP-161: created accounts start with nonce one.
+        for (spec_id, nonce) in [(SpecId::HOMESTEAD, 0), (SpecId::SPURIOUS_DRAGON, 1)] {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_spec_id(spec_id)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .with_default_ext_ctx()
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Create;
+                    tx.gas_limit = 100_000;
+                })
+                .build();
+
+            let ResultAndState { result, state, .. } = evm.transact().unwrap();
+            assert!(result.is_success());
+            assert_eq!(state[&caller.create(0)].info.nonce, nonce);
+        }
+    }
+
+    #[test]
+    fn contract_code_size_limit() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        // Returns 0x6001 zero bytes, one byte more than the EIP-170 limit.
+        let init_code = [PUSH2, 0x60, 0x01, PUSH1, 0x00, RETURN];
+        let transact = |spec_id, limit| {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_spec_id(spec_id)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .with_default_ext_ctx()
+                .modify_cfg_env(|cfg| cfg.limit_contract_code_size = limit)
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Create;
+                    tx.data = init_code.into();
+                    tx.gas_limit = 10_000_000;
+                })
+                .build();
+            evm.transact().unwrap().result
+        };
+
+        assert_eq!(
+            transact(SpecId::CANCUN, None),
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractSizeLimit,
+                gas_used: 10_000_000,
+            }
+        );
+        assert!(transact(SpecId::HOMESTEAD, None).is_success());
+        assert!(transact(SpecId::CANCUN, Some(0x6001)).is_success());
+        assert!(transact(SpecId::CANCUN, Some(usize::MAX)).is_success());
+    }
+
+    #[test]
+    fn code_starting_with_ef() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        // Returns the single byte 0xEF.
+        let init_code = [
+            PUSH1, 0xEF, PUSH1, 0x00, MSTORE8, PUSH1, 0x01, PUSH1, 0x00, RETURN,
+        ];
+        let transact = |spec_id| {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_spec_id(spec_id)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .with_default_ext_ctx()
+                .modify_tx_env(|tx| {
+                    tx.caller = caller;
+                    tx.transact_to = TxKind::Create;
+                    tx.data = init_code.into();
+                    tx.gas_limit = 100_000;
+                })
+                .build();
+            evm.transact().unwrap().result
+        };
+
+        // EIP-3541: Reject new contract code starting with the 0xEF byte.
+        assert_eq!(
+            transact(SpecId::LONDON),
+            ExecutionResult::Halt {
+                reason: HaltReason::CreateContractStartingWithEF,
+                gas_used: 100_000,
+            }
+        );
+        assert!(transact(SpecId::BERLIN).is_success());
+    }
+
+    #[test]
+    fn difficulty_returns_prevrandao() {
+        let contract = address!("0000000000000000000000000000000000000000");
+        let prevrandao = B256::with_last_byte(0x42);
+        let transact = |spec_id| {
+            let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+                .with_spec_id(spec_id)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    [DIFFICULTY, PUSH1, 0x00, SSTORE].into(),
+                )))
+                .with_default_ext_ctx()
+                .modify_block_env(|block| {
+                    block.difficulty = U256::from(7);
+                    block.prevrandao = Some(prevrandao);
+                })
+                .modify_tx_env(|tx| {
+                    tx.caller = address!("0000000000000000000000000000000000000001");
+                    tx.transact_to = TxKind::Call(contract);
+                    tx.gas_limit = 100_000;
+                })
+                .build();
+            let state = evm.transact().unwrap().state;
+            state[&contract].storage[&U256::ZERO].present_value
+        };
+
+        assert_eq!(transact(SpecId::LONDON), U256::from(7));
+        // EIP-4399: Supplant DIFFICULTY opcode with PREVRANDAO
+        assert_eq!(transact(SpecId::MERGE), U256::from_be_bytes(prevrandao.0));
+    }
+
+    #[test]
+    fn balance_overflow() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, SELFDESTRUCT].into(),
+            )))
+            .with_default_ext_ctx()
+            .modify_env(|env| {
+                env.state_override.insert(
+                    Address::ZERO,
+                    AccountOverride::default().with_balance(U256::MAX),
+                );
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.value = U256::from(1);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+
+        // Call value would overflow the balance of the target.
+        let result = evm.transact().unwrap().result;
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OverflowPayment,
+                gas_used: 100_000,
+            }
+        );
+
+        // Selfdestruct sweep would overflow the balance of the beneficiary.
+        evm = evm
+            .modify()
+            .modify_env(|env| {
+                env.state_override.clear();
+                env.state_override
+                    .insert(caller, AccountOverride::default().with_balance(U256::MAX));
+            })
+            .modify_tx_env(|tx| tx.value = U256::ZERO)
+            .build();
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert_eq!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OverflowPayment,
+                gas_used: 100_000,
+            }
+        );
+        assert_eq!(state[&caller].info.balance, U256::MAX);
+    }
+
+    #[test]
+    fn state_clear() {
+        // Calls the empty account 0xee, RIPEMD and SHA256 without gas. Both precompile
+        // calls revert, but RIPEMD stays touched.
+        let mut code = Vec::new();
+        for target in [0xee, 0x03, 0x02] {
+            code.extend([
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, target,
+                PUSH1, 0x00, CALL, POP,
+            ]);
+        }
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 200_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert!(state[&Address::with_last_byte(0xee)].is_state_cleared());
+        assert!(state[&Address::with_last_byte(0x03)].is_state_cleared());
+        assert!(state[&Address::with_last_byte(0x03)].is_touch_kept_on_revert());
+        assert!(!state[&Address::with_last_byte(0xee)].is_touch_kept_on_revert());
+        let sha256 = &state[&Address::with_last_byte(0x02)];
+        assert!(!sha256.is_touched());
+        assert!(!sha256.is_state_cleared());
+        assert!(!state[&Address::ZERO].is_state_cleared());
+
+        // Empty accounts are kept before Spurious Dragon.
+        evm = evm.modify().with_spec_id(SpecId::HOMESTEAD).build();
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let account = &state[&Address::with_last_byte(0xee)];
+        assert!(account.is_touched());
+        assert!(!account.is_state_cleared());
+        assert!(account.is_empty_created());
+        // The RIPEMD exception does not exist before Spurious Dragon.
+        let ripemd = &state[&Address::with_last_byte(0x03)];
+        assert!(!ripemd.is_touched());
+        assert!(!ripemd.is_touch_kept_on_revert());
+        assert!(!ripemd.is_empty_created());
+        assert!(!state[&Address::ZERO].is_empty_created());
+    }
+
+    #[test]
+    fn call_value_to_precompile() {
+        // Sends 1 wei to SHA256 with empty input, and 1 wei to RIPEMD with an input that
+        // costs more than the call stipend.
+        let code = [
+            PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x01, PUSH1, 0x02, PUSH1,
+            0x00, CALL, POP, PUSH1, 0x00, PUSH1, 0x00, PUSH2, 0x02, 0x00, PUSH1, 0x00, PUSH1, 0x01,
+            PUSH1, 0x03, PUSH1, 0x00, CALL, POP,
+        ];
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 200_000;
+            })
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(state[&Address::ZERO].info.balance, U256::from(9_999_999));
+
+        // Value stays with the precompile that succeeded.
+        let sha256 = &state[&Address::with_last_byte(0x02)];
+        assert_eq!(sha256.info.balance, U256::from(1));
+        assert!(sha256.is_touched());
+        assert!(!sha256.is_state_cleared());
+
+        // Value of the failed call is returned, but RIPEMD stays touched and is cleared.
+        let ripemd = &state[&Address::with_last_byte(0x03)];
+        assert_eq!(ripemd.info.balance, U256::ZERO);
+        assert!(ripemd.is_touch_kept_on_revert());
+        assert!(ripemd.is_state_cleared());
+    }
+
+    #[cfg(all(
+        feature = "optional_no_base_fee",
+        feature = "optional_balance_check",
+        feature = "optional_block_gas_limit"
+    ))]
+    #[test]
+    fn eth_call_checks_disabled() {
+        // Unfunded caller with zero gas price and a gas limit above the block gas limit.
+        let caller = address!("0000000000000000000000000000000000000002");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.basefee = U256::from(10);
+                block.gas_limit = U256::from(30_000);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+                tx.value = U256::from(1);
+            })
+            .build();
+        assert_eq!(
+            evm.transact().unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::GasPriceLessThanBasefee)
+        );
+
+        evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.disable_base_fee = true)
+            .build();
+        assert_eq!(
+            evm.transact().unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)
+        );
+
+        evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.disable_block_gas_limit = true)
+            .build();
+        assert!(matches!(
+            evm.transact().unwrap_err(),
+            EVMError::Transaction(InvalidTransaction::LackOfFundForMaxFee { .. })
+        ));
+
+        evm = evm
+            .modify()
+            .modify_cfg_env(|cfg| cfg.disable_balance_check = true)
+            .build();
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        // Caller is credited with the missing value and pays no fee.
+        assert_eq!(state[&caller].info.balance, U256::ZERO);
+        assert_eq!(state[&Address::ZERO].info.balance, U256::from(10_000_001));
+    }
+
+    #[cfg(feature = "tx-arena")]
+    #[test]
+    fn tx_arena() {
+        // Each call writes to its own journal.
+        let mut code = Vec::new();
+        for _ in 0..4 {
+            code.extend([
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0xee,
+                PUSH1, 0x00, CALL, POP,
+            ]);
+        }
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.into())))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 200_000;
+            })
+            .build();
+
+        assert!(evm.transact().unwrap().result.is_success());
+        let free_journals = evm.context.evm.journaled_state.arena.free_journals();
+        assert!(free_journals > 0);
+
+        // Next transaction reuses the same allocations.
+        assert!(evm.transact().unwrap().result.is_success());
+        assert_eq!(
+            evm.context.evm.journaled_state.arena.free_journals(),
+            free_journals
+        );
+    }
+
+    #[cfg(feature = "tx-arena")]
+    #[test]
+    fn tx_arena_error_paths() {
+        use bytecode::opcode::{LOG1, REVERT};
+        use database::{ExecutionWitness, WitnessDB};
+
+        let caller = address!("0000000000000000000000000000000000000001");
+        // Emits a log with 32 bytes of data and reverts.
+        let code = Bytecode::new_raw(
+            [
+                PUSH1, 0xaa, PUSH1, 0x20, PUSH1, 0x00, LOG1, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        assert!(matches!(
+            evm.transact().unwrap().result,
+            ExecutionResult::Revert { .. }
+        ));
+        // Buffers of the reverted log are kept.
+        assert_eq!(
+            evm.context.evm.journaled_state.arena.free_log_buffers(),
+            (1, 1)
+        );
+
+        // Reading the storage slot fails, the call stack is given back anyway.
+        let code = Bytecode::new_raw([PUSH1, 0x00, SLOAD, STOP].into());
+        let contract = address!("0000000000000000000000000000000000001000");
+        let mut witness = ExecutionWitness::default();
+        witness.accounts.insert(
+            caller,
+            Some(AccountInfo::from_balance(U256::from(1_000_000))),
+        );
+        witness
+            .accounts
+            .insert(contract, Some(AccountInfo::from_bytecode(code)));
+        witness.accounts.insert(Address::ZERO, None);
+        let mut evm = Evm::<EthereumWiring<WitnessDB, ()>>::builder()
+            .with_db(WitnessDB::new(witness))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        assert!(!evm.context.evm.journaled_state.arena.has_free_call_stack());
+        assert!(matches!(evm.transact(), Err(EVMError::Database(_))));
+        assert!(evm.context.evm.journaled_state.arena.has_free_call_stack());
+    }
 }
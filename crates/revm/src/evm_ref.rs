@@ -0,0 +1,238 @@
+use crate::{Context, Evm, EvmContext, EvmWiring};
+use database_interface::{DatabaseRef, WrapDatabaseRef};
+use specification::hardfork::SpecId;
+use std::vec::Vec;
+use wiring::{
+    default::{block::BlockEnv, CfgEnv, Env, TxEnv},
+    result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction, ResultAndState},
+    EthereumWiring,
+};
+
+/// Wiring used by [`EvmRef`] for a single call over a borrowed database.
+pub type EvmRefWiring<'db, DB> = EthereumWiring<WrapDatabaseRef<&'db DB>, ()>;
+
+/// Result of a single [`EvmRef`] execution.
+pub type EvmRefResult<DBError> =
+    Result<ResultAndState<HaltReason>, EVMError<DBError, InvalidTransaction>>;
+
+/// Read-only EVM that executes transactions against a shared [`DatabaseRef`].
+///
+/// Every call gets its own journal on top of the borrowed database and the resulting
+/// state is returned instead of committed, so one instance can serve many concurrent
+/// `eth_call`s through `&self` without cloning or locking the database.
+#[derive(Clone, Debug)]
+pub struct EvmRef<DB> {
+    db: DB,
+    cfg: CfgEnv,
+    block: BlockEnv,
+    spec_id: SpecId,
+}
+
+impl<DB: DatabaseRef> EvmRef<DB> {
+    /// Creates new read-only EVM over the given database.
+    pub fn new(db: DB, spec_id: SpecId) -> Self {
+        Self {
+            db,
+            cfg: CfgEnv::default(),
+            block: BlockEnv::default(),
+            spec_id,
+        }
+    }
+
+    /// Sets the configuration used for all calls.
+    pub fn with_cfg(mut self, cfg: CfgEnv) -> Self {
+        self.cfg = cfg;
+        self
+    }
+
+    /// Sets the block environment used for all calls.
+    pub fn with_block(mut self, block: BlockEnv) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Returns the shared database.
+    pub fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Returns the configuration used for all calls.
+    pub fn cfg(&self) -> &CfgEnv {
+        &self.cfg
+    }
+
+    /// Returns the block environment used for all calls.
+    pub fn block(&self) -> &BlockEnv {
+        &self.block
+    }
+
+    /// Returns the specification id used for all calls.
+    pub fn spec_id(&self) -> SpecId {
+        self.spec_id
+    }
+
+    /// Executes the transaction and returns the result with the changed state.
+    ///
+    /// State changes are never written to the database.
+    pub fn transact(&self, tx: TxEnv) -> EvmRefResult<DB::Error> {
+        self.build_evm(tx).transact()
+    }
+
+    /// Executes the transaction in a static context and returns the result.
+    ///
+    /// See [`Evm::transact_readonly`].
+    pub fn transact_readonly(
+        &self,
+        tx: TxEnv,
+    ) -> Result<ExecutionResult<HaltReason>, EVMError<DB::Error, InvalidTransaction>> {
+        self.build_evm(tx).transact_readonly()
+    }
+
+    /// Executes the transaction against each of the given specifications.
+    ///
+    /// Every execution starts from the same database state, results are returned in the
+    /// order of the specifications. Useful to check how a hardfork changes the outcome of
+    /// a transaction.
+    pub fn transact_specs(
+        &self,
+        tx: TxEnv,
+        specs: impl IntoIterator<Item = SpecId>,
+    ) -> Vec<(SpecId, EvmRefResult<DB::Error>)> {
+        specs
+            .into_iter()
+            .map(|spec_id| {
+                let result = self
+                    .build_evm_with(tx.clone(), spec_id, self.cfg.clone())
+                    .transact();
+                (spec_id, result)
+            })
+            .collect()
+    }
+
+    /// Executes the transaction against each of the given specification and configuration
+    /// pairs.
+    ///
+    /// Same as [`EvmRef::transact_specs`] but the configuration of this EVM is replaced
+    /// for each execution.
+    pub fn transact_configs(
+        &self,
+        tx: TxEnv,
+        configs: impl IntoIterator<Item = (SpecId, CfgEnv)>,
+    ) -> Vec<EvmRefResult<DB::Error>> {
+        configs
+            .into_iter()
+            .map(|(spec_id, cfg)| self.build_evm_with(tx.clone(), spec_id, cfg).transact())
+            .collect()
+    }
+
+    /// Builds a single-use [`Evm`] for the given transaction.
+    pub fn build_evm(&self, tx: TxEnv) -> Evm<'_, EvmRefWiring<'_, DB>> {
+        self.build_evm_with(tx, self.spec_id, self.cfg.clone())
+    }
+
+    fn build_evm_with(
+        &self,
+        tx: TxEnv,
+        spec_id: SpecId,
+        cfg: CfgEnv,
+    ) -> Evm<'_, EvmRefWiring<'_, DB>> {
+        let env = Env::boxed(cfg, self.block.clone(), tx);
+        Evm::new(
+            Context::new(EvmContext::new_with_env(WrapDatabaseRef(&self.db), env), ()),
+            EvmRefWiring::<'_, DB>::handler(spec_id),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{CHAINID, MSTORE, PUSH0, PUSH1, RETURN},
+        Bytecode,
+    };
+    use database::InMemoryDB;
+    use primitives::{address, Bytes, TxKind, U256};
+    use state::AccountInfo;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn concurrent_calls_share_db() {
+        let contract = address!("0000000000000000000000000000000000001000");
+        let code = Bytecode::new_legacy(
+            [PUSH1, 0x2a, PUSH1, 0, MSTORE, PUSH1, 0x20, PUSH1, 0, RETURN].into(),
+        );
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let evm = EvmRef::new(db, SpecId::CANCUN);
+        std::thread::scope(|scope| {
+            for i in 0..4u8 {
+                let evm = &evm;
+                scope.spawn(move || {
+                    let tx = TxEnv {
+                        caller: primitives::Address::with_last_byte(0xf0 + i),
+                        transact_to: TxKind::Call(contract),
+                        gas_limit: 100_000,
+                        ..Default::default()
+                    };
+                    let ResultAndState { result, state, .. } = evm.transact(tx).unwrap();
+                    assert!(result.is_success());
+                    assert_eq!(
+                        result.into_output(),
+                        Some(U256::from(0x2a).to_be_bytes_vec().into())
+                    );
+                    assert!(state.contains_key(&contract));
+                });
+            }
+        });
+        // Nothing is committed to the shared database.
+        assert_eq!(evm.db().accounts.len(), 1);
+    }
+
+    #[test]
+    fn transact_against_specs() {
+        let contract = address!("0000000000000000000000000000000000001000");
+        // PUSH0 is introduced in Shanghai.
+        let code =
+            Bytecode::new_legacy([CHAINID, PUSH0, MSTORE, PUSH1, 0x20, PUSH0, RETURN].into());
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(contract, AccountInfo::from_bytecode(code));
+
+        let evm = EvmRef::new(db, SpecId::CANCUN);
+        let tx = TxEnv {
+            caller: address!("0000000000000000000000000000000000000001"),
+            transact_to: TxKind::Call(contract),
+            gas_limit: 100_000,
+            // Legacy transaction without a chain id, valid on any chain.
+            chain_id: None,
+            ..Default::default()
+        };
+
+        let results = evm.transact_specs(tx.clone(), [SpecId::LONDON, SpecId::SHANGHAI]);
+        assert_eq!(results[0].0, SpecId::LONDON);
+        assert!(matches!(
+            results[0].1.as_ref().unwrap().result,
+            ExecutionResult::Halt {
+                reason: HaltReason::NotActivated,
+                ..
+            }
+        ));
+        assert_eq!(results[1].0, SpecId::SHANGHAI);
+        assert!(results[1].1.as_ref().unwrap().result.is_success());
+
+        let results = evm.transact_configs(
+            tx,
+            [
+                (SpecId::SHANGHAI, CfgEnv::default()),
+                (SpecId::SHANGHAI, CfgEnv::default().with_chain_id(10)),
+            ],
+        );
+        let outputs: Vec<_> = results
+            .into_iter()
+            .map(|result| result.unwrap().result.into_output().unwrap())
+            .collect();
+        assert_eq!(outputs[0], Bytes::from(U256::from(1).to_be_bytes_vec()));
+        assert_eq!(outputs[1], Bytes::from(U256::from(10).to_be_bytes_vec()));
+    }
+}
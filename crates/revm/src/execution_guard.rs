@@ -0,0 +1,244 @@
+use crate::{handler::register::HandleRegisterBox, EvmWiring};
+#[cfg(feature = "std")]
+use core::time::Duration;
+use core::{cell::Cell, mem};
+use interpreter::{table::InstructionTables, InstructionResult, EMPTY_SHARED_MEMORY};
+#[cfg(feature = "std")]
+use std::time::Instant;
+use std::{boxed::Box, rc::Rc, sync::Arc};
+
+/// Number of instructions between two checks of the wall-clock timeout, a power of two.
+#[cfg(feature = "std")]
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+/// Limits of a transaction execution that are enforced in addition to the gas limit.
+///
+/// When a limit is exceeded the execution is stopped and the transaction halts with
+/// [`HaltReason::Interrupted`](wiring::result::HaltReason::Interrupted). Services that
+/// simulate untrusted transactions can use it to bound the work done per transaction, as
+/// cheap opcodes allow a lot of work within the block gas limit.
+///
+/// Use [`execution_guard_register`] or [`EvmBuilder::with_execution_guard`] to install it.
+///
+/// [`EvmBuilder::with_execution_guard`]: crate::EvmBuilder::with_execution_guard
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionGuard {
+    /// Maximum number of executed instructions over all frames.
+    pub max_instructions: Option<u64>,
+    /// Maximum size of the memory of all frames, in bytes.
+    ///
+    /// Checked when the memory is resized, before it is allocated.
+    pub max_memory: Option<usize>,
+    /// Maximum wall-clock duration of the execution.
+    ///
+    /// Checked every 1024 instructions, so a single slow instruction like a precompile call
+    /// is not interrupted.
+    #[cfg(feature = "std")]
+    pub timeout: Option<Duration>,
+}
+
+impl ExecutionGuard {
+    /// Sets the maximum number of executed instructions.
+    pub fn with_max_instructions(mut self, max_instructions: u64) -> Self {
+        self.max_instructions = Some(max_instructions);
+        self
+    }
+
+    /// Sets the maximum size of the memory of all frames.
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Sets the maximum wall-clock duration of the execution.
+    #[cfg(feature = "std")]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// Execution progress of the current transaction.
+#[derive(Debug, Default)]
+struct GuardState {
+    instructions: Cell<u64>,
+    interrupted: Cell<bool>,
+    #[cfg(feature = "std")]
+    deadline: Cell<Option<Instant>>,
+}
+
+impl GuardState {
+    /// Resets the progress at the start of a transaction.
+    fn reset(&self, _guard: &ExecutionGuard) {
+        self.instructions.set(0);
+        self.interrupted.set(false);
+        #[cfg(feature = "std")]
+        self.deadline
+            .set(_guard.timeout.map(|timeout| Instant::now() + timeout));
+    }
+
+    /// Counts the instruction and returns `true` if the execution needs to be interrupted.
+    ///
+    /// Once interrupted, every following instruction is interrupted too, so the parent frames
+    /// stop as well.
+    #[inline]
+    fn should_interrupt(&self, guard: &ExecutionGuard) -> bool {
+        if self.interrupted.get() {
+            return true;
+        }
+        let instructions = self.instructions.get() + 1;
+        self.instructions.set(instructions);
+
+        let interrupted = guard.max_instructions.is_some_and(|max| instructions > max);
+        #[cfg(feature = "std")]
+        let interrupted = interrupted
+            || (instructions & (TIMEOUT_CHECK_INTERVAL - 1) == 0
+                && self
+                    .deadline
+                    .get()
+                    .is_some_and(|deadline| Instant::now() >= deadline));
+        self.interrupted.set(interrupted);
+        interrupted
+    }
+}
+
+/// Handle register that enforces the limits of the [`ExecutionGuard`].
+///
+/// Limits apply per transaction, the progress is reset when the first frame is created.
+///
+/// The register replaces the `execute_frame` handle with a loop that counts the instructions,
+/// so the instruction table is left as it is. Registers appended after this one that replace
+/// `execute_frame` again, like [`hot_contracts_register`](crate::handler::specialize::hot_contracts_register), are
+/// not guarded.
+pub fn execution_guard_register<'a, EvmWiringT: EvmWiring>(
+    guard: ExecutionGuard,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let state = Rc::new(GuardState::default());
+
+        let state_inner = state.clone();
+        let prev_handle = handler.execution.first_frame_creation.clone();
+        handler.execution.first_frame_creation = Arc::new(move |ctx, gas_limit| {
+            state_inner.reset(&guard);
+            prev_handle(ctx, gas_limit)
+        });
+
+        handler.execution.execute_frame =
+            Arc::new(move |frame, shared_memory, instruction_tables, context| {
+                let interpreter = frame.interpreter_mut();
+                let mut memory = mem::replace(shared_memory, EMPTY_SHARED_MEMORY);
+                memory.set_interrupt_limit(guard.max_memory);
+                let should_interrupt = || state.should_interrupt(&guard);
+                let next_action = match instruction_tables {
+                    InstructionTables::Plain(table) => {
+                        interpreter.run_interruptible(memory, table, context, should_interrupt)
+                    }
+                    InstructionTables::Boxed(table) => {
+                        interpreter.run_interruptible(memory, table, context, should_interrupt)
+                    }
+                };
+                *shared_memory = interpreter.take_memory();
+                // Frames interrupted by the memory limit stop their parents too.
+                if interpreter.instruction_result == InstructionResult::Interrupted {
+                    state.interrupted.set(true);
+                }
+                Ok(next_action)
+            });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use bytecode::{
+        opcode::{CALL, GAS, JUMP, JUMPDEST, MSTORE, PUSH1, PUSH2, PUSH3},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, TxKind};
+    use wiring::{
+        result::{ExecutionResult, HaltReason},
+        EthereumWiring,
+    };
+
+    fn transact(bytecode: Bytecode, guard: ExecutionGuard) -> ExecutionResult<HaltReason> {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 1_000_000;
+            })
+            .with_execution_guard(guard)
+            .build();
+        let result = evm.transact().unwrap().result;
+        // Progress is reset for the next transaction.
+        assert_eq!(evm.transact().unwrap().result, result);
+        result
+    }
+
+    fn is_interrupted(result: &ExecutionResult<HaltReason>) -> bool {
+        matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::Interrupted,
+                ..
+            }
+        )
+    }
+
+    #[test]
+    fn max_instructions() {
+        // Infinite loop.
+        let bytecode = Bytecode::new_legacy([JUMPDEST, PUSH1, 0x00, JUMP].into());
+        let result = transact(
+            bytecode.clone(),
+            ExecutionGuard::default().with_max_instructions(1_000),
+        );
+        assert!(is_interrupted(&result));
+        // Without a limit the loop runs out of gas.
+        let result = transact(bytecode, ExecutionGuard::default());
+        assert!(!is_interrupted(&result) && !result.is_success());
+    }
+
+    #[test]
+    fn max_memory() {
+        // Stores a word at offset 0x1000 and calls itself.
+        let bytecode = Bytecode::new_legacy(
+            [
+                PUSH1, 0x01, PUSH2, 0x10, 0x00, MSTORE, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00,
+                PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00, GAS, CALL,
+            ]
+            .into(),
+        );
+        // Each frame uses 0x1020 bytes.
+        let result = transact(
+            bytecode.clone(),
+            ExecutionGuard::default().with_max_memory(0x1020 * 4),
+        );
+        assert!(is_interrupted(&result));
+        let result = transact(
+            bytecode,
+            ExecutionGuard::default().with_max_memory(usize::MAX),
+        );
+        assert!(result.is_success());
+
+        // A single expansion over the limit is interrupted before it is allocated.
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH3, 0x10, 0x00, 0x00, MSTORE].into());
+        let result = transact(bytecode, ExecutionGuard::default().with_max_memory(0x1000));
+        assert!(is_interrupted(&result));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timeout() {
+        let bytecode = Bytecode::new_legacy([JUMPDEST, PUSH1, 0x00, JUMP].into());
+        let result = transact(
+            bytecode,
+            ExecutionGuard::default().with_timeout(Duration::ZERO),
+        );
+        assert!(is_interrupted(&result));
+    }
+}
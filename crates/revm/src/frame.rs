@@ -53,7 +53,7 @@ pub enum Frame {
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum FrameResult {
     Call(CallOutcome),
     Create(CreateOutcome),
@@ -0,0 +1,106 @@
+//! Gas computation without an interpreter.
+//!
+//! Functions that gas estimators can use to compute bounds of a transaction, e.g. its intrinsic
+//! gas or the cost of the memory it needs, without executing it.
+
+use interpreter::gas;
+use specification::hardfork::SpecId;
+use transaction::{Eip7702Tx, Transaction};
+use wiring::TransactionType;
+
+pub use interpreter::gas::{
+    calldata_cost, calldata_floor_gas, calldata_tokens, copy_cost, copy_cost_verylow,
+    cost_per_word, create2_cost, exp_cost, initcode_cost, keccak256_cost, log_cost,
+    memory_expansion_cost, memory_gas, memory_gas_for_len, sload_cost, sstore_cost, sstore_refund,
+    warm_cold_cost, Gas, GasTable, ACCESS_LIST_ADDRESS, ACCESS_LIST_STORAGE_KEY, CALL_STIPEND,
+    COPY, CREATE, INITCODE_WORD_COST, KECCAK256WORD, MEMORY, STANDARD_TOKEN_COST,
+    TOTAL_COST_FLOOR_PER_TOKEN, TRANSACTION_NON_ZERO_DATA_FRONTIER, TRANSACTION_NON_ZERO_DATA_INIT,
+    TRANSACTION_ZERO_DATA,
+};
+
+/// Intrinsic gas of the transaction, the gas it pays before any code is executed.
+///
+/// Contains the base cost and the costs of the input data, the access list and the
+/// authorization list. A transaction with a lower gas limit is invalid.
+pub fn intrinsic_gas<T: Transaction>(spec_id: SpecId, tx: &T) -> u64 {
+    let tx_type = tx.tx_type().into();
+    let authorization_list_num = if tx_type == TransactionType::Eip7702 {
+        tx.eip7702().authorization_list_len() as u64
+    } else {
+        0
+    };
+
+    gas::validate_initial_tx_gas(
+        spec_id,
+        tx.common_fields().input(),
+        tx.kind().is_create(),
+        tx.access_list(),
+        authorization_list_num,
+    )
+}
+
+/// EIP-7623 floor of the gas used by the transaction, `0` before Prague.
+///
+/// The transaction pays the maximum of the floor and the gas it used after refunds.
+pub fn floor_gas<T: Transaction>(spec_id: SpecId, tx: &T) -> u64 {
+    if spec_id.is_enabled_in(SpecId::PRAGUE) {
+        gas::calldata_floor_gas(tx.common_fields().input())
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{Bytes, TxKind};
+    use wiring::default::TxEnv;
+
+    #[test]
+    fn intrinsic_gas_of_transactions() {
+        let mut tx = TxEnv::default();
+        assert_eq!(intrinsic_gas(SpecId::CANCUN, &tx), 21_000);
+
+        tx.data = Bytes::from_static(&[0, 1]);
+        assert_eq!(intrinsic_gas(SpecId::CANCUN, &tx), 21_000 + 4 + 16);
+        assert_eq!(intrinsic_gas(SpecId::BYZANTIUM, &tx), 21_000 + 4 + 68);
+
+        tx.transact_to = TxKind::Create;
+        // Initcode of one word.
+        assert_eq!(intrinsic_gas(SpecId::CANCUN, &tx), 53_000 + 4 + 16 + 2);
+    }
+
+    #[test]
+    fn calldata_floor() {
+        let mut tx = TxEnv {
+            data: Bytes::from_static(&[0, 1]),
+            ..Default::default()
+        };
+        assert_eq!(calldata_tokens(&tx.data, true), 1 + 4);
+        assert_eq!(calldata_tokens(&tx.data, false), 1 + 17);
+        assert_eq!(floor_gas(SpecId::CANCUN, &tx), 0);
+        assert_eq!(floor_gas(SpecId::PRAGUE, &tx), 21_000 + 5 * 10);
+
+        // Floor of 100 non zero bytes is above their standard cost.
+        tx.data = Bytes::from_static(&[1; 100]);
+        assert_eq!(floor_gas(SpecId::PRAGUE, &tx), 25_000);
+        assert_eq!(intrinsic_gas(SpecId::PRAGUE, &tx), 22_600);
+    }
+
+    #[test]
+    fn memory_expansion() {
+        assert_eq!(memory_expansion_cost(0, 0), 0);
+        assert_eq!(memory_expansion_cost(0, 32), 3);
+        assert_eq!(memory_expansion_cost(0, 33), 6);
+        assert_eq!(memory_expansion_cost(64, 32), 0);
+        // Quadratic cost of 1024 words.
+        assert_eq!(
+            memory_expansion_cost(0, 32 * 1024),
+            3 * 1024 + 1024 * 1024 / 512
+        );
+        assert_eq!(
+            memory_expansion_cost(32, 32 * 1024),
+            memory_gas(1024) - memory_gas(1)
+        );
+    }
+}
@@ -2,6 +2,7 @@
 mod handle_types;
 pub mod mainnet;
 pub mod register;
+pub mod specialize;
 
 // Exports.
 pub use handle_types::*;
@@ -9,15 +9,18 @@ pub mod validation;
 // Exports
 
 pub use execution::{
-    ExecutionHandler, FrameCallHandle, FrameCallReturnHandle, FrameCreateHandle,
-    FrameCreateReturnHandle, InsertCallOutcomeHandle, InsertCreateOutcomeHandle,
+    ExecutionHandler, FirstFrameReadOnlyHandle, FrameCallHandle, FrameCallReturnHandle,
+    FrameCreateHandle, FrameCreateReturnHandle, InsertCallOutcomeHandle, InsertCreateOutcomeHandle,
+    TransferHandle,
 };
 pub use generic::{GenericContextHandle, GenericContextHandleRet};
 pub use post_execution::{
-    EndHandle, OutputHandle, PostExecutionHandler, ReimburseCallerHandle, RewardBeneficiaryHandle,
+    CalldataFloorHandle, EndHandle, OutputHandle, PostExecutionHandler, RefundCapHandle,
+    RefundHandle, ReimburseCallerHandle, RewardBeneficiaryHandle,
 };
 pub use pre_execution::{
-    DeductCallerHandle, LoadAccountsHandle, LoadPrecompilesHandle, PreExecutionHandler,
+    ApplyStateOverrideHandle, DeductCallerHandle, LoadAccountsHandle, LoadPrecompilesHandle,
+    PreExecutionHandler,
 };
 pub use validation::{
     ValidateEnvHandle, ValidateInitialTxGasHandle, ValidateTxEnvAgainstState, ValidationHandler,
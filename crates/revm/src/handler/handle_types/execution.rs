@@ -1,11 +1,14 @@
 use crate::{
     frame::EOFCreateFrame, handler::mainnet, CallFrame, Context, CreateFrame, EvmWiring, Frame,
-    FrameOrResult, FrameResult,
+    FrameOrResult, FrameResult, InnerEvmContext,
 };
+use database_interface::Database;
 use interpreter::{
     table::InstructionTables, CallInputs, CallOutcome, CreateInputs, CreateOutcome,
-    EOFCreateInputs, InterpreterAction, InterpreterResult, NewFrameAction, SharedMemory,
+    EOFCreateInputs, InstructionResult, InterpreterAction, InterpreterResult, NewFrameAction,
+    SharedMemory,
 };
+use primitives::{Address, U256};
 use specification::hardfork::Spec;
 use std::{boxed::Box, sync::Arc};
 use wiring::result::EVMResultGeneric;
@@ -14,6 +17,10 @@ use wiring::result::EVMResultGeneric;
 pub type FirstFrameCreation<'a, EvmWiringT> =
     Arc<dyn Fn(&mut Context<EvmWiringT>, u64) -> EVMResultGeneric<NewFrameAction, EvmWiringT> + 'a>;
 
+/// Turns the first frame action into the action of a read-only transaction.
+pub type FirstFrameReadOnlyHandle<'a, EvmWiringT> =
+    Arc<dyn Fn(NewFrameAction) -> EVMResultGeneric<NewFrameAction, EvmWiringT> + 'a>;
+
 /// Handles first frame return handle.
 pub type LastFrameReturnHandle<'a, EvmWiringT> = Arc<
     dyn Fn(&mut Context<EvmWiringT>, &mut FrameResult) -> EVMResultGeneric<(), EvmWiringT> + 'a,
@@ -31,8 +38,15 @@ pub type ExecuteFrameHandle<'a, EvmWiringT> = Arc<
 >;
 
 /// Handle sub call.
+///
+/// Shared memory is needed to read the call data that is referenced by the inputs, see
+/// [`CallInput`](interpreter::CallInput).
 pub type FrameCallHandle<'a, EvmWiringT> = Arc<
-    dyn Fn(&mut Context<EvmWiringT>, Box<CallInputs>) -> EVMResultGeneric<FrameOrResult, EvmWiringT>
+    dyn Fn(
+            &mut Context<EvmWiringT>,
+            Box<CallInputs>,
+            &SharedMemory,
+        ) -> EVMResultGeneric<FrameOrResult, EvmWiringT>
         + 'a,
 >;
 
@@ -107,11 +121,32 @@ pub type InsertEOFCreateOutcomeHandle<'a, EvmWiringT> = Arc<
         + 'a,
 >;
 
+/// Transfers value from the first to the second account, for calls and creates with value.
+///
+/// Returns the instruction result the frame fails with if the transfer is not possible, e.g.
+/// [`InstructionResult::OutOfFunds`]. Changes have to be journaled, so they are reverted with
+/// the frame.
+///
+/// Unlike other handles it is a function pointer, as it is copied to the
+/// [`EvmContext`](crate::EvmContext) for every transaction.
+pub type TransferHandle<EvmWiringT> = fn(
+    &mut InnerEvmContext<EvmWiringT>,
+    Address,
+    Address,
+    U256,
+) -> Result<
+    Option<InstructionResult>,
+    <<EvmWiringT as wiring::EvmWiring>::Database as Database>::Error,
+>;
+
 /// Handles related to stack frames.
 pub struct ExecutionHandler<'a, EvmWiringT: EvmWiring> {
     /// Handler that created first frame action. It uses transaction
     /// to determine if it is a call or create or EOF create.
     pub first_frame_creation: FirstFrameCreation<'a, EvmWiringT>,
+    /// Makes the first frame action read-only, used by
+    /// [`Evm::transact_readonly`](crate::Evm::transact_readonly).
+    pub first_frame_readonly: FirstFrameReadOnlyHandle<'a, EvmWiringT>,
     /// Handles last frame return, modified gas for refund and
     /// sets tx gas limit.
     pub last_frame_return: LastFrameReturnHandle<'a, EvmWiringT>,
@@ -135,6 +170,8 @@ pub struct ExecutionHandler<'a, EvmWiringT: EvmWiring> {
     pub eofcreate_return: FrameEOFCreateReturnHandle<'a, EvmWiringT>,
     /// Insert EOFCreate outcome.
     pub insert_eofcreate_outcome: InsertEOFCreateOutcomeHandle<'a, EvmWiringT>,
+    /// Value transfer of calls and creates.
+    pub transfer: TransferHandle<EvmWiringT>,
 }
 
 impl<'a, EvmWiringT: EvmWiring + 'a> ExecutionHandler<'a, EvmWiringT> {
@@ -142,6 +179,7 @@ impl<'a, EvmWiringT: EvmWiring + 'a> ExecutionHandler<'a, EvmWiringT> {
     pub fn new<SPEC: Spec + 'a>() -> Self {
         Self {
             first_frame_creation: Arc::new(mainnet::first_frame_creation::<EvmWiringT, SPEC>),
+            first_frame_readonly: Arc::new(mainnet::first_frame_readonly::<EvmWiringT>),
             last_frame_return: Arc::new(mainnet::last_frame_return::<EvmWiringT, SPEC>),
             execute_frame: Arc::new(mainnet::execute_frame::<EvmWiringT, SPEC>),
             call: Arc::new(mainnet::call::<EvmWiringT, SPEC>),
@@ -153,6 +191,7 @@ impl<'a, EvmWiringT: EvmWiring + 'a> ExecutionHandler<'a, EvmWiringT> {
             eofcreate: Arc::new(mainnet::eofcreate::<EvmWiringT, SPEC>),
             eofcreate_return: Arc::new(mainnet::eofcreate_return::<EvmWiringT, SPEC>),
             insert_eofcreate_outcome: Arc::new(mainnet::insert_eofcreate_outcome),
+            transfer: mainnet::transfer,
         }
     }
 }
@@ -180,6 +219,15 @@ impl<'a, EvmWiringT: EvmWiring> ExecutionHandler<'a, EvmWiringT> {
         (self.first_frame_creation)(context, gas_limit)
     }
 
+    /// Makes the first frame action read-only.
+    #[inline]
+    pub fn first_frame_readonly(
+        &self,
+        action: NewFrameAction,
+    ) -> EVMResultGeneric<NewFrameAction, EvmWiringT> {
+        (self.first_frame_readonly)(action)
+    }
+
     /// Handle call return, depending on instruction result gas will be reimbursed or not.
     #[inline]
     pub fn last_frame_return(
@@ -196,8 +244,9 @@ impl<'a, EvmWiringT: EvmWiring> ExecutionHandler<'a, EvmWiringT> {
         &self,
         context: &mut Context<EvmWiringT>,
         inputs: Box<CallInputs>,
+        shared_memory: &SharedMemory,
     ) -> EVMResultGeneric<FrameOrResult, EvmWiringT> {
-        (self.call)(context, inputs)
+        (self.call)(context, inputs, shared_memory)
     }
 
     /// Call registered handler for call return.
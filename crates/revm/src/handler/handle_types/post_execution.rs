@@ -1,5 +1,5 @@
 // Includes.
-use crate::{handler::mainnet, Context, EvmWiring, FrameResult};
+use crate::{handler::mainnet, Context, EvmWiring, FrameResult, RevertPolicy};
 use interpreter::Gas;
 use specification::hardfork::Spec;
 use std::sync::Arc;
@@ -27,12 +27,32 @@ pub type EndHandle<'a, EvmWiringT> =
 /// context. It will always be called even on failed validation.
 pub type ClearHandle<'a, EvmWiringT> = Arc<dyn Fn(&mut Context<EvmWiringT>) + 'a>;
 
-/// Refund handle, calculates the final refund.
+/// Refund handle, records additional refunds accumulated outside of the interpreter.
 pub type RefundHandle<'a, EvmWiringT> = Arc<dyn Fn(&mut Context<EvmWiringT>, &mut Gas, i64) + 'a>;
+
+/// Refund cap handle, limits the refund to the final amount given back to the caller.
+pub type RefundCapHandle<'a, EvmWiringT> = Arc<dyn Fn(&mut Context<EvmWiringT>, &mut Gas) + 'a>;
+
+/// Calldata floor handle, raises the gas used after the final refund to the minimum the
+/// transaction pays.
+pub type CalldataFloorHandle<'a, EvmWiringT> = RefundCapHandle<'a, EvmWiringT>;
+
+/// Revert policy handle, returns what happens to the state changes if the transaction fails.
+pub type RevertPolicyHandle<'a, EvmWiringT> =
+    Arc<dyn Fn(&Context<EvmWiringT>) -> RevertPolicy + 'a>;
+
 /// Handles related to post execution after the stack loop is finished.
 pub struct PostExecutionHandler<'a, EvmWiringT: EvmWiring> {
-    /// Calculate final refund
+    /// Record additional refunds.
     pub refund: RefundHandle<'a, EvmWiringT>,
+    /// Calculate final refund.
+    pub refund_cap: RefundCapHandle<'a, EvmWiringT>,
+    /// Apply the calldata floor after the final refund.
+    pub calldata_floor: CalldataFloorHandle<'a, EvmWiringT>,
+    /// Returns the policy for the state changes of a reverted or halted transaction.
+    ///
+    /// Called before the execution starts.
+    pub revert_policy: RevertPolicyHandle<'a, EvmWiringT>,
     /// Reimburse the caller with ethereum it didn't spend.
     pub reimburse_caller: ReimburseCallerHandle<'a, EvmWiringT>,
     /// Reward the beneficiary with caller fee.
@@ -53,6 +73,9 @@ impl<'a, EvmWiringT: EvmWiring + 'a> PostExecutionHandler<'a, EvmWiringT> {
     pub fn mainnet<SPEC: Spec + 'a>() -> Self {
         Self {
             refund: Arc::new(mainnet::refund::<EvmWiringT, SPEC>),
+            refund_cap: Arc::new(mainnet::refund_cap::<EvmWiringT, SPEC>),
+            calldata_floor: Arc::new(mainnet::calldata_floor::<EvmWiringT, SPEC>),
+            revert_policy: Arc::new(mainnet::revert_policy::<EvmWiringT>),
             reimburse_caller: Arc::new(mainnet::reimburse_caller::<EvmWiringT>),
             reward_beneficiary: Arc::new(mainnet::reward_beneficiary::<EvmWiringT, SPEC>),
             output: Arc::new(mainnet::output::<EvmWiringT>),
@@ -63,11 +86,26 @@ impl<'a, EvmWiringT: EvmWiring + 'a> PostExecutionHandler<'a, EvmWiringT> {
 }
 
 impl<'a, EvmWiringT: EvmWiring> PostExecutionHandler<'a, EvmWiringT> {
-    /// Calculate final refund
+    /// Record additional refunds.
     pub fn refund(&self, context: &mut Context<EvmWiringT>, gas: &mut Gas, eip7702_refund: i64) {
         (self.refund)(context, gas, eip7702_refund)
     }
 
+    /// Calculate final refund.
+    pub fn refund_cap(&self, context: &mut Context<EvmWiringT>, gas: &mut Gas) {
+        (self.refund_cap)(context, gas)
+    }
+
+    /// Apply the calldata floor.
+    pub fn calldata_floor(&self, context: &mut Context<EvmWiringT>, gas: &mut Gas) {
+        (self.calldata_floor)(context, gas)
+    }
+
+    /// Returns the policy for the state changes of a failed transaction.
+    pub fn revert_policy(&self, context: &Context<EvmWiringT>) -> RevertPolicy {
+        (self.revert_policy)(context)
+    }
+
     /// Reimburse the caller with gas that were not spend.
     pub fn reimburse_caller(
         &self,
@@ -1,6 +1,7 @@
 // Includes.
 use super::{GenericContextHandle, GenericContextHandleRet};
-use crate::{handler::mainnet, Context, ContextPrecompiles, EvmWiring};
+use crate::{context::AddressScheme, handler::mainnet, Context, ContextPrecompiles, EvmWiring};
+use interpreter::{gas::GasTable, BlockEnvOverride};
 use specification::hardfork::Spec;
 use std::sync::Arc;
 use wiring::result::EVMResultGeneric;
@@ -9,6 +10,18 @@ use wiring::result::EVMResultGeneric;
 pub type LoadPrecompilesHandle<'a, EvmWiringT> =
     Arc<dyn Fn() -> ContextPrecompiles<EvmWiringT> + 'a>;
 
+/// Loads the gas table that overrides the storage pricing of the spec.
+pub type LoadGasTableHandle<'a> = Arc<dyn Fn() -> Option<GasTable> + 'a>;
+
+/// Loads the derivation of the addresses of created contracts.
+pub type LoadAddressSchemeHandle<'a> = Arc<dyn Fn() -> AddressScheme + 'a>;
+
+/// Loads the values that block information opcodes return instead of the environment.
+pub type LoadBlockEnvOverrideHandle<'a> = Arc<dyn Fn() -> Option<BlockEnvOverride> + 'a>;
+
+/// Apply state overrides before the transaction is validated against the state.
+pub type ApplyStateOverrideHandle<'a, EvmWiringT> = GenericContextHandle<'a, EvmWiringT>;
+
 /// Load access list accounts and beneficiary.
 /// There is no need to load Caller as it is assumed that
 /// it will be loaded in DeductCallerHandle.
@@ -24,6 +37,14 @@ pub type ApplyEIP7702AuthListHandle<'a, EvmWiringT> = GenericContextHandleRet<'a
 pub struct PreExecutionHandler<'a, EvmWiringT: EvmWiring> {
     /// Load precompiles
     pub load_precompiles: LoadPrecompilesHandle<'a, EvmWiringT>,
+    /// Load gas table
+    pub load_gas_table: LoadGasTableHandle<'a>,
+    /// Load address scheme
+    pub load_address_scheme: LoadAddressSchemeHandle<'a>,
+    /// Load block environment override
+    pub load_block_env_override: LoadBlockEnvOverrideHandle<'a>,
+    /// Apply state overrides
+    pub apply_state_override: ApplyStateOverrideHandle<'a, EvmWiringT>,
     /// Main load handle
     pub load_accounts: LoadAccountsHandle<'a, EvmWiringT>,
     /// Deduct max value from the caller.
@@ -37,6 +58,10 @@ impl<'a, EvmWiringT: EvmWiring + 'a> PreExecutionHandler<'a, EvmWiringT> {
     pub fn new<SPEC: Spec + 'a>() -> Self {
         Self {
             load_precompiles: Arc::new(mainnet::load_precompiles::<EvmWiringT, SPEC>),
+            load_gas_table: Arc::new(mainnet::load_gas_table::<SPEC>),
+            load_address_scheme: Arc::new(mainnet::load_address_scheme::<SPEC>),
+            load_block_env_override: Arc::new(mainnet::load_block_env_override::<SPEC>),
+            apply_state_override: Arc::new(mainnet::apply_state_override::<EvmWiringT, SPEC>),
             load_accounts: Arc::new(mainnet::load_accounts::<EvmWiringT, SPEC>),
             deduct_caller: Arc::new(mainnet::deduct_caller::<EvmWiringT, SPEC>),
             apply_eip7702_auth_list: Arc::new(mainnet::apply_eip7702_auth_list::<EvmWiringT, SPEC>),
@@ -53,6 +78,14 @@ impl<'a, EvmWiringT: EvmWiring> PreExecutionHandler<'a, EvmWiringT> {
         (self.deduct_caller)(context)
     }
 
+    /// Apply state overrides
+    pub fn apply_state_override(
+        &self,
+        context: &mut Context<EvmWiringT>,
+    ) -> EVMResultGeneric<(), EvmWiringT> {
+        (self.apply_state_override)(context)
+    }
+
     /// Main load
     pub fn load_accounts(
         &self,
@@ -73,4 +106,19 @@ impl<'a, EvmWiringT: EvmWiring> PreExecutionHandler<'a, EvmWiringT> {
     pub fn load_precompiles(&self) -> ContextPrecompiles<EvmWiringT> {
         (self.load_precompiles)()
     }
+
+    /// Load gas table
+    pub fn load_gas_table(&self) -> Option<GasTable> {
+        (self.load_gas_table)()
+    }
+
+    /// Load address scheme
+    pub fn load_address_scheme(&self) -> AddressScheme {
+        (self.load_address_scheme)()
+    }
+
+    /// Load block environment override
+    pub fn load_block_env_override(&self) -> Option<BlockEnvOverride> {
+        (self.load_block_env_override)()
+    }
 }
@@ -9,12 +9,16 @@ mod validation;
 
 pub use execution::{
     call, call_return, create, create_return, eofcreate, eofcreate_return, execute_frame,
-    first_frame_creation, insert_call_outcome, insert_create_outcome, insert_eofcreate_outcome,
-    last_frame_return,
+    first_frame_creation, first_frame_readonly, insert_call_outcome, insert_create_outcome,
+    insert_eofcreate_outcome, last_frame_return, transfer,
+};
+pub use post_execution::{
+    calldata_floor, clear, end, output, refund, refund_cap, reimburse_caller, revert_policy,
+    reward_beneficiary,
 };
-pub use post_execution::{clear, end, output, refund, reimburse_caller, reward_beneficiary};
 pub use pre_execution::{
-    apply_eip7702_auth_list, deduct_caller, deduct_caller_inner, load_accounts, load_precompiles,
+    apply_eip7702_auth_list, apply_state_override, deduct_caller, deduct_caller_inner,
+    load_accounts, load_address_scheme, load_block_env_override, load_gas_table, load_precompiles,
 };
 pub use validation::{
     validate_block_env, validate_eip4844_tx, validate_env, validate_initial_tx_gas,
@@ -1,15 +1,17 @@
 use crate::{
     frame::EOFCreateFrame, CallFrame, Context, CreateFrame, EvmWiring, Frame, FrameOrResult,
-    FrameResult,
+    FrameResult, InnerEvmContext,
 };
 use bytecode::EOF_MAGIC_BYTES;
 use core::mem;
+use database_interface::Database;
 use interpreter::{
-    return_ok, return_revert, table::InstructionTables, CallInputs, CallOutcome, CallScheme,
-    CallValue, CreateInputs, CreateOutcome, CreateScheme, EOFCreateInputs, EOFCreateKind, Gas,
-    InterpreterAction, InterpreterResult, NewFrameAction, SharedMemory, EMPTY_SHARED_MEMORY,
+    return_ok, return_revert, table::InstructionTables, CallInput, CallInputs, CallOutcome,
+    CallScheme, CallValue, CreateInputs, CreateOutcome, CreateScheme, EOFCreateInputs,
+    EOFCreateKind, Gas, InstructionResult, InterpreterAction, InterpreterResult, NewFrameAction,
+    SharedMemory, EMPTY_SHARED_MEMORY,
 };
-use primitives::TxKind;
+use primitives::{Address, TxKind, U256};
 use specification::hardfork::{Spec, SpecId};
 use std::boxed::Box;
 use wiring::{
@@ -49,7 +51,7 @@ pub fn first_frame_creation<EvmWiringT: EvmWiring, SPEC: Spec>(
 
     let new_frame = match tx.kind() {
         TxKind::Call(target_address) => NewFrameAction::Call(Box::new(CallInputs {
-            input,
+            input: CallInput::Bytes(input),
             gas_limit,
             target_address,
             bytecode_address: target_address,
@@ -57,6 +59,7 @@ pub fn first_frame_creation<EvmWiringT: EvmWiring, SPEC: Spec>(
             value: CallValue::Transfer(tx.common_fields().value()),
             scheme: CallScheme::Call,
             is_static: false,
+            depth: 1,
             is_eof: false,
             return_memory_offset: 0..0,
         })),
@@ -76,6 +79,7 @@ pub fn first_frame_creation<EvmWiringT: EvmWiring, SPEC: Spec>(
                     value: tx.common_fields().value(),
                     init_code: input,
                     gas_limit,
+                    depth: 1,
                 }))
             }
         }
@@ -84,6 +88,24 @@ pub fn first_frame_creation<EvmWiringT: EvmWiring, SPEC: Spec>(
     Ok(new_frame)
 }
 
+/// Makes the first frame a static call.
+///
+/// Create transactions can not be executed read-only and return [`EVMError::Custom`].
+#[inline]
+pub fn first_frame_readonly<EvmWiringT: EvmWiring>(
+    mut action: NewFrameAction,
+) -> EVMResultGeneric<NewFrameAction, EvmWiringT> {
+    match &mut action {
+        NewFrameAction::Call(inputs) => inputs.is_static = true,
+        NewFrameAction::Create(_) | NewFrameAction::EOFCreate(_) => {
+            return Err(EVMError::Custom(
+                "create transaction can not be executed read-only".into(),
+            ))
+        }
+    }
+    Ok(action)
+}
+
 /// Handle output of the transaction
 #[inline]
 pub fn last_frame_return<EvmWiringT: EvmWiring, SPEC: Spec>(
@@ -116,19 +138,33 @@ pub fn last_frame_return<EvmWiringT: EvmWiring, SPEC: Spec>(
 pub fn call<EvmWiringT: EvmWiring, SPEC: Spec>(
     context: &mut Context<EvmWiringT>,
     inputs: Box<CallInputs>,
+    shared_memory: &SharedMemory,
 ) -> EVMResultGeneric<FrameOrResult, EvmWiringT> {
-    context.evm.make_call_frame(&inputs)
+    context.evm.make_call_frame(&inputs, shared_memory)
+}
+
+/// Transfers value by changing the balances of the accounts in the journaled state.
+#[inline]
+pub fn transfer<EvmWiringT: EvmWiring>(
+    context: &mut InnerEvmContext<EvmWiringT>,
+    from: Address,
+    to: Address,
+    value: U256,
+) -> Result<Option<InstructionResult>, <EvmWiringT::Database as Database>::Error> {
+    context
+        .journaled_state
+        .transfer(&from, &to, value, &mut context.db)
 }
 
 #[inline]
 pub fn call_return<EvmWiringT: EvmWiring>(
     context: &mut Context<EvmWiringT>,
     frame: Box<CallFrame>,
-    interpreter_result: InterpreterResult,
+    mut interpreter_result: InterpreterResult,
 ) -> EVMResultGeneric<CallOutcome, EvmWiringT> {
     context
         .evm
-        .call_return(&interpreter_result, frame.frame_data.checkpoint);
+        .call_return(&mut interpreter_result, frame.frame_data.checkpoint);
     Ok(CallOutcome::new(
         interpreter_result,
         frame.return_memory_range,
@@ -242,7 +278,7 @@ pub fn insert_eofcreate_outcome<EvmWiringT: EvmWiring>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::handler::mainnet::refund;
+    use crate::handler::mainnet::{refund, refund_cap};
     use interpreter::InstructionResult;
     use primitives::Bytes;
     use specification::hardfork::CancunSpec;
@@ -265,6 +301,7 @@ mod tests {
         ));
         last_frame_return::<DefaultEthereumWiring, CancunSpec>(&mut ctx, &mut first_frame).unwrap();
         refund::<DefaultEthereumWiring, CancunSpec>(&mut ctx, first_frame.gas_mut(), 0);
+        refund_cap::<DefaultEthereumWiring, CancunSpec>(&mut ctx, first_frame.gas_mut());
         *first_frame.gas()
     }
 
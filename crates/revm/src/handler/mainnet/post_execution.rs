@@ -1,7 +1,8 @@
-use crate::{Context, EvmWiring, FrameResult};
+use crate::{gas, Context, EvmWiring, FrameResult, RevertPolicy};
 use interpreter::{Gas, SuccessOrHalt};
 use primitives::U256;
 use specification::hardfork::{Spec, SpecId};
+use std::format;
 use wiring::{
     result::{EVMError, EVMResult, EVMResultGeneric, ExecutionResult, ResultAndState},
     Block, Transaction,
@@ -24,6 +25,12 @@ pub fn clear<EvmWiringT: EvmWiring>(context: &mut Context<EvmWiringT>) {
     context.evm.inner.journaled_state.clear();
 }
 
+/// Mainnet revert policy rolls back all changes of failed transactions.
+#[inline]
+pub fn revert_policy<EvmWiringT: EvmWiring>(_context: &Context<EvmWiringT>) -> RevertPolicy {
+    RevertPolicy::Rollback
+}
+
 /// Reward beneficiary with gas fee.
 #[inline]
 pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
@@ -49,28 +56,74 @@ pub fn reward_beneficiary<EvmWiringT: EvmWiring, SPEC: Spec>(
         .map_err(EVMError::Database)?;
 
     coinbase_account.data.mark_touch();
-    coinbase_account.data.info.balance = coinbase_account
-        .data
-        .info
-        .balance
-        .saturating_add(coinbase_gas_price * U256::from(gas.spent() - gas.refunded() as u64));
+    let Some(balance) = coinbase_gas_price
+        .checked_mul(U256::from(gas.spent() - gas.refunded() as u64))
+        .and_then(|reward| coinbase_account.data.info.balance.checked_add(reward))
+    else {
+        return Err(EVMError::Custom(
+            "beneficiary balance overflow on gas reward".into(),
+        ));
+    };
+    coinbase_account.data.info.balance = balance;
 
     Ok(())
 }
 
+/// Records the EIP-7702 refund.
 pub fn refund<EvmWiringT: EvmWiring, SPEC: Spec>(
     _context: &mut Context<EvmWiringT>,
     gas: &mut Gas,
     eip7702_refund: i64,
 ) {
     gas.record_refund(eip7702_refund);
+}
 
+/// Caps the refund as per [EIP-3529], or removes it if refunds are disabled.
+///
+/// [EIP-3529]: https://eips.ethereum.org/EIPS/eip-3529
+pub fn refund_cap<EvmWiringT: EvmWiring, SPEC: Spec>(
+    context: &mut Context<EvmWiringT>,
+    gas: &mut Gas,
+) {
+    if context.evm.env.cfg.is_gas_refund_disabled() {
+        gas.set_refund(0);
+        return;
+    }
     // Calculate gas refund for transaction.
     // If spec is set to london, it will decrease the maximum refund amount to 5th part of
     // gas spend. (Before london it was 2th part of gas spend)
     gas.set_final_refund(SPEC::SPEC_ID.is_enabled_in(SpecId::LONDON));
 }
 
+/// Raises the gas used after the final refund to the calldata floor of [EIP-7623].
+///
+/// Does nothing before Prague.
+///
+/// [EIP-7623]: https://eips.ethereum.org/EIPS/eip-7623
+pub fn calldata_floor<EvmWiringT: EvmWiring, SPEC: Spec>(
+    context: &mut Context<EvmWiringT>,
+    gas: &mut Gas,
+) {
+    let floor_gas = gas::floor_gas(SPEC::SPEC_ID, &context.evm.env.tx);
+    apply_floor_gas(gas, floor_gas);
+}
+
+/// Raises the gas used after the refund to at least `floor_gas`.
+///
+/// The refund is reduced first, then the remaining gas is spent. Validation ensures that the
+/// gas limit covers the floor.
+fn apply_floor_gas(gas: &mut Gas, floor_gas: u64) {
+    if gas.spent() - gas.refunded() as u64 >= floor_gas {
+        return;
+    }
+    if gas.spent() >= floor_gas {
+        gas.set_refund((gas.spent() - floor_gas) as i64);
+    } else {
+        gas.set_refund(0);
+        let _ = gas.record_cost(floor_gas - gas.spent());
+    }
+}
+
 #[inline]
 pub fn reimburse_caller<EvmWiringT: EvmWiring>(
     context: &mut Context<EvmWiringT>,
@@ -87,10 +140,15 @@ pub fn reimburse_caller<EvmWiringT: EvmWiring>(
         .load_account(caller, &mut context.evm.inner.db)
         .map_err(EVMError::Database)?;
 
-    caller_account.data.info.balance =
-        caller_account.data.info.balance.saturating_add(
-            effective_gas_price * U256::from(gas.remaining() + gas.refunded() as u64),
-        );
+    let Some(balance) = effective_gas_price
+        .checked_mul(U256::from(gas.remaining() + gas.refunded() as u64))
+        .and_then(|reimbursement| caller_account.data.info.balance.checked_add(reimbursement))
+    else {
+        return Err(EVMError::Custom(
+            "caller balance overflow on gas reimbursement".into(),
+        ));
+    };
+    caller_account.data.info.balance = balance;
 
     Ok(())
 }
@@ -130,12 +188,67 @@ pub fn output<EvmWiringT: EvmWiring>(
         },
         // Only two internal return flags.
         flag @ (SuccessOrHalt::FatalExternalError | SuccessOrHalt::Internal(_)) => {
-            panic!(
+            return Err(EVMError::Custom(format!(
                 "Encountered unexpected internal return flag: {:?} with instruction result: {:?}",
                 flag, instruction_result
-            )
+            )));
         }
     };
 
-    Ok(ResultAndState { result, state })
+    Ok(ResultAndState {
+        result,
+        state,
+        selfdestruct_legs: context.evm.journaled_state.last_selfdestruct_legs.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use interpreter::{CallOutcome, InstructionResult, InterpreterResult};
+    use primitives::Bytes;
+    use specification::hardfork::{CancunSpec, PragueSpec};
+    use wiring::{default::EnvWiring, DefaultEthereumWiring};
+
+    #[test]
+    fn calldata_floor_gas() {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        // Floor of 25_000 gas.
+        env.tx.data = Bytes::from_static(&[1; 100]);
+        env.tx.gas_limit = 30_000;
+        let mut ctx = Context::default();
+        *ctx.evm.inner.env = env;
+
+        let mut gas = Gas::new(30_000);
+        assert!(gas.record_cost(23_000));
+        calldata_floor::<DefaultEthereumWiring, CancunSpec>(&mut ctx, &mut gas);
+        assert_eq!(gas.spent(), 23_000);
+
+        calldata_floor::<DefaultEthereumWiring, PragueSpec>(&mut ctx, &mut gas);
+        assert_eq!(gas.spent(), 25_000);
+        assert_eq!(gas.refunded(), 0);
+
+        // Refund is cut down to the floor.
+        let mut gas = Gas::new(30_000);
+        assert!(gas.record_cost(29_000));
+        gas.record_refund(5_000);
+        refund_cap::<DefaultEthereumWiring, PragueSpec>(&mut ctx, &mut gas);
+        assert_eq!(gas.refunded(), 5_000);
+        calldata_floor::<DefaultEthereumWiring, PragueSpec>(&mut ctx, &mut gas);
+        assert_eq!(gas.spent(), 29_000);
+        assert_eq!(gas.refunded(), 4_000);
+    }
+
+    #[test]
+    fn output_internal_result() {
+        let mut ctx = Context::<DefaultEthereumWiring>::default();
+        let result = FrameResult::Call(CallOutcome::new(
+            InterpreterResult::new(InstructionResult::CallOrCreate, Bytes::new(), Gas::new(0)),
+            0..0,
+        ));
+        assert!(matches!(
+            output::<DefaultEthereumWiring>(&mut ctx, result),
+            Err(EVMError::Custom(_))
+        ));
+    }
 }
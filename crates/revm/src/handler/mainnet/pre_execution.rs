@@ -2,8 +2,9 @@
 //!
 //! They handle initial setup of the EVM, call loop and the final return of the EVM
 
-use crate::{Context, ContextPrecompiles, EvmWiring, JournalEntry};
+use crate::{context::AddressScheme, Context, ContextPrecompiles, EvmWiring, JournalEntry};
 use bytecode::Bytecode;
+use interpreter::{gas::GasTable, BlockEnvOverride};
 use precompile::PrecompileSpecId;
 use primitives::{BLOCKHASH_STORAGE_ADDRESS, U256};
 use specification::{
@@ -24,6 +25,41 @@ pub fn load_precompiles<EvmWiringT: EvmWiring, SPEC: Spec>() -> ContextPrecompil
     ContextPrecompiles::new(PrecompileSpecId::from_spec_id(SPEC::SPEC_ID))
 }
 
+/// Main gas table load.
+///
+/// Mainnet uses the storage pricing of the spec, so no table is loaded.
+#[inline]
+pub fn load_gas_table<SPEC: Spec>() -> Option<GasTable> {
+    None
+}
+
+/// Main address scheme load.
+///
+/// Mainnet derives the addresses of created contracts with keccak, see [`AddressScheme::ETHEREUM`].
+#[inline]
+pub fn load_address_scheme<SPEC: Spec>() -> AddressScheme {
+    AddressScheme::ETHEREUM
+}
+
+/// Main block environment override load.
+///
+/// Mainnet opcodes return the values of the environment, so nothing is overridden.
+#[inline]
+pub fn load_block_env_override<SPEC: Spec>() -> Option<BlockEnvOverride> {
+    None
+}
+
+/// Applies state overrides from the environment.
+#[inline]
+pub fn apply_state_override<EvmWiringT: EvmWiring, SPEC: Spec>(
+    context: &mut Context<EvmWiringT>,
+) -> EVMResultGeneric<(), EvmWiringT> {
+    context
+        .evm
+        .apply_state_override()
+        .map_err(EVMError::Database)
+}
+
 /// Main load handle
 #[inline]
 pub fn load_accounts<EvmWiringT: EvmWiring, SPEC: Spec>(
@@ -55,6 +91,9 @@ pub fn load_accounts<EvmWiringT: EvmWiring, SPEC: Spec>(
 
     // Load access list
     context.evm.load_access_list().map_err(EVMError::Database)?;
+
+    // Load externally provided warm accounts and storage slots.
+    context.evm.load_warm_set().map_err(EVMError::Database)?;
     Ok(())
 }
 
@@ -169,8 +208,8 @@ pub fn apply_eip7702_auth_list<EvmWiringT: EvmWiring, SPEC: Spec>(
             }
         }
 
-        // 5. Verify the nonce of authority is equal to nonce.
-        if authorization.nonce() != authority_acc.info.nonce {
+        // 5. Verify the nonce of authority is equal to nonce and can be incremented.
+        if authorization.nonce() != authority_acc.info.nonce || authorization.nonce() == u64::MAX {
             continue;
         }
 
@@ -1,11 +1,12 @@
 use core::cmp::{self, Ordering};
 
+use crate::gas;
 use crate::{Context, EvmWiring};
-use interpreter::gas;
 use primitives::{B256, U256};
 use specification::{
     constants::MAX_INITCODE_SIZE,
-    eip4844,
+    eip4844, eip7825,
+    experimental::ExperimentalEip,
     hardfork::{Spec, SpecId},
 };
 use state::Account;
@@ -78,10 +79,13 @@ pub fn validate_priority_fee_tx(
 }
 
 /// Validate EIP-4844 transaction.
+///
+/// `max_blobs` is the maximum number of blobs per block, see [`SpecId::max_blobs_per_block`].
 pub fn validate_eip4844_tx(
     blobs: &[B256],
     max_blob_fee: u128,
     block_blob_gas_price: u128,
+    max_blobs: u64,
 ) -> Result<(), InvalidTransaction> {
     // ensure that the user was willing to at least pay the current blob gasprice
     if block_blob_gas_price > max_blob_fee {
@@ -101,11 +105,11 @@ pub fn validate_eip4844_tx(
     }
 
     // ensure the total blob gas spent is at most equal to the limit
-    // assert blob_gas_used <= MAX_BLOB_GAS_PER_BLOCK
-    if blobs.len() > eip4844::MAX_BLOB_NUMBER_PER_BLOCK as usize {
+    // assert blob_gas_used <= max_blob_gas_per_block
+    if blobs.len() > max_blobs as usize {
         return Err(InvalidTransaction::TooManyBlobs {
             have: blobs.len(),
-            max: eip4844::MAX_BLOB_NUMBER_PER_BLOCK as usize,
+            max: max_blobs as usize,
         });
     }
     Ok(())
@@ -182,6 +186,9 @@ pub fn validate_tx_env<EvmWiringT: EvmWiring, SPEC: Spec>(
             if !SPEC::enabled(SpecId::CANCUN) {
                 return Err(InvalidTransaction::Eip4844NotSupported);
             }
+            if tx.kind().is_create() {
+                return Err(InvalidTransaction::BlobCreateTransaction);
+            }
             let tx = tx.eip4844();
 
             if cfg.chain_id != tx.chain_id() {
@@ -198,6 +205,7 @@ pub fn validate_tx_env<EvmWiringT: EvmWiring, SPEC: Spec>(
                 tx.blob_versioned_hashes(),
                 tx.max_fee_per_blob_gas(),
                 block.blob_gasprice().unwrap_or_default(),
+                SPEC::SPEC_ID.max_blobs_per_block(),
             )?;
         }
         TransactionType::Eip7702 => {
@@ -205,6 +213,9 @@ pub fn validate_tx_env<EvmWiringT: EvmWiring, SPEC: Spec>(
             if !SPEC::enabled(SpecId::PRAGUE) {
                 return Err(InvalidTransaction::Eip7702NotSupported);
             }
+            if tx.kind().is_create() {
+                return Err(InvalidTransaction::Eip7702CreateTransaction);
+            }
             let tx = tx.eip7702();
 
             if cfg.chain_id != tx.chain_id() {
@@ -242,6 +253,16 @@ pub fn validate_tx_env<EvmWiringT: EvmWiring, SPEC: Spec>(
         return Err(InvalidTransaction::CallerGasLimitMoreThanBlock);
     }
 
+    // EIP-7825: Transaction gas limit cap
+    if SPEC::SPEC_ID.is_eip_enabled(ExperimentalEip::Eip7825)
+        && common_field.gas_limit() > eip7825::TX_GAS_LIMIT_CAP
+    {
+        return Err(InvalidTransaction::TxGasLimitGreaterThanCap {
+            gas_limit: common_field.gas_limit(),
+            cap: eip7825::TX_GAS_LIMIT_CAP,
+        });
+    }
+
     // EIP-3860: Limit and meter initcode
     if SPEC::enabled(SpecId::SHANGHAI) && tx.kind().is_create() {
         let max_initcode_size = cfg
@@ -282,6 +303,10 @@ where
     if !cfg.is_nonce_check_disabled() {
         let tx = tx.common_fields().nonce();
         let state = account.info.nonce;
+        // EIP-2681: nonce can't be incremented past the maximum value.
+        if tx == u64::MAX {
+            return Err(InvalidTransaction::NonceOverflowInTransaction);
+        }
         match tx.cmp(&state) {
             Ordering::Greater => {
                 return Err(InvalidTransaction::NonceTooHigh { tx, state });
@@ -359,32 +384,216 @@ pub fn validate_initial_tx_gas<EvmWiringT: EvmWiring, SPEC: Spec>(
 where
     <EvmWiringT::Transaction as Transaction>::TransactionError: From<InvalidTransaction>,
 {
-    let tx_type = env.tx.tx_type().into();
-
-    let authorization_list_num = if tx_type == TransactionType::Eip7702 {
-        env.tx.eip7702().authorization_list_len() as u64
-    } else {
-        0
-    };
-
-    let common_fields = env.tx.common_fields();
-    let is_create = env.tx.kind().is_create();
-    let input = common_fields.input();
-    let access_list = env.tx.access_list();
-
-    let initial_gas_spend = gas::validate_initial_tx_gas(
-        SPEC::SPEC_ID,
-        input,
-        is_create,
-        access_list,
-        authorization_list_num,
-    );
+    let initial_gas_spend = gas::intrinsic_gas(SPEC::SPEC_ID, &env.tx);
+    // EIP-7623: Gas limit has to cover the calldata floor too.
+    let floor_gas = gas::floor_gas(SPEC::SPEC_ID, &env.tx);
 
     // Additional check to see if limit is big enough to cover initial gas.
-    if initial_gas_spend > common_fields.gas_limit() {
+    if initial_gas_spend.max(floor_gas) > env.tx.common_fields().gas_limit() {
         return Err(EVMError::Transaction(
             InvalidTransaction::CallGasCostMoreThanGasLimit.into(),
         ));
     }
     Ok(initial_gas_spend)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{Bytes, TxKind};
+    use specification::hardfork::{
+        CancunSpec, LondonSpec, MergeSpec, OsakaSpec, PragueSpec, ShanghaiSpec,
+    };
+    use state::AccountInfo;
+    use wiring::DefaultEthereumWiring;
+
+    fn env() -> EnvWiring<DefaultEthereumWiring> {
+        let mut env = EnvWiring::<DefaultEthereumWiring>::default();
+        env.tx.gas_limit = 21_000;
+        env.tx.gas_price = U256::from(1);
+        env
+    }
+
+    fn validate_account(
+        env: &EnvWiring<DefaultEthereumWiring>,
+        info: AccountInfo,
+    ) -> Result<(), InvalidTransaction> {
+        validate_tx_against_account::<DefaultEthereumWiring, CancunSpec>(
+            &mut info.into(),
+            &env.tx,
+            &env.cfg,
+        )
+    }
+
+    #[test]
+    fn nonce() {
+        let mut env = env();
+        env.tx.nonce = 1;
+        let info = |nonce| AccountInfo {
+            balance: U256::MAX,
+            nonce,
+            ..Default::default()
+        };
+        assert_eq!(validate_account(&env, info(1)), Ok(()));
+        assert_eq!(
+            validate_account(&env, info(0)),
+            Err(InvalidTransaction::NonceTooHigh { tx: 1, state: 0 })
+        );
+        assert_eq!(
+            validate_account(&env, info(2)),
+            Err(InvalidTransaction::NonceTooLow { tx: 1, state: 2 })
+        );
+        env.tx.nonce = u64::MAX;
+        assert_eq!(
+            validate_account(&env, info(u64::MAX)),
+            Err(InvalidTransaction::NonceOverflowInTransaction)
+        );
+    }
+
+    #[test]
+    fn nonce_check_disabled() {
+        let mut env = env();
+        env.cfg.disable_nonce_check = true;
+        let info = |nonce| AccountInfo {
+            balance: U256::MAX,
+            nonce,
+            ..Default::default()
+        };
+        for nonce in [0, 1, u64::MAX] {
+            env.tx.nonce = nonce;
+            assert_eq!(validate_account(&env, info(1)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn balance() {
+        let mut env = env();
+        env.tx.value = U256::from(1);
+        let info = |balance| AccountInfo {
+            balance: U256::from(balance),
+            ..Default::default()
+        };
+        // gas_limit * gas_price + value
+        assert_eq!(validate_account(&env, info(21_001)), Ok(()));
+        assert_eq!(
+            validate_account(&env, info(21_000)),
+            Err(InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(U256::from(21_001)),
+                balance: Box::new(U256::from(21_000)),
+            })
+        );
+        // Gas cost is at most `u64 * u128`, only the value can overflow.
+        env.tx.value = U256::MAX;
+        assert_eq!(
+            validate_account(&env, info(0)),
+            Err(InvalidTransaction::OverflowPaymentInTransaction)
+        );
+    }
+
+    #[test]
+    fn intrinsic_gas() {
+        let mut env = env();
+        // Zero byte costs 4 gas and non zero byte 16 gas.
+        env.tx.data = Bytes::from_static(&[0, 1]);
+        env.tx.gas_limit = 21_020;
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, ShanghaiSpec>(&env),
+            Ok(21_020)
+        );
+        env.tx.gas_limit = 21_019;
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, ShanghaiSpec>(&env),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallGasCostMoreThanGasLimit
+            ))
+        );
+
+        // Create costs 32000 gas and initcode 2 gas per word.
+        env.tx.transact_to = TxKind::Create;
+        env.tx.gas_limit = 53_022;
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, ShanghaiSpec>(&env),
+            Ok(53_022)
+        );
+    }
+
+    #[test]
+    fn calldata_floor_gas() {
+        let mut env = env();
+        // Floor of 100 non zero bytes is 25_000 gas, their standard cost 22_600 gas.
+        env.tx.data = Bytes::from_static(&[1; 100]);
+        env.tx.gas_limit = 22_600;
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, CancunSpec>(&env),
+            Ok(22_600)
+        );
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, PragueSpec>(&env),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallGasCostMoreThanGasLimit
+            ))
+        );
+        env.tx.gas_limit = 25_000;
+        assert_eq!(
+            validate_initial_tx_gas::<DefaultEthereumWiring, PragueSpec>(&env),
+            Ok(22_600)
+        );
+    }
+
+    #[test]
+    fn block_gas_limit() {
+        let mut env = env();
+        env.block.gas_limit = U256::from(21_000);
+        assert_eq!(
+            validate_tx_env::<DefaultEthereumWiring, CancunSpec>(&env.tx, &env.block, &env.cfg),
+            Ok(())
+        );
+        env.tx.gas_limit = 21_001;
+        assert_eq!(
+            validate_tx_env::<DefaultEthereumWiring, CancunSpec>(&env.tx, &env.block, &env.cfg),
+            Err(InvalidTransaction::CallerGasLimitMoreThanBlock)
+        );
+    }
+
+    #[test]
+    fn tx_gas_limit_cap() {
+        let mut env = env();
+        env.tx.gas_limit = eip7825::TX_GAS_LIMIT_CAP + 1;
+        assert_eq!(
+            validate_tx_env::<DefaultEthereumWiring, CancunSpec>(&env.tx, &env.block, &env.cfg),
+            Ok(())
+        );
+        let result =
+            validate_tx_env::<DefaultEthereumWiring, OsakaSpec>(&env.tx, &env.block, &env.cfg);
+        if ExperimentalEip::Eip7825.is_compiled() {
+            assert_eq!(
+                result,
+                Err(InvalidTransaction::TxGasLimitGreaterThanCap {
+                    gas_limit: eip7825::TX_GAS_LIMIT_CAP + 1,
+                    cap: eip7825::TX_GAS_LIMIT_CAP,
+                })
+            );
+        } else {
+            assert_eq!(result, Ok(()));
+        }
+    }
+
+    #[test]
+    fn prevrandao() {
+        let mut env = env();
+        env.block.prevrandao = None;
+        assert_eq!(
+            validate_block_env::<DefaultEthereumWiring, LondonSpec>(&env.block),
+            Ok(())
+        );
+        assert_eq!(
+            validate_block_env::<DefaultEthereumWiring, MergeSpec>(&env.block),
+            Err(InvalidHeader::PrevrandaoNotSet)
+        );
+        env.block.prevrandao = Some(B256::ZERO);
+        assert_eq!(
+            validate_block_env::<DefaultEthereumWiring, MergeSpec>(&env.block),
+            Ok(())
+        );
+    }
+}
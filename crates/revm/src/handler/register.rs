@@ -1,5 +1,10 @@
-use crate::{handler::Handler, Context, EvmWiring};
-use std::boxed::Box;
+#[cfg(feature = "secp256r1")]
+use crate::ContextPrecompiles;
+use crate::{context::AddressScheme, handler::Handler, Context, EvmWiring, RevertPolicy};
+use interpreter::{gas::GasTable, BlockEnvOverride};
+use precompile::{crypto, CryptoProvider, PrecompileSpecId};
+use specification::hardfork::SpecId;
+use std::{boxed::Box, sync::Arc};
 
 /// EVM Handler
 pub type EvmHandler<'a, EvmWiringT> = Handler<'a, EvmWiringT, Context<EvmWiringT>>;
@@ -11,6 +16,93 @@ pub type HandleRegister<EvmWiringT> = for<'a> fn(&mut EvmHandler<'a, EvmWiringT>
 pub type HandleRegisterBox<'a, EvmWiringT> =
     Box<dyn for<'e> Fn(&mut EvmHandler<'e, EvmWiringT>) + 'a>;
 
+/// Handle register that removes all gas refunds, for chains that disabled them.
+///
+/// See [EIP-3298](https://eips.ethereum.org/EIPS/eip-3298) for the motivation.
+pub fn disable_refund_register<EvmWiringT: EvmWiring>(handler: &mut EvmHandler<'_, EvmWiringT>) {
+    handler.post_execution.refund_cap = Arc::new(|_, gas| gas.set_refund(0));
+}
+
+/// Handle register that adds the `P256VERIFY` precompile of
+/// [RIP-7212](https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md) to the precompiles
+/// of the spec.
+#[cfg(feature = "secp256r1")]
+pub fn p256_verify_register<EvmWiringT: EvmWiring>(handler: &mut EvmHandler<'_, EvmWiringT>) {
+    let spec_id = PrecompileSpecId::from_spec_id(handler.spec_id().into());
+    handler.pre_execution.load_precompiles = Arc::new(move || {
+        let mut precompiles = ContextPrecompiles::new(spec_id);
+        precompiles.extend([precompile::secp256r1::P256VERIFY]);
+        precompiles
+    });
+}
+
+/// Handle register that runs the precompiles that use cryptography on the given provider.
+///
+/// The precompiles replace the ones loaded by the previous handle, so the register composes
+/// with chain specific precompile sets.
+pub fn crypto_provider_register<'a, EvmWiringT: EvmWiring>(
+    crypto: Arc<dyn CryptoProvider>,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let spec_id = PrecompileSpecId::from_spec_id(handler.spec_id().into());
+        let load_precompiles = handler.pre_execution.load_precompiles.clone();
+        let crypto = crypto.clone();
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut precompiles = load_precompiles();
+            precompiles.extend(crypto::precompiles(spec_id, crypto.clone()));
+            precompiles
+        });
+    })
+}
+
+/// Handle register that keeps the state changes of failed transactions, see [`RevertPolicy::Keep`].
+///
+/// Useful for analytics of failed transactions, the resulting state must not be committed and
+/// [`Evm::transact_commit`](crate::Evm::transact_commit) returns an error for it.
+pub fn keep_reverted_state_register<EvmWiringT: EvmWiring>(
+    handler: &mut EvmHandler<'_, EvmWiringT>,
+) {
+    handler.post_execution.revert_policy = Arc::new(|_| RevertPolicy::Keep);
+}
+
+/// Handle register that replaces the storage pricing of `SLOAD` and `SSTORE`.
+///
+/// The table is created from the spec of the handler, so it follows spec changes made after
+/// registration. Use [`GasTable::new`] as the base to only modify single costs.
+pub fn gas_table_register<'a, EvmWiringT: EvmWiring>(
+    gas_table: impl Fn(SpecId) -> GasTable + 'a,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let table = gas_table(handler.spec_id().into());
+        handler.pre_execution.load_gas_table = Arc::new(move || Some(table));
+    })
+}
+
+/// Handle register that replaces the derivation of the addresses of created contracts.
+///
+/// The scheme is created from the spec of the handler, like [`gas_table_register`].
+pub fn address_scheme_register<'a, EvmWiringT: EvmWiring>(
+    address_scheme: impl Fn(SpecId) -> AddressScheme + 'a,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let scheme = address_scheme(handler.spec_id().into());
+        handler.pre_execution.load_address_scheme = Arc::new(move || scheme);
+    })
+}
+
+/// Handle register that overrides the values returned by `CHAINID`, `COINBASE`, `DIFFICULTY`
+/// and `NUMBER`.
+///
+/// The override is created from the spec of the handler, like [`gas_table_register`].
+pub fn block_env_override_register<'a, EvmWiringT: EvmWiring>(
+    block_env_override: impl Fn(SpecId) -> BlockEnvOverride + 'a,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let block_env_override = block_env_override(handler.spec_id().into());
+        handler.pre_execution.load_block_env_override = Arc::new(move || Some(block_env_override));
+    })
+}
+
 pub enum HandleRegisters<'a, EvmWiringT: EvmWiring> {
     /// Plain function register
     Plain(HandleRegister<EvmWiringT>),
@@ -30,3 +122,333 @@ impl<'register, EvmWiringT: EvmWiring> HandleRegisters<'register, EvmWiringT> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Evm, InnerEvmContext};
+    use bytecode::{
+        opcode::{
+            CALLVALUE, CHAINID, COINBASE, DIFFICULTY, DUP1, JUMPDEST, JUMPI, NUMBER, PUSH1, REVERT,
+            SLOAD, SSTORE, STOP,
+        },
+        Bytecode,
+    };
+    use database::{BenchmarkDB, CacheDB};
+    use database_interface::{Database, EmptyDB};
+    use interpreter::InstructionResult;
+    use precompile::PrecompileErrors;
+    use primitives::{address, Address, Bytes, TxKind, U256};
+    use state::AccountInfo;
+    use wiring::{
+        result::{EVMError, ResultAndState},
+        EthereumWiring,
+    };
+
+    fn gas_refunded(disable_refund: bool) -> u64 {
+        // Sets slot 0 to 1 and back to 0, which is refunded.
+        let bytecode = Bytecode::new_raw(
+            [
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, SSTORE,
+            ]
+            .into(),
+        );
+        let builder = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            });
+        let mut evm = if disable_refund {
+            builder
+                .append_handler_register(disable_refund_register)
+                .build()
+        } else {
+            builder.build()
+        };
+        evm.transact().unwrap().result.gas_refunded()
+    }
+
+    #[test]
+    fn disable_refund() {
+        assert!(gas_refunded(false) > 0);
+        assert_eq!(gas_refunded(true), 0);
+    }
+
+    #[test]
+    fn disable_refund_keeps_calldata_floor() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_raw([STOP].into())))
+            .with_default_ext_ctx()
+            .with_spec_id(SpecId::PRAGUE)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.data = Bytes::from_static(&[1; 100]);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(disable_refund_register)
+            .build();
+        // Floor of 100 non zero bytes is above their standard cost of 22_600 gas.
+        assert_eq!(evm.transact().unwrap().result.gas_used(), 25_000);
+    }
+
+    fn reverted_slot(keep_reverted_state: bool) -> U256 {
+        // Sets slot 0 to 1 and reverts.
+        let bytecode = Bytecode::new_raw(
+            [
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, PUSH1, 0x00, PUSH1, 0x00, REVERT,
+            ]
+            .into(),
+        );
+        let contract = address!("0000000000000000000000000000000000000000");
+        let builder = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            });
+        let mut evm = if keep_reverted_state {
+            builder
+                .append_handler_register(keep_reverted_state_register)
+                .build()
+        } else {
+            builder.build()
+        };
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(!result.is_success());
+        // Policy is reset after the transaction.
+        assert_eq!(
+            evm.context.evm.journaled_state.revert_policy,
+            RevertPolicy::Rollback
+        );
+        state[&contract]
+            .storage
+            .get(&U256::ZERO)
+            .map(|slot| slot.present_value)
+            .unwrap_or_default()
+    }
+
+    #[derive(Debug)]
+    struct ZeroHash;
+
+    impl CryptoProvider for ZeroHash {
+        fn sha256(&self, _input: &[u8]) -> Result<[u8; 32], PrecompileErrors> {
+            Ok([0; 32])
+        }
+    }
+
+    fn sha256_output(crypto: Option<Arc<dyn CryptoProvider>>) -> Bytes {
+        let builder = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000002"));
+                tx.data = Bytes::from_static(b"abc");
+                tx.gas_limit = 100_000;
+            });
+        let mut evm = match crypto {
+            Some(crypto) => builder.with_crypto_provider(crypto).build(),
+            None => builder.build(),
+        };
+        evm.transact().unwrap().result.into_output().unwrap()
+    }
+
+    #[test]
+    fn crypto_provider() {
+        assert_ne!(sha256_output(None), Bytes::from([0; 32]));
+        assert_eq!(
+            sha256_output(Some(Arc::new(ZeroHash))),
+            Bytes::from([0; 32])
+        );
+    }
+
+    #[test]
+    fn keep_reverted_state() {
+        assert_eq!(reverted_slot(false), U256::ZERO);
+        assert_eq!(reverted_slot(true), U256::from(1));
+    }
+
+    #[test]
+    fn keep_reverted_state_commit() {
+        // Sets slot 0 to 1 and reverts if the call value is zero.
+        let bytecode = Bytecode::new_raw(
+            [
+                PUSH1, 0x01, PUSH1, 0x00, SSTORE, CALLVALUE, PUSH1, 0x0d, JUMPI, PUSH1, 0x00, DUP1,
+                REVERT, JUMPDEST, STOP,
+            ]
+            .into(),
+        );
+        let contract = address!("0000000000000000000000000000000000000000");
+        let caller = address!("0000000000000000000000000000000000000001");
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(contract, AccountInfo::from_bytecode(bytecode));
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1)));
+        let mut evm = Evm::<EthereumWiring<CacheDB<EmptyDB>, ()>>::builder()
+            .with_db(db)
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(keep_reverted_state_register)
+            .build();
+
+        assert!(matches!(evm.transact_commit(), Err(EVMError::Custom(_))));
+        assert_eq!(
+            evm.context.evm.db.storage(contract, U256::ZERO),
+            Ok(U256::ZERO)
+        );
+
+        // Successful transactions are committed.
+        evm.context.evm.env.tx.value = U256::from(1);
+        assert!(evm.transact_commit().unwrap().is_success());
+        assert_eq!(
+            evm.context.evm.db.storage(contract, U256::ZERO),
+            Ok(U256::from(1))
+        );
+    }
+
+    fn sload_gas_used(custom_pricing: bool) -> u64 {
+        let bytecode = Bytecode::new_raw([PUSH1, 0x00, SLOAD].into());
+        let builder = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            });
+        let mut evm = if custom_pricing {
+            builder
+                .append_handler_register_box(gas_table_register(|spec_id| GasTable {
+                    cold_sload: Some(10_000),
+                    ..GasTable::new(spec_id)
+                }))
+                .build()
+        } else {
+            builder.build()
+        };
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        // Table is loaded for each transaction.
+        assert_eq!(evm.context.evm.gas_table.is_some(), custom_pricing);
+        result.gas_used()
+    }
+
+    #[test]
+    fn gas_table() {
+        assert_eq!(sload_gas_used(true) - sload_gas_used(false), 10_000 - 2_100);
+    }
+
+    #[test]
+    fn address_scheme() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let created = address!("00000000000000000000000000000000000c0de0");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Create;
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register_box(address_scheme_register(|_| AddressScheme {
+                create: |_, _| address!("00000000000000000000000000000000000c0de0"),
+                ..AddressScheme::ETHEREUM
+            }))
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert!(state[&created].is_created());
+        assert!(!state.contains_key(&caller.create(0)));
+    }
+
+    #[test]
+    fn block_env_override() {
+        let bytecode = Bytecode::new_raw(
+            [
+                CHAINID, PUSH1, 0x00, SSTORE, COINBASE, PUSH1, 0x01, SSTORE, DIFFICULTY, PUSH1,
+                0x02, SSTORE, NUMBER, PUSH1, 0x03, SSTORE,
+            ]
+            .into(),
+        );
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| block.coinbase = coinbase)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 200_000;
+            })
+            .append_handler_register_box(block_env_override_register(|_| BlockEnvOverride {
+                chain_id: Some(7),
+                difficulty: Some(U256::from(1)),
+                number: Some(U256::from(42)),
+                ..Default::default()
+            }))
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        let slot = |index: u64| state[&Address::ZERO].storage[&U256::from(index)].present_value;
+        assert_eq!(slot(0), U256::from(7));
+        // Not overridden.
+        assert_eq!(slot(1), coinbase.into_word().into());
+        assert_eq!(slot(2), U256::from(1));
+        assert_eq!(slot(3), U256::from(42));
+    }
+
+    const TREASURY: Address = address!("0000000000000000000000000000000000000fee");
+
+    /// Transfers value and pays a fee of 1% of it to the treasury.
+    fn transfer_with_fee<EvmWiringT: EvmWiring>(
+        context: &mut InnerEvmContext<EvmWiringT>,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<Option<InstructionResult>, <EvmWiringT::Database as Database>::Error> {
+        let fee = value / U256::from(100);
+        let journal = &mut context.journaled_state;
+        if let Some(result) = journal.transfer(&from, &TREASURY, fee, &mut context.db)? {
+            return Ok(Some(result));
+        }
+        journal.transfer(&from, &to, value - fee, &mut context.db)
+    }
+
+    fn transfer_with_fee_register<EvmWiringT: EvmWiring>(handler: &mut EvmHandler<'_, EvmWiringT>) {
+        handler.execution.transfer = transfer_with_fee::<EvmWiringT>;
+    }
+
+    #[test]
+    fn custom_transfer() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(Address::ZERO);
+                tx.value = U256::from(1_000);
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register(transfer_with_fee_register)
+            .build();
+
+        let ResultAndState { result, state, .. } = evm.transact().unwrap();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&Address::ZERO].info.balance,
+            U256::from(10_000_000 + 990)
+        );
+        assert_eq!(state[&TREASURY].info.balance, U256::from(10));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{handler::register::HandleRegisterBox, Context, EvmWiring};
+use core::{cell::RefCell, mem};
+use interpreter::{
+    table::{InstructionTable, InstructionTables, SpecializedTable},
+    EMPTY_SHARED_MEMORY,
+};
+use primitives::{HashMap, HashSet, B256};
+use std::{boxed::Box, sync::Arc};
+
+/// Returns handle register that specializes instruction dispatch for the given code hashes.
+///
+/// The first time a hot contract is executed a [`SpecializedTable`] is built from the plain
+/// instruction table and cached for the lifetime of the handler. Other contracts, EOF code and
+/// boxed instruction tables use the previous `execute_frame` handle.
+///
+/// With the `superinstructions` feature, common instruction sequences are fused, see
+/// `SpecializedTable::with_superinstructions`. Sequences with custom instructions in the plain
+/// table are not fused.
+pub fn hot_contracts_register<'a, EvmWiringT: EvmWiring>(
+    code_hashes: HashSet<B256>,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let code_hashes = code_hashes.clone();
+        let cache: RefCell<HashMap<B256, Arc<SpecializedTable<Context<EvmWiringT>>>>> =
+            RefCell::default();
+        let prev = handler.execution.execute_frame.clone();
+        handler.execution.execute_frame =
+            Arc::new(move |frame, shared_memory, instruction_tables, context| {
+                let InstructionTables::Plain(table) = instruction_tables else {
+                    return prev(frame, shared_memory, instruction_tables, context);
+                };
+                let interpreter = frame.interpreter_mut();
+                let hash = match interpreter.contract.hash {
+                    Some(hash) if !interpreter.is_eof && code_hashes.contains(&hash) => hash,
+                    _ => return prev(frame, shared_memory, instruction_tables, context),
+                };
+                let specialized = cache
+                    .borrow_mut()
+                    .entry(hash)
+                    .or_insert_with(|| Arc::new(specialize(&interpreter.bytecode, table)))
+                    .clone();
+
+                let memory = mem::replace(shared_memory, EMPTY_SHARED_MEMORY);
+                let next_action = interpreter.run_specialized(memory, &specialized, table, context);
+                *shared_memory = interpreter.take_memory();
+                Ok(next_action)
+            });
+    })
+}
+
+fn specialize<EvmWiringT: EvmWiring>(
+    bytecode: &[u8],
+    table: &InstructionTable<Context<EvmWiringT>>,
+) -> SpecializedTable<Context<EvmWiringT>> {
+    #[cfg(feature = "superinstructions")]
+    let specialized = SpecializedTable::with_superinstructions(bytecode, table);
+    #[cfg(not(feature = "superinstructions"))]
+    let specialized = SpecializedTable::new(bytecode, table);
+    specialized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use bytecode::{
+        opcode::{ADD, PUSH1, SSTORE},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, TxKind, U256};
+    use wiring::EthereumWiring;
+
+    #[test]
+    fn hot_contract_is_executed() {
+        let bytecode =
+            Bytecode::new_raw([PUSH1, 0x01, PUSH1, 0x02, ADD, PUSH1, 0x00, SSTORE].into());
+        let hash = bytecode.hash_slow();
+
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register_box(hot_contracts_register(HashSet::from_iter([hash])))
+            .build();
+
+        let state = evm.transact().unwrap().state;
+        let account = state
+            .get(&address!("0000000000000000000000000000000000000000"))
+            .unwrap();
+        assert_eq!(
+            account.storage.get(&U256::ZERO).unwrap().present_value,
+            U256::from(3)
+        );
+    }
+}
@@ -8,8 +8,15 @@ use primitives::{
 };
 use specification::hardfork::{SpecId, SpecId::*};
 use state::{Account, EvmState, EvmStorageSlot, TransientStorage};
-
-use core::mem;
+use wiring::{default::StateOverride, result::SelfdestructLeg};
+
+#[cfg(feature = "access-stats")]
+use crate::StateAccessStats;
+#[cfg(feature = "tx-arena")]
+use crate::TxArena;
+#[cfg(feature = "read-write-set")]
+use crate::{ReadWriteSet, StateKey, StateValue};
+use core::{convert::Infallible, mem};
 use std::vec::Vec;
 
 /// A journal of state changes internal to the EVM.
@@ -30,6 +37,9 @@ pub struct JournaledState {
     pub depth: usize,
     /// The journal of state changes, one for each call.
     pub journal: Vec<Vec<JournalEntry>>,
+    /// Depth of the call frame that entered the static context, see
+    /// [Self::enter_static_context].
+    pub static_depth: Option<usize>,
     /// The spec ID for the EVM.
     ///
     /// This spec is used for two things:
@@ -47,6 +57,41 @@ pub struct JournaledState {
     /// Note that this not include newly loaded accounts, account and storage
     /// is considered warm if it is found in the `State`.
     pub warm_preloaded_addresses: HashSet<Address>,
+    /// State overrides of the current transaction, see [Self::set_state_override].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub state_override: StateOverride,
+    /// What happens to the changes of the transaction when it reverts or halts.
+    ///
+    /// Reset to [`RevertPolicy::Rollback`] after every transaction.
+    pub revert_policy: RevertPolicy,
+    /// Buffers of journal entries, logs and the call stack that are reused between
+    /// transactions.
+    #[cfg(feature = "tx-arena")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub arena: TxArena,
+    /// Balance movements of the `SELFDESTRUCT`s of the last finalized transaction, from the
+    /// [`JournalEntry::SelfdestructDebit`] and [`JournalEntry::SelfdestructCredit`] entries.
+    ///
+    /// Reverted selfdestructs are not included. Also returned in
+    /// [`ResultAndState::selfdestruct_legs`](wiring::result::ResultAndState::selfdestruct_legs).
+    /// Kept by [Self::clear].
+    pub last_selfdestruct_legs: Vec<SelfdestructLeg>,
+    /// Access statistics of the current transaction.
+    #[cfg(feature = "access-stats")]
+    pub access_stats: StateAccessStats,
+    /// Access statistics of the last finalized transaction.
+    ///
+    /// Kept by [Self::clear].
+    #[cfg(feature = "access-stats")]
+    pub last_access_stats: StateAccessStats,
+    /// Read set and write set of the current transaction.
+    #[cfg(feature = "read-write-set")]
+    pub read_write_set: ReadWriteSet,
+    /// Read set and write set of the last finalized transaction.
+    ///
+    /// Kept by [Self::clear].
+    #[cfg(feature = "read-write-set")]
+    pub last_read_write_set: ReadWriteSet,
 }
 
 impl JournaledState {
@@ -66,8 +111,22 @@ impl JournaledState {
             logs: Vec::new(),
             journal: vec![vec![]],
             depth: 0,
+            static_depth: None,
             spec,
             warm_preloaded_addresses,
+            state_override: StateOverride::default(),
+            revert_policy: RevertPolicy::Rollback,
+            last_selfdestruct_legs: Vec::new(),
+            #[cfg(feature = "tx-arena")]
+            arena: TxArena::default(),
+            #[cfg(feature = "access-stats")]
+            access_stats: StateAccessStats::default(),
+            #[cfg(feature = "access-stats")]
+            last_access_stats: StateAccessStats::default(),
+            #[cfg(feature = "read-write-set")]
+            read_write_set: ReadWriteSet::default(),
+            #[cfg(feature = "read-write-set")]
+            last_read_write_set: ReadWriteSet::default(),
         }
     }
 
@@ -105,11 +164,34 @@ impl JournaledState {
     /// Clears the JournaledState. Preserving only the spec.
     pub fn clear(&mut self) {
         let spec = self.spec;
+        let last_selfdestruct_legs = mem::take(&mut self.last_selfdestruct_legs);
+        #[cfg(feature = "tx-arena")]
+        let arena = mem::take(&mut self.arena);
+        #[cfg(feature = "access-stats")]
+        let last_access_stats = mem::take(&mut self.last_access_stats);
+        #[cfg(feature = "read-write-set")]
+        let last_read_write_set = mem::take(&mut self.last_read_write_set);
         *self = Self::new(spec, HashSet::default());
+        self.last_selfdestruct_legs = last_selfdestruct_legs;
+        #[cfg(feature = "tx-arena")]
+        {
+            self.arena = arena;
+        }
+        #[cfg(feature = "access-stats")]
+        {
+            self.last_access_stats = last_access_stats;
+        }
+        #[cfg(feature = "read-write-set")]
+        {
+            self.last_read_write_set = last_read_write_set;
+        }
     }
 
     /// Does cleanup and returns modified state.
     ///
+    /// Since Spurious Dragon, touched empty accounts are marked as cleared, see
+    /// [Account::is_state_cleared].
+    ///
     /// This resets the [JournaledState] to its initial state in [Self::new]
     #[inline]
     pub fn finalize(&mut self) -> (EvmState, Vec<Log>) {
@@ -119,15 +201,100 @@ impl JournaledState {
             logs,
             depth,
             journal,
+            static_depth,
             // kept, see [Self::new]
-            spec: _,
+            spec,
             warm_preloaded_addresses: _,
+            state_override,
+            revert_policy,
+            last_selfdestruct_legs,
+            #[cfg(feature = "tx-arena")]
+            arena,
+            #[cfg(feature = "access-stats")]
+            access_stats,
+            #[cfg(feature = "access-stats")]
+            last_access_stats,
+            #[cfg(feature = "read-write-set")]
+            read_write_set,
+            #[cfg(feature = "read-write-set")]
+            last_read_write_set,
         } = self;
 
+        // EIP-161: State trie clearing. Touched empty accounts are removed.
+        if spec.is_enabled_in(SPURIOUS_DRAGON) {
+            for account in state.values_mut() {
+                if account.is_touched() && !account.is_selfdestructed() && account.is_empty() {
+                    account.mark_state_cleared();
+                }
+            }
+        } else {
+            // Touched non existing accounts are stored as empty accounts.
+            for account in state.values_mut() {
+                if account.is_touched()
+                    && account.is_loaded_as_not_existing()
+                    && !account.is_created()
+                    && !account.is_selfdestructed()
+                    && account.is_empty()
+                {
+                    account.mark_empty_created();
+                }
+            }
+        }
+
         *transient_storage = TransientStorage::default();
-        *journal = vec![vec![]];
+        // Reverted entries were already removed, the journal only holds applied changes.
+        *last_selfdestruct_legs = journal
+            .iter()
+            .flatten()
+            .filter_map(JournalEntry::selfdestruct_leg)
+            .collect();
+        #[cfg(feature = "tx-arena")]
+        {
+            for entries in journal.drain(..) {
+                arena.give_journal(entries);
+            }
+            journal.push(arena.take_journal());
+        }
+        #[cfg(not(feature = "tx-arena"))]
+        {
+            *journal = vec![vec![]];
+        }
         *depth = 0;
+        *static_depth = None;
+        *revert_policy = RevertPolicy::Rollback;
+        #[cfg(feature = "access-stats")]
+        {
+            access_stats.record_state(state);
+            *last_access_stats = mem::take(access_stats);
+        }
+        #[cfg(feature = "read-write-set")]
+        {
+            read_write_set.record_state(state);
+            *last_read_write_set = mem::take(read_write_set);
+        }
+        // State overrides are not changes of the transaction.
+        for (address, account_override) in mem::take(state_override) {
+            let Some(account) = state.get_mut(&address) else {
+                continue;
+            };
+            if !account.is_touched() {
+                state.remove(&address);
+                continue;
+            }
+            account.storage.retain(|key, slot| {
+                slot.is_changed()
+                    || (account_override.state.is_none()
+                        && !account_override
+                            .state_diff
+                            .as_ref()
+                            .is_some_and(|state_diff| state_diff.contains_key(key)))
+            });
+        }
+
         let state = mem::take(state);
+        #[cfg(feature = "tx-arena")]
+        let logs = mem::replace(logs, arena.take_logs());
+        #[cfg(not(feature = "tx-arena"))]
         let logs = mem::take(logs);
 
         (state, logs)
@@ -147,6 +314,15 @@ impl JournaledState {
             .expect("Account expected to be loaded") // Always assume that acc is already loaded
     }
 
+    /// Returns the storage slot if it was already loaded in this transaction.
+    ///
+    /// The slot holds both the original value from before the transaction and the present
+    /// value. Unlike [`Self::sload`], it does not load the slot or mark it as warm.
+    #[inline]
+    pub fn storage_slot(&self, address: Address, key: U256) -> Option<&EvmStorageSlot> {
+        self.state.get(&address)?.storage.get(&key)
+    }
+
     /// Returns call depth.
     #[inline]
     pub fn depth(&self) -> u64 {
@@ -197,6 +373,9 @@ impl JournaledState {
     }
 
     /// Transfers balance from two accounts. Returns error if sender balance is not enough.
+    ///
+    /// Moving a nonzero balance to another account fails with
+    /// [`InstructionResult::StateChangeDuringStaticCall`] in the static context.
     #[inline]
     pub fn transfer<DB: Database>(
         &mut self,
@@ -205,6 +384,10 @@ impl JournaledState {
         balance: U256,
         db: &mut DB,
     ) -> Result<Option<InstructionResult>, DB::Error> {
+        if from != to && !balance.is_zero() && self.is_static_context() {
+            return Ok(Some(InstructionResult::StateChangeDuringStaticCall));
+        }
+
         // load accounts
         self.load_account(*from, db)?;
         self.load_account(*to, db)?;
@@ -212,22 +395,24 @@ impl JournaledState {
         // sub balance from
         let from_account = &mut self.state.get_mut(from).unwrap();
         Self::touch_account(self.journal.last_mut().unwrap(), from, from_account);
-        let from_balance = &mut from_account.info.balance;
-
-        let Some(from_balance_incr) = from_balance.checked_sub(balance) else {
+        let Some(from_balance) = from_account.info.balance.checked_sub(balance) else {
             return Ok(Some(InstructionResult::OutOfFunds));
         };
-        *from_balance = from_balance_incr;
 
-        // add balance to
+        // add balance to. Overflow is checked before any balance is changed.
         let to_account = &mut self.state.get_mut(to).unwrap();
         Self::touch_account(self.journal.last_mut().unwrap(), to, to_account);
-        let to_balance = &mut to_account.info.balance;
-        let Some(to_balance_decr) = to_balance.checked_add(balance) else {
+        let to_balance = if from == to {
+            from_balance
+        } else {
+            to_account.info.balance
+        };
+        let Some(to_balance) = to_balance.checked_add(balance) else {
             return Ok(Some(InstructionResult::OverflowPayment));
         };
-        *to_balance = to_balance_decr;
-        // Overflow of U256 balance is not possible to happen on mainnet. We don't bother to return funds from from_acc.
+
+        self.state.get_mut(from).unwrap().info.balance = from_balance;
+        self.state.get_mut(to).unwrap().info.balance = to_balance;
 
         self.journal
             .last_mut()
@@ -264,6 +449,9 @@ impl JournaledState {
         balance: U256,
         spec_id: SpecId,
     ) -> Result<JournalCheckpoint, InstructionResult> {
+        if self.is_static_context() {
+            return Err(InstructionResult::StateChangeDuringStaticCall);
+        }
         // Enter subroutine
         let checkpoint = self.checkpoint();
 
@@ -324,16 +512,17 @@ impl JournaledState {
     fn journal_revert(
         state: &mut EvmState,
         transient_storage: &mut TransientStorage,
-        journal_entries: Vec<JournalEntry>,
+        journal_entries: &mut Vec<JournalEntry>,
         is_spurious_dragon_enabled: bool,
     ) {
-        for entry in journal_entries.into_iter().rev() {
+        for entry in journal_entries.drain(..).rev() {
             match entry {
                 JournalEntry::AccountWarmed { address } => {
                     state.get_mut(&address).unwrap().mark_cold();
                 }
                 JournalEntry::AccountTouched { address } => {
                     if is_spurious_dragon_enabled && address == PRECOMPILE3 {
+                        state.get_mut(&address).unwrap().mark_touch_kept_on_revert();
                         continue;
                     }
                     // remove touched status
@@ -341,9 +530,8 @@ impl JournaledState {
                 }
                 JournalEntry::AccountDestroyed {
                     address,
-                    target,
                     was_destroyed,
-                    had_balance,
+                    ..
                 } => {
                     let account = state.get_mut(&address).unwrap();
                     // set previous state of selfdestructed flag, as there could be multiple
@@ -355,12 +543,16 @@ impl JournaledState {
                         // flag that is not selfdestructed
                         account.unmark_selfdestruct();
                     }
-                    account.info.balance += had_balance;
-
-                    if address != target {
-                        let target = state.get_mut(&target).unwrap();
-                        target.info.balance -= had_balance;
-                    }
+                }
+                JournalEntry::SelfdestructDebit {
+                    address, balance, ..
+                } => {
+                    state.get_mut(&address).unwrap().info.balance += balance;
+                }
+                JournalEntry::SelfdestructCredit {
+                    target, balance, ..
+                } => {
+                    state.get_mut(&target).unwrap().info.balance -= balance;
                 }
                 JournalEntry::BalanceTransfer { from, to, balance } => {
                     // we don't need to check overflow and underflow when adding and subtracting the balance.
@@ -434,23 +626,184 @@ impl JournaledState {
             journal_i: self.journal.len(),
         };
         self.depth += 1;
+        #[cfg(feature = "tx-arena")]
+        self.journal.push(self.arena.take_journal());
+        #[cfg(not(feature = "tx-arena"))]
         self.journal.push(Default::default());
         checkpoint
     }
 
+    /// Returns `true` if the state was changed or a log was emitted after the checkpoint.
+    ///
+    /// Warming and touching of accounts are not considered to be state changes.
+    #[inline]
+    pub fn has_state_changes_since(&self, checkpoint: &JournalCheckpoint) -> bool {
+        self.logs.len() > checkpoint.log_i
+            || self.journal[checkpoint.journal_i..]
+                .iter()
+                .flatten()
+                .any(JournalEntry::is_state_change)
+    }
+
     /// Commit the checkpoint.
     #[inline]
     pub fn checkpoint_commit(&mut self) {
         self.depth -= 1;
+        self.exit_static_context();
+    }
+
+    /// Marks the current call frame as static, nested call frames inherit the static context.
+    ///
+    /// State changes are not allowed in the static context. Storage writes, logs,
+    /// selfdestructs, creates and value transfers of the journal fail with
+    /// [JournalError::StateChangeDuringStaticCall]. [Self::static_context_violated] checks the
+    /// journal of the frame that entered the static context once it returns, so a change made
+    /// directly to the state cannot go unnoticed either.
+    #[inline]
+    pub fn enter_static_context(&mut self) {
+        if self.static_depth.is_none() {
+            self.static_depth = Some(self.depth);
+        }
+    }
+
+    /// Returns `true` if the current call frame is in the static context.
+    #[inline]
+    pub fn is_static_context(&self) -> bool {
+        self.static_depth.is_some()
+    }
+
+    /// Returns `true` if the current call frame entered the static context and the state was
+    /// changed or a log was emitted after the checkpoint of the frame.
+    #[inline]
+    pub fn static_context_violated(&self, checkpoint: &JournalCheckpoint) -> bool {
+        self.static_depth == Some(self.depth) && self.has_state_changes_since(checkpoint)
+    }
+
+    /// Fails with [JournalError::StateChangeDuringStaticCall] in the static context.
+    #[inline]
+    fn check_state_change<E>(&self) -> Result<(), JournalError<E>> {
+        if self.is_static_context() {
+            return Err(JournalError::StateChangeDuringStaticCall);
+        }
+        Ok(())
+    }
+
+    /// Leaves the static context if the frame that entered it has returned.
+    #[inline]
+    fn exit_static_context(&mut self) {
+        if self.static_depth.is_some_and(|depth| depth > self.depth) {
+            self.static_depth = None;
+        }
     }
 
     /// Reverts all changes to state until given checkpoint.
+    ///
+    /// Changes of the first call of the transaction are kept if the
+    /// [`revert_policy`](Self::revert_policy) is [`RevertPolicy::Keep`].
     #[inline]
     pub fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
+        if self.depth == 1 && self.revert_policy == RevertPolicy::Keep {
+            self.depth = 0;
+            self.exit_static_context();
+            return;
+        }
+        self.depth -= 1;
+        self.exit_static_context();
+        self.revert_journal(checkpoint);
+    }
+
+    /// Makes a snapshot of the state that [`Self::revert_to`] can go back to.
+    ///
+    /// Unlike [`Self::checkpoint`] this does not start a new call frame, only the positions of
+    /// the journal and the logs are recorded. It allows branching execution at a decision
+    /// point, e.g. in an inspector, and trying several continuations without cloning the state.
+    ///
+    /// The snapshot is valid until the end of the call frame it was made in.
+    #[inline]
+    pub fn snapshot(&mut self) -> JournalSnapshot {
+        let checkpoint = JournalCheckpoint {
+            log_i: self.logs.len(),
+            journal_i: self.journal.len(),
+        };
+        #[cfg(feature = "tx-arena")]
+        self.journal.push(self.arena.take_journal());
+        #[cfg(not(feature = "tx-arena"))]
+        self.journal.push(Default::default());
+        JournalSnapshot {
+            checkpoint,
+            depth: self.depth,
+            static_depth: self.static_depth,
+        }
+    }
+
+    /// Reverts all changes to state made after the snapshot.
+    ///
+    /// The snapshot stays valid and can be reverted to again.
+    ///
+    /// Returns [`JournalError::InvalidSnapshot`] and changes nothing if the snapshot was made in
+    /// a different call frame or the journal was finalized since.
+    #[inline]
+    pub fn revert_to(&mut self, snapshot: &JournalSnapshot) -> Result<(), JournalError> {
+        let JournalCheckpoint { log_i, journal_i } = snapshot.checkpoint;
+        if self.depth != snapshot.depth
+            || journal_i >= self.journal.len()
+            || log_i > self.logs.len()
+        {
+            return Err(JournalError::InvalidSnapshot);
+        }
+        self.revert_journal(snapshot.checkpoint);
+        #[cfg(feature = "tx-arena")]
+        self.journal.push(self.arena.take_journal());
+        #[cfg(not(feature = "tx-arena"))]
+        self.journal.push(Default::default());
+        Ok(())
+    }
+
+    /// Reverts all changes to state made after the snapshot, including call frames that were
+    /// entered after it and did not return.
+    ///
+    /// Used to retry the creation of a call frame that failed with a database error. Unlike
+    /// [`Self::revert_to`] the snapshot can not be reverted to again.
+    #[inline]
+    pub(crate) fn rewind_to(&mut self, snapshot: JournalSnapshot) {
+        self.depth = snapshot.depth;
+        self.static_depth = snapshot.static_depth;
+        self.revert_journal(snapshot.checkpoint);
+    }
+
+    /// Returns the number of journal entries of the current call frame.
+    #[cfg(any(test, feature = "suspendable"))]
+    #[inline]
+    pub(crate) fn frame_journal_len(&self) -> usize {
+        self.journal.last().map_or(0, Vec::len)
+    }
+
+    /// Reverts the journal entries of the current call frame after the first `len` ones.
+    ///
+    /// Used to undo the account warming of an instruction that failed with a database error.
+    #[cfg(any(test, feature = "suspendable"))]
+    #[inline]
+    pub(crate) fn revert_frame_journal(&mut self, len: usize) {
+        let is_spurious_dragon_enabled = SpecId::enabled(self.spec, SPURIOUS_DRAGON);
+        let Some(entries) = self.journal.last_mut() else {
+            return;
+        };
+        if entries.len() > len {
+            let mut reverted = entries.split_off(len);
+            Self::journal_revert(
+                &mut self.state,
+                &mut self.transient_storage,
+                &mut reverted,
+                is_spurious_dragon_enabled,
+            );
+        }
+    }
+
+    /// Reverts and removes the journal entries and logs after the checkpoint.
+    fn revert_journal(&mut self, checkpoint: JournalCheckpoint) {
         let is_spurious_dragon_enabled = SpecId::enabled(self.spec, SPURIOUS_DRAGON);
         let state = &mut self.state;
         let transient_storage = &mut self.transient_storage;
-        self.depth -= 1;
         // iterate over last N journals sets and revert our global state
         let leng = self.journal.len();
         self.journal
@@ -458,16 +811,23 @@ impl JournaledState {
             .rev()
             .take(leng - checkpoint.journal_i)
             .for_each(|cs| {
-                Self::journal_revert(
-                    state,
-                    transient_storage,
-                    mem::take(cs),
-                    is_spurious_dragon_enabled,
-                )
+                Self::journal_revert(state, transient_storage, cs, is_spurious_dragon_enabled)
             });
 
-        self.logs.truncate(checkpoint.log_i);
-        self.journal.truncate(checkpoint.journal_i);
+        #[cfg(feature = "tx-arena")]
+        {
+            for log in self.logs.drain(checkpoint.log_i..) {
+                self.arena.give_log(log);
+            }
+            for entries in self.journal.drain(checkpoint.journal_i..) {
+                self.arena.give_journal(entries);
+            }
+        }
+        #[cfg(not(feature = "tx-arena"))]
+        {
+            self.logs.truncate(checkpoint.log_i);
+            self.journal.truncate(checkpoint.journal_i);
+        }
     }
 
     /// Performances selfdestruct action.
@@ -487,61 +847,79 @@ impl JournaledState {
         address: Address,
         target: Address,
         db: &mut DB,
-    ) -> Result<StateLoad<SelfDestructResult>, DB::Error> {
+    ) -> Result<StateLoad<SelfDestructResult>, JournalError<DB::Error>> {
+        self.check_state_change()?;
         let spec = self.spec;
-        let account_load = self.load_account(target, db)?;
+        let account_load = self
+            .load_account(target, db)
+            .map_err(JournalError::Database)?;
         let is_cold = account_load.is_cold;
         let is_empty = account_load.state_clear_aware_is_empty(spec);
 
-        if address != target {
-            // Both accounts are loaded before this point, `address` as we execute its contract.
-            // and `target` at the beginning of the function.
-            let acc_balance = self.state.get_mut(&address).unwrap().info.balance;
+        // Both accounts are loaded before this point, `address` as we execute its contract.
+        // and `target` at the beginning of the function.
+        let acc = self.state.get(&address).unwrap();
+        let balance = acc.info.balance;
+        let previously_destroyed = acc.is_selfdestructed();
+        let is_destroyed = acc.is_created() || !SpecId::enabled(self.spec, CANCUN);
 
-            let target_account = self.state.get_mut(&target).unwrap();
-            Self::touch_account(self.journal.last_mut().unwrap(), &target, target_account);
-            target_account.info.balance += acc_balance;
+        if address != target {
+            let target_account = self.state.get(&target).unwrap();
+            if target_account.info.balance.checked_add(balance).is_none() {
+                return Ok(StateLoad {
+                    data: SelfDestructResult {
+                        had_value: !balance.is_zero(),
+                        target_exists: !is_empty,
+                        balance_overflow: true,
+                        ..Default::default()
+                    },
+                    is_cold,
+                });
+            }
         }
 
-        let acc = self.state.get_mut(&address).unwrap();
-        let balance = acc.info.balance;
-        let previously_destroyed = acc.is_selfdestructed();
-        let is_cancun_enabled = SpecId::enabled(self.spec, CANCUN);
+        // EIP-6780 (Cancun hard-fork): selfdestruct only if contract is created in the same tx.
+        // Otherwise the balance is only moved, and the state is not changed at all if the
+        // target is the same account.
+        if is_destroyed || address != target {
+            let journal = self.journal.last_mut().unwrap();
 
-        // EIP-6780 (Cancun hard-fork): selfdestruct only if contract is created in the same tx
-        let journal_entry = if acc.is_created() || !is_cancun_enabled {
-            acc.mark_selfdestruct();
-            acc.info.balance = U256::ZERO;
-            Some(JournalEntry::AccountDestroyed {
+            // Debit the destroyed account. If it is also the target, the balance is burned.
+            self.state.get_mut(&address).unwrap().info.balance = U256::ZERO;
+            journal.push(JournalEntry::SelfdestructDebit {
                 address,
                 target,
-                was_destroyed: previously_destroyed,
-                had_balance: balance,
-            })
-        } else if address != target {
-            acc.info.balance = U256::ZERO;
-            Some(JournalEntry::BalanceTransfer {
-                from: address,
-                to: target,
                 balance,
-            })
-        } else {
-            // State is not changed:
-            // * if we are after Cancun upgrade and
-            // * Selfdestruct account that is created in the same transaction and
-            // * Specify the target is same as selfdestructed account. The balance stays unchanged.
-            None
-        };
+            });
 
-        if let Some(entry) = journal_entry {
-            self.journal.last_mut().unwrap().push(entry);
-        };
+            if address != target {
+                let target_account = self.state.get_mut(&target).unwrap();
+                Self::touch_account(journal, &target, target_account);
+                target_account.info.balance += balance;
+                journal.push(JournalEntry::SelfdestructCredit {
+                    address,
+                    target,
+                    balance,
+                });
+            }
+
+            if is_destroyed {
+                self.state.get_mut(&address).unwrap().mark_selfdestruct();
+                journal.push(JournalEntry::AccountDestroyed {
+                    address,
+                    target,
+                    was_destroyed: previously_destroyed,
+                    had_balance: balance,
+                });
+            }
+        }
 
         Ok(StateLoad {
             data: SelfDestructResult {
                 had_value: !balance.is_zero(),
                 target_exists: !is_empty,
                 previously_destroyed,
+                balance_overflow: false,
             },
             is_cold,
         })
@@ -558,22 +936,95 @@ impl JournaledState {
         // load or get account.
         let account = match self.state.entry(address) {
             Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(vac) => vac.insert(
-                db.basic(address)?
-                    .map(|i| i.into())
-                    .unwrap_or(Account::new_not_existing()),
-            ),
+            Entry::Vacant(vac) => {
+                let account = Self::load_overridden_account(&self.state_override, address, db)?;
+                #[cfg(feature = "read-write-set")]
+                self.read_write_set.record_access(
+                    StateKey::Account(address),
+                    Some(StateValue::from(&account.info)),
+                );
+                vac.insert(account)
+            }
         };
         // preload storages.
         for storage_key in storage_keys.into_iter() {
             if let Entry::Vacant(entry) = account.storage.entry(storage_key) {
-                let storage = db.storage(address, storage_key)?;
+                let storage =
+                    Self::load_overridden_storage(&self.state_override, address, storage_key, db)?;
+                #[cfg(feature = "read-write-set")]
+                self.read_write_set.record_access(
+                    StateKey::Storage(address, storage_key),
+                    Some(StateValue::Storage(storage)),
+                );
                 entry.insert(EvmStorageSlot::new(storage));
             }
         }
         Ok(account)
     }
 
+    /// Sets the state overrides of the transaction.
+    ///
+    /// Overrides are an overlay of the database: they are applied when an account or a storage
+    /// slot is loaded, and are not journaled nor reported as changes. Accounts and slots that
+    /// the transaction does not change are not a part of the finalized state, accounts it
+    /// changes start from their overridden values.
+    ///
+    /// Needs to be set before overridden accounts are loaded, overrides are removed by
+    /// [Self::finalize].
+    pub fn set_state_override(&mut self, state_override: StateOverride) {
+        self.state_override = state_override;
+    }
+
+    /// Loads account from the database and applies its overrides.
+    fn load_overridden_account<DB: Database>(
+        state_override: &StateOverride,
+        address: Address,
+        db: &mut DB,
+    ) -> Result<Account, DB::Error> {
+        let mut account = db
+            .basic(address)?
+            .map(|i| i.into())
+            .unwrap_or(Account::new_not_existing());
+        if let Some(account_override) = state_override.get(&address) {
+            if let Some(balance) = account_override.balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = account_override.nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code) = &account_override.code {
+                account.info.code_hash = code.hash_slow();
+                account.info.code = Some(code.clone());
+            }
+        }
+        Ok(account)
+    }
+
+    /// Loads storage slot from the database and applies its overrides.
+    ///
+    /// Slots that are not set by a full storage override read as zero.
+    fn load_overridden_storage<DB: Database>(
+        state_override: &StateOverride,
+        address: Address,
+        key: U256,
+        db: &mut DB,
+    ) -> Result<U256, DB::Error> {
+        let Some(account_override) = state_override.get(&address) else {
+            return db.storage(address, key);
+        };
+        if let Some(value) = account_override
+            .state_diff
+            .as_ref()
+            .and_then(|state_diff| state_diff.get(&key))
+        {
+            return Ok(*value);
+        }
+        match &account_override.state {
+            Some(state) => Ok(state.get(&key).copied().unwrap_or_default()),
+            None => db.storage(address, key),
+        }
+    }
+
     /// load account into memory. return if it is cold or warm accessed
     #[inline]
     pub fn load_account<DB: Database>(
@@ -591,11 +1042,12 @@ impl JournaledState {
                 }
             }
             Entry::Vacant(vac) => {
-                let account = if let Some(account) = db.basic(address)? {
-                    account.into()
-                } else {
-                    Account::new_not_existing()
-                };
+                let account = Self::load_overridden_account(&self.state_override, address, db)?;
+                #[cfg(feature = "read-write-set")]
+                self.read_write_set.record_access(
+                    StateKey::Account(address),
+                    Some(StateValue::from(&account.info)),
+                );
 
                 // precompiles are warm loaded so we need to take that into account
                 let is_cold = !self.warm_preloaded_addresses.contains(&address);
@@ -607,6 +1059,9 @@ impl JournaledState {
             }
         };
 
+        #[cfg(feature = "access-stats")]
+        self.access_stats.record_account_load(load.is_cold);
+
         // journal loading of cold account.
         if load.is_cold {
             self.journal
@@ -692,8 +1147,13 @@ impl JournaledState {
                 let value = if is_newly_created {
                     U256::ZERO
                 } else {
-                    db.storage(address, key)?
+                    Self::load_overridden_storage(&self.state_override, address, key, db)?
                 };
+                #[cfg(feature = "read-write-set")]
+                self.read_write_set.record_access(
+                    StateKey::Storage(address, key),
+                    (!is_newly_created).then_some(StateValue::Storage(value)),
+                );
 
                 vac.insert(EvmStorageSlot::new(value));
 
@@ -701,6 +1161,9 @@ impl JournaledState {
             }
         };
 
+        #[cfg(feature = "access-stats")]
+        self.access_stats.record_storage_load(is_cold);
+
         if is_cold {
             // add it to journal as cold loaded.
             self.journal
@@ -725,9 +1188,17 @@ impl JournaledState {
         key: U256,
         new: U256,
         db: &mut DB,
-    ) -> Result<StateLoad<SStoreResult>, DB::Error> {
+    ) -> Result<StateLoad<SStoreResult>, JournalError<DB::Error>> {
+        self.check_state_change()?;
+        #[cfg(feature = "access-stats")]
+        {
+            self.access_stats.storage_writes += 1;
+        }
+
         // assume that acc exists and load the slot.
-        let present = self.sload(address, key, db)?;
+        let present = self
+            .sload(address, key, db)
+            .map_err(JournalError::Database)?;
         let acc = self.state.get_mut(&address).unwrap();
 
         // if there is no original value in dirty return present value, that is our original.
@@ -783,7 +1254,8 @@ impl JournaledState {
     ///
     /// EIP-1153: Transient storage opcodes
     #[inline]
-    pub fn tstore(&mut self, address: Address, key: U256, new: U256) {
+    pub fn tstore(&mut self, address: Address, key: U256, new: U256) -> Result<(), JournalError> {
+        self.check_state_change()?;
         let had_value = if new.is_zero() {
             // if new values is zero, remove entry from transient storage.
             // if previous values was some insert it inside journal.
@@ -816,12 +1288,15 @@ impl JournaledState {
                     had_value,
                 });
         }
+        Ok(())
     }
 
     /// push log into subroutine
     #[inline]
-    pub fn log(&mut self, log: Log) {
+    pub fn log(&mut self, log: Log) -> Result<(), JournalError> {
+        self.check_state_change()?;
         self.logs.push(log);
+        Ok(())
     }
 }
 
@@ -833,15 +1308,34 @@ pub enum JournalEntry {
     /// Action: We will add Account to state.
     /// Revert: we will remove account from state.
     AccountWarmed { address: Address },
-    /// Mark account to be destroyed and journal balance to be reverted
-    /// Action: Mark account and transfer the balance
-    /// Revert: Unmark the account and transfer balance back
+    /// Mark account to be destroyed
+    /// Action: Mark account as selfdestructed
+    /// Revert: Unmark the account
+    ///
+    /// The balance is moved by the [`JournalEntry::SelfdestructDebit`] and
+    /// [`JournalEntry::SelfdestructCredit`] entries before it, `had_balance` is informational.
     AccountDestroyed {
         address: Address,
         target: Address,
         was_destroyed: bool, // if account had already been destroyed before this journal entry
         had_balance: U256,
     },
+    /// Balance taken from the account by `SELFDESTRUCT`
+    /// Action: Set balance of `address` to zero
+    /// Revert: Add balance back to `address`
+    SelfdestructDebit {
+        address: Address,
+        target: Address,
+        balance: U256,
+    },
+    /// Balance given to the beneficiary of `SELFDESTRUCT`, only if it is another account
+    /// Action: Add balance to `target`
+    /// Revert: Subtract balance from `target`
+    SelfdestructCredit {
+        address: Address,
+        target: Address,
+        balance: U256,
+    },
     /// Loading account does not mean that account will need to be added to MerkleTree (touched).
     /// Only when account is called (to execute contract or transfer balance) only then account is made touched.
     /// Action: Mark account touched
@@ -891,6 +1385,79 @@ pub enum JournalEntry {
     CodeChange { address: Address },
 }
 
+impl JournalEntry {
+    /// Returns `true` if the entry changes the state, `false` for warming and touching of accounts.
+    pub fn is_state_change(&self) -> bool {
+        !matches!(
+            self,
+            Self::AccountWarmed { .. } | Self::AccountTouched { .. } | Self::StorageWarmed { .. }
+        )
+    }
+
+    /// Returns the balance movement of a `SELFDESTRUCT` recorded by the entry, if any.
+    pub fn selfdestruct_leg(&self) -> Option<SelfdestructLeg> {
+        match *self {
+            Self::SelfdestructDebit {
+                address,
+                target,
+                balance,
+            } => Some(SelfdestructLeg::Debit {
+                address,
+                target,
+                balance,
+            }),
+            Self::SelfdestructCredit {
+                address,
+                target,
+                balance,
+            } => Some(SelfdestructLeg::Credit {
+                address,
+                target,
+                balance,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Policy applied to the state changes of a transaction that reverts or halts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RevertPolicy {
+    /// Reverts all changes of the transaction, as required by the consensus rules.
+    ///
+    /// Only the gas payment and the nonce bump of the caller stay in the state.
+    #[default]
+    Rollback,
+    /// Keeps the changes that the transaction would have made if it did not fail.
+    ///
+    /// Reverts of nested calls are still applied. The resulting state is not valid and must
+    /// not be committed, it is meant for analytics of failed transactions.
+    /// [`Evm::transact_commit`](crate::Evm::transact_commit) returns an error instead of
+    /// committing it.
+    Keep,
+}
+
+/// Error of a state change in the [JournaledState].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JournalError<E = Infallible> {
+    /// The state can not be changed in the static context, see
+    /// [JournaledState::enter_static_context].
+    StateChangeDuringStaticCall,
+    /// The snapshot can not be reverted to, see [JournaledState::revert_to].
+    InvalidSnapshot,
+    /// Error of the database.
+    Database(E),
+}
+
+/// Snapshot of the [JournaledState] made by [JournaledState::snapshot].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct JournalSnapshot {
+    checkpoint: JournalCheckpoint,
+    depth: usize,
+    static_depth: Option<usize>,
+}
+
 /// SubRoutine checkpoint that will help us to go back from this
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -898,3 +1465,156 @@ pub struct JournalCheckpoint {
     log_i: usize,
     journal_i: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database_interface::EmptyDB;
+    use primitives::address;
+
+    #[test]
+    fn storage_slot() {
+        let address = address!("0000000000000000000000000000000000000100");
+        let mut db = EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::default());
+        journal.load_account(address, &mut db).unwrap();
+        assert_eq!(journal.storage_slot(address, U256::ZERO), None);
+
+        journal
+            .sstore(address, U256::ZERO, U256::from(1), &mut db)
+            .unwrap();
+        let slot = journal.storage_slot(address, U256::ZERO).unwrap();
+        assert_eq!(slot.original_value, U256::ZERO);
+        assert_eq!(slot.present_value, U256::from(1));
+    }
+
+    #[test]
+    fn snapshot_revert_to() {
+        let address = address!("0000000000000000000000000000000000000100");
+        let mut db = EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::default());
+        journal.load_account(address, &mut db).unwrap();
+        journal
+            .sstore(address, U256::ZERO, U256::from(1), &mut db)
+            .unwrap();
+
+        let snapshot = journal.snapshot();
+        for value in [2, 3] {
+            journal
+                .sstore(address, U256::ZERO, U256::from(value), &mut db)
+                .unwrap();
+            journal.log(Log::default()).unwrap();
+            assert_eq!(journal.logs.len(), 1);
+
+            journal.revert_to(&snapshot).unwrap();
+            let slot = journal.storage_slot(address, U256::ZERO).unwrap();
+            assert_eq!(slot.present_value, U256::from(1));
+            assert!(journal.logs.is_empty());
+        }
+        assert_eq!(journal.depth(), 0);
+
+        // Snapshot of another call frame.
+        let checkpoint = journal.checkpoint();
+        assert_eq!(
+            journal.revert_to(&snapshot),
+            Err(JournalError::InvalidSnapshot)
+        );
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.revert_to(&snapshot), Ok(()));
+
+        // Snapshot of a finalized transaction.
+        journal.finalize();
+        assert_eq!(
+            journal.revert_to(&snapshot),
+            Err(JournalError::InvalidSnapshot)
+        );
+    }
+
+    #[test]
+    fn selfdestruct_legs() {
+        let address = address!("0000000000000000000000000000000000000100");
+        let target = address!("0000000000000000000000000000000000000200");
+        let mut db = EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::LONDON, HashSet::default());
+        journal.load_account(address, &mut db).unwrap();
+        journal.load_account(target, &mut db).unwrap();
+        journal.state.get_mut(&address).unwrap().info.balance = U256::from(10);
+        journal.state.get_mut(&target).unwrap().info.balance = U256::from(5);
+
+        // Selfdestruct in a nested frame is reverted by the outer frame.
+        let outer = journal.checkpoint();
+        journal.checkpoint();
+        journal.selfdestruct(address, target, &mut db).unwrap();
+        assert_eq!(journal.account(address).info.balance, U256::ZERO);
+        assert_eq!(journal.account(target).info.balance, U256::from(15));
+        journal.checkpoint_commit();
+        journal.checkpoint_revert(outer);
+        assert_eq!(journal.account(address).info.balance, U256::from(10));
+        assert_eq!(journal.account(target).info.balance, U256::from(5));
+        assert!(!journal.account(address).is_selfdestructed());
+
+        journal.checkpoint();
+        journal.selfdestruct(address, target, &mut db).unwrap();
+        journal.checkpoint_commit();
+        let (state, _) = journal.finalize();
+        assert_eq!(state[&target].info.balance, U256::from(15));
+        let legs = [
+            SelfdestructLeg::Debit {
+                address,
+                target,
+                balance: U256::from(10),
+            },
+            SelfdestructLeg::Credit {
+                address,
+                target,
+                balance: U256::from(10),
+            },
+        ];
+        assert_eq!(journal.last_selfdestruct_legs, legs);
+        journal.clear();
+        assert_eq!(journal.last_selfdestruct_legs, legs);
+    }
+
+    #[test]
+    fn static_context() {
+        let address = address!("0000000000000000000000000000000000000100");
+        let mut db = EmptyDB::default();
+        let mut journal = JournaledState::new(SpecId::CANCUN, HashSet::default());
+        journal.load_account(address, &mut db).unwrap();
+
+        let outer = journal.checkpoint();
+        journal.enter_static_context();
+        let inner = journal.checkpoint();
+        journal.enter_static_context();
+        assert!(journal.is_static_context());
+        assert_eq!(
+            journal.tstore(address, U256::ZERO, U256::from(1)),
+            Err(JournalError::StateChangeDuringStaticCall)
+        );
+        assert_eq!(
+            journal.log(Log::default()),
+            Err(JournalError::StateChangeDuringStaticCall)
+        );
+        assert_eq!(
+            journal.sstore(address, U256::ZERO, U256::from(1), &mut db),
+            Err(JournalError::StateChangeDuringStaticCall)
+        );
+        assert_eq!(
+            journal.selfdestruct(address, Address::ZERO, &mut db),
+            Err(JournalError::StateChangeDuringStaticCall)
+        );
+        assert_eq!(
+            journal.transfer(&address, &Address::ZERO, U256::from(1), &mut db),
+            Ok(Some(InstructionResult::StateChangeDuringStaticCall))
+        );
+        // A change made directly to the state is detected once the frame returns.
+        journal.set_code(address, bytecode::Bytecode::new());
+        // Only the frame that entered the static context checks it.
+        assert!(!journal.static_context_violated(&inner));
+        journal.checkpoint_commit();
+        assert!(journal.static_context_violated(&outer));
+
+        journal.checkpoint_revert(outer);
+        assert!(!journal.is_static_context());
+    }
+}
@@ -18,28 +18,78 @@ pub use transaction;
 pub use wiring;
 
 // Define modules.
+#[cfg(feature = "access-stats")]
+mod access_stats;
+#[cfg(feature = "block-executor")]
+mod block_executor;
 mod builder;
+mod chain_spec;
 mod context;
+#[cfg(feature = "differential")]
+mod differential;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
+mod estimate_gas;
 mod evm;
+mod evm_ref;
 mod evm_wiring;
+mod execution_guard;
 mod frame;
+pub mod gas;
 pub mod handler;
 mod journaled_state;
+#[cfg(feature = "read-write-set")]
+mod read_write_set;
+#[cfg(feature = "self-check")]
+mod self_check;
+mod simulate_bundle;
+mod storage_labels;
+#[cfg(feature = "suspendable")]
+mod suspendable;
+mod tx_analysis;
+#[cfg(feature = "tx-arena")]
+mod tx_arena;
 
 // Export items.
 
+#[cfg(feature = "access-stats")]
+pub use access_stats::StateAccessStats;
+#[cfg(feature = "block-executor")]
+pub use block_executor::{
+    BlockExecutionError, BlockExecutionOutput, BlockExecutor, BlockExecutorWiring, Receipt,
+};
 pub use builder::EvmBuilder;
+pub use chain_spec::ChainSpec;
 pub use context::{
-    Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
+    AddressScheme, Context, ContextPrecompile, ContextPrecompiles, ContextStatefulPrecompile,
     ContextStatefulPrecompileArc, ContextStatefulPrecompileBox, ContextStatefulPrecompileMut,
     ContextWithEvmWiring, EvmContext, InnerEvmContext,
 };
 pub use database_interface::{Database, DatabaseCommit, DatabaseRef};
+#[cfg(feature = "differential")]
+pub use differential::{diff_transaction, Divergence, DivergenceForChain};
+pub use estimate_gas::GasEstimate;
 pub use evm::{Evm, CALL_STACK_LIMIT};
+pub use evm_ref::{EvmRef, EvmRefResult, EvmRefWiring};
 pub use evm_wiring::EvmWiring;
+pub use execution_guard::{execution_guard_register, ExecutionGuard};
 pub use frame::{CallFrame, CreateFrame, Frame, FrameData, FrameOrResult, FrameResult};
 pub use handler::{register::EvmHandler, Handler};
-pub use journaled_state::{JournalCheckpoint, JournalEntry, JournaledState};
+pub use journaled_state::{
+    JournalCheckpoint, JournalEntry, JournalError, JournalSnapshot, JournaledState, RevertPolicy,
+};
+#[cfg(feature = "read-write-set")]
+pub use read_write_set::{ReadWriteSet, StateKey, StateValue};
+#[cfg(feature = "self-check")]
+pub use self_check::{
+    fixture_block, FixtureAccount, ReferenceVector, SelfCheckError, FIXTURE_BASE_FEE,
+    FIXTURE_COINBASE, FIXTURE_GAS_PRICE, FIXTURE_SENDER, REFERENCE_VECTORS,
+};
+pub use simulate_bundle::{BundleSimulation, SimulatedTx};
+pub use storage_labels::{mapping_slot, StorageLabels};
+#[cfg(feature = "suspendable")]
+pub use suspendable::{suspendable_register, Resume, StateData, Suspendable};
+pub use tx_analysis::{analyze_transaction, TxAnalysis};
+#[cfg(feature = "tx-arena")]
+pub use tx_arena::{TxArena, DEFAULT_MAX_BUFFER_SIZE, DEFAULT_MAX_FREE_BUFFERS};
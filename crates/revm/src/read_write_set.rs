@@ -0,0 +1,195 @@
+use primitives::{Address, B256, KECCAK_EMPTY, U256};
+use state::{AccountInfo, EvmState};
+use std::vec::Vec;
+
+/// Location of the state read or written by a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateKey {
+    /// Balance, nonce and code of an account.
+    Account(Address),
+    /// Storage slot of an account.
+    Storage(Address, U256),
+}
+
+/// Value of a [`StateKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateValue {
+    /// Balance, nonce and code hash of an account.
+    ///
+    /// Accounts that do not exist have the values of an empty account.
+    Account {
+        /// Account balance.
+        balance: U256,
+        /// Account nonce.
+        nonce: u64,
+        /// Hash of the account code.
+        code_hash: B256,
+    },
+    /// Account that was destroyed, its storage is cleared.
+    ///
+    /// Only present in the writes.
+    Destroyed,
+    /// Value of a storage slot.
+    Storage(U256),
+}
+
+impl From<&AccountInfo> for StateValue {
+    fn from(info: &AccountInfo) -> Self {
+        Self::Account {
+            balance: info.balance,
+            nonce: info.nonce,
+            code_hash: info.code_hash,
+        }
+    }
+}
+
+/// Read set and write set of a transaction.
+///
+/// Recorded by the [`JournaledState`](crate::JournaledState) and available with
+/// [`Evm::last_read_write_set`](crate::Evm::last_read_write_set) after the transaction. It
+/// allows an external scheduler, like Block-STM, to execute transactions optimistically in
+/// parallel without committing them and to validate and commit them itself, in block order.
+///
+/// A transaction is valid if every read still has the recorded value in the state the
+/// transaction is committed on. The reads are the version of the state the transaction has
+/// seen: they contain the value each location had when it was first read from the database,
+/// also when the read happened in a call that was reverted.
+///
+/// Locations are ordered by the first access of the transaction, which is deterministic, so
+/// the writes can be applied in the same order by every node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReadWriteSet {
+    /// Locations read from the database with the value that was read.
+    pub reads: Vec<(StateKey, StateValue)>,
+    /// Locations changed by the transaction with their new value.
+    pub writes: Vec<(StateKey, StateValue)>,
+    /// Locations in the order of the first access with the value read from the database.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    accessed: Vec<(StateKey, Option<StateValue>)>,
+}
+
+impl ReadWriteSet {
+    /// Records the first access of a location.
+    ///
+    /// `value` is the value read from the database, `None` if the database was not read.
+    #[inline]
+    pub fn record_access(&mut self, key: StateKey, value: Option<StateValue>) {
+        self.accessed.push((key, value));
+        if let Some(value) = value {
+            self.reads.push((key, value));
+        }
+    }
+
+    /// Records the writes of the final state of the transaction.
+    pub fn record_state(&mut self, state: &EvmState) {
+        for (key, read) in &self.accessed {
+            let write = match *key {
+                StateKey::Account(address) => {
+                    let account = &state[&address];
+                    if !account.is_touched() {
+                        continue;
+                    }
+                    if account.is_selfdestructed() {
+                        StateValue::Destroyed
+                    } else if account.is_state_cleared() {
+                        StateValue::Account {
+                            balance: U256::ZERO,
+                            nonce: 0,
+                            code_hash: KECCAK_EMPTY,
+                        }
+                    } else {
+                        StateValue::from(&account.info)
+                    }
+                }
+                StateKey::Storage(address, slot_key) => {
+                    let account = &state[&address];
+                    let slot = &account.storage[&slot_key];
+                    if !account.is_touched() || account.is_selfdestructed() || !slot.is_changed() {
+                        continue;
+                    }
+                    StateValue::Storage(slot.present_value)
+                }
+            };
+            if *read != Some(write) {
+                self.writes.push((*key, write));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Evm;
+    use bytecode::{
+        opcode::{POP, PUSH1, SLOAD, SSTORE, STOP},
+        Bytecode,
+    };
+    use database::BenchmarkDB;
+    use primitives::{address, TxKind};
+    use wiring::EthereumWiring;
+
+    #[test]
+    fn records_read_write_set() {
+        let contract = Address::ZERO;
+        let caller = address!("0000000000000000000000000000000000000001");
+        let bytecode = Bytecode::new_legacy(
+            [
+                PUSH1, 0x00, SLOAD, POP, PUSH1, 0x01, PUSH1, 0x01, SSTORE, PUSH1, 0x00, PUSH1,
+                0x02, SSTORE, STOP,
+            ]
+            .into(),
+        );
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(contract);
+                tx.gas_limit = 100_000;
+            })
+            .build();
+        assert!(evm.transact().unwrap().result.is_success());
+        let rw_set = evm.last_read_write_set();
+
+        let storage_reads: Vec<_> = rw_set
+            .reads
+            .iter()
+            .filter(|(key, _)| matches!(key, StateKey::Storage(..)))
+            .collect();
+        assert_eq!(
+            storage_reads,
+            [
+                &(
+                    StateKey::Storage(contract, U256::ZERO),
+                    StateValue::Storage(U256::ZERO)
+                ),
+                &(
+                    StateKey::Storage(contract, U256::from(1)),
+                    StateValue::Storage(U256::ZERO)
+                ),
+                &(
+                    StateKey::Storage(contract, U256::from(2)),
+                    StateValue::Storage(U256::ZERO)
+                ),
+            ]
+        );
+        // Writing zero to the zero slot is not a write.
+        assert!(rw_set.writes.contains(&(
+            StateKey::Storage(contract, U256::from(1)),
+            StateValue::Storage(U256::from(1))
+        )));
+        assert!(!rw_set
+            .writes
+            .iter()
+            .any(|(key, _)| *key == StateKey::Storage(contract, U256::from(2))));
+        // The nonce of the caller is bumped.
+        assert!(rw_set.writes.iter().any(|(key, value)| {
+            *key == StateKey::Account(caller)
+                && matches!(value, StateValue::Account { nonce: 1, .. })
+        }));
+    }
+}
@@ -0,0 +1,519 @@
+use crate::Evm;
+use alloy_rlp::RlpEncodable;
+use bytecode::Bytecode;
+use core::fmt::{self, Debug};
+use database_interface::Database;
+use hash_db::Hasher;
+use plain_hasher::PlainHasher;
+use primitives::{address, b256, hex, keccak256, Address, HashMap, TxKind, B256, U256};
+use specification::hardfork::SpecId;
+use state::{AccountInfo, EvmState};
+use triehash::sec_trie_root;
+use wiring::{
+    default::{block::BlockEnv, AccountOverride, TxEnv},
+    result::{EVMError, InvalidTransaction},
+    EthereumWiring,
+};
+
+/// Sender of the fixture transactions.
+pub const FIXTURE_SENDER: Address = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+
+/// Coinbase of the fixture blocks, it is not in the pre state.
+pub const FIXTURE_COINBASE: Address = address!("2adc25665018aa1fe0e6bc666dac8fc2697ff9ba");
+
+/// Gas price of the fixture transactions.
+pub const FIXTURE_GAS_PRICE: u64 = 10;
+
+/// Base fee of the fixture blocks since London.
+pub const FIXTURE_BASE_FEE: u64 = 7;
+
+/// Account of the pre state of a fixture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixtureAccount {
+    pub address: Address,
+    pub nonce: u64,
+    pub balance: u128,
+    pub code: &'static [u8],
+    /// Storage as `(slot, value)` pairs.
+    pub storage: &'static [(u64, u64)],
+}
+
+/// State test of the Ethereum execution-spec-tests with its expected post state on one fork.
+///
+/// The vectors are taken from the state test fixtures in `tests/prague_suite`, the expected
+/// state root is the post state `hash` of the fixture. The transaction is sent by
+/// [`FIXTURE_SENDER`] with [`FIXTURE_GAS_PRICE`] and no value or data, in the block
+/// environment shared by the fixtures, see [`fixture_block`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReferenceVector {
+    /// Name of the fixture in the execution-spec-tests.
+    pub name: &'static str,
+    /// Fork the fixture is executed in.
+    pub spec_id: SpecId,
+    /// Accounts of the pre state.
+    pub pre: &'static [FixtureAccount],
+    /// Called contract.
+    pub to: Address,
+    /// Gas limit of the transaction.
+    pub gas_limit: u64,
+    /// Root of the post state trie of the fixture.
+    pub state_root: B256,
+}
+
+const YUL_CONTRACT: Address = address!("0000000000000000000000000000000000001000");
+const YUL_BALANCE: u128 = 0x0ba1a9ce0ba1a9ce;
+
+const fn yul_pre(code: &'static [u8]) -> [FixtureAccount; 2] {
+    [
+        FixtureAccount {
+            address: YUL_CONTRACT,
+            nonce: 1,
+            balance: YUL_BALANCE,
+            code,
+            storage: &[],
+        },
+        FixtureAccount {
+            address: FIXTURE_SENDER,
+            nonce: 0,
+            balance: YUL_BALANCE,
+            code: &[],
+            storage: &[],
+        },
+    ]
+}
+
+// The yul compiler output differs between the forks of the fixture.
+const YUL_HOMESTEAD_PRE: &[FixtureAccount] = &yul_pre(&hex!(
+    "6009600260016012565b60005560206000f35b600082820190509291505056"
+));
+const YUL_BYZANTIUM_PRE: &[FixtureAccount] =
+    &yul_pre(&hex!("6009600260016012565b60005560206000f35b019056"));
+const YUL_SHANGHAI_PRE: &[FixtureAccount] =
+    &yul_pre(&hex!("6009600260016010565b5f5560205ff35b019056"));
+
+const PUSH0_CONTRACT: Address = address!("0000000000000000000000000000000000000100");
+const PUSH0_STORAGE_OVERWRITE_PRE: &[FixtureAccount] = &[
+    FixtureAccount {
+        address: FIXTURE_SENDER,
+        nonce: 0,
+        balance: 0x3635c9adc5dea00000,
+        code: &[],
+        storage: &[],
+    },
+    FixtureAccount {
+        address: PUSH0_CONTRACT,
+        nonce: 0,
+        balance: 0,
+        code: &hex!("60025f555f600155"),
+        storage: &[(0, 10), (1, 10)],
+    },
+];
+
+const fn yul(spec_id: SpecId, pre: &'static [FixtureAccount], state_root: B256) -> ReferenceVector {
+    ReferenceVector {
+        name: "tests/homestead/yul/test_yul_example.py::test_yul",
+        spec_id,
+        pre,
+        to: YUL_CONTRACT,
+        gas_limit: 500_000,
+        state_root,
+    }
+}
+
+const fn push0_storage_overwrite(spec_id: SpecId) -> ReferenceVector {
+    ReferenceVector {
+        name: "tests/shanghai/eip3855_push0/test_push0.py::test_push0_storage_overwrite",
+        spec_id,
+        pre: PUSH0_STORAGE_OVERWRITE_PRE,
+        to: PUSH0_CONTRACT,
+        gas_limit: 100_000,
+        // The second store clears a slot and is refunded.
+        state_root: b256!("2380b564cb75114fa851510fc894626d4e24a55e0ec8c3f7645344d6763f0fe2"),
+    }
+}
+
+const YUL_BYZANTIUM_ROOT: B256 =
+    b256!("7dc61640680eaa1caf8d8f7458872c370a6c31cec9983d5b8a591c3a89b58cfb");
+const YUL_LONDON_ROOT: B256 =
+    b256!("ad2805e2bebbce8cc20207e2cefc20e4bc9e080bae569b734b3177db925b5e4f");
+const YUL_SHANGHAI_ROOT: B256 =
+    b256!("b56915796a9b7a9b1c40d2cf7b8b7e6c25098ab3aafd76d6cd6edabb7d74e083");
+
+/// Reference vectors checked by [`Evm::self_check`].
+pub const REFERENCE_VECTORS: &[ReferenceVector] = &[
+    yul(
+        SpecId::HOMESTEAD,
+        YUL_HOMESTEAD_PRE,
+        b256!("154f5935a676cbe98229394ce886e15212312de9a32076027fd4b77e79ad3d18"),
+    ),
+    yul(SpecId::BYZANTIUM, YUL_BYZANTIUM_PRE, YUL_BYZANTIUM_ROOT),
+    yul(SpecId::PETERSBURG, YUL_BYZANTIUM_PRE, YUL_BYZANTIUM_ROOT),
+    yul(SpecId::ISTANBUL, YUL_BYZANTIUM_PRE, YUL_BYZANTIUM_ROOT),
+    // EIP-2929: cold slot access.
+    yul(
+        SpecId::BERLIN,
+        YUL_BYZANTIUM_PRE,
+        b256!("6b782b3826b53ae2e8ebea3585648c75e87459fb70a3ad0ad58025e867c9e86c"),
+    ),
+    // EIP-1559: the base fee is burned.
+    yul(SpecId::LONDON, YUL_BYZANTIUM_PRE, YUL_LONDON_ROOT),
+    yul(SpecId::MERGE, YUL_BYZANTIUM_PRE, YUL_LONDON_ROOT),
+    // EIP-3855: PUSH0 instruction.
+    yul(SpecId::SHANGHAI, YUL_SHANGHAI_PRE, YUL_SHANGHAI_ROOT),
+    yul(SpecId::CANCUN, YUL_SHANGHAI_PRE, YUL_SHANGHAI_ROOT),
+    yul(SpecId::PRAGUE, YUL_SHANGHAI_PRE, YUL_SHANGHAI_ROOT),
+    push0_storage_overwrite(SpecId::SHANGHAI),
+    push0_storage_overwrite(SpecId::CANCUN),
+    push0_storage_overwrite(SpecId::PRAGUE),
+];
+
+/// Block environment of the fixtures on the given fork.
+pub fn fixture_block(spec_id: SpecId) -> BlockEnv {
+    let is_merge = spec_id.is_enabled_in(SpecId::MERGE);
+    BlockEnv {
+        number: U256::from(1),
+        coinbase: FIXTURE_COINBASE,
+        timestamp: U256::from(1000),
+        gas_limit: U256::from(0x016345785d8a0000u64),
+        basefee: if spec_id.is_enabled_in(SpecId::LONDON) {
+            U256::from(FIXTURE_BASE_FEE)
+        } else {
+            U256::ZERO
+        },
+        difficulty: if is_merge {
+            U256::ZERO
+        } else {
+            U256::from(0x020000)
+        },
+        ..Default::default()
+    }
+}
+
+/// Error returned by [`Evm::self_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelfCheckError<DBError> {
+    /// Transaction of the vector returned an error.
+    Evm {
+        vector: &'static ReferenceVector,
+        error: EVMError<DBError, InvalidTransaction>,
+    },
+    /// Post state root differs from the expected one.
+    StateRoot {
+        vector: &'static ReferenceVector,
+        got: B256,
+    },
+}
+
+impl<DBError> SelfCheckError<DBError> {
+    /// Returns the vector that failed.
+    pub fn vector(&self) -> &ReferenceVector {
+        match self {
+            Self::Evm { vector, .. } | Self::StateRoot { vector, .. } => vector,
+        }
+    }
+}
+
+impl<DBError: fmt::Display> fmt::Display for SelfCheckError<DBError> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vector = self.vector();
+        write!(
+            f,
+            "self-check `{}` on {:?} failed: ",
+            vector.name, vector.spec_id
+        )?;
+        match self {
+            Self::Evm { error, .. } => write!(f, "{error}"),
+            Self::StateRoot { vector, got } => {
+                write!(f, "expected state root {}, got {got}", vector.state_root)
+            }
+        }
+    }
+}
+
+impl<DBError: Debug + fmt::Display> core::error::Error for SelfCheckError<DBError> {}
+
+impl<DB: Database, EXT: Debug> Evm<'_, EthereumWiring<DB, EXT>> {
+    /// Executes the [`REFERENCE_VECTORS`] and checks their post state root against the
+    /// execution-spec-tests fixtures they are taken from.
+    ///
+    /// Meant to be called once at startup by embedders that enable features or append
+    /// handler registers, to verify that the configured EVM is still mainnet equivalent.
+    /// Vectors are executed with the configured handler registers and [`CfgEnv`], nothing is
+    /// committed to the database and the environment and spec id are restored afterwards.
+    ///
+    /// The pre state of a vector and the coinbase are set with state overrides, so the
+    /// outcome does not depend on the database.
+    ///
+    /// [`CfgEnv`]: wiring::default::CfgEnv
+    pub fn self_check(&mut self) -> Result<(), SelfCheckError<DB::Error>> {
+        let env = self.context.evm.env.clone();
+        let spec_id = self.spec_id();
+        let result = REFERENCE_VECTORS
+            .iter()
+            .try_for_each(|vector| self.check_vector(vector));
+        self.context.evm.env = env;
+        self.modify_spec_id(spec_id);
+        result
+    }
+
+    fn check_vector(
+        &mut self,
+        vector: &'static ReferenceVector,
+    ) -> Result<(), SelfCheckError<DB::Error>> {
+        let env = &mut self.context.evm.env;
+        env.block = fixture_block(vector.spec_id);
+        env.tx = TxEnv {
+            caller: FIXTURE_SENDER,
+            transact_to: TxKind::Call(vector.to),
+            gas_limit: vector.gas_limit,
+            gas_price: U256::from(FIXTURE_GAS_PRICE),
+            chain_id: None,
+            ..Default::default()
+        };
+        env.state_override.clear();
+        env.warm_set = Default::default();
+        // The coinbase does not exist in the pre state.
+        env.state_override.insert(
+            FIXTURE_COINBASE,
+            AccountOverride::default()
+                .with_balance(U256::ZERO)
+                .with_nonce(0)
+                .with_code(Bytecode::new())
+                .with_state(HashMap::default()),
+        );
+        for account in vector.pre {
+            env.state_override.insert(
+                account.address,
+                AccountOverride::default()
+                    .with_balance(U256::from(account.balance))
+                    .with_nonce(account.nonce)
+                    .with_code(Bytecode::new_legacy(account.code.into()))
+                    .with_state(slots(account.storage)),
+            );
+        }
+        self.modify_spec_id(vector.spec_id);
+
+        let result_and_state = self
+            .transact()
+            .map_err(|error| SelfCheckError::Evm { vector, error })?;
+        let got = post_state_root(vector.pre, &result_and_state.state);
+        if got != vector.state_root {
+            return Err(SelfCheckError::StateRoot { vector, got });
+        }
+        Ok(())
+    }
+}
+
+fn slots(pairs: &[(u64, u64)]) -> HashMap<U256, U256> {
+    pairs
+        .iter()
+        .map(|(slot, value)| (U256::from(*slot), U256::from(*value)))
+        .collect()
+}
+
+/// Account as it is stored in the state trie.
+#[derive(RlpEncodable)]
+struct TrieAccount {
+    nonce: u64,
+    balance: U256,
+    storage_root: B256,
+    code_hash: B256,
+}
+
+/// Keccak-256 hasher of the state trie.
+#[derive(Debug)]
+struct KeccakHasher;
+
+impl Hasher for KeccakHasher {
+    type Out = B256;
+    type StdHasher = PlainHasher;
+    const LENGTH: usize = 32;
+
+    fn hash(x: &[u8]) -> Self::Out {
+        keccak256(x)
+    }
+}
+
+/// Root of the state trie after the changes of the transaction are applied to the pre state.
+///
+/// Changes are applied the way a database commits them, see `CacheDB::commit`.
+fn post_state_root(pre: &[FixtureAccount], changes: &EvmState) -> B256 {
+    let mut accounts: HashMap<Address, (AccountInfo, HashMap<U256, U256>)> = pre
+        .iter()
+        .map(|account| {
+            let code = Bytecode::new_legacy(account.code.into());
+            let info = AccountInfo::new(
+                U256::from(account.balance),
+                account.nonce,
+                code.hash_slow(),
+                code,
+            );
+            (account.address, (info, slots(account.storage)))
+        })
+        .collect();
+    for (address, account) in changes {
+        if !account.is_touched() {
+            continue;
+        }
+        if account.is_selfdestructed() || account.is_state_cleared() {
+            accounts.remove(address);
+            continue;
+        }
+        let (info, storage) = accounts.entry(*address).or_default();
+        if account.is_created() {
+            storage.clear();
+        }
+        *info = account.info.clone();
+        storage.extend(
+            account
+                .storage
+                .iter()
+                .map(|(slot, value)| (*slot, value.present_value)),
+        );
+    }
+
+    sec_trie_root::<KeccakHasher, _, _, _>(accounts.into_iter().map(
+        |(address, (info, storage))| {
+            let storage_root = sec_trie_root::<KeccakHasher, _, _, _>(
+                storage
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (slot.to_be_bytes::<32>(), alloy_rlp::encode(value))),
+            );
+            let trie_account = TrieAccount {
+                nonce: info.nonce,
+                balance: info.balance,
+                storage_root,
+                code_hash: info.code_hash,
+            };
+            (address, alloy_rlp::encode(trie_account))
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::register::disable_refund_register;
+    use database::BenchmarkDB;
+    use std::string::ToString;
+
+    #[test]
+    fn self_check() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_spec_id(SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| tx.gas_limit = 1)
+            .build();
+        assert_eq!(evm.self_check(), Ok(()));
+        // Environment and spec id are restored.
+        assert_eq!(evm.spec_id(), SpecId::PRAGUE);
+        assert_eq!(evm.tx().gas_limit, 1);
+        assert!(evm.context.evm.env.state_override.is_empty());
+    }
+
+    #[test]
+    fn self_check_register() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .append_handler_register(disable_refund_register)
+            .build();
+        let error = evm.self_check().unwrap_err();
+        assert_eq!(error.vector(), &push0_storage_overwrite(SpecId::SHANGHAI));
+        assert!(matches!(error, SelfCheckError::StateRoot { .. }));
+    }
+
+    /// Checks the vectors against the fixtures they are taken from.
+    #[test]
+    fn vectors_match_fixtures() {
+        let fixtures: [serde_json::Value; 2] = [
+            include_str!(
+                "../../../tests/prague_suite/state_tests/homestead/yul/yul_example/yul.json"
+            ),
+            include_str!("../../../tests/prague_suite/state_tests/shanghai/eip3855_push0/push0/push0_storage_overwrite.json"),
+        ]
+        .map(|fixture| serde_json::from_str(fixture).unwrap());
+        let hex_u256 =
+            |value: &serde_json::Value| -> U256 { value.as_str().unwrap().parse().unwrap() };
+
+        for vector in REFERENCE_VECTORS {
+            let fork = match vector.spec_id {
+                SpecId::HOMESTEAD => "Homestead",
+                SpecId::BYZANTIUM => "Byzantium",
+                SpecId::PETERSBURG => "ConstantinopleFix",
+                SpecId::ISTANBUL => "Istanbul",
+                SpecId::BERLIN => "Berlin",
+                SpecId::LONDON => "London",
+                SpecId::MERGE => "Paris",
+                SpecId::SHANGHAI => "Shanghai",
+                SpecId::CANCUN => "Cancun",
+                SpecId::PRAGUE => "Prague",
+                spec_id => panic!("no fixture fork for {spec_id:?}"),
+            };
+            let name = format!("{}[fork_{fork}-state_test]", vector.name);
+            let test = fixtures
+                .iter()
+                .find_map(|fixture| fixture.get(&name))
+                .unwrap_or_else(|| panic!("missing fixture {name}"));
+
+            let post = &test["post"][fork][0];
+            assert_eq!(
+                post["hash"].as_str().unwrap().parse::<B256>().unwrap(),
+                vector.state_root,
+                "{name}"
+            );
+
+            let tx = &test["transaction"];
+            assert_eq!(
+                hex_u256(&tx["sender"]),
+                U256::from_be_slice(FIXTURE_SENDER.as_slice())
+            );
+            assert_eq!(
+                hex_u256(&tx["to"]),
+                U256::from_be_slice(vector.to.as_slice())
+            );
+            assert_eq!(hex_u256(&tx["gasLimit"][0]), U256::from(vector.gas_limit));
+            assert_eq!(hex_u256(&tx["gasPrice"]), U256::from(FIXTURE_GAS_PRICE));
+            assert!(hex_u256(&tx["value"][0]).is_zero());
+            assert_eq!(tx["data"][0], "0x");
+
+            let env = &test["env"];
+            let block = fixture_block(vector.spec_id);
+            assert_eq!(
+                hex_u256(&env["currentCoinbase"]),
+                U256::from_be_slice(block.coinbase.as_slice())
+            );
+            assert_eq!(hex_u256(&env["currentNumber"]), block.number);
+            assert_eq!(hex_u256(&env["currentTimestamp"]), block.timestamp);
+            assert_eq!(hex_u256(&env["currentGasLimit"]), block.gas_limit);
+            assert_eq!(hex_u256(&env["currentDifficulty"]), block.difficulty);
+            if let Some(base_fee) = env.get("currentBaseFee") {
+                assert_eq!(hex_u256(base_fee), block.basefee);
+            } else {
+                assert!(block.basefee.is_zero());
+            }
+
+            let pre = test["pre"].as_object().unwrap();
+            assert_eq!(pre.len(), vector.pre.len(), "{name}");
+            for account in vector.pre {
+                let fixture = &pre[&account.address.to_string().to_lowercase()];
+                assert_eq!(hex_u256(&fixture["nonce"]), U256::from(account.nonce));
+                assert_eq!(hex_u256(&fixture["balance"]), U256::from(account.balance));
+                assert_eq!(
+                    fixture["code"].as_str().unwrap(),
+                    hex::encode_prefixed(account.code),
+                    "{name}"
+                );
+                let storage = fixture["storage"]
+                    .as_object()
+                    .unwrap()
+                    .iter()
+                    .map(|(slot, value)| (slot.parse().unwrap(), hex_u256(value)))
+                    .collect::<HashMap<U256, U256>>();
+                assert_eq!(storage, slots(account.storage), "{name}");
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Evm, EvmWiring};
+use database_interface::DatabaseCommit;
+use primitives::hash_map::Entry;
+use state::EvmState;
+use std::vec::Vec;
+use wiring::{
+    default::TxEnv,
+    result::{EVMResultGeneric, ExecutionResult, ResultAndState},
+    HaltReasonTrait,
+};
+
+/// Result of a transaction of a bundle simulated with [`Evm::simulate_bundle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulatedTx<HaltReasonT: HaltReasonTrait> {
+    /// Result of the execution.
+    pub result: ExecutionResult<HaltReasonT>,
+    /// Whether the state of the transaction was committed, `false` if it failed and failed
+    /// transactions are dropped.
+    pub included: bool,
+}
+
+/// Output of [`Evm::simulate_bundle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleSimulation<HaltReasonT: HaltReasonTrait> {
+    /// Results of the transactions, in order.
+    pub txs: Vec<SimulatedTx<HaltReasonT>>,
+    /// Combined changes of the included transactions.
+    ///
+    /// Original values of storage slots are the values before the bundle, present values the
+    /// values after it.
+    pub state: EvmState,
+}
+
+impl<EvmWiringT> Evm<'_, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<Transaction = TxEnv, Database: DatabaseCommit>,
+{
+    /// Executes the transactions in order, each on top of the state of the previous ones.
+    ///
+    /// The state of every included transaction is committed to the database, so the loaded
+    /// accounts and slots stay warm in its cache for the following transactions. Wrap the
+    /// database in the `CacheDB` of `revm-database` to discard the bundle afterwards.
+    ///
+    /// If `drop_failed` is set, the state of a transaction that reverts or halts is not
+    /// committed, as if it was not part of the bundle. Otherwise it is committed like in a
+    /// block, where the caller still pays for the gas.
+    ///
+    /// Errors, including invalid transactions, stop the simulation. Transactions before the
+    /// error stay committed.
+    pub fn simulate_bundle(
+        &mut self,
+        txs: impl IntoIterator<Item = TxEnv>,
+        drop_failed: bool,
+    ) -> EVMResultGeneric<BundleSimulation<EvmWiringT::HaltReason>, EvmWiringT> {
+        let mut simulation = BundleSimulation {
+            txs: Vec::new(),
+            state: EvmState::default(),
+        };
+        for tx in txs {
+            *self.tx_mut() = tx;
+            let ResultAndState { result, state, .. } = self.transact()?;
+
+            let included = !drop_failed || result.is_success();
+            if included {
+                merge_state(&mut simulation.state, state.clone());
+                self.db_mut().commit(state);
+            }
+            simulation.txs.push(SimulatedTx { result, included });
+        }
+        Ok(simulation)
+    }
+}
+
+/// Merges the changes of a transaction into the changes of the previous ones.
+fn merge_state(combined: &mut EvmState, state: EvmState) {
+    for (address, account) in state {
+        if !account.is_touched() {
+            continue;
+        }
+        match combined.entry(address) {
+            Entry::Vacant(entry) => {
+                entry.insert(account);
+            }
+            Entry::Occupied(entry) => {
+                let combined = entry.into_mut();
+                // Storage of the previous transactions is gone.
+                if account.is_created() || account.is_selfdestructed() || account.is_state_cleared()
+                {
+                    *combined = account;
+                    continue;
+                }
+                combined.info = account.info;
+                combined.status |= account.status;
+                for (slot, value) in account.storage {
+                    match combined.storage.entry(slot) {
+                        Entry::Vacant(entry) => {
+                            entry.insert(value);
+                        }
+                        Entry::Occupied(entry) => {
+                            entry.into_mut().present_value = value.present_value;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::{
+        opcode::{ADD, PUSH1, REVERT, SLOAD, SSTORE},
+        Bytecode,
+    };
+    use database::InMemoryDB;
+    use primitives::{address, TxKind, U256};
+    use state::AccountInfo;
+    use wiring::EthereumWiring;
+
+    #[test]
+    fn simulates_bundle() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let counter = address!("0000000000000000000000000000000000001000");
+        let reverter = address!("0000000000000000000000000000000000002000");
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(caller, AccountInfo::from_balance(U256::from(1_000_000_000)));
+        // Increments slot 0.
+        db.insert_account_info(
+            counter,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x00, SLOAD, PUSH1, 0x01, ADD, PUSH1, 0x00, SSTORE].into(),
+            )),
+        );
+        db.insert_account_info(
+            reverter,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x00, PUSH1, 0x00, REVERT].into(),
+            )),
+        );
+
+        let tx = |to, nonce| TxEnv {
+            caller,
+            transact_to: TxKind::Call(to),
+            gas_limit: 100_000,
+            nonce,
+            ..Default::default()
+        };
+        let simulate = |drop_failed| {
+            let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+                .with_db(db.clone())
+                .with_default_ext_ctx()
+                .build();
+            // The nonce of the dropped transaction is reused.
+            let second_nonce = if drop_failed { 1 } else { 2 };
+            evm.simulate_bundle(
+                [tx(counter, 0), tx(reverter, 1), tx(counter, second_nonce)],
+                drop_failed,
+            )
+            .unwrap()
+        };
+
+        for drop_failed in [false, true] {
+            let simulation = simulate(drop_failed);
+            let included: Vec<_> = simulation.txs.iter().map(|tx| tx.included).collect();
+            assert_eq!(included, [true, !drop_failed, true]);
+            assert!(!simulation.txs[1].result.is_success());
+
+            let slot = &simulation.state[&counter].storage[&U256::ZERO];
+            assert_eq!(slot.original_value, U256::ZERO);
+            assert_eq!(slot.present_value, U256::from(2));
+            assert_eq!(
+                simulation.state[&caller].info.nonce,
+                if drop_failed { 2 } else { 3 }
+            );
+        }
+    }
+}
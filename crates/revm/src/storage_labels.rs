@@ -0,0 +1,147 @@
+use core::fmt::Write;
+use primitives::{keccak256, Address, HashMap, B256, U256};
+use state::EvmState;
+use std::{format, string::String, vec::Vec};
+
+/// Returns the storage slot of the value of `key` in a Solidity mapping at `slot`.
+///
+/// The slot is `keccak256(key ++ slot)`, where value type keys like addresses and integers are
+/// left padded to 32 bytes. For nested mappings, the returned slot is the slot of the inner
+/// mapping.
+pub fn mapping_slot(key: B256, slot: U256) -> U256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_slice());
+    preimage[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage).into()
+}
+
+/// Human readable labels of storage slots, used to render state diffs.
+///
+/// Slots without a label are rendered as their hex value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageLabels {
+    labels: HashMap<Address, HashMap<U256, String>>,
+}
+
+impl StorageLabels {
+    /// Creates empty labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Labels the storage slot of the account and returns the previous label.
+    pub fn insert(
+        &mut self,
+        address: Address,
+        slot: U256,
+        label: impl Into<String>,
+    ) -> Option<String> {
+        self.labels
+            .entry(address)
+            .or_default()
+            .insert(slot, label.into())
+    }
+
+    /// Labels the value of `key` in the mapping `name` at `slot` as `name[key]`.
+    ///
+    /// Keys whose first 12 bytes are zero are rendered as an address, see [`mapping_slot`].
+    pub fn insert_mapping(&mut self, address: Address, name: &str, slot: U256, key: B256) {
+        let label = if key[..12].iter().all(|byte| *byte == 0) {
+            format!("{name}[{}]", Address::from_word(key))
+        } else {
+            format!("{name}[{key}]")
+        };
+        self.insert(address, mapping_slot(key, slot), label);
+    }
+
+    /// Returns the label of the storage slot of the account.
+    pub fn get(&self, address: &Address, slot: &U256) -> Option<&str> {
+        self.labels.get(address)?.get(slot).map(String::as_str)
+    }
+
+    /// Renders the changed storage slots of the state, one account after another.
+    ///
+    /// Accounts and slots are sorted by address and slot, so the output is deterministic:
+    ///
+    /// ```text
+    /// 0x…0a
+    ///   0x2: 0x1 -> 0x0
+    ///   balanceOf[0x…01]: 0x0 -> 0x64
+    /// ```
+    pub fn render_diff(&self, state: &EvmState) -> String {
+        let mut accounts: Vec<_> = state.iter().collect();
+        accounts.sort_unstable_by_key(|(address, _)| **address);
+
+        let mut out = String::new();
+        for (address, account) in accounts {
+            let mut slots: Vec<_> = account.changed_storage_slots().collect();
+            if slots.is_empty() {
+                continue;
+            }
+            slots.sort_unstable_by_key(|(slot, _)| **slot);
+
+            let _ = writeln!(out, "{address}");
+            for (slot, value) in slots {
+                let _ = match self.get(address, slot) {
+                    Some(label) => write!(out, "  {label}"),
+                    None => write!(out, "  {slot:#x}"),
+                };
+                let _ = writeln!(
+                    out,
+                    ": {:#x} -> {:#x}",
+                    value.original_value, value.present_value
+                );
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{address, b256};
+    use state::{Account, EvmStorageSlot};
+
+    #[test]
+    fn mapping_slots() {
+        // `balanceOf[0x…01]` of a mapping at slot 0.
+        assert_eq!(
+            mapping_slot(
+                address!("0000000000000000000000000000000000000001").into_word(),
+                U256::ZERO
+            ),
+            b256!("ada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d").into()
+        );
+    }
+
+    #[test]
+    fn renders_labeled_diff() {
+        let token = address!("000000000000000000000000000000000000000a");
+        let holder = address!("0000000000000000000000000000000000000001");
+
+        let mut labels = StorageLabels::new();
+        labels.insert_mapping(token, "balanceOf", U256::ZERO, holder.into_word());
+        labels.insert(token, U256::from(2), "totalSupply");
+
+        let mut account = Account::default();
+        account.storage.insert(
+            mapping_slot(holder.into_word(), U256::ZERO),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(100)),
+        );
+        account.storage.insert(
+            U256::from(3),
+            EvmStorageSlot::new_changed(U256::from(1), U256::ZERO),
+        );
+        // Unchanged slots are not rendered.
+        account
+            .storage
+            .insert(U256::from(2), EvmStorageSlot::new(U256::from(5)));
+        let state = EvmState::from_iter([(token, account)]);
+
+        assert_eq!(
+            labels.render_diff(&state),
+            format!("{token}\n  0x3: 0x1 -> 0x0\n  balanceOf[{holder}]: 0x0 -> 0x64\n")
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{
+    evm::CallLoop, handler::register::EvmHandler, Evm, EvmWiring, FrameOrResult, FrameResult,
+    JournaledState,
+};
+use bytecode::opcode;
+use database::{MissingWitnessData, WitnessDB};
+use interpreter::{Gas, InstructionResult, Interpreter};
+use primitives::{B256, U256};
+use state::{AccountInfo, Bytecode};
+use std::format;
+use wiring::result::{EVMError, EVMErrorForChain, EVMResultGeneric, ResultAndState};
+
+/// Instructions that read the state from the database.
+const STATE_OPCODES: [u8; 15] = [
+    opcode::BALANCE,
+    opcode::EXTCODESIZE,
+    opcode::EXTCODECOPY,
+    opcode::EXTCODEHASH,
+    opcode::BLOCKHASH,
+    opcode::SLOAD,
+    opcode::SSTORE,
+    opcode::CALL,
+    opcode::CALLCODE,
+    opcode::DELEGATECALL,
+    opcode::STATICCALL,
+    opcode::EXTCALL,
+    opcode::EXTDELEGATECALL,
+    opcode::EXTSTATICCALL,
+    opcode::SELFDESTRUCT,
+];
+
+/// Maximum number of stack inputs of the [`STATE_OPCODES`], the seven of `CALL`.
+const MAX_INPUTS: usize = 7;
+
+/// Handle register that makes the instructions reading the state resumable, required by
+/// [`Evm::transact_suspendable`].
+///
+/// When the database misses data, the interpreter is rewound to the state before the
+/// instruction and the frame stops with [`InstructionResult::Suspended`], so the instruction is
+/// executed again when the frame is resumed. Other instructions are not wrapped.
+pub fn suspendable_register<EvmWiringT: EvmWiring>(handler: &mut EvmHandler<'_, EvmWiringT>) {
+    for opcode in STATE_OPCODES {
+        handler
+            .instruction_table
+            .update_boxed(opcode, |prev, interpreter, host| {
+                let snapshot = InstructionSnapshot::new(interpreter, &host.evm.journaled_state);
+                prev(interpreter, host);
+                if host.evm.error.is_err() {
+                    snapshot.restore(interpreter, &mut host.evm.journaled_state);
+                    interpreter.instruction_result = InstructionResult::Suspended;
+                }
+            });
+    }
+}
+
+/// State of the interpreter before an instruction that reads the state.
+struct InstructionSnapshot {
+    instruction_pointer: *const u8,
+    gas: Gas,
+    stack_len: usize,
+    inputs: [U256; MAX_INPUTS],
+    memory_len: usize,
+    journal_len: usize,
+}
+
+impl InstructionSnapshot {
+    fn new(interpreter: &Interpreter, journaled_state: &JournaledState) -> Self {
+        let stack = interpreter.stack.data();
+        let mut inputs = [U256::ZERO; MAX_INPUTS];
+        let len = stack.len().min(MAX_INPUTS);
+        inputs[..len].copy_from_slice(&stack[stack.len() - len..]);
+        Self {
+            // The instruction pointer already points to the next instruction.
+            instruction_pointer: interpreter.instruction_pointer.wrapping_sub(1),
+            gas: interpreter.gas,
+            stack_len: stack.len(),
+            inputs,
+            memory_len: interpreter.shared_memory.len(),
+            journal_len: journaled_state.frame_journal_len(),
+        }
+    }
+
+    /// Rewinds the interpreter so that the instruction is executed again.
+    ///
+    /// Accounts warmed by the instruction before the data was missed are cold again, so the
+    /// cold access is charged when the instruction is executed again.
+    fn restore(self, interpreter: &mut Interpreter, journaled_state: &mut JournaledState) {
+        interpreter.instruction_pointer = self.instruction_pointer;
+        interpreter.gas = self.gas;
+        let len = self.stack_len.min(MAX_INPUTS);
+        let stack = interpreter.stack.data_mut();
+        stack.truncate(self.stack_len - len);
+        stack.extend_from_slice(&self.inputs[..len]);
+        interpreter.shared_memory.resize(self.memory_len);
+        journaled_state.revert_frame_journal(self.journal_len);
+    }
+}
+
+/// Execution that is either done or suspended until the host provides missing data.
+#[derive(Debug)]
+pub enum Suspendable<T> {
+    /// Execution is done.
+    Done(T),
+    /// Execution needs data that is not available yet.
+    Suspended(Resume),
+}
+
+/// Token of a transaction suspended by [`Evm::transact_suspendable`].
+///
+/// The host fetches the [requested](Self::request) data, e.g. asynchronously from a remote node,
+/// and passes it to [`Evm::resume`].
+#[derive(Debug)]
+#[must_use = "the transaction is only executed after it is resumed"]
+pub struct Resume {
+    request: MissingWitnessData,
+    stage: Stage,
+}
+
+impl Resume {
+    /// Returns the data the transaction is waiting for.
+    pub fn request(&self) -> &MissingWitnessData {
+        &self.request
+    }
+}
+
+/// Part of the transaction that a suspended transaction continues with.
+#[derive(Debug)]
+enum Stage {
+    /// Validation and pre-execution, they are run again as no code was executed yet.
+    Start,
+    /// Call loop, continued with the instruction or the frame creation that missed the data.
+    Loop {
+        call_loop: CallLoop,
+        eip7702_gas_refund: i64,
+    },
+    /// Post-execution, run again on the journal of the finished call loop.
+    Finish {
+        result: FrameResult,
+        eip7702_gas_refund: i64,
+    },
+}
+
+/// Data that answers the [request](Resume::request) of a suspended transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateData {
+    /// Account, `None` if it does not exist.
+    Account(Option<AccountInfo>),
+    /// Value of a storage slot.
+    Storage(U256),
+    /// Bytecode.
+    Code(Bytecode),
+    /// Block hash.
+    BlockHash(B256),
+}
+
+impl<EvmWiringT: EvmWiring<Database = WitnessDB>> Evm<'_, EvmWiringT> {
+    /// Executes the transaction, or suspends it when it needs data that is not in the witness
+    /// of the database.
+    ///
+    /// Execution does not block on the host: every account, storage slot, code and block hash
+    /// is read from the [`ExecutionWitness`](database::ExecutionWitness), and a missing one
+    /// returns a [`Resume`] token instead of an error. The host can then fetch the data
+    /// asynchronously and continue with [`Evm::resume`]. The EVM must not execute anything else
+    /// until the token is resumed.
+    ///
+    /// A transaction suspended by its bytecode keeps its call stack and continues with the
+    /// instruction that missed the data, which requires the EVM to be built with
+    /// [`suspendable_register`]. Without it the missing data fails the transaction with
+    /// [`EVMError::Database`]. Data missed before the first frame runs, e.g. the caller account,
+    /// restarts the validation and pre-execution, and data missed in the post-execution runs it
+    /// again on a copy of the journal.
+    pub fn transact_suspendable(
+        &mut self,
+    ) -> EVMResultGeneric<Suspendable<ResultAndState<EvmWiringT::HaltReason>>, EvmWiringT> {
+        self.continue_suspendable(Stage::Start)
+    }
+
+    /// Resumes the suspended transaction with the requested data.
+    ///
+    /// Returns [`EVMError::Custom`] and discards the transaction if the data does not answer
+    /// the request of the token.
+    pub fn resume(
+        &mut self,
+        resume: Resume,
+        data: StateData,
+    ) -> EVMResultGeneric<Suspendable<ResultAndState<EvmWiringT::HaltReason>>, EvmWiringT> {
+        let Resume { request, stage } = resume;
+        let witness = &mut self.db_mut().witness;
+        match (request, data) {
+            (MissingWitnessData::Account(address), StateData::Account(info)) => {
+                witness.accounts.insert(address, info);
+            }
+            (MissingWitnessData::Storage { address, index }, StateData::Storage(value)) => {
+                witness
+                    .storage
+                    .entry(address)
+                    .or_default()
+                    .insert(index, value);
+            }
+            (MissingWitnessData::Code(code_hash), StateData::Code(code)) => {
+                witness.codes.insert(code_hash, code);
+            }
+            (MissingWitnessData::BlockHash(number), StateData::BlockHash(hash)) => {
+                witness.block_hashes.insert(number, hash);
+            }
+            (request, data) => {
+                if let Stage::Loop { call_loop, .. } = stage {
+                    self.end_the_loop(call_loop);
+                }
+                self.clear();
+                return Err(EVMError::Custom(format!(
+                    "{data:?} does not answer the request of {request:?}"
+                )));
+            }
+        }
+        self.continue_suspendable(stage)
+    }
+
+    /// Runs the transaction from the stage until it is done or suspended.
+    fn continue_suspendable(
+        &mut self,
+        mut stage: Stage,
+    ) -> EVMResultGeneric<Suspendable<ResultAndState<EvmWiringT::HaltReason>>, EvmWiringT> {
+        loop {
+            stage = match stage {
+                Stage::Start => {
+                    let initial_gas_spend = match self.preverify_transaction_inner() {
+                        Ok(initial_gas_spend) => initial_gas_spend,
+                        Err(e) => {
+                            self.clear();
+                            return Self::suspend_or_fail(e, Stage::Start);
+                        }
+                    };
+                    match self.start_transaction(initial_gas_spend, false) {
+                        Ok((FrameOrResult::Frame(first_frame), eip7702_gas_refund)) => {
+                            Stage::Loop {
+                                call_loop: self.start_the_loop(first_frame, true),
+                                eip7702_gas_refund,
+                            }
+                        }
+                        Ok((FrameOrResult::Result(result), eip7702_gas_refund)) => Stage::Finish {
+                            result,
+                            eip7702_gas_refund,
+                        },
+                        // The caller was already charged, start over from a clean journal.
+                        Err(EVMError::Database(request)) => {
+                            self.clear();
+                            return Self::suspend_or_fail(
+                                EVMError::Database(request),
+                                Stage::Start,
+                            );
+                        }
+                        Err(e) => return self.end_suspendable(Err(e)),
+                    }
+                }
+                Stage::Loop {
+                    mut call_loop,
+                    eip7702_gas_refund,
+                } => match self.continue_the_loop(&mut call_loop) {
+                    Ok(result) => {
+                        self.end_the_loop(call_loop);
+                        Stage::Finish {
+                            result,
+                            eip7702_gas_refund,
+                        }
+                    }
+                    Err(e @ EVMError::Database(_)) if call_loop.is_suspended() => {
+                        return Self::suspend_or_fail(
+                            e,
+                            Stage::Loop {
+                                call_loop,
+                                eip7702_gas_refund,
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        self.end_the_loop(call_loop);
+                        return self.end_suspendable(Err(e));
+                    }
+                },
+                Stage::Finish {
+                    result,
+                    eip7702_gas_refund,
+                } => {
+                    let journaled_state = self.context.evm.journaled_state.clone();
+                    match self.finish_transaction(result.clone(), eip7702_gas_refund) {
+                        Err(e @ EVMError::Database(_)) => {
+                            self.context.evm.journaled_state = journaled_state;
+                            return Self::suspend_or_fail(
+                                e,
+                                Stage::Finish {
+                                    result,
+                                    eip7702_gas_refund,
+                                },
+                            );
+                        }
+                        output => return self.end_suspendable(output),
+                    }
+                }
+            };
+        }
+    }
+
+    /// Ends the transaction like [`Evm::transact`] does.
+    fn end_suspendable(
+        &mut self,
+        output: EVMResultGeneric<ResultAndState<EvmWiringT::HaltReason>, EvmWiringT>,
+    ) -> EVMResultGeneric<Suspendable<ResultAndState<EvmWiringT::HaltReason>>, EvmWiringT> {
+        let output = self.handler.post_execution().end(&mut self.context, output);
+        self.clear();
+        output.map(Suspendable::Done)
+    }
+
+    /// Suspends the transaction at the stage if the error is missing data.
+    fn suspend_or_fail(
+        error: EVMErrorForChain<EvmWiringT>,
+        stage: Stage,
+    ) -> EVMResultGeneric<Suspendable<ResultAndState<EvmWiringT::HaltReason>>, EvmWiringT> {
+        match error {
+            EVMError::Database(request) => Ok(Suspendable::Suspended(Resume { request, stage })),
+            e => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode::{CREATE, POP, PUSH1, SLOAD, SSTORE, STOP};
+    use primitives::{address, Address, HashMap, TxKind};
+    use wiring::EthereumWiring;
+
+    const CALLER: Address = address!("0000000000000000000000000000000000000001");
+    const CONTRACT: Address = address!("0000000000000000000000000000000000001000");
+
+    fn evm<'a>(register: bool) -> Evm<'a, EthereumWiring<WitnessDB, ()>> {
+        let builder = Evm::<EthereumWiring<WitnessDB, ()>>::builder()
+            .with_default_db()
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = CALLER;
+                tx.transact_to = TxKind::Call(CONTRACT);
+                tx.gas_limit = 100_000;
+            });
+        if register {
+            builder
+                .append_handler_register(suspendable_register)
+                .build()
+        } else {
+            builder.build()
+        }
+    }
+
+    #[test]
+    fn resumes_with_requested_data() {
+        // Copies the first storage slot to the second one and creates an empty contract.
+        let code = Bytecode::new_raw(
+            [
+                PUSH1, 0x00, SLOAD, PUSH1, 0x01, SSTORE, PUSH1, 0x00, PUSH1, 0x00, PUSH1, 0x00,
+                CREATE, POP, STOP,
+            ]
+            .into(),
+        );
+        let info = AccountInfo {
+            code: None,
+            ..AccountInfo::from_bytecode(code.clone())
+        };
+        let created = CONTRACT.create(info.nonce);
+        let mut remote = [
+            (
+                MissingWitnessData::Account(CALLER),
+                StateData::Account(Some(AccountInfo::from_balance(U256::from(1_000_000)))),
+            ),
+            (
+                MissingWitnessData::Account(CONTRACT),
+                StateData::Account(Some(info)),
+            ),
+            (
+                MissingWitnessData::Code(code.hash_slow()),
+                StateData::Code(code),
+            ),
+            (
+                MissingWitnessData::Storage {
+                    address: CONTRACT,
+                    index: U256::ZERO,
+                },
+                StateData::Storage(U256::from(7)),
+            ),
+            (
+                MissingWitnessData::Storage {
+                    address: CONTRACT,
+                    index: U256::from(1),
+                },
+                StateData::Storage(U256::ZERO),
+            ),
+            // Created by the frame creation of `CREATE`.
+            (
+                MissingWitnessData::Account(created),
+                StateData::Account(None),
+            ),
+            // Beneficiary of the block.
+            (
+                MissingWitnessData::Account(Address::ZERO),
+                StateData::Account(None),
+            ),
+        ]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        let mut evm = evm(true);
+        let mut execution = evm.transact_suspendable().unwrap();
+        let mut suspensions = 0;
+        let result = loop {
+            match execution {
+                Suspendable::Done(result) => break result,
+                Suspendable::Suspended(resume) => {
+                    suspensions += 1;
+                    if let MissingWitnessData::Storage { index, .. } = resume.request() {
+                        if *index == U256::from(1) {
+                            // The first slot was already read, the execution is not restarted.
+                            let storage = &mut evm.db_mut().witness.storage;
+                            storage
+                                .get_mut(&CONTRACT)
+                                .unwrap()
+                                .insert(U256::ZERO, U256::from(8));
+                        }
+                    }
+                    let data = remote.remove(resume.request()).unwrap();
+                    execution = evm.resume(resume, data).unwrap();
+                }
+            }
+        };
+
+        assert!(result.result.is_success());
+        assert_eq!(
+            result.state[&CONTRACT].storage[&U256::from(1)].present_value,
+            U256::from(7)
+        );
+        assert!(result.state[&created].is_created());
+        assert_eq!(suspensions, 7);
+        assert!(remote.is_empty());
+    }
+
+    #[test]
+    fn resume_with_other_data_fails() {
+        let mut evm = evm(true);
+        let Suspendable::Suspended(resume) = evm.transact_suspendable().unwrap() else {
+            panic!("caller is missing");
+        };
+        assert_eq!(resume.request(), &MissingWitnessData::Account(CALLER));
+        assert!(matches!(
+            evm.resume(resume, StateData::Storage(U256::ZERO)),
+            Err(EVMError::Custom(_))
+        ));
+        // The transaction is discarded.
+        assert!(evm.context.evm.journaled_state.state.is_empty());
+    }
+
+    #[test]
+    fn fails_without_register() {
+        let code = Bytecode::new_raw([PUSH1, 0x00, SLOAD, STOP].into());
+        let mut evm = evm(false);
+        let witness = &mut evm.db_mut().witness;
+        witness.accounts.insert(
+            CALLER,
+            Some(AccountInfo::from_balance(U256::from(1_000_000))),
+        );
+        witness
+            .accounts
+            .insert(CONTRACT, Some(AccountInfo::from_bytecode(code)));
+        witness.accounts.insert(Address::ZERO, None);
+
+        let request = MissingWitnessData::Storage {
+            address: CONTRACT,
+            index: U256::ZERO,
+        };
+        assert_eq!(
+            evm.transact_suspendable().unwrap_err(),
+            EVMError::Database(request)
+        );
+    }
+}
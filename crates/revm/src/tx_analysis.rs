@@ -0,0 +1,115 @@
+use bytecode::{legacy::static_storage_slots, Bytecode};
+use database_interface::Database;
+use primitives::{Address, FixedBytes, TxKind, KECCAK_EMPTY, U256};
+use std::vec::Vec;
+use wiring::Transaction;
+
+/// Result of the static analysis of a transaction.
+///
+/// It is done without executing the transaction and can be used by schedulers to warm caches
+/// or to route transactions to specialized executors.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TxAnalysis {
+    /// Function selector, the first four bytes of the call input.
+    ///
+    /// `None` for create transactions and calls with shorter input.
+    pub selector: Option<FixedBytes<4>>,
+    /// Whether the call target has code. Always `false` for create transactions.
+    pub target_has_code: bool,
+    /// Storage slots of the target that are statically known to be accessed.
+    ///
+    /// For create transactions the init code is analyzed. See
+    /// [`bytecode::legacy::static_storage_slots`] for the detection rules.
+    pub storage_slots: Vec<U256>,
+}
+
+/// Analyzes the transaction without executing it.
+///
+/// Code of the call target is loaded from the database. If the target is delegated with
+/// EIP-7702, the code of the delegated account is analyzed.
+pub fn analyze_transaction<DB: Database>(
+    tx: &impl Transaction,
+    db: &mut DB,
+) -> Result<TxAnalysis, DB::Error> {
+    let input = tx.common_fields().input();
+    let target = match tx.kind() {
+        TxKind::Call(target) => target,
+        TxKind::Create => {
+            return Ok(TxAnalysis {
+                storage_slots: static_storage_slots(input),
+                ..Default::default()
+            })
+        }
+    };
+
+    let selector = input.get(..4).map(FixedBytes::from_slice);
+    let mut code = load_code(target, db)?;
+    if let Bytecode::Eip7702(eip7702) = &code {
+        code = load_code(eip7702.address(), db)?;
+    }
+
+    Ok(TxAnalysis {
+        selector,
+        target_has_code: !code.is_empty(),
+        storage_slots: code.static_storage_slots(),
+    })
+}
+
+/// Loads the code of the account, empty if the account does not exist.
+fn load_code<DB: Database>(address: Address, db: &mut DB) -> Result<Bytecode, DB::Error> {
+    let Some(info) = db.basic(address)? else {
+        return Ok(Bytecode::new());
+    };
+    match info.code {
+        Some(code) => Ok(code),
+        None if info.code_hash == KECCAK_EMPTY => Ok(Bytecode::new()),
+        None => db.code_by_hash(info.code_hash),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytecode::opcode::{PUSH1, SLOAD, SSTORE};
+    use database::InMemoryDB;
+    use primitives::{address, Bytes};
+    use state::AccountInfo;
+    use wiring::default::TxEnv;
+
+    #[test]
+    fn analyze_call() {
+        let contract = address!("0000000000000000000000000000000000001000");
+        let delegated = address!("0000000000000000000000000000000000002000");
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, SLOAD, PUSH1, 0x02, SSTORE].into(),
+            )),
+        );
+        db.insert_account_info(
+            delegated,
+            AccountInfo::from_bytecode(Bytecode::new_eip7702(contract)),
+        );
+
+        let mut tx = TxEnv {
+            transact_to: TxKind::Call(delegated),
+            data: Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]),
+            ..Default::default()
+        };
+        let analysis = analyze_transaction(&tx, &mut db).unwrap();
+        assert_eq!(
+            analysis.selector,
+            Some(FixedBytes::new([0xa9, 0x05, 0x9c, 0xbb]))
+        );
+        assert!(analysis.target_has_code);
+        assert_eq!(analysis.storage_slots, vec![U256::from(1), U256::from(2)]);
+
+        tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000003000"));
+        tx.data = Bytes::new();
+        assert_eq!(
+            analyze_transaction(&tx, &mut db).unwrap(),
+            TxAnalysis::default()
+        );
+    }
+}
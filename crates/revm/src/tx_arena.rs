@@ -0,0 +1,312 @@
+use crate::{Frame, JournalEntry};
+use core::mem;
+use primitives::{Log, B256};
+use std::vec::Vec;
+
+/// Capacity of the reused call stack, one frame more than the call depth limit.
+const CALL_STACK_CAPACITY: usize = crate::CALL_STACK_LIMIT as usize + 1;
+
+/// Default number of free buffers of each kind that the arena keeps.
+pub const DEFAULT_MAX_FREE_BUFFERS: usize = 1024;
+
+/// Default size in bytes above which a returned buffer is dropped instead of kept.
+pub const DEFAULT_MAX_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Arena of temporary buffers that are reset after each transaction.
+///
+/// Journal entries of each call, log topics and data, and the call stack are allocated many
+/// times during the execution and dropped at its end. The arena takes them back once they are
+/// not needed anymore and hands out their cleared allocations, so a block builder that
+/// executes many transactions in a row rarely calls the allocator for them.
+///
+/// Retention is capped: at most `max_free_buffers` buffers of each kind are kept, and buffers
+/// larger than `max_buffer_size` bytes are dropped, so a single large transaction does not pin
+/// its memory for the rest of the block.
+///
+/// Two arenas are equal if they have the same limits and the same number of free buffers. A
+/// clone has the same limits and free buffers of the same capacities.
+#[derive(Debug)]
+pub struct TxArena {
+    journals: Vec<Vec<JournalEntry>>,
+    topics: Vec<Vec<B256>>,
+    data: Vec<Vec<u8>>,
+    logs: Vec<Log>,
+    call_stack: Vec<Frame>,
+    max_free_buffers: usize,
+    max_buffer_size: usize,
+}
+
+impl Default for TxArena {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FREE_BUFFERS, DEFAULT_MAX_BUFFER_SIZE)
+    }
+}
+
+impl TxArena {
+    /// Creates an empty arena that keeps at most `max_free_buffers` buffers of each kind and
+    /// drops buffers larger than `max_buffer_size` bytes.
+    pub fn new(max_free_buffers: usize, max_buffer_size: usize) -> Self {
+        Self {
+            journals: Vec::new(),
+            topics: Vec::new(),
+            data: Vec::new(),
+            logs: Vec::new(),
+            call_stack: Vec::new(),
+            max_free_buffers,
+            max_buffer_size,
+        }
+    }
+
+    /// Returns an empty journal, reusing a previous allocation if there is one.
+    #[inline]
+    pub fn take_journal(&mut self) -> Vec<JournalEntry> {
+        self.journals.pop().unwrap_or_default()
+    }
+
+    /// Gives the journal back to the arena.
+    ///
+    /// Entries are dropped and only the allocation is kept.
+    #[inline]
+    pub fn give_journal(&mut self, journal: Vec<JournalEntry>) {
+        Self::keep(
+            &mut self.journals,
+            journal,
+            self.max_free_buffers,
+            self.max_buffer_size,
+        );
+    }
+
+    /// Returns an empty buffer for log topics, reusing a previous allocation if there is one.
+    #[inline]
+    pub fn take_topics(&mut self) -> Vec<B256> {
+        self.topics.pop().unwrap_or_default()
+    }
+
+    /// Returns an empty buffer for log data, reusing a previous allocation if there is one.
+    #[inline]
+    pub fn take_data(&mut self) -> Vec<u8> {
+        self.data.pop().unwrap_or_default()
+    }
+
+    /// Returns an empty log list, reusing the previous allocation if there is one.
+    #[inline]
+    pub fn take_logs(&mut self) -> Vec<Log> {
+        mem::take(&mut self.logs)
+    }
+
+    /// Gives the topics and the data of the log back to the arena.
+    ///
+    /// The data is only kept if the log holds the last reference to it.
+    #[inline]
+    pub fn give_log(&mut self, log: Log) {
+        let (topics, data) = log.data.split();
+        Self::keep(
+            &mut self.topics,
+            topics,
+            self.max_free_buffers,
+            self.max_buffer_size,
+        );
+        // Converting shared or static data would copy it.
+        if data.is_empty() || !data.0.is_unique() {
+            return;
+        }
+        let data: Vec<u8> = data.0.into();
+        Self::keep(
+            &mut self.data,
+            data,
+            self.max_free_buffers,
+            self.max_buffer_size,
+        );
+    }
+
+    /// Gives the logs of an executed transaction back to the arena.
+    ///
+    /// Call this once the logs returned in the execution result are not needed anymore.
+    pub fn give_logs(&mut self, mut logs: Vec<Log>) {
+        for log in logs.drain(..) {
+            self.give_log(log);
+        }
+        if logs.capacity() * mem::size_of::<Log>() <= self.max_buffer_size {
+            self.logs = logs;
+        }
+    }
+
+    /// Returns an empty call stack, reusing the previous allocation if there is one.
+    #[inline]
+    pub fn take_call_stack(&mut self) -> Vec<Frame> {
+        let call_stack = mem::take(&mut self.call_stack);
+        if call_stack.capacity() == 0 {
+            Vec::with_capacity(CALL_STACK_CAPACITY)
+        } else {
+            call_stack
+        }
+    }
+
+    /// Gives the call stack back to the arena.
+    #[inline]
+    pub fn give_call_stack(&mut self, mut call_stack: Vec<Frame>) {
+        call_stack.clear();
+        self.call_stack = call_stack;
+    }
+
+    /// Returns the number of journal allocations that are available for reuse.
+    #[inline]
+    pub fn free_journals(&self) -> usize {
+        self.journals.len()
+    }
+
+    /// Returns the number of log topics and log data allocations that are available for reuse.
+    #[inline]
+    pub fn free_log_buffers(&self) -> (usize, usize) {
+        (self.topics.len(), self.data.len())
+    }
+
+    /// Returns `true` if a call stack allocation is available for reuse.
+    #[inline]
+    pub fn has_free_call_stack(&self) -> bool {
+        self.call_stack.capacity() != 0
+    }
+
+    /// Clears the buffer and keeps it in `free` if it is not empty and the limits allow it.
+    #[inline]
+    fn keep<T>(free: &mut Vec<Vec<T>>, mut buffer: Vec<T>, max_free: usize, max_size: usize) {
+        let size = buffer.capacity() * mem::size_of::<T>();
+        if size != 0 && size <= max_size && free.len() < max_free {
+            buffer.clear();
+            free.push(buffer);
+        }
+    }
+}
+
+impl Clone for TxArena {
+    fn clone(&self) -> Self {
+        fn with_capacities<T>(free: &[Vec<T>]) -> Vec<Vec<T>> {
+            free.iter()
+                .map(|buffer| Vec::with_capacity(buffer.capacity()))
+                .collect()
+        }
+        Self {
+            journals: with_capacities(&self.journals),
+            topics: with_capacities(&self.topics),
+            data: with_capacities(&self.data),
+            logs: Vec::with_capacity(self.logs.capacity()),
+            call_stack: Vec::with_capacity(self.call_stack.capacity()),
+            max_free_buffers: self.max_free_buffers,
+            max_buffer_size: self.max_buffer_size,
+        }
+    }
+}
+
+impl PartialEq for TxArena {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_free_buffers == other.max_free_buffers
+            && self.max_buffer_size == other.max_buffer_size
+            && self.journals.len() == other.journals.len()
+            && self.topics.len() == other.topics.len()
+            && self.data.len() == other.data.len()
+            && self.has_free_call_stack() == other.has_free_call_stack()
+    }
+}
+
+impl Eq for TxArena {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitives::{Address, Bytes};
+    use std::vec;
+
+    #[test]
+    fn reuses_journals() {
+        let mut arena = TxArena::default();
+        let mut journal = arena.take_journal();
+        assert_eq!(journal.capacity(), 0);
+
+        // Empty allocations are not kept.
+        arena.give_journal(journal);
+        assert_eq!(arena.free_journals(), 0);
+
+        journal = vec![JournalEntry::AccountTouched {
+            address: Address::ZERO,
+        }];
+        let ptr = journal.as_ptr();
+        arena.give_journal(journal);
+        assert_eq!(arena.free_journals(), 1);
+
+        let journal = arena.take_journal();
+        assert!(journal.is_empty());
+        assert_eq!(journal.as_ptr(), ptr);
+        assert_eq!(arena.free_journals(), 0);
+    }
+
+    #[test]
+    fn reuses_call_stack() {
+        let mut arena = TxArena::default();
+        let call_stack = arena.take_call_stack();
+        assert_eq!(call_stack.capacity(), CALL_STACK_CAPACITY);
+        let ptr = call_stack.as_ptr();
+        arena.give_call_stack(call_stack);
+        assert!(arena.has_free_call_stack());
+        let call_stack = arena.take_call_stack();
+        assert_eq!(call_stack.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn reuses_log_buffers() {
+        let mut arena = TxArena::default();
+        let topics = vec![B256::ZERO; 2];
+        let topics_ptr = topics.as_ptr();
+        let data = vec![1u8; 32];
+        let data_ptr = data.as_ptr();
+        let log = Log::new(Address::ZERO, topics, data.into()).unwrap();
+        arena.give_logs(vec![log]);
+        assert_eq!(arena.free_log_buffers(), (1, 1));
+
+        let topics = arena.take_topics();
+        assert!(topics.is_empty());
+        assert_eq!(topics.as_ptr(), topics_ptr);
+        let data = arena.take_data();
+        assert!(data.is_empty());
+        assert_eq!(data.as_ptr(), data_ptr);
+        assert_eq!(arena.take_logs().capacity(), 1);
+
+        // Shared data is not kept, taking it would copy it.
+        let data = Bytes::from(vec![1u8; 32]);
+        let log = Log::new(Address::ZERO, vec![], data.clone()).unwrap();
+        arena.give_log(log);
+        assert_eq!(arena.free_log_buffers(), (0, 0));
+    }
+
+    #[test]
+    fn retention_cap() {
+        let log = |topics| Log::new(Address::ZERO, vec![B256::ZERO; topics], Bytes::new()).unwrap();
+
+        let mut arena = TxArena::new(1, 64);
+        arena.give_log(log(2));
+        // Over the count limit.
+        arena.give_log(log(2));
+        assert_eq!(arena.free_log_buffers(), (1, 0));
+
+        let mut arena = TxArena::new(8, 64);
+        // Over the size limit.
+        arena.give_log(log(3));
+        assert_eq!(arena.free_log_buffers(), (0, 0));
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let mut arena = TxArena::new(4, 1024);
+        arena.give_journal(vec![JournalEntry::AccountTouched {
+            address: Address::ZERO,
+        }]);
+        let call_stack = arena.take_call_stack();
+        arena.give_call_stack(call_stack);
+
+        let clone = arena.clone();
+        assert_eq!(clone, arena);
+        assert_eq!(clone.free_journals(), 1);
+        assert!(clone.has_free_call_stack());
+        assert_ne!(clone, TxArena::new(4, 1024));
+        assert_ne!(TxArena::new(4, 1024), TxArena::default());
+    }
+}
@@ -15,6 +15,19 @@ pub const MAX_BLOB_GAS_PER_BLOCK: u64 = MAX_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB
 /// Target consumable blob gas for data blobs per block (for 1559-like pricing).
 pub const TARGET_BLOB_GAS_PER_BLOCK: u64 = TARGET_BLOB_NUMBER_PER_BLOCK * GAS_PER_BLOB;
 
+/// Target number of the blob per block since Prague, EIP-7691.
+pub const TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE: u64 = 6;
+
+/// Max number of blobs per block since Prague, EIP-7691.
+pub const MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE: u64 = 9;
+
+/// Maximum consumable blob gas for data blobs per block since Prague, EIP-7691.
+pub const MAX_BLOB_GAS_PER_BLOCK_PRAGUE: u64 = MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE * GAS_PER_BLOB;
+
+/// Target consumable blob gas for data blobs per block since Prague, EIP-7691.
+pub const TARGET_BLOB_GAS_PER_BLOCK_PRAGUE: u64 =
+    TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE * GAS_PER_BLOB;
+
 /// Minimum gas price for data blobs.
 pub const MIN_BLOB_GASPRICE: u64 = 1;
 
@@ -0,0 +1,44 @@
+//! Experimental EIPs that are not scheduled for a hardfork yet.
+//!
+//! Each EIP is compiled in with the cargo feature of the same name and is then enabled from
+//! its [`ExperimentalEip::spec_id`] onward, see [`SpecId::is_eip_enabled`]. This allows
+//! benchmarking proposed changes on [`SpecId::OSAKA`] without a fork of revm.
+use crate::hardfork::SpecId;
+
+/// Experimental EIP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ExperimentalEip {
+    /// EIP-7825: Transaction gas limit cap, enabled by the `eip7825` feature.
+    Eip7825,
+}
+
+impl ExperimentalEip {
+    /// All experimental EIPs.
+    pub const ALL: &'static [Self] = &[Self::Eip7825];
+
+    /// Returns the EIP number.
+    #[inline]
+    pub const fn number(self) -> u32 {
+        match self {
+            Self::Eip7825 => 7825,
+        }
+    }
+
+    /// Returns the spec from which the EIP is enabled when compiled in.
+    #[inline]
+    pub const fn spec_id(self) -> SpecId {
+        match self {
+            Self::Eip7825 => SpecId::OSAKA,
+        }
+    }
+
+    /// Returns `true` if the cargo feature of the EIP is enabled.
+    #[inline]
+    pub const fn is_compiled(self) -> bool {
+        match self {
+            Self::Eip7825 => cfg!(feature = "eip7825"),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Hardfork activation schedules of known chains.
+//!
+//! Used by [`SpecId::from_block_and_timestamp`] to pick the spec of historical blocks. The
+//! Merge is scheduled by the number of its first block instead of the terminal total
+//! difficulty, so no chain state is needed to select the spec.
+use crate::hardfork::SpecId;
+
+/// Condition under which a hardfork is active.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForkCondition {
+    /// Active from the block with the given number.
+    Block(u64),
+    /// Active from the first block with a timestamp at or after the given one.
+    Timestamp(u64),
+}
+
+impl ForkCondition {
+    /// Returns `true` if the fork is active in the block with the given number and timestamp.
+    #[inline]
+    pub const fn is_active(self, number: u64, timestamp: u64) -> bool {
+        match self {
+            Self::Block(block) => number >= block,
+            Self::Timestamp(time) => timestamp >= time,
+        }
+    }
+}
+
+/// Hardfork schedule of a chain, ordered by activation.
+pub type ForkSchedule = &'static [(SpecId, ForkCondition)];
+
+/// Chain id of Ethereum mainnet.
+pub const MAINNET_CHAIN_ID: u64 = 1;
+
+/// Chain id of the Sepolia testnet.
+pub const SEPOLIA_CHAIN_ID: u64 = 11155111;
+
+/// Hardfork schedule of Ethereum mainnet.
+///
+/// Constantinople was replaced by Petersburg at the same block, so it is not part of it.
+pub const MAINNET: ForkSchedule = &[
+    (SpecId::FRONTIER, ForkCondition::Block(0)),
+    (SpecId::FRONTIER_THAWING, ForkCondition::Block(200000)),
+    (SpecId::HOMESTEAD, ForkCondition::Block(1150000)),
+    (SpecId::DAO_FORK, ForkCondition::Block(1920000)),
+    (SpecId::TANGERINE, ForkCondition::Block(2463000)),
+    (SpecId::SPURIOUS_DRAGON, ForkCondition::Block(2675000)),
+    (SpecId::BYZANTIUM, ForkCondition::Block(4370000)),
+    (SpecId::PETERSBURG, ForkCondition::Block(7280000)),
+    (SpecId::ISTANBUL, ForkCondition::Block(9069000)),
+    (SpecId::MUIR_GLACIER, ForkCondition::Block(9200000)),
+    (SpecId::BERLIN, ForkCondition::Block(12244000)),
+    (SpecId::LONDON, ForkCondition::Block(12965000)),
+    (SpecId::ARROW_GLACIER, ForkCondition::Block(13773000)),
+    (SpecId::GRAY_GLACIER, ForkCondition::Block(15050000)),
+    (SpecId::MERGE, ForkCondition::Block(15537394)),
+    (SpecId::SHANGHAI, ForkCondition::Timestamp(1681338455)),
+    (SpecId::CANCUN, ForkCondition::Timestamp(1710338135)),
+    (SpecId::PRAGUE, ForkCondition::Timestamp(1746612311)),
+];
+
+/// Hardfork schedule of the Sepolia testnet, which started with London rules.
+pub const SEPOLIA: ForkSchedule = &[
+    (SpecId::LONDON, ForkCondition::Block(0)),
+    (SpecId::MERGE, ForkCondition::Block(1735371)),
+    (SpecId::SHANGHAI, ForkCondition::Timestamp(1677557088)),
+    (SpecId::CANCUN, ForkCondition::Timestamp(1706655072)),
+    (SpecId::PRAGUE, ForkCondition::Timestamp(1741159776)),
+];
+
+/// Returns the hardfork schedule of the chain with the given id.
+#[inline]
+pub const fn fork_schedule(chain_id: u64) -> Option<ForkSchedule> {
+    match chain_id {
+        MAINNET_CHAIN_ID => Some(MAINNET),
+        SEPOLIA_CHAIN_ID => Some(SEPOLIA),
+        _ => None,
+    }
+}
+
+/// Returns the latest hardfork of the schedule that is active in the given block.
+#[inline]
+pub fn active_fork(schedule: ForkSchedule, number: u64, timestamp: u64) -> Option<SpecId> {
+    schedule
+        .iter()
+        .rev()
+        .find(|(_, condition)| condition.is_active(number, timestamp))
+        .map(|(spec_id, _)| *spec_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schedules_are_ordered() {
+        for schedule in [MAINNET, SEPOLIA] {
+            assert!(schedule.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+    }
+
+    #[test]
+    fn mainnet() {
+        let spec = |number, timestamp| {
+            SpecId::from_block_and_timestamp(MAINNET_CHAIN_ID, number, timestamp).unwrap()
+        };
+        assert_eq!(spec(0, 0), SpecId::FRONTIER);
+        assert_eq!(spec(1149999, 0), SpecId::FRONTIER_THAWING);
+        assert_eq!(spec(7280000, 0), SpecId::PETERSBURG);
+        assert_eq!(spec(15537393, 1663224162), SpecId::GRAY_GLACIER);
+        assert_eq!(spec(15537394, 1663224179), SpecId::MERGE);
+        assert_eq!(spec(17034870, 1681338455), SpecId::SHANGHAI);
+        assert_eq!(spec(19426587, 1710338135), SpecId::CANCUN);
+        assert_eq!(spec(22431084, 1746612311), SpecId::PRAGUE);
+    }
+
+    #[test]
+    fn unknown_chain() {
+        assert_eq!(SpecId::from_block_and_timestamp(10, 0, 0), None);
+        assert_eq!(
+            SpecId::from_block_and_timestamp(SEPOLIA_CHAIN_ID, 0, 0),
+            Some(SpecId::LONDON)
+        );
+    }
+}
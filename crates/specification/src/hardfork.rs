@@ -1,5 +1,7 @@
 #![allow(non_camel_case_types)]
 
+use crate::{eip4844, experimental::ExperimentalEip, fork_schedule};
+
 pub use SpecId::*;
 
 /// Specification IDs and their activation block.
@@ -27,8 +29,9 @@ pub enum SpecId {
     MERGE = 15,           // Paris/Merge            15537394 (TTD: 58750000000000000000000)
     SHANGHAI = 16,        // Shanghai               17034870 (Timestamp: 1681338455)
     CANCUN = 17,          // Cancun                 19426587 (Timestamp: 1710338135)
-    PRAGUE = 18,          // Prague                 TBD
+    PRAGUE = 18,          // Prague                 22431084 (Timestamp: 1746612311)
     PRAGUE_EOF = 19,      // Prague+EOF             TBD
+    OSAKA = 20,           // Osaka                  TBD
     #[default]
     LATEST = u8::MAX,
 }
@@ -40,6 +43,15 @@ impl SpecId {
         Self::n(spec_id)
     }
 
+    /// Returns the spec of the block with the given number and timestamp on the given chain.
+    ///
+    /// Returns `None` if the fork schedule of the chain is not known, see
+    /// [`fork_schedule::fork_schedule`].
+    #[inline]
+    pub fn from_block_and_timestamp(chain_id: u64, number: u64, timestamp: u64) -> Option<Self> {
+        fork_schedule::active_fork(fork_schedule::fork_schedule(chain_id)?, number, timestamp)
+    }
+
     /// Returns `true` if the given specification ID is enabled in this spec.
     #[inline]
     pub const fn is_enabled_in(self, other: Self) -> bool {
@@ -51,6 +63,44 @@ impl SpecId {
     pub const fn enabled(our: SpecId, other: SpecId) -> bool {
         our as u8 >= other as u8
     }
+
+    /// Returns `true` if the experimental EIP is compiled in and enabled in this spec.
+    #[inline]
+    pub const fn is_eip_enabled(self, eip: ExperimentalEip) -> bool {
+        eip.is_compiled() && self.is_enabled_in(eip.spec_id())
+    }
+
+    /// Returns the maximum number of blobs per block, zero before Cancun.
+    ///
+    /// Single transaction can't have more blobs than the block.
+    #[inline]
+    pub const fn max_blobs_per_block(self) -> u64 {
+        if self.is_enabled_in(PRAGUE) {
+            eip4844::MAX_BLOB_NUMBER_PER_BLOCK_PRAGUE
+        } else if self.is_enabled_in(CANCUN) {
+            eip4844::MAX_BLOB_NUMBER_PER_BLOCK
+        } else {
+            0
+        }
+    }
+
+    /// Returns the target number of blobs per block, zero before Cancun.
+    #[inline]
+    pub const fn target_blobs_per_block(self) -> u64 {
+        if self.is_enabled_in(PRAGUE) {
+            eip4844::TARGET_BLOB_NUMBER_PER_BLOCK_PRAGUE
+        } else if self.is_enabled_in(CANCUN) {
+            eip4844::TARGET_BLOB_NUMBER_PER_BLOCK
+        } else {
+            0
+        }
+    }
+
+    /// Returns the maximum blob gas per block, zero before Cancun.
+    #[inline]
+    pub const fn max_blob_gas_per_block(self) -> u64 {
+        self.max_blobs_per_block() * eip4844::GAS_PER_BLOB
+    }
 }
 
 /// String identifiers for hardforks.
@@ -75,6 +125,7 @@ pub mod id {
     pub const CANCUN: &str = "Cancun";
     pub const PRAGUE: &str = "Prague";
     pub const PRAGUE_EOF: &str = "PragueEOF";
+    pub const OSAKA: &str = "Osaka";
     pub const LATEST: &str = "Latest";
 }
 
@@ -101,6 +152,7 @@ impl From<&str> for SpecId {
             id::CANCUN => Self::CANCUN,
             id::PRAGUE => Self::PRAGUE,
             id::PRAGUE_EOF => Self::PRAGUE_EOF,
+            id::OSAKA => Self::OSAKA,
             id::LATEST => Self::LATEST,
             _ => Self::LATEST,
         }
@@ -130,6 +182,7 @@ impl From<SpecId> for &'static str {
             SpecId::CANCUN => id::CANCUN,
             SpecId::PRAGUE => id::PRAGUE,
             SpecId::PRAGUE_EOF => id::PRAGUE_EOF,
+            SpecId::OSAKA => id::OSAKA,
             SpecId::LATEST => id::LATEST,
         }
     }
@@ -177,6 +230,7 @@ spec!(SHANGHAI, ShanghaiSpec);
 spec!(CANCUN, CancunSpec);
 spec!(PRAGUE, PragueSpec);
 spec!(PRAGUE_EOF, PragueEofSpec);
+spec!(OSAKA, OsakaSpec);
 
 spec!(LATEST, LatestSpec);
 
@@ -246,6 +300,10 @@ macro_rules! spec_to_generic {
                 use $crate::hardfork::PragueEofSpec as SPEC;
                 $e
             }
+            $crate::hardfork::SpecId::OSAKA => {
+                use $crate::hardfork::OsakaSpec as SPEC;
+                $e
+            }
         }
     }};
 }
@@ -277,6 +335,40 @@ mod tests {
         spec_to_generic!(CANCUN, assert_eq!(SPEC::SPEC_ID, CANCUN));
         spec_to_generic!(PRAGUE, assert_eq!(SPEC::SPEC_ID, PRAGUE));
         spec_to_generic!(PRAGUE_EOF, assert_eq!(SPEC::SPEC_ID, PRAGUE_EOF));
+        spec_to_generic!(OSAKA, assert_eq!(SPEC::SPEC_ID, OSAKA));
         spec_to_generic!(LATEST, assert_eq!(SPEC::SPEC_ID, LATEST));
     }
+
+    #[test]
+    fn stable_discriminants() {
+        // New forks are appended, persisted discriminants keep their meaning.
+        assert_eq!(SpecId::try_from_u8(18), Some(PRAGUE));
+        assert_eq!(SpecId::try_from_u8(19), Some(PRAGUE_EOF));
+        assert_eq!(SpecId::try_from_u8(20), Some(OSAKA));
+    }
+
+    #[test]
+    fn experimental_eips() {
+        for eip in ExperimentalEip::ALL.iter().copied() {
+            assert!(!SpecId::PRAGUE.is_eip_enabled(eip));
+            assert_eq!(SpecId::OSAKA.is_eip_enabled(eip), eip.is_compiled());
+        }
+    }
+
+    #[test]
+    fn blobs_per_block() {
+        assert_eq!(SpecId::SHANGHAI.max_blobs_per_block(), 0);
+        assert_eq!(SpecId::CANCUN.max_blobs_per_block(), 6);
+        assert_eq!(SpecId::CANCUN.target_blobs_per_block(), 3);
+        assert_eq!(
+            SpecId::CANCUN.max_blob_gas_per_block(),
+            eip4844::MAX_BLOB_GAS_PER_BLOCK
+        );
+        assert_eq!(SpecId::PRAGUE.max_blobs_per_block(), 9);
+        assert_eq!(SpecId::PRAGUE.target_blobs_per_block(), 6);
+        assert_eq!(
+            SpecId::LATEST.max_blob_gas_per_block(),
+            eip4844::MAX_BLOB_GAS_PER_BLOCK_PRAGUE
+        );
+    }
 }
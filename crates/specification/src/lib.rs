@@ -11,4 +11,7 @@ pub mod eip2;
 pub mod eip2930;
 pub mod eip4844;
 pub mod eip7702;
+pub mod eip7825;
+pub mod experimental;
+pub mod fork_schedule;
 pub mod hardfork;
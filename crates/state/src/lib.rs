@@ -116,6 +116,37 @@ impl Account {
         self.status.contains(AccountStatus::Created)
     }
 
+    /// Mark account as removed by EIP-161 state clearing.
+    pub fn mark_state_cleared(&mut self) {
+        self.status |= AccountStatus::StateCleared;
+    }
+
+    /// Is account removed by EIP-161 state clearing at the end of the transaction.
+    pub fn is_state_cleared(&self) -> bool {
+        self.status.contains(AccountStatus::StateCleared)
+    }
+
+    /// Mark account touch as kept after the revert of the touching frame.
+    pub fn mark_touch_kept_on_revert(&mut self) {
+        self.status |= AccountStatus::TouchKeptOnRevert;
+    }
+
+    /// Is account touch kept after the revert of the touching frame, see
+    /// [`AccountStatus::TouchKeptOnRevert`].
+    pub fn is_touch_kept_on_revert(&self) -> bool {
+        self.status.contains(AccountStatus::TouchKeptOnRevert)
+    }
+
+    /// Mark account as created empty by a touch before Spurious Dragon.
+    pub fn mark_empty_created(&mut self) {
+        self.status |= AccountStatus::EmptyCreated;
+    }
+
+    /// Is account created empty by a touch before Spurious Dragon.
+    pub fn is_empty_created(&self) -> bool {
+        self.status.contains(AccountStatus::EmptyCreated)
+    }
+
     /// Is account empty, check if nonce and balance are zero and code is empty.
     pub fn is_empty(&self) -> bool {
         self.info.is_empty()
@@ -160,6 +191,17 @@ bitflags! {
         const LoadedAsNotExisting = 0b0001000;
         /// used to mark account as cold
         const Cold = 0b0010000;
+        /// Account is empty and was touched, it is removed from the state by EIP-161: State trie clearing.
+        const StateCleared = 0b0100000;
+        /// Account touch survived the revert of the frame that touched it.
+        ///
+        /// Only RIPEMD-160 since Spurious Dragon, a consensus bug kept for compatibility, see
+        /// [EIP-716](https://eips.ethereum.org/EIPS/eip-716).
+        const TouchKeptOnRevert = 0b1000000;
+        /// Account did not exist, was touched and is stored as an empty account.
+        ///
+        /// Only before Spurious Dragon, after it such accounts are [`AccountStatus::StateCleared`].
+        const EmptyCreated = 0b10000000;
     }
 }
 
@@ -26,7 +26,7 @@ pub enum SpecName {
     Shanghai,
     Cancun,
     Prague,
-    Osaka, // SKIPPED
+    Osaka,
     #[serde(other)]
     Unknown,
 }
@@ -53,7 +53,7 @@ impl SpecName {
             Self::ByzantiumToConstantinopleAt5 | Self::Constantinople => {
                 panic!("Overridden with PETERSBURG")
             }
-            Self::Osaka => panic!("Osaka is not implemented"),
+            Self::Osaka => SpecId::OSAKA,
             Self::Unknown => panic!("Unknown spec"),
         }
     }
@@ -0,0 +1,58 @@
+//! C ABI exported to the WebAssembly host.
+//!
+//! Strings are passed as UTF-8 buffers in the linear memory of the module. The host allocates
+//! the inputs with [`revm_alloc`], calls [`revm_transact`] and releases every buffer with
+//! [`revm_free`] once it is read.
+use crate::transact;
+use serde_json::json;
+use std::{slice, str};
+
+/// Allocates a buffer of `len` bytes and returns its pointer.
+#[no_mangle]
+pub extern "C" fn revm_alloc(len: usize) -> *mut u8 {
+    into_raw(vec![0; len])
+}
+
+/// Releases a buffer returned by [`revm_alloc`] or [`revm_transact`].
+///
+/// # Safety
+///
+/// `ptr` and `len` have to describe a buffer allocated by this module that was not released.
+#[no_mangle]
+pub unsafe extern "C" fn revm_free(ptr: *mut u8, len: usize) {
+    drop(Box::from_raw(slice::from_raw_parts_mut(ptr, len)));
+}
+
+/// Executes a transaction, see [`transact`].
+///
+/// Returns the pointer of the output in the upper 32 bits and its length in the lower ones. The
+/// output is `{"ok": <ResultAndState>}` or `{"error": "<message>"}`.
+///
+/// # Safety
+///
+/// The pointers have to be valid for reads of their lengths.
+#[no_mangle]
+pub unsafe extern "C" fn revm_transact(
+    env_ptr: *const u8,
+    env_len: usize,
+    state_ptr: *const u8,
+    state_len: usize,
+) -> u64 {
+    let env = str::from_utf8(slice::from_raw_parts(env_ptr, env_len));
+    let state = str::from_utf8(slice::from_raw_parts(state_ptr, state_len));
+    let output = match (env, state) {
+        (Ok(env), Ok(state)) => match transact(env, state) {
+            Ok(result) => format!(r#"{{"ok":{result}}}"#),
+            Err(e) => json!({ "error": e.to_string() }).to_string(),
+        },
+        (Err(e), _) | (_, Err(e)) => json!({ "error": e.to_string() }).to_string(),
+    };
+
+    let len = output.len();
+    ((into_raw(output.into_bytes()) as u64) << 32) | len as u64
+}
+
+/// Leaks the buffer, it is released by [`revm_free`].
+fn into_raw(buffer: Vec<u8>) -> *mut u8 {
+    Box::into_raw(buffer.into_boxed_slice()) as *mut u8
+}
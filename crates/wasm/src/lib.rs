@@ -0,0 +1,126 @@
+//! JSON bindings of Revm for WebAssembly hosts.
+//!
+//! [`transact`] executes a transaction described in JSON against a state in the Geth genesis
+//! `alloc` format and returns the result and the changed state as JSON. On `wasm32` it is
+//! exported through a C ABI, see the [`ffi`] module.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+#[cfg(target_arch = "wasm32")]
+pub mod ffi;
+
+use core::{convert::Infallible, fmt};
+use database::InMemoryDB;
+use revm::{
+    specification::hardfork::SpecId,
+    wiring::{
+        default::{block::BlockEnv, CfgEnv, TxEnv},
+        result::{EVMError, InvalidTransaction},
+        EthereumWiring,
+    },
+    Evm,
+};
+use serde::Deserialize;
+
+/// Environment of the transaction executed by [`transact`].
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TransactEnv {
+    /// Hardfork to execute the transaction with, defaults to the latest one.
+    #[serde(default)]
+    pub spec_id: SpecId,
+    /// Configuration of the EVM.
+    #[serde(default)]
+    pub cfg: CfgEnv,
+    /// Block the transaction is executed in.
+    #[serde(default)]
+    pub block: BlockEnv,
+    /// The transaction.
+    pub tx: TxEnv,
+}
+
+/// Error returned by [`transact`].
+#[derive(Debug)]
+pub enum Error {
+    /// The environment or the state is not valid JSON.
+    Json(serde_json::Error),
+    /// The transaction could not be executed.
+    Transaction(EVMError<Infallible, InvalidTransaction>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid JSON: {e}"),
+            Self::Transaction(e) => write!(f, "transaction failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// Executes the transaction of `env_json` against the accounts of `state_json`.
+///
+/// `env_json` is a [`TransactEnv`] and `state_json` a Geth genesis file of which only the
+/// `alloc` section is read. Accounts that are not in the genesis are empty.
+///
+/// Returns the [`ResultAndState`](revm::wiring::result::ResultAndState) as JSON. The state is
+/// not committed, it contains the accounts touched by the transaction.
+pub fn transact(env_json: &str, state_json: &str) -> Result<String, Error> {
+    let env: TransactEnv = serde_json::from_str(env_json)?;
+    let db = InMemoryDB::from_genesis_json(state_json.as_bytes())?;
+
+    let mut evm = Evm::<EthereumWiring<InMemoryDB, ()>>::builder()
+        .with_db(db)
+        .with_default_ext_ctx()
+        .modify_cfg_env(|cfg| *cfg = env.cfg)
+        .with_block_env(env.block)
+        .with_tx_env(env.tx)
+        .with_spec_id(env.spec_id)
+        .build();
+    let result_and_state = evm.transact().map_err(Error::Transaction)?;
+    Ok(serde_json::to_string(&result_and_state)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{address, TxKind, U256};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn transfers_value() {
+        let tx = TxEnv {
+            caller: address!("0000000000000000000000000000000000000001"),
+            gas_limit: 21_000,
+            transact_to: TxKind::Call(address!("00000000000000000000000000000000000000aa")),
+            value: U256::from(100),
+            ..Default::default()
+        };
+        let env = json!({ "tx": tx });
+        let state = json!({
+            "alloc": {
+                "0x0000000000000000000000000000000000000001": { "balance": "0x3e8" }
+            }
+        });
+
+        let output: Value =
+            serde_json::from_str(&transact(&env.to_string(), &state.to_string()).unwrap()).unwrap();
+        assert_eq!(output["result"]["Success"]["gas_used"], 21000);
+        let receiver = &output["state"]["0x00000000000000000000000000000000000000aa"];
+        assert_eq!(receiver["info"]["balance"], "0x64");
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(transact("{}", "{}"), Err(Error::Json(_))));
+        assert!(matches!(
+            transact(r#"{"tx":{}}"#, "not json"),
+            Err(Error::Json(_))
+        ));
+    }
+}
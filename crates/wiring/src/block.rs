@@ -48,6 +48,14 @@ pub trait Block {
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
     fn blob_excess_gas_and_price(&self) -> Option<&BlobExcessGasAndPrice>;
 
+    /// Root of the parent beacon block.
+    ///
+    /// Stored in the beacon roots contract before the first transaction of the block,
+    /// incorporated as part of the Cancun upgrade via [EIP-4788].
+    ///
+    /// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+    fn parent_beacon_block_root(&self) -> Option<&B256>;
+
     /// See [EIP-4844] and [`calc_blob_gasprice`].
     ///
     /// Returns `None` if `Cancun` is not enabled.
@@ -1,8 +1,12 @@
 pub mod block;
+pub mod state_override;
 pub mod tx;
+pub mod warm_set;
 
+pub use state_override::{AccountOverride, StateOverride};
 use transaction::{Eip4844Tx, TransactionType};
 pub use tx::TxEnv;
+pub use warm_set::WarmSet;
 
 use crate::block::blob::calc_blob_gasprice;
 use crate::{Block, EvmWiring, Transaction};
@@ -26,13 +30,27 @@ pub struct Env<BlockT: Block, TxT: Transaction> {
     pub block: BlockT,
     /// Configuration of the transaction that is being executed.
     pub tx: TxT,
+    /// State overrides applied before the transaction is executed.
+    ///
+    /// Used to simulate a transaction on top of a modified state, like `eth_call` does.
+    pub state_override: StateOverride,
+    /// Accounts and storage slots that are warm at the start of the transaction.
+    ///
+    /// Loaded together with the access list, but not charged for.
+    pub warm_set: WarmSet,
 }
 
 impl<BlockT: Block, TxT: Transaction> Env<BlockT, TxT> {
     /// Create boxed [Env].
     #[inline]
     pub fn boxed(cfg: CfgEnv, block: BlockT, tx: TxT) -> Box<Self> {
-        Box::new(Self { cfg, block, tx })
+        Box::new(Self {
+            cfg,
+            block,
+            tx,
+            state_override: StateOverride::default(),
+            warm_set: WarmSet::default(),
+        })
     }
 
     pub fn effective_gas_price(&self) -> U256 {
@@ -95,13 +113,23 @@ pub struct CfgEnv {
     /// Bytecode that is created with CREATE/CREATE2 is by default analysed and jumptable is created.
     /// This is very beneficial for testing and speeds up execution of that bytecode if called multiple times.
     ///
+    /// With [`AnalysisKind::Check`], bytecode loaded for execution is analysed once per
+    /// transaction instead of once per frame.
+    ///
     /// Default: Analyse
     pub perf_analyse_created_bytecodes: AnalysisKind,
     /// If some it will effects EIP-170: Contract code size limit. Useful to increase this because of tests.
     /// By default it is 0x6000 (~25kb).
+    ///
+    /// The EIP-3860 initcode size limit is twice this value. Set it to `Some(usize::MAX)` to
+    /// disable both limits.
     pub limit_contract_code_size: Option<usize>,
     /// Skips the nonce validation against the account's nonce.
     pub disable_nonce_check: bool,
+    /// Disables the deprecated `CALLCODE` opcode, see EIP-2488.
+    ///
+    /// When set, `CALLCODE` halts with `NotActivated` as if it was not defined.
+    pub disable_callcode: bool,
     /// A hard memory limit in bytes beyond which [crate::result::OutOfGasError::Memory] cannot be resized.
     ///
     /// In cases where the gas limit may be extraordinarily high, it is recommended to set this to
@@ -146,6 +174,13 @@ impl CfgEnv {
         self
     }
 
+    /// Sets the KZG settings used by the point evaluation precompile.
+    #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
+    pub fn with_kzg_settings(mut self, kzg_settings: crate::kzg::EnvKzgSettings) -> Self {
+        self.kzg_settings = kzg_settings;
+        self
+    }
+
     #[cfg(feature = "optional_eip3607")]
     pub fn is_eip3607_disabled(&self) -> bool {
         self.disable_eip3607
@@ -199,6 +234,10 @@ impl CfgEnv {
     pub const fn is_nonce_check_disabled(&self) -> bool {
         self.disable_nonce_check
     }
+
+    pub const fn is_callcode_disabled(&self) -> bool {
+        self.disable_callcode
+    }
 }
 
 impl Default for CfgEnv {
@@ -208,6 +247,7 @@ impl Default for CfgEnv {
             perf_analyse_created_bytecodes: AnalysisKind::default(),
             limit_contract_code_size: None,
             disable_nonce_check: false,
+            disable_callcode: false,
             #[cfg(any(feature = "c-kzg", feature = "kzg-rs"))]
             kzg_settings: crate::kzg::EnvKzgSettings::Default,
             #[cfg(feature = "memory_limit")]
@@ -268,12 +308,20 @@ pub enum CreateScheme {
 }
 
 /// What bytecode analysis to perform.
-#[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
+///
+/// Legacy bytecode needs a jump table to be executed. Raw bytecode is analysed by every frame
+/// that executes it, so integrators that store analysed bytecode in their database skip the
+/// analysis at runtime, see `Bytecode::into_analyzed`.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnalysisKind {
     /// Do not perform bytecode analysis.
     Raw,
-    /// Perform bytecode analysis.
+    /// Analyse bytecode lazily, the first time it is executed, and keep the analysed bytecode
+    /// in the journaled state for later frames. Created bytecode is stored raw.
+    Check,
+    /// Analyse created bytecode straight away. Bytecode loaded from the database is used as
+    /// stored.
     #[default]
     Analyse,
 }
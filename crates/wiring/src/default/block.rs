@@ -40,6 +40,13 @@ pub struct BlockEnv {
     ///
     /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
     pub blob_excess_gas_and_price: Option<BlobExcessGasAndPrice>,
+    /// Root of the parent beacon block.
+    ///
+    /// Stored in the beacon roots contract before the first transaction of the block,
+    /// incorporated as part of the Cancun upgrade via [EIP-4788].
+    ///
+    /// [EIP-4788]: https://eips.ethereum.org/EIPS/eip-4788
+    pub parent_beacon_block_root: Option<B256>,
 }
 
 impl BlockEnv {
@@ -90,6 +97,11 @@ impl Block for BlockEnv {
     fn blob_excess_gas_and_price(&self) -> Option<&BlobExcessGasAndPrice> {
         self.blob_excess_gas_and_price.as_ref()
     }
+
+    #[inline]
+    fn parent_beacon_block_root(&self) -> Option<&B256> {
+        self.parent_beacon_block_root.as_ref()
+    }
 }
 
 impl Default for BlockEnv {
@@ -103,6 +115,7 @@ impl Default for BlockEnv {
             difficulty: U256::ZERO,
             prevrandao: Some(B256::ZERO),
             blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
+            parent_beacon_block_root: None,
         }
     }
 }
@@ -0,0 +1,61 @@
+use primitives::{Address, HashMap, U256};
+use state::Bytecode;
+
+/// Per account state overrides, applied before the transaction is executed.
+///
+/// Mirrors the state override set of `eth_call`.
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+/// Overrides of a single account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AccountOverride {
+    /// Replaces the account balance.
+    pub balance: Option<U256>,
+    /// Replaces the account nonce.
+    pub nonce: Option<u64>,
+    /// Replaces the account code.
+    pub code: Option<Bytecode>,
+    /// Replaces the whole account storage, slots that are not set read as zero.
+    pub state: Option<HashMap<U256, U256>>,
+    /// Sets the given storage slots, other slots keep their value.
+    ///
+    /// Applied after [`AccountOverride::state`].
+    pub state_diff: Option<HashMap<U256, U256>>,
+}
+
+impl AccountOverride {
+    /// Sets the balance override.
+    pub fn with_balance(mut self, balance: U256) -> Self {
+        self.balance = Some(balance);
+        self
+    }
+
+    /// Sets the nonce override.
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the code override.
+    pub fn with_code(mut self, code: Bytecode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the full storage override.
+    pub fn with_state(mut self, state: HashMap<U256, U256>) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Sets the storage diff override.
+    pub fn with_state_diff(mut self, state_diff: HashMap<U256, U256>) -> Self {
+        self.state_diff = Some(state_diff);
+        self
+    }
+}
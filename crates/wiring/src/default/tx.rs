@@ -5,8 +5,8 @@ use specification::eip2930::AccessList;
 use specification::eip7702::AuthorizationList;
 use std::vec::Vec;
 use transaction::{
-    eip7702::Authorization, CommonTxFields, Eip1559CommonTxFields, Eip1559Tx, Eip2930Tx, Eip4844Tx,
-    Eip7702Tx, LegacyTx, TransactionType,
+    eip7702::Authorization, CommonTxFields, CustomTx, Eip1559CommonTxFields, Eip1559Tx, Eip2930Tx,
+    Eip4844Tx, Eip7702Tx, LegacyTx, TransactionType,
 };
 
 /// The transaction environment.
@@ -179,11 +179,9 @@ impl Eip1559Tx for TxEnv {
 }
 
 impl Eip4844Tx for TxEnv {
+    /// Returns the zero address for create transactions, which are rejected by validation.
     fn destination(&self) -> Address {
-        match self.transact_to {
-            TxKind::Call(addr) => addr,
-            TxKind::Create => panic!("Create transaction are not allowed in Eip4844"),
-        }
+        self.transact_to.to().copied().unwrap_or_default()
     }
 
     fn blob_versioned_hashes(&self) -> &[B256] {
@@ -196,11 +194,9 @@ impl Eip4844Tx for TxEnv {
 }
 
 impl Eip7702Tx for TxEnv {
+    /// Returns the zero address for create transactions, which are rejected by validation.
     fn destination(&self) -> Address {
-        match self.transact_to {
-            TxKind::Call(addr) => addr,
-            TxKind::Create => panic!("Create transaction are not allowed in Eip7702"),
-        }
+        self.transact_to.to().copied().unwrap_or_default()
     }
 
     fn authorization_list_len(&self) -> usize {
@@ -212,6 +208,21 @@ impl Eip7702Tx for TxEnv {
     }
 }
 
+/// Transactions of a custom type are priced like legacy transactions.
+impl CustomTx for TxEnv {
+    fn kind(&self) -> TxKind {
+        self.transact_to
+    }
+
+    fn max_fee(&self) -> u128 {
+        self.gas_price.to()
+    }
+
+    fn effective_gas_price(&self, _base_fee: U256) -> U256 {
+        self.gas_price
+    }
+}
+
 impl Transaction for TxEnv {
     type TransactionError = InvalidTransaction;
     type TransactionType = TransactionType;
@@ -228,6 +239,8 @@ impl Transaction for TxEnv {
 
     type Eip7702 = Self;
 
+    type Custom = Self;
+
     fn tx_type(&self) -> Self::TransactionType {
         self.tx_type
     }
@@ -251,4 +264,12 @@ impl Transaction for TxEnv {
     fn eip7702(&self) -> &Self::Eip7702 {
         self
     }
+
+    fn custom(&self) -> &Self::Custom {
+        self
+    }
+
+    fn kind(&self) -> TxKind {
+        self.transact_to
+    }
 }
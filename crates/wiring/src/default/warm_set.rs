@@ -0,0 +1,41 @@
+use primitives::{Address, HashMap, HashSet, U256};
+use state::EvmState;
+
+/// Accounts and storage slots that are warm when the transaction starts, in addition to the
+/// access list.
+///
+/// Can be seeded from the state of a previous transaction in the same block, to model
+/// block-level warming.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WarmSet {
+    /// Warm accounts with their warm storage slots.
+    pub accounts: HashMap<Address, HashSet<U256>>,
+}
+
+impl WarmSet {
+    /// Returns `true` if there are no warm accounts.
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+
+    /// Marks the account as warm.
+    pub fn insert_account(&mut self, address: Address) {
+        self.accounts.entry(address).or_default();
+    }
+
+    /// Marks the storage slot and its account as warm.
+    pub fn insert_slot(&mut self, address: Address, slot: U256) {
+        self.accounts.entry(address).or_default().insert(slot);
+    }
+
+    /// Marks all accounts and storage slots that were accessed in the state as warm.
+    pub fn extend_from_state(&mut self, state: &EvmState) {
+        for (address, account) in state {
+            self.accounts
+                .entry(*address)
+                .or_default()
+                .extend(account.storage.keys().copied());
+        }
+    }
+}
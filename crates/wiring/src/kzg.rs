@@ -18,6 +18,21 @@ pub enum EnvKzgSettings {
 }
 
 impl EnvKzgSettings {
+    /// Creates custom KZG settings from the given trusted setup.
+    pub fn new_custom(settings: KzgSettings) -> Self {
+        Self::Custom(std::sync::Arc::new(settings))
+    }
+
+    /// Loads a custom trusted setup from the file at `path`.
+    ///
+    /// The file format is the one used by `c-kzg` (e.g. `trusted_setup.txt`).
+    #[cfg(all(feature = "c-kzg", feature = "std"))]
+    pub fn load_trusted_setup_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, c_kzg::Error> {
+        KzgSettings::load_trusted_setup_file(path.as_ref()).map(Self::new_custom)
+    }
+
     /// Return set KZG settings.
     ///
     /// In will initialize the default settings if it is not already loaded.
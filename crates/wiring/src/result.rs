@@ -1,7 +1,7 @@
 use crate::{evm_wiring::HaltReasonTrait, EvmWiring};
 use core::fmt::{self, Debug};
 use database_interface::Database;
-use primitives::{Address, Bytes, Log, U256};
+use primitives::{Address, Bytes, Log, RevertReason, U256};
 use specification::eip7702::InvalidAuthorization;
 use state::EvmState;
 use std::{boxed::Box, string::String, vec::Vec};
@@ -27,6 +27,30 @@ pub struct ResultAndState<HaltReasonT: HaltReasonTrait> {
     pub result: ExecutionResult<HaltReasonT>,
     /// State that got updated
     pub state: EvmState,
+    /// Balance movements of the `SELFDESTRUCT`s that were not reverted, in execution order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selfdestruct_legs: Vec<SelfdestructLeg>,
+}
+
+/// One side of the balance movement of a `SELFDESTRUCT`.
+///
+/// A selfdestruct debits the destroyed account and credits the target. If the target is the
+/// destroyed account itself, the balance is burnt and there is only the debit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SelfdestructLeg {
+    /// Balance taken from the destroyed `address`.
+    Debit {
+        address: Address,
+        target: Address,
+        balance: U256,
+    },
+    /// Balance given to the `target` of `address`.
+    Credit {
+        address: Address,
+        target: Address,
+        balance: U256,
+    },
 }
 
 /// Result of a transaction execution.
@@ -86,6 +110,16 @@ impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
         }
     }
 
+    /// Decodes the output of a reverted execution, see [RevertReason::decode].
+    ///
+    /// Returns `None` if the execution did not revert or reverted without data.
+    pub fn revert_reason(&self) -> Option<RevertReason> {
+        match self {
+            Self::Revert { output, .. } => RevertReason::decode(output),
+            _ => None,
+        }
+    }
+
     /// Returns the logs if execution is successful, or an empty list otherwise.
     pub fn logs(&self) -> &[Log] {
         match self {
@@ -110,6 +144,16 @@ impl<HaltReasonT: HaltReasonTrait> ExecutionResult<HaltReasonT> {
             | Self::Halt { gas_used, .. } => gas_used,
         }
     }
+
+    /// Returns the gas refunded to the caller, already subtracted from [`Self::gas_used`].
+    ///
+    /// Only successful executions receive refunds.
+    pub fn gas_refunded(&self) -> u64 {
+        match *self {
+            Self::Success { gas_refunded, .. } => gas_refunded,
+            Self::Revert { .. } | Self::Halt { .. } => 0,
+        }
+    }
 }
 
 /// Output of a transaction execution.
@@ -245,6 +289,11 @@ pub enum InvalidTransaction {
     GasPriceLessThanBasefee,
     /// `gas_limit` in the tx is bigger than `block_gas_limit`.
     CallerGasLimitMoreThanBlock,
+    /// Transaction gas limit is above the cap of EIP-7825.
+    TxGasLimitGreaterThanCap {
+        gas_limit: u64,
+        cap: u64,
+    },
     /// Initial gas for a Call is bigger than `gas_limit`.
     ///
     /// Initial gas for a Call contains:
@@ -287,7 +336,7 @@ pub enum InvalidTransaction {
     /// Blob transaction can't be a create transaction.
     /// `to` must be present
     BlobCreateTransaction,
-    /// Transaction has more then [`specification::eip4844::MAX_BLOB_NUMBER_PER_BLOCK`] blobs
+    /// Transaction has more then [`specification::hardfork::SpecId::max_blobs_per_block`] blobs
     TooManyBlobs {
         max: usize,
         have: usize,
@@ -300,6 +349,9 @@ pub enum InvalidTransaction {
     AuthorizationListNotSupported,
     /// EIP-7702 transaction has invalid fields set.
     AuthorizationListInvalidFields,
+    /// EIP-7702 transaction can't be a create transaction.
+    /// `to` must be present
+    Eip7702CreateTransaction,
     /// Empty Authorization List is not allowed.
     EmptyAuthorizationList,
     /// Invalid EIP-7702 Authorization List
@@ -336,6 +388,12 @@ impl fmt::Display for InvalidTransaction {
             Self::CallerGasLimitMoreThanBlock => {
                 write!(f, "caller gas limit exceeds the block gas limit")
             }
+            Self::TxGasLimitGreaterThanCap { gas_limit, cap } => {
+                write!(
+                    f,
+                    "transaction gas limit {gas_limit} is greater than the cap {cap}"
+                )
+            }
             Self::CallGasCostMoreThanGasLimit => {
                 write!(f, "call gas cost exceeds the gas limit")
             }
@@ -382,6 +440,7 @@ impl fmt::Display for InvalidTransaction {
             Self::AuthorizationListInvalidFields => {
                 write!(f, "authorization list tx has invalid fields")
             }
+            Self::Eip7702CreateTransaction => write!(f, "EIP-7702 create transaction"),
             Self::EmptyAuthorizationList => write!(f, "empty authorization list"),
             Self::Eip2930NotSupported => write!(f, "Eip2930 is not supported"),
             Self::Eip1559NotSupported => write!(f, "Eip1559 is not supported"),
@@ -459,8 +518,51 @@ pub enum HaltReason {
     EofAuxDataTooSmall,
     /// EOF Subroutine stack overflow
     EOFFunctionStackOverflow,
+    /// `RETURNCONTRACT` called outside of EOF init code.
+    ReturnContractInNotInitEOF,
+    /// Legacy bytecode used an opcode that is only valid in EOF.
+    EOFOpcodeDisabledInLegacy,
     /// Check for target address validity is only done inside subcall.
     InvalidEXTCALLTarget,
+    /// Execution was interrupted by an execution guard before it finished.
+    Interrupted,
+}
+
+impl fmt::Display for HaltReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfGas(error) => write!(f, "out of gas: {error}"),
+            Self::OpcodeNotFound => write!(f, "opcode not found"),
+            Self::InvalidFEOpcode => write!(f, "invalid 0xFE opcode"),
+            Self::InvalidJump => write!(f, "invalid jump destination"),
+            Self::NotActivated => write!(f, "opcode is not activated in this spec"),
+            Self::StackUnderflow => write!(f, "stack underflow"),
+            Self::StackOverflow => write!(f, "stack overflow"),
+            Self::OutOfOffset => write!(f, "out of bounds offset"),
+            Self::CreateCollision => write!(f, "create collision"),
+            Self::PrecompileError => write!(f, "precompile error"),
+            Self::NonceOverflow => write!(f, "nonce overflow"),
+            Self::CreateContractSizeLimit => write!(f, "created contract size limit exceeded"),
+            Self::CreateContractStartingWithEF => {
+                write!(f, "created contract starts with 0xEF")
+            }
+            Self::CreateInitCodeSizeLimit => write!(f, "init code size limit exceeded"),
+            Self::OverflowPayment => write!(f, "payment overflow"),
+            Self::StateChangeDuringStaticCall => write!(f, "state change during static call"),
+            Self::CallNotAllowedInsideStatic => write!(f, "call with value inside static call"),
+            Self::OutOfFunds => write!(f, "out of funds"),
+            Self::CallTooDeep => write!(f, "call too deep"),
+            Self::EofAuxDataOverflow => write!(f, "EOF aux data overflow"),
+            Self::EofAuxDataTooSmall => write!(f, "EOF aux data too small"),
+            Self::EOFFunctionStackOverflow => write!(f, "EOF function stack overflow"),
+            Self::InvalidEXTCALLTarget => write!(f, "invalid EXTCALL target"),
+            Self::ReturnContractInNotInitEOF => {
+                write!(f, "RETURNCONTRACT outside of EOF init code")
+            }
+            Self::EOFOpcodeDisabledInLegacy => write!(f, "EOF opcode used in legacy bytecode"),
+            Self::Interrupted => write!(f, "interrupted"),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -480,3 +582,16 @@ pub enum OutOfGasError {
     // When performing SSTORE the gasleft is less than or equal to 2300
     ReentrancySentry,
 }
+
+impl fmt::Display for OutOfGasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic => write!(f, "basic"),
+            Self::MemoryLimit => write!(f, "memory limit"),
+            Self::Memory => write!(f, "memory expansion"),
+            Self::Precompile => write!(f, "precompile"),
+            Self::InvalidOperand => write!(f, "invalid operand"),
+            Self::ReentrancySentry => write!(f, "reentrancy sentry"),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::CommonTxFields;
+use primitives::{TxKind, U256};
+
+/// Transaction of a type that is not defined by Ethereum, see
+/// [`TransactionType::Custom`](crate::TransactionType::Custom).
+///
+/// The provided methods of [`Transaction`](crate::Transaction) route custom transactions to
+/// this trait.
+pub trait CustomTx: CommonTxFields {
+    /// Transaction kind.
+    fn kind(&self) -> TxKind;
+
+    /// Maximum fee that can be paid for the transaction.
+    fn max_fee(&self) -> u128;
+
+    /// Effective gas price of the transaction for the given base fee.
+    fn effective_gas_price(&self, base_fee: U256) -> U256;
+}
@@ -4,6 +4,7 @@
 
 mod access_list;
 mod common;
+mod custom;
 pub mod eip1559;
 pub mod eip2930;
 pub mod eip4844;
@@ -14,6 +15,7 @@ pub mod transaction_type;
 
 pub use access_list::AccessListTrait;
 pub use common::CommonTxFields;
+pub use custom::CustomTx;
 pub use eip1559::{Eip1559CommonTxFields, Eip1559Tx};
 pub use eip2930::Eip2930Tx;
 pub use eip4844::Eip4844Tx;
@@ -1,6 +1,6 @@
 use crate::{
-    eip1559::Eip1559CommonTxFields, AccessListTrait, CommonTxFields, Eip1559Tx, Eip2930Tx,
-    Eip4844Tx, Eip7702Tx, LegacyTx, TransactionType,
+    eip1559::Eip1559CommonTxFields, AccessListTrait, CommonTxFields, CustomTx, Eip1559Tx,
+    Eip2930Tx, Eip4844Tx, Eip7702Tx, LegacyTx, TransactionType,
 };
 use core::cmp::min;
 use core::fmt::Debug;
@@ -28,6 +28,7 @@ pub trait Transaction {
     type Eip1559: Eip1559Tx<AccessList = Self::AccessList>;
     type Eip4844: Eip4844Tx<AccessList = Self::AccessList>;
     type Eip7702: Eip7702Tx<AccessList = Self::AccessList>;
+    type Custom: CustomTx;
 
     /// Transaction type. Depending on this field other functions should be called.
     /// If transaction is Legacy, then `legacy()` should be called.
@@ -58,6 +59,9 @@ pub trait Transaction {
         unimplemented!("Eip7702 tx not supported")
     }
 
+    /// Transaction of a custom type, returned for [`TransactionType::Custom`].
+    fn custom(&self) -> &Self::Custom;
+
     /// Common fields for all transactions.
     fn common_fields(&self) -> &dyn CommonTxFields {
         match self.tx_type().into() {
@@ -66,7 +70,7 @@ pub trait Transaction {
             TransactionType::Eip1559 => self.eip1559(),
             TransactionType::Eip4844 => self.eip4844(),
             TransactionType::Eip7702 => self.eip7702(),
-            TransactionType::Custom => unimplemented!("Custom tx not supported"),
+            TransactionType::Custom => self.custom(),
         }
     }
 
@@ -78,7 +82,7 @@ pub trait Transaction {
             TransactionType::Eip1559 => self.eip1559().max_fee_per_gas(),
             TransactionType::Eip4844 => self.eip4844().max_fee_per_gas(),
             TransactionType::Eip7702 => self.eip7702().max_fee_per_gas(),
-            TransactionType::Custom => unimplemented!("Custom tx not supported"),
+            TransactionType::Custom => self.custom().max_fee(),
         }
     }
 
@@ -101,7 +105,7 @@ pub trait Transaction {
                 self.eip7702().max_fee_per_gas(),
                 self.eip7702().max_priority_fee_per_gas(),
             ),
-            TransactionType::Custom => unimplemented!("Custom tx not supported"),
+            TransactionType::Custom => return self.custom().effective_gas_price(base_fee),
         };
 
         min(U256::from(max_fee), base_fee + U256::from(max_priority_fee))
@@ -116,11 +120,13 @@ pub trait Transaction {
             TransactionType::Eip1559 => self.eip1559().kind(),
             TransactionType::Eip4844 => TxKind::Call(self.eip4844().destination()),
             TransactionType::Eip7702 => TxKind::Call(self.eip7702().destination()),
-            TransactionType::Custom => unimplemented!("Custom tx not supported"),
+            TransactionType::Custom => self.custom().kind(),
         }
     }
 
     /// Returns access list.
+    ///
+    /// Custom transactions have no access list, unless this method is overridden.
     fn access_list(&self) -> Option<&Self::AccessList> {
         let tx_type = self.tx_type().into();
         match tx_type {
@@ -129,7 +135,7 @@ pub trait Transaction {
             TransactionType::Eip1559 => Some(self.eip1559().access_list()),
             TransactionType::Eip4844 => Some(self.eip4844().access_list()),
             TransactionType::Eip7702 => Some(self.eip7702().access_list()),
-            TransactionType::Custom => unimplemented!("Custom tx not supported"),
+            TransactionType::Custom => None,
         }
     }
 }
@@ -0,0 +1,95 @@
+//! Split of the coinbase payout with a treasury account.
+use revm::{
+    handler::register::HandleRegisterBox,
+    primitives::{Address, U256},
+    wiring::{result::EVMError, Block},
+    EvmWiring,
+};
+use std::sync::Arc;
+
+/// Basis points denominator, `10_000` equals the whole payout.
+pub const MAX_BPS: u64 = 10_000;
+
+/// Returns handle register that forwards `share_bps` of every coinbase payout to `treasury`.
+///
+/// The default `reward_beneficiary` handle is called first and the share is taken from the
+/// amount it credited, so the split composes with any fee logic registered before it.
+pub fn coinbase_split_register<'a, EvmWiringT: EvmWiring>(
+    treasury: Address,
+    share_bps: u64,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    assert!(share_bps <= MAX_BPS, "share can't exceed {MAX_BPS} bps");
+    Box::new(move |handler| {
+        let reward_beneficiary = handler.post_execution.reward_beneficiary.clone();
+        handler.post_execution.reward_beneficiary = Arc::new(move |context, gas| {
+            let coinbase = *context.evm.env.block.coinbase();
+            let inner = &mut context.evm.inner;
+            let before = inner
+                .journaled_state
+                .load_account(coinbase, &mut inner.db)
+                .map_err(EVMError::Database)?
+                .info
+                .balance;
+
+            reward_beneficiary(context, gas)?;
+
+            let inner = &mut context.evm.inner;
+            let after = inner
+                .journaled_state
+                .load_account(coinbase, &mut inner.db)
+                .map_err(EVMError::Database)?
+                .info
+                .balance;
+            let share = after.saturating_sub(before) * U256::from(share_bps) / U256::from(MAX_BPS);
+            if share.is_zero() {
+                return Ok(());
+            }
+
+            if let Some(result) = inner
+                .journaled_state
+                .transfer(&coinbase, &treasury, share, &mut inner.db)
+                .map_err(EVMError::Database)?
+            {
+                return Err(EVMError::Custom(format!(
+                    "coinbase split transfer failed: {result:?}"
+                )));
+            }
+            Ok(())
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    #[test]
+    fn payout_is_split() {
+        let coinbase = address!("00000000000000000000000000000000000000cb");
+        let treasury = address!("00000000000000000000000000000000000000ee");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| block.coinbase = coinbase)
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(10);
+            })
+            .append_handler_register_box(coinbase_split_register(treasury, 2_500))
+            .build();
+
+        let state = evm.transact().unwrap().state;
+        // 21_000 gas at price 10, a quarter of it goes to the treasury.
+        assert_eq!(state[&coinbase].info.balance, U256::from(157_500));
+        assert_eq!(state[&treasury].info.balance, U256::from(52_500));
+    }
+}
@@ -0,0 +1,154 @@
+//! Redirection of the transaction fee, as done by chains that collect the base fee.
+use revm::{
+    handler::register::HandleRegisterBox,
+    primitives::{Address, U256},
+    specification::hardfork::SpecId,
+    wiring::{
+        result::{EVMError, EVMResultGeneric, InvalidTransaction},
+        Block, Transaction,
+    },
+    Context, EvmWiring,
+};
+use std::sync::Arc;
+
+/// Destination of a part of the transaction fee.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FeeRecipient {
+    /// Credited to the block beneficiary.
+    Coinbase,
+    /// Credited to the given account.
+    Account(Address),
+    /// Not credited to anyone.
+    Burn,
+}
+
+/// Returns handle register that replaces the `reward_beneficiary` handle and pays the
+/// priority fee and the base fee of every transaction to the given recipients.
+///
+/// Mainnet behaviour is `priority_fee` paid to [`FeeRecipient::Coinbase`] and `base_fee`
+/// [`FeeRecipient::Burn`]ed. Before London there is no base fee and the whole gas price is
+/// the priority fee. Fees are paid for the gas used after the refund.
+pub fn fee_redirect_register<'a, EvmWiringT>(
+    priority_fee: FeeRecipient,
+    base_fee: FeeRecipient,
+) -> HandleRegisterBox<'a, EvmWiringT>
+where
+    EvmWiringT: EvmWiring<Transaction: Transaction<TransactionError: From<InvalidTransaction>>>,
+{
+    Box::new(move |handler| {
+        let spec_id: SpecId = handler.spec_id().into();
+        let is_london = spec_id.is_enabled_in(SpecId::LONDON);
+        handler.post_execution.reward_beneficiary = Arc::new(move |context, gas| {
+            let effective_gas_price = context.evm.env.effective_gas_price();
+            let basefee = if is_london {
+                *context.evm.env.block.basefee()
+            } else {
+                U256::ZERO
+            };
+            let gas_used = U256::from(gas.spent() - gas.refunded() as u64);
+
+            let priority_fee_amount = effective_gas_price.saturating_sub(basefee) * gas_used;
+            credit(context, priority_fee, priority_fee_amount)?;
+            credit(context, base_fee, basefee * gas_used)
+        });
+    })
+}
+
+/// Adds the amount to the balance of the recipient.
+fn credit<EvmWiringT>(
+    context: &mut Context<EvmWiringT>,
+    recipient: FeeRecipient,
+    amount: U256,
+) -> EVMResultGeneric<(), EvmWiringT>
+where
+    EvmWiringT: EvmWiring<Transaction: Transaction<TransactionError: From<InvalidTransaction>>>,
+{
+    let address = match recipient {
+        FeeRecipient::Coinbase => *context.evm.env.block.coinbase(),
+        FeeRecipient::Account(address) => address,
+        FeeRecipient::Burn => return Ok(()),
+    };
+
+    let account = context
+        .evm
+        .inner
+        .journaled_state
+        .load_account(address, &mut context.evm.inner.db)
+        .map_err(EVMError::Database)?;
+
+    account.data.mark_touch();
+    let Some(balance) = account.data.info.balance.checked_add(amount) else {
+        return Err(EVMError::Transaction(
+            InvalidTransaction::OverflowPaymentInTransaction.into(),
+        ));
+    };
+    account.data.info.balance = balance;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, TxKind},
+        state::EvmState,
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    const COINBASE: Address = address!("00000000000000000000000000000000000000cb");
+
+    fn transact(priority_fee: FeeRecipient, base_fee: FeeRecipient) -> EvmState {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| {
+                block.coinbase = COINBASE;
+                block.basefee = U256::from(7);
+            })
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(10);
+            })
+            .append_handler_register_box(fee_redirect_register(priority_fee, base_fee))
+            .build();
+        evm.transact().unwrap().state
+    }
+
+    fn balance(state: &EvmState, address: Address) -> U256 {
+        state
+            .get(&address)
+            .map_or(U256::ZERO, |account| account.info.balance)
+    }
+
+    #[test]
+    fn fees_are_redirected() {
+        let sequencer = address!("00000000000000000000000000000000000000ee");
+        let base_fee_vault = address!("00000000000000000000000000000000000000ff");
+        let state = transact(
+            FeeRecipient::Account(sequencer),
+            FeeRecipient::Account(base_fee_vault),
+        );
+        // 21_000 gas at price 10 with a base fee of 7.
+        assert_eq!(state[&sequencer].info.balance, U256::from(63_000));
+        assert_eq!(state[&base_fee_vault].info.balance, U256::from(147_000));
+        assert_eq!(balance(&state, COINBASE), U256::ZERO);
+    }
+
+    #[test]
+    fn mainnet_and_burn() {
+        let state = transact(FeeRecipient::Coinbase, FeeRecipient::Burn);
+        assert_eq!(balance(&state, COINBASE), U256::from(63_000));
+
+        let state = transact(FeeRecipient::Burn, FeeRecipient::Coinbase);
+        assert_eq!(balance(&state, COINBASE), U256::from(147_000));
+
+        let state = transact(FeeRecipient::Burn, FeeRecipient::Burn);
+        assert_eq!(balance(&state, COINBASE), U256::ZERO);
+    }
+}
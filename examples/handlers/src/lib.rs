@@ -0,0 +1,18 @@
+//! Collection of handle registers implementing common chain customizations.
+//!
+//! Every register is a self contained template that can be appended to the
+//! [`EvmBuilder`](revm::EvmBuilder) with `append_handler_register` or
+//! `append_handler_register_box`.
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod coinbase_split;
+pub mod fee_redirect;
+pub mod no_base_fee;
+pub mod sponsored;
+pub mod whitelist;
+
+pub use coinbase_split::coinbase_split_register;
+pub use fee_redirect::{fee_redirect_register, FeeRecipient};
+pub use no_base_fee::no_base_fee_register;
+pub use sponsored::sponsored_calls_register;
+pub use whitelist::tx_whitelist_register;
@@ -0,0 +1,58 @@
+//! Chain without EIP-1559 base fee.
+use revm::{
+    database_interface::Database, handler::register::EvmHandler, primitives::U256,
+    wiring::EthereumWiring,
+};
+use std::{fmt::Debug, sync::Arc};
+
+/// Handle register that zeroes the block base fee.
+///
+/// Environment is validated as if base fee was zero and the block env is updated before
+/// the caller is charged, so `BASEFEE` opcode returns zero and nothing is burned.
+pub fn no_base_fee_register<DB: Database, EXT: Debug>(
+    handler: &mut EvmHandler<'_, EthereumWiring<DB, EXT>>,
+) {
+    let validate_env = handler.validation.env.clone();
+    handler.validation.env = Arc::new(move |env| {
+        let mut env = env.clone();
+        env.block.basefee = U256::ZERO;
+        validate_env(&env)
+    });
+
+    let tx_against_state = handler.validation.tx_against_state.clone();
+    handler.validation.tx_against_state = Arc::new(move |context| {
+        context.evm.env.block.basefee = U256::ZERO;
+        tx_against_state(context)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, TxKind},
+        Evm,
+    };
+
+    #[test]
+    fn zero_gas_price_is_accepted() {
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_block_env(|block| block.basefee = U256::from(1_000))
+            .modify_tx_env(|tx| {
+                tx.caller = address!("0000000000000000000000000000000000000001");
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::ZERO;
+            })
+            .append_handler_register(no_base_fee_register)
+            .build();
+
+        let result = evm.transact().unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(evm.block().basefee, U256::ZERO);
+    }
+}
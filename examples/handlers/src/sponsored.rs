@@ -0,0 +1,61 @@
+//! Gas-free calls to sponsored contracts.
+use revm::{
+    database_interface::Database,
+    handler::register::HandleRegisterBox,
+    primitives::{Address, HashSet, TxKind, U256},
+    wiring::EthereumWiring,
+};
+use std::{fmt::Debug, sync::Arc};
+
+/// Returns handle register that makes calls to `targets` free of charge.
+///
+/// Gas price of a sponsored transaction is zeroed after env validation, so the caller is
+/// charged only the transferred value and the beneficiary is not rewarded. Gas is still
+/// metered and limited by the transaction gas limit.
+pub fn sponsored_calls_register<'a, DB: Database, EXT: Debug>(
+    targets: HashSet<Address>,
+) -> HandleRegisterBox<'a, EthereumWiring<DB, EXT>> {
+    Box::new(move |handler| {
+        let targets = targets.clone();
+        let tx_against_state = handler.validation.tx_against_state.clone();
+        handler.validation.tx_against_state = Arc::new(move |context| {
+            let tx = &mut context.evm.env.tx;
+            if matches!(tx.transact_to, TxKind::Call(target) if targets.contains(&target)) {
+                tx.gas_price = U256::ZERO;
+                tx.gas_priority_fee = None;
+            }
+            tx_against_state(context)
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{bytecode::Bytecode, primitives::address, Evm};
+
+    #[test]
+    fn sponsored_call_is_free() {
+        let caller = address!("0000000000000000000000000000000000000001");
+        let target = address!("0000000000000000000000000000000000000000");
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(target);
+                tx.gas_limit = 100_000;
+                tx.gas_price = U256::from(10);
+            })
+            .append_handler_register_box(sponsored_calls_register(HashSet::from_iter([target])))
+            .build();
+
+        let result_and_state = evm.transact().unwrap();
+        assert!(result_and_state.result.is_success());
+        assert_eq!(
+            result_and_state.state[&caller].info.balance,
+            U256::from(10_000_000)
+        );
+    }
+}
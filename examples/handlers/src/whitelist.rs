@@ -0,0 +1,66 @@
+//! Permissioned chain that only accepts transactions from known senders.
+use revm::{
+    handler::register::HandleRegisterBox,
+    primitives::{Address, HashSet},
+    transaction::Transaction,
+    wiring::result::EVMError,
+    EvmWiring,
+};
+use std::sync::Arc;
+
+/// Returns handle register that rejects transactions whose caller is not in `allowed`.
+///
+/// The check is done during env validation, before any state is loaded.
+pub fn tx_whitelist_register<'a, EvmWiringT: EvmWiring>(
+    allowed: HashSet<Address>,
+) -> HandleRegisterBox<'a, EvmWiringT> {
+    Box::new(move |handler| {
+        let allowed = allowed.clone();
+        let validate_env = handler.validation.env.clone();
+        handler.validation.env = Arc::new(move |env| {
+            let caller = env.tx.common_fields().caller();
+            if !allowed.contains(&caller) {
+                return Err(EVMError::Custom(format!(
+                    "caller {caller} is not whitelisted"
+                )));
+            }
+            validate_env(env)
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use database::BenchmarkDB;
+    use revm::{
+        bytecode::Bytecode,
+        primitives::{address, TxKind},
+        wiring::EthereumWiring,
+        Evm,
+    };
+
+    fn transact(caller: Address) -> Result<(), String> {
+        let allowed = HashSet::from_iter([address!("0000000000000000000000000000000000000001")]);
+        let mut evm = Evm::<EthereumWiring<BenchmarkDB, ()>>::builder()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .with_default_ext_ctx()
+            .modify_tx_env(|tx| {
+                tx.caller = caller;
+                tx.transact_to = TxKind::Call(address!("0000000000000000000000000000000000000000"));
+                tx.gas_limit = 100_000;
+            })
+            .append_handler_register_box(tx_whitelist_register(allowed))
+            .build();
+        evm.transact().map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn only_whitelisted_callers() {
+        assert!(transact(address!("0000000000000000000000000000000000000001")).is_ok());
+        assert_eq!(
+            transact(address!("0000000000000000000000000000000000000002")),
+            Err("caller 0x0000000000000000000000000000000000000002 is not whitelisted".into())
+        );
+    }
+}